@@ -1,10 +1,13 @@
-//! Placeholder bench for columnar v2 — anchors the `columnar-v2` feature and
-//! gives a place to add row-major vs columnar A/B tests later.
+//! Columnar v2 bench — anchors the `columnar-v2` feature: wire constants plus
+//! encode/decode of the `2.2` array layout on a mixed fixed/variable table.
 //!
 //! Run: `cargo bench --bench columnar_v2_placeholder --features columnar-v2`
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use odbc_engine::protocol::columnar_v2::{COLUMNAR_V2_MAGIC, COLUMNAR_V2_VERSION};
+use odbc_engine::protocol::columnar_v2::{
+    ColumnarV2Decoder, ColumnarV2Encoder, COLUMNAR_V2_MAGIC, COLUMNAR_V2_VERSION,
+};
+use odbc_engine::protocol::{CellEncoding, OdbcType, RowBuffer};
 
 fn bench_header_constants(c: &mut Criterion) {
     c.bench_function("columnar_v2_magic_black_box", |b| {
@@ -15,5 +18,31 @@ fn bench_header_constants(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_header_constants);
+fn make_fixture(rows: usize) -> RowBuffer {
+    let mut rb = RowBuffer::with_cell_encoding(CellEncoding::Native);
+    rb.add_column("id".to_string(), OdbcType::BigInt);
+    rb.add_column("amount".to_string(), OdbcType::Double);
+    rb.add_column("label".to_string(), OdbcType::Varchar);
+    for r in 0..rows {
+        rb.add_row(vec![
+            Some((r as i64).to_le_bytes().to_vec()),
+            (r % 7 != 0).then(|| (r as f64 * 0.25).to_le_bytes().to_vec()),
+            Some(format!("row-{r:06}").into_bytes()),
+        ]);
+    }
+    rb
+}
+
+fn bench_array_layout(c: &mut Criterion) {
+    let rb = make_fixture(4096);
+    let encoded = ColumnarV2Encoder::encode(&rb, false).expect("encode");
+    c.bench_function("columnar_v2_arrays_encode_4096", |b| {
+        b.iter(|| black_box(ColumnarV2Encoder::encode(black_box(&rb), false).expect("encode")))
+    });
+    c.bench_function("columnar_v2_arrays_decode_4096", |b| {
+        b.iter(|| black_box(ColumnarV2Decoder::parse(black_box(&encoded)).expect("decode")))
+    });
+}
+
+criterion_group!(benches, bench_header_constants, bench_array_layout);
 criterion_main!(benches);
@@ -0,0 +1,696 @@
+//! Block-cursor (`SQLFetchScroll`) row fetching shared by [`crate::engine::core::ExecutionEngine`]
+//! and [`crate::engine::StreamingExecutor`].
+//!
+//! Row-wise `SQLFetch` + `SQLGetData` costs one driver round-trip per row (and usually one per
+//! cell), which dominates wide or long result sets. When every column of a result set has a
+//! bounded width we bind an `odbc_api` columnar buffer instead and pull a whole rowset per
+//! fetch. LOB and unbounded columns (`VARCHAR(MAX)`, `TEXT`, `LONGVARBINARY`, driver-reported
+//! size 0, ...) are read with [`CellReader`], because a bound buffer would either truncate those
+//! values or reserve gigabytes per row.
+//!
+//! A result set that mixes both kinds is fetched one row at a time with the bounded columns
+//! in front of the first LOB column bound, and only the remaining columns read with
+//! `SQLGetData`. ODBC only guarantees `SQLGetData` for unbound columns after the last bound
+//! one (drivers without `SQL_GD_ANY_COLUMN` reject the rest), so a bounded column that follows
+//! a LOB column is read with `SQLGetData` too.
+//!
+//! Both paths produce byte-identical cells: integers that the driver reports as
+//! `INTEGER`/`SMALLINT`/`TINYINT`/`BIGINT` are bound natively, everything else is bound as
+//! UTF-16 text and converted with the same rules as [`CellReader::read_cell_bytes_with`], using
+//! the [`RowBuffer::cell_encoding`] of the buffer being filled.
+
+use crate::engine::cell_reader::{wide_text_to_cell_bytes, CellReader};
+use crate::error::{OdbcError, Result};
+use crate::protocol::encoder::{EncodeError, PatchWrite, RowStreamEncoder};
+use crate::protocol::{CellEncoding, OdbcType, RowBuffer};
+use odbc_api::buffers::{AnySlice, BufferDesc, ColumnarAnyBuffer};
+use odbc_api::handles::{AsStatementRef, Statement, StatementRef};
+use odbc_api::{BlockCursor, Cursor, CursorImpl, DataType, RowSetBuffer};
+use std::collections::VecDeque;
+
+/// Largest bound cell, in UTF-16 code units for text or bytes for binary. Wider columns
+/// (e.g. `NVARCHAR(4001+)`) fall back to `SQLGetData`.
+pub const MAX_BLOCK_CELL_LEN: usize = 8192;
+
+/// Upper bound on the memory reserved for one bound rowset. The rows-per-block count is
+/// reduced for wide rows so a single fetch never reserves more than this.
+pub const MAX_BLOCK_BUFFER_BYTES: usize = 32 * 1024 * 1024;
+
+/// Number of rows requested per block fetch: `fetch_size` when given, capped by the
+/// driver's `max_row_array_size` (see [`crate::engine::core::DriverCapabilities`]).
+pub fn block_fetch_rows(max_row_array_size: u32, fetch_size: Option<u32>) -> usize {
+    let cap = max_row_array_size.max(1);
+    let rows = fetch_size.filter(|&n| n > 0).map_or(cap, |n| n.min(cap));
+    rows as usize
+}
+
+/// How one result column is bound in the rowset buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnBinding {
+    I32,
+    I64,
+    /// UTF-16 text of up to `max_len` code units, converted per `odbc_type`.
+    Text {
+        max_len: usize,
+        odbc_type: OdbcType,
+    },
+    Binary {
+        max_len: usize,
+    },
+}
+
+impl ColumnBinding {
+    /// `None` when the column has no usable upper bound and must be read with `SQLGetData`.
+    fn for_column(data_type: &DataType, odbc_type: OdbcType) -> Option<Self> {
+        match data_type {
+            DataType::LongVarchar { .. }
+            | DataType::WLongVarchar { .. }
+            | DataType::LongVarbinary { .. }
+            | DataType::Unknown => None,
+            DataType::Integer | DataType::SmallInt | DataType::TinyInt
+                if odbc_type == OdbcType::Integer =>
+            {
+                Some(Self::I32)
+            }
+            DataType::BigInt if odbc_type == OdbcType::BigInt => Some(Self::I64),
+            DataType::Binary { length } | DataType::Varbinary { length }
+                if odbc_type == OdbcType::Binary =>
+            {
+                bounded(length.map(|l| l.get())).map(|max_len| Self::Binary { max_len })
+            }
+            other if odbc_type == OdbcType::Binary => bounded(other.column_size().map(|l| l.get()))
+                .map(|max_len| Self::Binary { max_len }),
+            other => bounded(other.utf16_len().map(|l| l.get()))
+                .map(|max_len| Self::Text { max_len, odbc_type }),
+        }
+    }
+
+    fn buffer_desc(self) -> BufferDesc {
+        match self {
+            Self::I32 => BufferDesc::I32 { nullable: true },
+            Self::I64 => BufferDesc::I64 { nullable: true },
+            Self::Text { max_len, .. } => BufferDesc::WText {
+                max_str_len: max_len,
+            },
+            Self::Binary { max_len } => BufferDesc::Binary { length: max_len },
+        }
+    }
+
+    fn cell(
+        self,
+        column: AnySlice<'_>,
+        row: usize,
+        encoding: CellEncoding,
+    ) -> Result<Option<Vec<u8>>> {
+        let cell = match (self, column) {
+            (Self::I32, AnySlice::NullableI32(values)) => {
+                values.get(row).map(|v| v.to_le_bytes().to_vec())
+            }
+            (Self::I64, AnySlice::NullableI64(values)) => {
+                values.get(row).map(|v| v.to_le_bytes().to_vec())
+            }
+            (Self::Text { odbc_type, .. }, AnySlice::WText(view)) => view
+                .get(row)
+                .map(|wide| wide_text_to_cell_bytes(wide, odbc_type, encoding)),
+            (Self::Binary { .. }, AnySlice::Binary(view)) => view.get(row).map(<[u8]>::to_vec),
+            (binding, _) => {
+                return Err(OdbcError::InternalError(format!(
+                    "Block fetch buffer does not match binding {binding:?}"
+                )))
+            }
+        };
+        Ok(cell)
+    }
+}
+
+fn bounded(len: Option<usize>) -> Option<usize> {
+    len.filter(|&l| l > 0 && l <= MAX_BLOCK_CELL_LEN)
+}
+
+/// Per-column bindings for a result set; `None` entries need `SQLGetData`. Empty when the
+/// descriptions do not match.
+fn plan_bindings(data_types: &[DataType], column_types: &[OdbcType]) -> Vec<Option<ColumnBinding>> {
+    if data_types.len() != column_types.len() {
+        return Vec::new();
+    }
+    data_types
+        .iter()
+        .zip(column_types)
+        .map(|(dt, &ty)| ColumnBinding::for_column(dt, ty))
+        .collect()
+}
+
+/// Bindings of every column, or `None` when any column needs `SQLGetData`.
+fn all_bound(plan: &[Option<ColumnBinding>]) -> Option<Vec<ColumnBinding>> {
+    if plan.is_empty() {
+        return None;
+    }
+    plan.iter().copied().collect()
+}
+
+/// Bindings of the columns in front of the first column that needs `SQLGetData` (see the
+/// module docs).
+fn bound_prefix(plan: &[Option<ColumnBinding>]) -> Vec<ColumnBinding> {
+    plan.iter().map_while(|binding| *binding).collect()
+}
+
+/// Rows per block after applying [`MAX_BLOCK_BUFFER_BYTES`] to the bound row width.
+fn rows_within_budget(bindings: &[ColumnBinding], requested_rows: usize) -> usize {
+    let row_bytes: usize = bindings
+        .iter()
+        .map(|b| b.buffer_desc().bytes_per_row())
+        .sum::<usize>()
+        .max(1);
+    requested_rows
+        .min(MAX_BLOCK_BUFFER_BYTES / row_bytes)
+        .max(1)
+}
+
+/// Bound buffer for up to `requested_rows` rows (fewer for wide rows, see
+/// [`rows_within_budget`]).
+fn rowset_buffer(bindings: &[ColumnBinding], requested_rows: usize) -> Result<ColumnarAnyBuffer> {
+    let rows = rows_within_budget(bindings, requested_rows);
+    ColumnarAnyBuffer::try_from_descs(rows, bindings.iter().map(|b| b.buffer_desc()))
+        .map_err(OdbcError::from)
+}
+
+/// Converts every fetched row of `batch` to cells and hands them to `on_row` in order.
+fn decode_rowset(
+    bindings: &[ColumnBinding],
+    batch: &ColumnarAnyBuffer,
+    encoding: CellEncoding,
+    mut on_row: impl FnMut(Row),
+) -> Result<()> {
+    for row in 0..batch.num_rows() {
+        let mut cells = Vec::with_capacity(bindings.len());
+        for (col, binding) in bindings.iter().enumerate() {
+            cells.push(binding.cell(batch.column(col), row, encoding)?);
+        }
+        on_row(cells);
+    }
+    Ok(())
+}
+
+/// Column bindings of a result set whose rowsets are fetched into a bound buffer by the
+/// caller itself (e.g. with a scroll orientation other than `SQL_FETCH_NEXT`).
+pub(crate) struct BoundRowset {
+    bindings: Vec<ColumnBinding>,
+}
+
+impl BoundRowset {
+    /// `None` when any column needs `SQLGetData` (see the module docs).
+    pub(crate) fn plan(data_types: &[DataType], column_types: &[OdbcType]) -> Option<Self> {
+        all_bound(&plan_bindings(data_types, column_types)).map(|bindings| Self { bindings })
+    }
+
+    /// Buffer for a rowset of up to `rows` rows.
+    pub(crate) fn buffer(&self, rows: usize) -> Result<ColumnarAnyBuffer> {
+        rowset_buffer(&self.bindings, rows)
+    }
+
+    /// Appends the rows the last fetch wrote into `buffer` to `row_buffer`.
+    pub(crate) fn append_rows(
+        &self,
+        buffer: &ColumnarAnyBuffer,
+        row_buffer: &mut RowBuffer,
+    ) -> Result<usize> {
+        let encoding = row_buffer.cell_encoding;
+        let before = row_buffer.row_count();
+        decode_rowset(&self.bindings, buffer, encoding, |row| {
+            row_buffer.add_row(row)
+        })?;
+        Ok(row_buffer.row_count() - before)
+    }
+}
+
+type Row = Vec<Option<Vec<u8>>>;
+type BorrowedBlockCursor<'c> = BlockCursor<CursorImpl<StatementRef<'c>>, ColumnarAnyBuffer>;
+
+enum FetchMode<'c, C: Cursor> {
+    Block {
+        block: Option<BorrowedBlockCursor<'c>>,
+        bindings: Vec<ColumnBinding>,
+        pending: VecDeque<Row>,
+        exhausted: bool,
+    },
+    /// One row per fetch: `bound` columns land in `buffer`, the rest are read with
+    /// `SQLGetData`.
+    Mixed {
+        cursor: &'c mut C,
+        bound: Vec<ColumnBinding>,
+        buffer: ColumnarAnyBuffer,
+        column_types: Vec<OdbcType>,
+        cell_reader: CellReader,
+    },
+    RowByRow {
+        cursor: &'c mut C,
+        column_types: Vec<OdbcType>,
+        cell_reader: CellReader,
+    },
+}
+
+/// Pulls rows out of an open cursor, using a block cursor when the result shape allows it.
+///
+/// The cursor is only borrowed: dropping the fetcher unbinds the rowset buffer and restores a
+/// row array size of 1 without closing the cursor, so callers can still move on with
+/// `SQLMoreResults` (multi-result paths) or drop the cursor themselves.
+pub struct RowFetcher<'c, C: Cursor> {
+    mode: FetchMode<'c, C>,
+}
+
+impl<'c, C: Cursor> RowFetcher<'c, C> {
+    /// `data_types` and `column_types` describe every column of the current result set, in
+    /// order; `block_rows` is the requested rowset size (see [`block_fetch_rows`]).
+    pub fn new(
+        cursor: &'c mut C,
+        data_types: &[DataType],
+        column_types: &[OdbcType],
+        block_rows: usize,
+    ) -> Result<Self> {
+        let plan = plan_bindings(data_types, column_types);
+        let bindings = match all_bound(&plan) {
+            Some(bindings) if block_rows > 1 => bindings,
+            Some(_) => return Ok(Self::row_by_row(cursor, column_types)),
+            None => return Self::mixed(cursor, bound_prefix(&plan), column_types),
+        };
+        let buffer = rowset_buffer(&bindings, block_rows)?;
+        // SAFETY: `cursor` is an open cursor, so its statement is in cursor state. The
+        // borrowed `CursorImpl` is never dropped as a cursor (see `release`), so the real
+        // owner stays responsible for `SQLCloseCursor`.
+        let borrowed = unsafe { CursorImpl::new(cursor.as_stmt_ref()) };
+        let block = borrowed.bind_buffer(buffer).map_err(OdbcError::from)?;
+        Ok(Self {
+            mode: FetchMode::Block {
+                block: Some(block),
+                bindings,
+                pending: VecDeque::new(),
+                exhausted: false,
+            },
+        })
+    }
+
+    /// Binds `bound` to the leading columns with a rowset of one row; without any bound
+    /// column every cell is read with `SQLGetData`.
+    fn mixed(
+        cursor: &'c mut C,
+        bound: Vec<ColumnBinding>,
+        column_types: &[OdbcType],
+    ) -> Result<Self> {
+        if bound.is_empty() {
+            return Ok(Self::row_by_row(cursor, column_types));
+        }
+        let mut buffer = rowset_buffer(&bound, 1)?;
+        let mut stmt = cursor.as_stmt_ref();
+        // SAFETY: `buffer` is owned by the fetcher, its column and row-count storage lives on
+        // the heap so moving the fetcher does not move it, and `release` unbinds it before the
+        // fetcher is dropped.
+        unsafe {
+            stmt.set_row_bind_type(buffer.bind_type())
+                .into_result(&stmt)
+                .map_err(OdbcError::from)?;
+            stmt.set_row_array_size(1)
+                .into_result(&stmt)
+                .map_err(OdbcError::from)?;
+            stmt.set_num_rows_fetched(buffer.mut_num_fetch_rows())
+                .into_result(&stmt)
+                .map_err(OdbcError::from)?;
+            buffer
+                .bind_colmuns_to_cursor(stmt.as_stmt_ref())
+                .map_err(OdbcError::from)?;
+        }
+        Ok(Self {
+            mode: FetchMode::Mixed {
+                cursor,
+                bound,
+                buffer,
+                column_types: column_types.to_vec(),
+                cell_reader: CellReader::new(),
+            },
+        })
+    }
+
+    fn row_by_row(cursor: &'c mut C, column_types: &[OdbcType]) -> Self {
+        Self {
+            mode: FetchMode::RowByRow {
+                cursor,
+                column_types: column_types.to_vec(),
+                cell_reader: CellReader::new(),
+            },
+        }
+    }
+
+    /// True when rows are fetched in blocks rather than through `SQLGetData`.
+    pub fn is_block_fetch(&self) -> bool {
+        matches!(self.mode, FetchMode::Block { .. })
+    }
+
+    /// Appends up to `limit` rows to `row_buffer`; returns how many were added. Fewer than
+    /// `limit` means the result set is exhausted.
+    pub fn fetch_into(&mut self, row_buffer: &mut RowBuffer, limit: usize) -> Result<usize> {
+        let mut added = 0;
+        let encoding = row_buffer.cell_encoding;
+        match &mut self.mode {
+            FetchMode::Block {
+                block,
+                bindings,
+                pending,
+                exhausted,
+            } => {
+                while added < limit {
+                    if let Some(row) = pending.pop_front() {
+                        row_buffer.add_row(row);
+                        added += 1;
+                        continue;
+                    }
+                    if *exhausted {
+                        break;
+                    }
+                    let Some(block) = block.as_mut() else {
+                        break;
+                    };
+                    match block
+                        .fetch_with_truncation_check(true)
+                        .map_err(OdbcError::from)?
+                    {
+                        Some(batch) => {
+                            decode_rowset(bindings, batch, encoding, |row| pending.push_back(row))?
+                        }
+                        None => *exhausted = true,
+                    }
+                }
+            }
+            FetchMode::Mixed {
+                cursor,
+                bound,
+                buffer,
+                column_types,
+                cell_reader,
+            } => {
+                while added < limit {
+                    let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? else {
+                        break;
+                    };
+                    if let Some(truncation) = buffer.find_truncation() {
+                        return Err(OdbcError::InternalError(format!(
+                            "Bound column {} was truncated",
+                            truncation.buffer_index + 1
+                        )));
+                    }
+                    let mut row_data = Vec::with_capacity(column_types.len());
+                    for (col, binding) in bound.iter().enumerate() {
+                        row_data.push(binding.cell(buffer.column(col), 0, encoding)?);
+                    }
+                    for (col_idx, &odbc_type) in column_types.iter().enumerate().skip(bound.len()) {
+                        let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
+                            OdbcError::InternalError("Invalid column number".to_string())
+                        })?;
+                        row_data.push(
+                            cell_reader
+                                .read_cell_bytes_with(&mut row, col_number, odbc_type, encoding)?,
+                        );
+                    }
+                    row_buffer.add_row(row_data);
+                    added += 1;
+                }
+            }
+            FetchMode::RowByRow {
+                cursor,
+                column_types,
+                cell_reader,
+            } => {
+                while added < limit {
+                    let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? else {
+                        break;
+                    };
+                    let mut row_data = Vec::with_capacity(column_types.len());
+                    for (col_idx, &odbc_type) in column_types.iter().enumerate() {
+                        let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
+                            OdbcError::InternalError("Invalid column number".to_string())
+                        })?;
+                        row_data.push(
+                            cell_reader
+                                .read_cell_bytes_with(&mut row, col_number, odbc_type, encoding)?,
+                        );
+                    }
+                    row_buffer.add_row(row_data);
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
+    }
+
+    /// Appends every remaining row to `row_buffer`.
+    pub fn fetch_all(&mut self, row_buffer: &mut RowBuffer) -> Result<usize> {
+        self.fetch_into(row_buffer, usize::MAX)
+    }
+
+    /// Encodes the remaining rows into `writer` as one v1 row-major message, holding at most
+    /// `batch_rows` rows in `row_buffer` (whose columns describe this result set) at a time.
+    /// See [`RowStreamEncoder`]; `row_buffer` is left without rows.
+    pub fn encode_into<W: PatchWrite>(
+        &mut self,
+        row_buffer: &mut RowBuffer,
+        batch_rows: usize,
+        writer: W,
+    ) -> Result<W> {
+        let batch_rows = batch_rows.max(1);
+        let mut encoder = RowStreamEncoder::new(writer, row_buffer).map_err(encode_error)?;
+        loop {
+            row_buffer.rows.clear();
+            let added = self.fetch_into(row_buffer, batch_rows)?;
+            encoder.write_rows(row_buffer).map_err(encode_error)?;
+            if added < batch_rows {
+                break;
+            }
+        }
+        row_buffer.rows.clear();
+        encoder.finish().map_err(encode_error)
+    }
+
+    fn release(&mut self) {
+        match &mut self.mode {
+            FetchMode::Block { block, .. } => {
+                if let Some(block) = block.take() {
+                    if let Ok((borrowed, _buffer)) = block.unbind() {
+                        let mut stmt = borrowed.into_stmt();
+                        // SAFETY: no buffers are bound any more, so a row array size of 1 is
+                        // valid for the row-wise `SQLGetData` reads that may follow on this
+                        // statement.
+                        let _ = unsafe { stmt.set_row_array_size(1) };
+                    }
+                }
+            }
+            FetchMode::Mixed { cursor, .. } => {
+                let mut stmt = cursor.as_stmt_ref();
+                let _ = stmt.unbind_cols();
+                let _ = stmt.unset_num_rows_fetched();
+            }
+            FetchMode::RowByRow { .. } => {}
+        }
+    }
+}
+
+impl<C: Cursor> Drop for RowFetcher<'_, C> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+fn encode_error(e: EncodeError) -> OdbcError {
+    OdbcError::ResourceLimitReached(format!("result encoding failed: {e}"))
+}
+
+/// Reads every remaining row of `cursor` into `row_buffer`, block-fetching when possible.
+pub fn fetch_all_rows<C: Cursor>(
+    cursor: &mut C,
+    data_types: &[DataType],
+    column_types: &[OdbcType],
+    block_rows: usize,
+    row_buffer: &mut RowBuffer,
+) -> Result<usize> {
+    RowFetcher::new(cursor, data_types, column_types, block_rows)?.fetch_all(row_buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn varchar(len: usize) -> DataType {
+        DataType::Varchar {
+            length: NonZeroUsize::new(len),
+        }
+    }
+
+    #[test]
+    fn test_block_fetch_rows_uses_fetch_size_below_cap() {
+        assert_eq!(block_fetch_rows(2000, Some(100)), 100);
+    }
+
+    #[test]
+    fn test_block_fetch_rows_caps_at_max_row_array_size() {
+        assert_eq!(block_fetch_rows(1000, Some(5000)), 1000);
+    }
+
+    #[test]
+    fn test_block_fetch_rows_defaults_to_cap() {
+        assert_eq!(block_fetch_rows(1500, None), 1500);
+        assert_eq!(block_fetch_rows(1500, Some(0)), 1500);
+        assert_eq!(block_fetch_rows(0, None), 1);
+    }
+
+    #[test]
+    fn test_binding_native_integers() {
+        assert_eq!(
+            ColumnBinding::for_column(&DataType::Integer, OdbcType::Integer),
+            Some(ColumnBinding::I32)
+        );
+        assert_eq!(
+            ColumnBinding::for_column(&DataType::SmallInt, OdbcType::Integer),
+            Some(ColumnBinding::I32)
+        );
+        assert_eq!(
+            ColumnBinding::for_column(&DataType::BigInt, OdbcType::BigInt),
+            Some(ColumnBinding::I64)
+        );
+    }
+
+    #[test]
+    fn test_binding_integer_mapped_to_other_type_uses_text() {
+        let binding = ColumnBinding::for_column(&DataType::Integer, OdbcType::BigInt);
+        assert_eq!(
+            binding,
+            Some(ColumnBinding::Text {
+                max_len: 11,
+                odbc_type: OdbcType::BigInt
+            })
+        );
+    }
+
+    #[test]
+    fn test_binding_bounded_varchar_is_text() {
+        assert_eq!(
+            ColumnBinding::for_column(&varchar(50), OdbcType::Varchar),
+            Some(ColumnBinding::Text {
+                max_len: 100,
+                odbc_type: OdbcType::Varchar
+            })
+        );
+    }
+
+    #[test]
+    fn test_binding_unbounded_or_lob_falls_back() {
+        assert_eq!(
+            ColumnBinding::for_column(&varchar(0), OdbcType::Varchar),
+            None
+        );
+        assert_eq!(
+            ColumnBinding::for_column(&varchar(MAX_BLOCK_CELL_LEN), OdbcType::Varchar),
+            None
+        );
+        assert_eq!(
+            ColumnBinding::for_column(
+                &DataType::LongVarchar {
+                    length: NonZeroUsize::new(10)
+                },
+                OdbcType::Varchar
+            ),
+            None
+        );
+        assert_eq!(
+            ColumnBinding::for_column(
+                &DataType::LongVarbinary {
+                    length: NonZeroUsize::new(10)
+                },
+                OdbcType::Binary
+            ),
+            None
+        );
+        assert_eq!(
+            ColumnBinding::for_column(&DataType::Unknown, OdbcType::Varchar),
+            None
+        );
+    }
+
+    #[test]
+    fn test_binding_varbinary() {
+        assert_eq!(
+            ColumnBinding::for_column(
+                &DataType::Varbinary {
+                    length: NonZeroUsize::new(16)
+                },
+                OdbcType::Binary
+            ),
+            Some(ColumnBinding::Binary { max_len: 16 })
+        );
+    }
+
+    #[test]
+    fn test_plan_bindings_mixed_lob_result_binds_per_column() {
+        let types = [
+            DataType::Integer,
+            varchar(10),
+            DataType::WLongVarchar { length: None },
+            varchar(10),
+        ];
+        let odbc = [
+            OdbcType::Integer,
+            OdbcType::Varchar,
+            OdbcType::NVarchar,
+            OdbcType::Varchar,
+        ];
+        let plan = plan_bindings(&types, &odbc);
+        assert_eq!(plan.len(), 4);
+        assert!(plan[2].is_none());
+        assert!(plan[3].is_some());
+        assert!(all_bound(&plan).is_none());
+        assert_eq!(
+            bound_prefix(&plan),
+            vec![
+                ColumnBinding::I32,
+                ColumnBinding::Text {
+                    max_len: 20,
+                    odbc_type: OdbcType::Varchar
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bound_prefix_empty_when_first_column_is_lob() {
+        let types = [DataType::LongVarbinary { length: None }, DataType::Integer];
+        let odbc = [OdbcType::Binary, OdbcType::Integer];
+        assert!(bound_prefix(&plan_bindings(&types, &odbc)).is_empty());
+    }
+
+    #[test]
+    fn test_plan_bindings_all_bounded() {
+        let types = [DataType::Integer, varchar(10), DataType::Date];
+        let odbc = [OdbcType::Integer, OdbcType::Varchar, OdbcType::Date];
+        let plan = all_bound(&plan_bindings(&types, &odbc)).expect("bounded result set");
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0], ColumnBinding::I32);
+    }
+
+    #[test]
+    fn test_plan_bindings_length_mismatch() {
+        assert!(plan_bindings(&[DataType::Integer], &[]).is_empty());
+        assert!(all_bound(&plan_bindings(&[], &[])).is_none());
+    }
+
+    #[test]
+    fn test_rows_within_budget_shrinks_wide_rows() {
+        let wide = vec![
+            ColumnBinding::Text {
+                max_len: MAX_BLOCK_CELL_LEN,
+                odbc_type: OdbcType::Varchar
+            };
+            64
+        ];
+        let rows = rows_within_budget(&wide, 5000);
+        assert!(rows < 5000);
+        assert!(rows >= 1);
+        assert_eq!(rows_within_budget(&[ColumnBinding::I32], 5000), 5000);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::{OdbcError, Result};
+use crate::protocol::native_cell::{self, CellEncoding};
 use crate::protocol::OdbcType;
 use odbc_api::CursorRow;
 
@@ -18,11 +19,27 @@ impl CellReader {
         row: &mut CursorRow<'_>,
         column_number: u16,
         odbc_type: OdbcType,
+    ) -> Result<Option<Vec<u8>>> {
+        self.read_cell_bytes_with(row, column_number, odbc_type, CellEncoding::Text)
+    }
+
+    /// Like [`Self::read_cell_bytes`], but with [`CellEncoding::Native`] the
+    /// types listed in [`native_cell`] are converted to their fixed-width form.
+    pub fn read_cell_bytes_with(
+        &mut self,
+        row: &mut CursorRow<'_>,
+        column_number: u16,
+        odbc_type: OdbcType,
+        encoding: CellEncoding,
     ) -> Result<Option<Vec<u8>>> {
         match odbc_type {
             OdbcType::Binary => self.read_binary(row, column_number),
             OdbcType::Integer => self.read_i32_as_le_bytes(row, column_number),
             OdbcType::BigInt => self.read_i64_as_le_bytes(row, column_number),
+            _ if encoding.is_native() && native_cell::native_width(odbc_type).is_some() => {
+                let wide_text = self.read_wide_text(row, column_number)?;
+                Ok(wide_text.map(|w| wide_text_to_cell_bytes(w, odbc_type, encoding)))
+            }
             _ => self.read_text(row, column_number),
         }
     }
@@ -86,15 +103,7 @@ impl CellReader {
         column_number: u16,
     ) -> Result<Option<Vec<u8>>> {
         let wide_text = self.read_wide_text(row, column_number)?;
-        let Some(wide_text) = wide_text else {
-            return Ok(None);
-        };
-
-        let s = String::from_utf16_lossy(wide_text);
-        if let Ok(value) = s.trim().parse::<i32>() {
-            return Ok(Some(value.to_le_bytes().to_vec()));
-        }
-        Ok(Some(s.into_bytes()))
+        Ok(wide_text.map(|w| wide_text_to_cell_bytes(w, OdbcType::Integer, CellEncoding::Text)))
     }
 
     fn read_i64_as_le_bytes(
@@ -103,15 +112,7 @@ impl CellReader {
         column_number: u16,
     ) -> Result<Option<Vec<u8>>> {
         let wide_text = self.read_wide_text(row, column_number)?;
-        let Some(wide_text) = wide_text else {
-            return Ok(None);
-        };
-
-        let s = String::from_utf16_lossy(wide_text);
-        if let Ok(value) = s.trim().parse::<i64>() {
-            return Ok(Some(value.to_le_bytes().to_vec()));
-        }
-        Ok(Some(s.into_bytes()))
+        Ok(wide_text.map(|w| wide_text_to_cell_bytes(w, OdbcType::BigInt, CellEncoding::Text)))
     }
 
     fn read_wide_text(
@@ -158,6 +159,40 @@ pub fn read_cell_bytes(
     CellReader::new().read_cell_bytes(row, column_number, odbc_type)
 }
 
+/// Converts a UTF-16 text cell into the wire bytes for `odbc_type`: integer
+/// columns become little-endian `i32` / `i64` when the text parses, every
+/// other type (and unparseable integers) stays UTF-8. With
+/// [`CellEncoding::Native`], parseable values of the native-cell types are
+/// encoded by [`native_cell::encode_text`]. Shared with
+/// [`crate::engine::block_fetch`] so row-wise and block fetches agree.
+pub(crate) fn wide_text_to_cell_bytes(
+    wide_buf: &[u16],
+    odbc_type: OdbcType,
+    encoding: CellEncoding,
+) -> Vec<u8> {
+    match odbc_type {
+        OdbcType::Integer => {
+            let s = String::from_utf16_lossy(wide_buf);
+            match s.trim().parse::<i32>() {
+                Ok(value) => value.to_le_bytes().to_vec(),
+                Err(_) => s.into_bytes(),
+            }
+        }
+        OdbcType::BigInt => {
+            let s = String::from_utf16_lossy(wide_buf);
+            match s.trim().parse::<i64>() {
+                Ok(value) => value.to_le_bytes().to_vec(),
+                Err(_) => s.into_bytes(),
+            }
+        }
+        _ if encoding.is_native() => {
+            let s = String::from_utf16_lossy(wide_buf);
+            native_cell::encode_text(&s, odbc_type).unwrap_or_else(|| s.into_bytes())
+        }
+        _ => wide_text_to_utf8_bytes(wide_buf),
+    }
+}
+
 fn wide_text_to_utf8_bytes(wide_buf: &[u16]) -> Vec<u8> {
     // `from_utf16_lossy` replaces any unpaired surrogate with U+FFFD.
     // SQL Server / SQL_C_WCHAR never emit those in practice, but if a
@@ -169,7 +204,9 @@ fn wide_text_to_utf8_bytes(wide_buf: &[u16]) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::{execute_query_with_cached_connection, OdbcConnection, OdbcEnvironment};
+    use crate::engine::{
+        execute_query_with_cached_connection, OdbcConnection, OdbcEnvironment, ResultOptions,
+    };
     use crate::test_helpers::load_dotenv;
 
     #[test]
@@ -208,6 +245,52 @@ mod tests {
         assert_eq!(out, b"not-a-number");
     }
 
+    #[test]
+    fn test_wide_text_to_cell_bytes_matches_row_wise_rules() {
+        let wide = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide(" 7 "), OdbcType::Integer, CellEncoding::Text),
+            7i32.to_le_bytes()
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("-9"), OdbcType::BigInt, CellEncoding::Text),
+            (-9i64).to_le_bytes()
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("x1"), OdbcType::Integer, CellEncoding::Text),
+            b"x1"
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("olá"), OdbcType::Varchar, CellEncoding::Text),
+            "olá".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_wide_text_to_cell_bytes_native_encoding() {
+        let wide = |s: &str| s.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("2.5"), OdbcType::Double, CellEncoding::Native),
+            2.5f64.to_le_bytes()
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("1"), OdbcType::Boolean, CellEncoding::Native),
+            [1]
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("2.5"), OdbcType::Double, CellEncoding::Text),
+            b"2.5"
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("n/a"), OdbcType::Double, CellEncoding::Native),
+            b"n/a"
+        );
+        assert_eq!(
+            wide_text_to_cell_bytes(&wide("olá"), OdbcType::Varchar, CellEncoding::Native),
+            "olá".as_bytes()
+        );
+    }
+
     fn get_test_dsn() -> Option<String> {
         load_dotenv();
         std::env::var("ODBC_TEST_DSN")
@@ -236,8 +319,9 @@ mod tests {
         let mut odbc_conn = conn_arc.lock().unwrap();
 
         let sql = "SELECT 42 AS value";
-        let buffer = execute_query_with_cached_connection(&mut odbc_conn, sql)
-            .expect("Failed to execute query");
+        let buffer =
+            execute_query_with_cached_connection(&mut odbc_conn, sql, &ResultOptions::default())
+                .expect("Failed to execute query");
         conn.disconnect().expect("Failed to disconnect");
 
         let decoded =
@@ -268,8 +352,9 @@ mod tests {
         let mut odbc_conn = conn_arc.lock().unwrap();
 
         let sql = "SELECT 'test' AS value";
-        let buffer = execute_query_with_cached_connection(&mut odbc_conn, sql)
-            .expect("Failed to execute query");
+        let buffer =
+            execute_query_with_cached_connection(&mut odbc_conn, sql, &ResultOptions::default())
+                .expect("Failed to execute query");
         conn.disconnect().expect("Failed to disconnect");
 
         let decoded =
@@ -300,8 +385,9 @@ mod tests {
         let mut odbc_conn = conn_arc.lock().unwrap();
 
         let sql = "SELECT NULL AS value";
-        let buffer = execute_query_with_cached_connection(&mut odbc_conn, sql)
-            .expect("Failed to execute query");
+        let buffer =
+            execute_query_with_cached_connection(&mut odbc_conn, sql, &ResultOptions::default())
+                .expect("Failed to execute query");
         conn.disconnect().expect("Failed to disconnect");
 
         let decoded =
@@ -333,8 +419,9 @@ mod tests {
         let mut odbc_conn = conn_arc.lock().unwrap();
 
         let sql = "SELECT 9223372036854775807 AS value";
-        let buffer = execute_query_with_cached_connection(&mut odbc_conn, sql)
-            .expect("Failed to execute query");
+        let buffer =
+            execute_query_with_cached_connection(&mut odbc_conn, sql, &ResultOptions::default())
+                .expect("Failed to execute query");
         conn.disconnect().expect("Failed to disconnect");
 
         let decoded =
@@ -1,12 +1,19 @@
 use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
 use crate::error::{OdbcError, Result};
 use crate::protocol::bulk_insert::{
-    is_null, BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload, BulkTimestamp,
+    format_uuid, is_null, serialize_bulk_rejected, BulkColumnData, BulkColumnSpec, BulkColumnType,
+    BulkInsertPayload, BulkRejectedRow, BulkTimestamp,
+};
+use odbc_api::buffers::Item;
+use odbc_api::handles::{AsStatementRef, Statement};
+use odbc_api::sys::{
+    HStmt, HandleType, HeaderDiagnosticIdentifier, Len, Pointer, SQLExecute, SQLGetDiagFieldW,
+    SQLGetDiagRecW, SQLMoreResults, SQLRowCount, SQLSetStmtAttr, SqlReturn, StatementAttribute,
+    NULL_DATA,
 };
-use odbc_api::handles::AsStatementRef;
-use odbc_api::sys::NULL_DATA;
 use odbc_api::{buffers::BufferDesc, Connection};
 use std::iter::once;
+use std::ops::Range;
 
 /// Validate and quote each `&str` in `columns`, returning a comma-separated
 /// list ready to inject into a SQL `INSERT (...)` clause.
@@ -184,36 +191,25 @@ impl ArrayBinding {
         &self,
         conn: &Connection<'static>,
         payload: &BulkInsertPayload,
+    ) -> Result<usize> {
+        let qtable = quote_qualified_default(&payload.table)?;
+        self.bulk_insert_generic_into(conn, &qtable, payload)
+    }
+
+    /// [`bulk_insert_generic`](Self::bulk_insert_generic) into an
+    /// already-quoted table, for names the identifier rules reject such as
+    /// SQL Server `#temp` tables. `payload.table` is ignored.
+    pub(crate) fn bulk_insert_generic_into(
+        &self,
+        conn: &Connection<'static>,
+        qtable: &str,
+        payload: &BulkInsertPayload,
     ) -> Result<usize> {
         let n_rows = payload.row_count as usize;
         if n_rows == 0 {
             return Ok(0);
         }
-        let n_cols = payload.columns.len();
-        if payload.column_data.len() != n_cols {
-            return Err(OdbcError::ValidationError(
-                "column_data length must match columns length".to_string(),
-            ));
-        }
-
-        let mut quoted_cols = Vec::with_capacity(payload.columns.len());
-        for s in &payload.columns {
-            quoted_cols.push(quote_identifier_default(&s.name)?);
-        }
-        let col_list = quoted_cols.join(", ");
-        let placeholders = once("?")
-            .cycle()
-            .take(n_cols)
-            .collect::<Vec<_>>()
-            .join(", ");
-        let qtable = quote_qualified_default(&payload.table)?;
-        let sql = format!("INSERT INTO {qtable} ({col_list}) VALUES ({placeholders})");
-
-        let descs: Vec<BufferDesc> = payload
-            .columns
-            .iter()
-            .map(spec_to_buffer_desc)
-            .collect::<Result<Vec<_>>>()?;
+        let (sql, descs) = generic_insert_plan(payload, qtable)?;
 
         let capacity = self.paramset_size.min(n_rows);
         let prepared = conn.prepare(&sql).map_err(OdbcError::from)?;
@@ -242,6 +238,485 @@ impl ArrayBinding {
 
         Ok(total)
     }
+
+    /// Like [`bulk_insert_generic`](Self::bulk_insert_generic), but rows the
+    /// driver rejects do not abort the load.
+    ///
+    /// A parameter status array (`SQL_ATTR_PARAM_STATUS_PTR`) is bound to the
+    /// statement and every batch runs to completion; rows flagged as failed or
+    /// left unprocessed are reported with the SQLSTATE and message the driver
+    /// attached to them. Drivers that do not fill the status array and fail a
+    /// batch get every row of that batch reported with the batch diagnostic.
+    pub fn bulk_insert_generic_with_rejects(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+    ) -> Result<BulkInsertOutcome> {
+        let n_rows = payload.row_count as usize;
+        if n_rows == 0 {
+            return Ok(BulkInsertOutcome::default());
+        }
+        let qtable = quote_qualified_default(&payload.table)?;
+        let (sql, descs) = generic_insert_plan(payload, &qtable)?;
+
+        let capacity = self.paramset_size.min(n_rows);
+        // Declared before the statement so the driver never outlives the
+        // buffer it writes row statuses into.
+        let mut statuses = vec![PARAM_STATUS_UNSET; capacity];
+        let mut prepared = conn.prepare(&sql).map_err(OdbcError::from)?;
+        let hstmt = prepared.as_stmt_ref().as_sys();
+        let mut inserter = prepared
+            .into_column_inserter(capacity, descs)
+            .map_err(OdbcError::from)?;
+        // SAFETY: `statuses` has `capacity` slots, stays allocated and is never
+        // resized while the statement is alive.
+        let ret = unsafe {
+            SQLSetStmtAttr(
+                hstmt,
+                StatementAttribute::ParamStatusPtr,
+                statuses.as_mut_ptr() as Pointer,
+                0,
+            )
+        };
+        check_stmt_return(hstmt, ret, "SQLSetStmtAttr(SQL_ATTR_PARAM_STATUS_PTR)")?;
+
+        let mut outcome = BulkInsertOutcome::default();
+        for chunk_start in (0..n_rows).step_by(capacity) {
+            let end = (chunk_start + capacity).min(n_rows);
+            let chunk_len = end - chunk_start;
+            inserter.set_num_rows(chunk_len);
+
+            for (buf_idx, (spec, data)) in payload
+                .columns
+                .iter()
+                .zip(payload.column_data.iter())
+                .enumerate()
+            {
+                fill_column(&mut inserter, buf_idx, spec, data, chunk_start, chunk_len)?;
+            }
+
+            statuses[..chunk_len].fill(PARAM_STATUS_UNSET);
+            // odbc-api's own execute inspects the result set afterwards, which
+            // clears the per-row diagnostics, so the batch runs through raw
+            // SQLExecute.
+            // SAFETY: parameters stay bound by `inserter` for the whole loop.
+            let ret = unsafe {
+                let size_ret = SQLSetStmtAttr(
+                    hstmt,
+                    StatementAttribute::ParamsetSize,
+                    chunk_len as Pointer,
+                    0,
+                );
+                check_stmt_return(hstmt, size_ret, "SQLSetStmtAttr(SQL_ATTR_PARAMSET_SIZE)")?;
+                SQLExecute(hstmt)
+            };
+            if ret != SqlReturn::SUCCESS
+                && ret != SqlReturn::SUCCESS_WITH_INFO
+                && ret != SqlReturn::ERROR
+                && ret != SqlReturn::NO_DATA
+            {
+                return Err(OdbcError::InternalError(format!(
+                    "SQLExecute returned unexpected code {}",
+                    ret.0
+                )));
+            }
+            let diagnostics = statement_diagnostics(hstmt);
+            classify_batch(
+                &mut outcome,
+                &statuses[..chunk_len],
+                &diagnostics,
+                ret == SqlReturn::ERROR,
+                chunk_start,
+            );
+        }
+
+        Ok(outcome)
+    }
+
+    /// Array-bound `UPDATE table SET v1 = ?, ... WHERE k1 = ? AND ...`.
+    ///
+    /// Columns of `payload` named in `key_columns` go into the `WHERE` clause,
+    /// every other column is assigned. Rows whose key matches nothing are not
+    /// an error; they simply do not count towards `rows_affected`.
+    pub fn bulk_update_by_key(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        key_columns: &[&str],
+    ) -> Result<BulkDmlOutcome> {
+        self.bulk_keyed_dml(conn, payload, key_columns, KeyedDml::Update)
+    }
+
+    /// Array-bound `DELETE FROM table WHERE k1 = ? AND ...`, one parameter set
+    /// per payload row. Payload columns outside `key_columns` are ignored, so
+    /// the same payload can feed [`bulk_update_by_key`](Self::bulk_update_by_key).
+    pub fn bulk_delete_by_key(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        key_columns: &[&str],
+    ) -> Result<BulkDmlOutcome> {
+        self.bulk_keyed_dml(conn, payload, key_columns, KeyedDml::Delete)
+    }
+
+    fn bulk_keyed_dml(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        key_columns: &[&str],
+        verb: KeyedDml,
+    ) -> Result<BulkDmlOutcome> {
+        payload.validate_shape()?;
+        let (sql, params) = keyed_dml_plan(payload, key_columns, verb)?;
+        let n_rows = payload.row_count as usize;
+        if n_rows == 0 {
+            return Ok(BulkDmlOutcome::default());
+        }
+        let descs = params
+            .iter()
+            .map(|&col| spec_to_buffer_desc(&payload.columns[col]))
+            .collect::<Result<Vec<_>>>()?;
+
+        let capacity = self.paramset_size.min(n_rows);
+        let mut prepared = conn.prepare(&sql).map_err(OdbcError::from)?;
+        let hstmt = prepared.as_stmt_ref().as_sys();
+        let mut inserter = prepared
+            .into_column_inserter(capacity, descs)
+            .map_err(OdbcError::from)?;
+
+        let mut outcome = BulkDmlOutcome::default();
+        for chunk_start in (0..n_rows).step_by(capacity) {
+            let end = (chunk_start + capacity).min(n_rows);
+            let chunk_len = end - chunk_start;
+            inserter.set_num_rows(chunk_len);
+
+            for (buf_idx, &col) in params.iter().enumerate() {
+                fill_column(
+                    &mut inserter,
+                    buf_idx,
+                    &payload.columns[col],
+                    &payload.column_data[col],
+                    chunk_start,
+                    chunk_len,
+                )?;
+            }
+
+            inserter.execute().map_err(OdbcError::from)?;
+            let affected = drain_row_counts(hstmt)?;
+            outcome.rows_affected += affected.unwrap_or(0);
+            outcome.batch_rows_affected.push(affected);
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Result of [`ArrayBinding::bulk_update_by_key`] and
+/// [`ArrayBinding::bulk_delete_by_key`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkDmlOutcome {
+    /// Sum of the per-batch counts the driver reported.
+    pub rows_affected: u64,
+    /// Rows affected by each array execution, in order; `None` when the
+    /// driver did not report a count for that batch.
+    pub batch_rows_affected: Vec<Option<u64>>,
+}
+
+/// Result of [`ArrayBinding::bulk_insert_generic_with_rejects`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkInsertOutcome {
+    pub inserted: usize,
+    /// Rejected rows in ascending `row_index` order.
+    pub rejected: Vec<BulkRejectedRow>,
+}
+
+impl BulkInsertOutcome {
+    /// The rows of `source` that were rejected, in the order of `rejected`.
+    pub fn rejected_payload(&self, source: &BulkInsertPayload) -> BulkInsertPayload {
+        let indices: Vec<usize> = self.rejected.iter().map(|r| r.row_index as usize).collect();
+        source.select_rows(&indices)
+    }
+
+    /// Rejected-rows buffer (see [`serialize_bulk_rejected`]) for `source`.
+    pub fn encode_rejected(&self, source: &BulkInsertPayload) -> Result<Vec<u8>> {
+        serialize_bulk_rejected(&self.rejected, &self.rejected_payload(source))
+    }
+}
+
+/// Value no driver writes into a parameter status slot; marks rows the driver
+/// did not report on.
+const PARAM_STATUS_UNSET: u16 = u16::MAX;
+const SQL_PARAM_SUCCESS: u16 = 0;
+const SQL_PARAM_DIAG_UNAVAILABLE: u16 = 1;
+const SQL_PARAM_ERROR: u16 = 5;
+const SQL_PARAM_SUCCESS_WITH_INFO: u16 = 6;
+
+/// One statement diagnostic record; `row` is the 1-based parameter set it
+/// belongs to, or <= 0 when the driver did not tie it to a row.
+struct StatementDiag {
+    row: Len,
+    sqlstate: String,
+    native_error: i32,
+    message: String,
+}
+
+impl StatementDiag {
+    fn reject(&self, row_index: usize) -> BulkRejectedRow {
+        BulkRejectedRow {
+            row_index: row_index as u32,
+            sqlstate: self.sqlstate.clone(),
+            native_error: self.native_error,
+            message: self.message.clone(),
+        }
+    }
+}
+
+fn rejected_without_diag(row_index: usize, message: &str) -> BulkRejectedRow {
+    BulkRejectedRow {
+        row_index: row_index as u32,
+        sqlstate: String::new(),
+        native_error: 0,
+        message: message.to_string(),
+    }
+}
+
+fn classify_batch(
+    outcome: &mut BulkInsertOutcome,
+    statuses: &[u16],
+    diagnostics: &[StatementDiag],
+    batch_failed: bool,
+    row_offset: usize,
+) {
+    let batch_diag = diagnostics
+        .iter()
+        .find(|d| d.row <= 0)
+        .or(diagnostics.first());
+    let reject_with_batch_diag = |row_index: usize, fallback: &str| match batch_diag {
+        Some(d) => d.reject(row_index),
+        None => rejected_without_diag(row_index, fallback),
+    };
+
+    if statuses.iter().all(|&s| s == PARAM_STATUS_UNSET) {
+        if batch_failed {
+            outcome.rejected.extend(
+                (0..statuses.len()).map(|i| reject_with_batch_diag(row_offset + i, "batch failed")),
+            );
+        } else {
+            outcome.inserted += statuses.len();
+        }
+        return;
+    }
+
+    for (i, &status) in statuses.iter().enumerate() {
+        let row_index = row_offset + i;
+        match status {
+            SQL_PARAM_SUCCESS | SQL_PARAM_SUCCESS_WITH_INFO => outcome.inserted += 1,
+            SQL_PARAM_ERROR => {
+                let row_number = (i + 1) as Len;
+                outcome
+                    .rejected
+                    .push(match diagnostics.iter().find(|d| d.row == row_number) {
+                        Some(d) => d.reject(row_index),
+                        None => reject_with_batch_diag(row_index, "row failed"),
+                    });
+            }
+            SQL_PARAM_DIAG_UNAVAILABLE => outcome.rejected.push(reject_with_batch_diag(
+                row_index,
+                "driver reported no diagnostics for the row",
+            )),
+            // SQL_PARAM_UNUSED, or a slot the driver never got to.
+            _ => outcome
+                .rejected
+                .push(rejected_without_diag(row_index, "row was not processed")),
+        }
+    }
+}
+
+pub(crate) fn check_stmt_return(hstmt: HStmt, ret: SqlReturn, context: &str) -> Result<()> {
+    if ret == SqlReturn::SUCCESS || ret == SqlReturn::SUCCESS_WITH_INFO {
+        return Ok(());
+    }
+    let detail = statement_diagnostics(hstmt)
+        .into_iter()
+        .next()
+        .map(|d| format!("{}: {}", d.sqlstate, d.message))
+        .unwrap_or_else(|| format!("return code {}", ret.0));
+    Err(OdbcError::InternalError(format!(
+        "{context} failed: {detail}"
+    )))
+}
+
+/// Reads every diagnostic record on `hstmt` together with its row number.
+fn statement_diagnostics(hstmt: HStmt) -> Vec<StatementDiag> {
+    let handle = hstmt.as_handle();
+    let mut out = Vec::new();
+    for record in 1..=i16::MAX {
+        let mut state = [0u16; 6];
+        let mut native_error = 0i32;
+        let mut message = vec![0u16; 1024];
+        let mut message_len = 0i16;
+        // SAFETY: every out pointer references a live local of the size passed.
+        let ret = unsafe {
+            SQLGetDiagRecW(
+                HandleType::Stmt,
+                handle,
+                record,
+                state.as_mut_ptr(),
+                &mut native_error,
+                message.as_mut_ptr(),
+                message.len() as i16,
+                &mut message_len,
+            )
+        };
+        if ret != SqlReturn::SUCCESS && ret != SqlReturn::SUCCESS_WITH_INFO {
+            break;
+        }
+        let mut row: Len = 0;
+        // SAFETY: SQL_DIAG_ROW_NUMBER is an SQLLEN written into `row`.
+        let row_ret = unsafe {
+            SQLGetDiagFieldW(
+                HandleType::Stmt,
+                handle,
+                record,
+                HeaderDiagnosticIdentifier::RowNumber as i16,
+                &mut row as *mut Len as Pointer,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if row_ret != SqlReturn::SUCCESS && row_ret != SqlReturn::SUCCESS_WITH_INFO {
+            row = 0;
+        }
+        let message_len = (message_len.max(0) as usize).min(message.len() - 1);
+        out.push(StatementDiag {
+            row,
+            sqlstate: String::from_utf16_lossy(&state[..5]),
+            native_error,
+            message: String::from_utf16_lossy(&message[..message_len]),
+        });
+    }
+    out
+}
+
+/// Rows affected by the last execution, summed over every count the driver
+/// reports for the parameter array: one in total, or one per parameter set
+/// behind `SQLMoreResults` for drivers that do not batch row counts.
+fn drain_row_counts(hstmt: HStmt) -> Result<Option<u64>> {
+    let mut total = None;
+    loop {
+        let mut count: Len = 0;
+        // SAFETY: `count` is a live local; `hstmt` belongs to a live statement.
+        let ret = unsafe { SQLRowCount(hstmt, &mut count) };
+        check_stmt_return(hstmt, ret, "SQLRowCount")?;
+        if count >= 0 {
+            total = Some(total.unwrap_or(0) + count as u64);
+        }
+        // SAFETY: as above.
+        let ret = unsafe { SQLMoreResults(hstmt) };
+        if ret == SqlReturn::NO_DATA {
+            return Ok(total);
+        }
+        check_stmt_return(hstmt, ret, "SQLMoreResults")?;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyedDml {
+    Update,
+    Delete,
+}
+
+/// UPDATE/DELETE statement keyed on `key_columns`, and the payload column
+/// index bound to each of its parameters, in order.
+fn keyed_dml_plan(
+    payload: &BulkInsertPayload,
+    key_columns: &[&str],
+    verb: KeyedDml,
+) -> Result<(String, Vec<usize>)> {
+    if key_columns.is_empty() {
+        return Err(OdbcError::ValidationError(
+            "At least one key column is required".to_string(),
+        ));
+    }
+    let mut keys = Vec::with_capacity(key_columns.len());
+    for key in key_columns {
+        let idx = payload
+            .columns
+            .iter()
+            .position(|c| c.name == *key)
+            .ok_or_else(|| {
+                OdbcError::ValidationError(format!("Key column {key:?} is not in the payload"))
+            })?;
+        if keys.contains(&idx) {
+            return Err(OdbcError::ValidationError(format!(
+                "Key column {key:?} listed twice"
+            )));
+        }
+        keys.push(idx);
+    }
+    let qtable = quote_qualified_default(&payload.table)?;
+    let predicate = |indices: &[usize], sep: &str| -> Result<String> {
+        let mut parts = Vec::with_capacity(indices.len());
+        for &i in indices {
+            parts.push(format!(
+                "{} = ?",
+                quote_identifier_default(&payload.columns[i].name)?
+            ));
+        }
+        Ok(parts.join(sep))
+    };
+    let where_clause = predicate(&keys, " AND ")?;
+
+    match verb {
+        KeyedDml::Update => {
+            let values: Vec<usize> = (0..payload.columns.len())
+                .filter(|i| !keys.contains(i))
+                .collect();
+            if values.is_empty() {
+                return Err(OdbcError::ValidationError(
+                    "Bulk update needs at least one non-key column".to_string(),
+                ));
+            }
+            let sql = format!(
+                "UPDATE {qtable} SET {} WHERE {where_clause}",
+                predicate(&values, ", ")?
+            );
+            Ok((sql, values.into_iter().chain(keys).collect()))
+        }
+        KeyedDml::Delete => Ok((format!("DELETE FROM {qtable} WHERE {where_clause}"), keys)),
+    }
+}
+
+/// INSERT statement and parameter buffer layout for a generic payload.
+fn generic_insert_plan(
+    payload: &BulkInsertPayload,
+    qtable: &str,
+) -> Result<(String, Vec<BufferDesc>)> {
+    let n_cols = payload.columns.len();
+    if payload.column_data.len() != n_cols {
+        return Err(OdbcError::ValidationError(
+            "column_data length must match columns length".to_string(),
+        ));
+    }
+
+    let mut quoted_cols = Vec::with_capacity(payload.columns.len());
+    for s in &payload.columns {
+        quoted_cols.push(quote_identifier_default(&s.name)?);
+    }
+    let col_list = quoted_cols.join(", ");
+    let placeholders = once("?")
+        .cycle()
+        .take(n_cols)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!("INSERT INTO {qtable} ({col_list}) VALUES ({placeholders})");
+
+    let descs: Vec<BufferDesc> = payload
+        .columns
+        .iter()
+        .map(spec_to_buffer_desc)
+        .collect::<Result<Vec<_>>>()?;
+    Ok((sql, descs))
 }
 
 fn spec_to_buffer_desc(spec: &BulkColumnSpec) -> Result<BufferDesc> {
@@ -255,9 +730,61 @@ fn spec_to_buffer_desc(spec: &BulkColumnSpec) -> Result<BufferDesc> {
         },
         BulkColumnType::Binary => BufferDesc::Binary { length: max_len },
         BulkColumnType::Timestamp => BufferDesc::Timestamp { nullable },
+        BulkColumnType::Bool => BufferDesc::Bit { nullable },
+        BulkColumnType::F64 => BufferDesc::F64 { nullable },
+        BulkColumnType::F32 => BufferDesc::F32 { nullable },
+        BulkColumnType::Date => BufferDesc::Date { nullable },
+        BulkColumnType::Time => BufferDesc::Time { nullable },
+        // odbc-api has no GUID buffer; the canonical text form converts
+        // implicitly on SQL Server, PostgreSQL and the other UUID-aware DBMS.
+        BulkColumnType::Uuid => BufferDesc::Text {
+            max_str_len: UUID_TEXT_LEN,
+        },
+        BulkColumnType::NText => BufferDesc::WText {
+            max_str_len: max_len,
+        },
     })
 }
 
+const UUID_TEXT_LEN: usize = 36;
+
+/// Copy `values[rows]` into a fixed-width column buffer, converting each
+/// value with `convert`.
+fn fill_fixed<S, V, T>(
+    inserter: &mut odbc_api::ColumnarBulkInserter<S, odbc_api::buffers::AnyBuffer>,
+    buf_idx: usize,
+    values: &[V],
+    null_bitmap: Option<&[u8]>,
+    rows: Range<usize>,
+    label: &str,
+    convert: impl Fn(&V) -> T,
+) -> Result<()>
+where
+    S: AsStatementRef,
+    T: Item,
+{
+    if let Some(bm) = null_bitmap {
+        let mut writer = inserter
+            .column_mut(buf_idx)
+            .as_nullable_slice::<T>()
+            .ok_or_else(|| OdbcError::InternalError(format!("{label} nullable column expected")))?;
+        let (vals, inds) = writer.raw_values();
+        for (i, r) in rows.enumerate() {
+            vals[i] = convert(&values[r]);
+            inds[i] = if is_null(bm, r) { NULL_DATA } else { 0 };
+        }
+    } else {
+        let col = inserter
+            .column_mut(buf_idx)
+            .as_slice::<T>()
+            .ok_or_else(|| OdbcError::InternalError(format!("{label} column expected")))?;
+        for (i, r) in rows.enumerate() {
+            col[i] = convert(&values[r]);
+        }
+    }
+    Ok(())
+}
+
 fn fill_column<S>(
     inserter: &mut odbc_api::ColumnarBulkInserter<S, odbc_api::buffers::AnyBuffer>,
     buf_idx: usize,
@@ -432,6 +959,127 @@ where
                 }
             }
         }
+        (
+            BulkColumnData::Bool {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Bool,
+        ) => fill_fixed(
+            inserter,
+            buf_idx,
+            values,
+            null_bitmap.as_deref(),
+            chunk_start..chunk_start + chunk_len,
+            "Bool",
+            |&v| odbc_api::Bit::from_bool(v),
+        )?,
+        (
+            BulkColumnData::F64 {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::F64,
+        ) => fill_fixed(
+            inserter,
+            buf_idx,
+            values,
+            null_bitmap.as_deref(),
+            chunk_start..chunk_start + chunk_len,
+            "F64",
+            |&v| v,
+        )?,
+        (
+            BulkColumnData::F32 {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::F32,
+        ) => fill_fixed(
+            inserter,
+            buf_idx,
+            values,
+            null_bitmap.as_deref(),
+            chunk_start..chunk_start + chunk_len,
+            "F32",
+            |&v| v,
+        )?,
+        (
+            BulkColumnData::Date {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Date,
+        ) => fill_fixed(
+            inserter,
+            buf_idx,
+            values,
+            null_bitmap.as_deref(),
+            chunk_start..chunk_start + chunk_len,
+            "Date",
+            |d| odbc_api::sys::Date {
+                year: d.year,
+                month: d.month,
+                day: d.day,
+            },
+        )?,
+        (
+            BulkColumnData::Time {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Time,
+        ) => fill_fixed(
+            inserter,
+            buf_idx,
+            values,
+            null_bitmap.as_deref(),
+            chunk_start..chunk_start + chunk_len,
+            "Time",
+            |t| odbc_api::sys::Time {
+                hour: t.hour,
+                minute: t.minute,
+                second: t.second,
+            },
+        )?,
+        (
+            BulkColumnData::Uuid {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Uuid,
+        ) => {
+            let mut view = inserter
+                .column_mut(buf_idx)
+                .as_text_view()
+                .ok_or_else(|| OdbcError::InternalError("Uuid column expected".to_string()))?;
+            for (i, r) in (chunk_start..chunk_start + chunk_len).enumerate() {
+                if null_bitmap.as_ref().is_some_and(|bm| is_null(bm, r)) {
+                    view.set_cell(i, None);
+                } else {
+                    view.set_cell(i, Some(format_uuid(&values[r]).as_bytes()));
+                }
+            }
+        }
+        (
+            BulkColumnData::NText {
+                rows, null_bitmap, ..
+            },
+            BulkColumnType::NText,
+        ) => {
+            let mut view = inserter
+                .column_mut(buf_idx)
+                .as_w_text_view()
+                .ok_or_else(|| OdbcError::InternalError("NText column expected".to_string()))?;
+            for (i, r) in (chunk_start..chunk_start + chunk_len).enumerate() {
+                let cell = if null_bitmap.as_ref().is_some_and(|bm| is_null(bm, r)) {
+                    None
+                } else {
+                    Some(rows[r].as_slice())
+                };
+                view.set_cell(i, cell);
+            }
+        }
         _ => {
             return Err(OdbcError::ValidationError(
                 "Column data does not match spec".to_string(),
@@ -486,6 +1134,57 @@ mod tests {
         assert!(r.is_ok());
     }
 
+    fn diag(row: Len, sqlstate: &str, message: &str) -> StatementDiag {
+        StatementDiag {
+            row,
+            sqlstate: sqlstate.to_string(),
+            native_error: 1,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify_batch_uses_row_diagnostics() {
+        let mut outcome = BulkInsertOutcome::default();
+        let statuses = [
+            SQL_PARAM_SUCCESS,
+            SQL_PARAM_ERROR,
+            SQL_PARAM_SUCCESS_WITH_INFO,
+            7,
+        ];
+        let diags = [diag(0, "01000", "batch"), diag(2, "23000", "duplicate key")];
+        classify_batch(&mut outcome, &statuses, &diags, false, 10);
+        assert_eq!(outcome.inserted, 2);
+        assert_eq!(outcome.rejected.len(), 2);
+        assert_eq!(outcome.rejected[0].row_index, 11);
+        assert_eq!(outcome.rejected[0].sqlstate, "23000");
+        assert_eq!(outcome.rejected[0].message, "duplicate key");
+        assert_eq!(outcome.rejected[1].row_index, 13);
+        assert_eq!(outcome.rejected[1].message, "row was not processed");
+    }
+
+    #[test]
+    fn test_classify_batch_without_status_array() {
+        let statuses = [PARAM_STATUS_UNSET; 3];
+
+        let mut ok = BulkInsertOutcome::default();
+        classify_batch(&mut ok, &statuses, &[], false, 0);
+        assert_eq!(ok.inserted, 3);
+        assert!(ok.rejected.is_empty());
+
+        let mut failed = BulkInsertOutcome::default();
+        classify_batch(
+            &mut failed,
+            &statuses,
+            &[diag(-1, "22001", "truncated")],
+            true,
+            0,
+        );
+        assert_eq!(failed.inserted, 0);
+        assert_eq!(failed.rejected.len(), 3);
+        assert!(failed.rejected.iter().all(|r| r.sqlstate == "22001"));
+    }
+
     #[test]
     fn test_array_binding_new() {
         let ab = ArrayBinding::new(500);
@@ -503,4 +1202,56 @@ mod tests {
         let ab = ArrayBinding::new(0);
         assert_eq!(ab.paramset_size(), 1);
     }
+
+    fn keyed_payload() -> BulkInsertPayload {
+        let spec = |name: &str| BulkColumnSpec {
+            name: name.to_string(),
+            col_type: BulkColumnType::I32,
+            nullable: false,
+            max_len: 0,
+        };
+        let col = || BulkColumnData::I32 {
+            values: vec![1, 2],
+            null_bitmap: None,
+        };
+        BulkInsertPayload {
+            table: "dbo.t".to_string(),
+            columns: vec![spec("a"), spec("id"), spec("b"), spec("tenant")],
+            row_count: 2,
+            column_data: vec![col(), col(), col(), col()],
+        }
+    }
+
+    #[test]
+    fn test_keyed_dml_plan_update_binds_values_then_keys() {
+        let (sql, params) =
+            keyed_dml_plan(&keyed_payload(), &["tenant", "id"], KeyedDml::Update).unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE \"dbo\".\"t\" SET \"a\" = ?, \"b\" = ? WHERE \"tenant\" = ? AND \"id\" = ?"
+        );
+        assert_eq!(params, vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_keyed_dml_plan_delete_binds_keys_only() {
+        let (sql, params) = keyed_dml_plan(&keyed_payload(), &["id"], KeyedDml::Delete).unwrap();
+        assert_eq!(sql, "DELETE FROM \"dbo\".\"t\" WHERE \"id\" = ?");
+        assert_eq!(params, vec![1]);
+    }
+
+    #[test]
+    fn test_keyed_dml_plan_rejects_bad_keys() {
+        let payload = keyed_payload();
+        for keys in [&[][..], &["missing"][..], &["id", "id"][..]] {
+            assert!(matches!(
+                keyed_dml_plan(&payload, keys, KeyedDml::Delete),
+                Err(OdbcError::ValidationError(_))
+            ));
+        }
+        assert!(matches!(
+            keyed_dml_plan(&payload, &["a", "id", "b", "tenant"], KeyedDml::Update),
+            Err(OdbcError::ValidationError(_))
+        ));
+    }
 }
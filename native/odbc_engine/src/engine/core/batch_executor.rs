@@ -1,9 +1,12 @@
 use super::pipeline::QueryPipeline;
+use super::typed_params::{input_parameters, TemporalBinding};
 use crate::engine::cell_reader::CellReader;
 use crate::engine::sqlserver_json::coalesce_for_json_rows;
 use crate::error::{OdbcError, Result};
-use crate::protocol::{param_values_to_strings, OdbcType, ParamValue, RowBuffer, RowBufferEncoder};
-use odbc_api::{Connection, Cursor, IntoParameter, ResultSetMetadata};
+use crate::protocol::{
+    OdbcType, ParamDate, ParamTime, ParamTimestamp, ParamValue, RowBuffer, RowBufferEncoder,
+};
+use odbc_api::{Connection, Cursor, ResultSetMetadata};
 use std::sync::Arc;
 
 pub struct BatchQuery {
@@ -16,6 +19,13 @@ pub enum BatchParam {
     Integer(i32),
     BigInt(i64),
     Null,
+    Boolean(bool),
+    Double(f64),
+    Date(ParamDate),
+    Time(ParamTime),
+    Timestamp(ParamTimestamp),
+    Uuid([u8; 16]),
+    Json(String),
 }
 
 impl BatchQuery {
@@ -34,6 +44,7 @@ impl BatchQuery {
 pub struct BatchExecutor {
     pipeline: Arc<QueryPipeline>,
     batch_size: usize,
+    temporal: TemporalBinding,
 }
 
 impl BatchExecutor {
@@ -41,9 +52,17 @@ impl BatchExecutor {
         Self {
             pipeline: Arc::new(QueryPipeline::new(cache_size)),
             batch_size,
+            temporal: TemporalBinding::Standard,
         }
     }
 
+    /// Binds time fractions and UTC offsets per `temporal`, normally the
+    /// connection's [`ResultOptions::temporal`](super::ResultOptions::temporal).
+    pub fn with_temporal_binding(mut self, temporal: TemporalBinding) -> Self {
+        self.temporal = temporal;
+        self
+    }
+
     pub fn batch_size(&self) -> usize {
         self.batch_size
     }
@@ -92,49 +111,10 @@ impl BatchExecutor {
                 let param_values: Vec<ParamValue> =
                     param_set.iter().map(batch_param_to_param_value).collect();
 
-                let optional_strings = param_values_to_strings(&param_values)?;
-
-                let mut cursor = match optional_strings.len() {
-                    0 => stmt.execute(()).map_err(OdbcError::from)?,
-                    1 => {
-                        let p0 = optional_strings[0].as_deref().into_parameter();
-                        stmt.execute((&p0,)).map_err(OdbcError::from)?
-                    }
-                    2 => {
-                        let p0 = optional_strings[0].as_deref().into_parameter();
-                        let p1 = optional_strings[1].as_deref().into_parameter();
-                        stmt.execute((&p0, &p1)).map_err(OdbcError::from)?
-                    }
-                    3 => {
-                        let p0 = optional_strings[0].as_deref().into_parameter();
-                        let p1 = optional_strings[1].as_deref().into_parameter();
-                        let p2 = optional_strings[2].as_deref().into_parameter();
-                        stmt.execute((&p0, &p1, &p2)).map_err(OdbcError::from)?
-                    }
-                    4 => {
-                        let p0 = optional_strings[0].as_deref().into_parameter();
-                        let p1 = optional_strings[1].as_deref().into_parameter();
-                        let p2 = optional_strings[2].as_deref().into_parameter();
-                        let p3 = optional_strings[3].as_deref().into_parameter();
-                        stmt.execute((&p0, &p1, &p2, &p3))
-                            .map_err(OdbcError::from)?
-                    }
-                    5 => {
-                        let p0 = optional_strings[0].as_deref().into_parameter();
-                        let p1 = optional_strings[1].as_deref().into_parameter();
-                        let p2 = optional_strings[2].as_deref().into_parameter();
-                        let p3 = optional_strings[3].as_deref().into_parameter();
-                        let p4 = optional_strings[4].as_deref().into_parameter();
-                        stmt.execute((&p0, &p1, &p2, &p3, &p4))
-                            .map_err(OdbcError::from)?
-                    }
-                    n => {
-                        return Err(OdbcError::ValidationError(format!(
-                            "At most 5 parameters supported in batch, got {}",
-                            n
-                        )));
-                    }
-                };
+                let input_params = input_parameters(&param_values, self.temporal)?;
+                let mut cursor = stmt
+                    .execute(input_params.as_slice())
+                    .map_err(OdbcError::from)?;
 
                 let mut taken = cursor.take();
                 let encoded = if taken.is_none() {
@@ -199,6 +179,13 @@ fn batch_param_to_param_value(param: &BatchParam) -> ParamValue {
         BatchParam::Integer(n) => ParamValue::Integer(*n),
         BatchParam::BigInt(n) => ParamValue::BigInt(*n),
         BatchParam::Null => ParamValue::Null,
+        BatchParam::Boolean(b) => ParamValue::Boolean(*b),
+        BatchParam::Double(v) => ParamValue::Double(*v),
+        BatchParam::Date(d) => ParamValue::Date(*d),
+        BatchParam::Time(t) => ParamValue::Time(*t),
+        BatchParam::Timestamp(ts) => ParamValue::Timestamp(*ts),
+        BatchParam::Uuid(u) => ParamValue::Uuid(*u),
+        BatchParam::Json(s) => ParamValue::Json(s.clone()),
     }
 }
 
@@ -379,4 +366,33 @@ mod tests {
             _ => panic!("Expected Null value"),
         }
     }
+
+    #[test]
+    fn test_batch_param_typed_conversion() {
+        assert_eq!(
+            batch_param_to_param_value(&BatchParam::Boolean(true)),
+            ParamValue::Boolean(true)
+        );
+        assert_eq!(
+            batch_param_to_param_value(&BatchParam::Double(2.5)),
+            ParamValue::Double(2.5)
+        );
+        let date = ParamDate {
+            year: 2024,
+            month: 6,
+            day: 1,
+        };
+        assert_eq!(
+            batch_param_to_param_value(&BatchParam::Date(date)),
+            ParamValue::Date(date)
+        );
+        assert_eq!(
+            batch_param_to_param_value(&BatchParam::Uuid([7; 16])),
+            ParamValue::Uuid([7; 16])
+        );
+        assert_eq!(
+            batch_param_to_param_value(&BatchParam::Json("[]".to_string())),
+            ParamValue::Json("[]".to_string())
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use crate::error::{OdbcError, Result};
 use crate::protocol::BulkInsertPayload;
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 use super::sqlserver_bcp;
 #[cfg(feature = "sqlserver-bcp")]
 use odbc_api::Connection;
@@ -90,7 +90,7 @@ impl BulkCopyExecutor {
         payload: &BulkInsertPayload,
         conn_str: Option<&str>,
     ) -> Result<usize> {
-        #[cfg(windows)]
+        #[cfg(any(windows, target_os = "linux"))]
         {
             if !Self::is_native_bcp_runtime_enabled() {
                 return Err(OdbcError::UnsupportedFeature(
@@ -113,11 +113,12 @@ impl BulkCopyExecutor {
             sqlserver_bcp::execute_native_bcp(conn_str, payload, self.batch_size)
         }
 
-        #[cfg(not(windows))]
+        #[cfg(not(any(windows, target_os = "linux")))]
         {
             let _ = (payload, conn_str);
             Err(OdbcError::UnsupportedFeature(
-                "Native SQL Server BCP is currently supported only on Windows builds".to_string(),
+                "Native SQL Server BCP is currently supported only on Windows and Linux builds"
+                    .to_string(),
             ))
         }
     }
@@ -159,7 +160,7 @@ impl BulkCopyExecutor {
 
         let row_count = payload.row_count as usize;
         for (idx, data) in payload.column_data.iter().enumerate() {
-            let actual_rows = Self::column_row_count(data);
+            let actual_rows = data.len();
             if actual_rows != row_count {
                 return Err(OdbcError::ValidationError(format!(
                     "Bulk insert payload column '{}' (index {}) has {} rows, expected {}",
@@ -171,16 +172,6 @@ impl BulkCopyExecutor {
         Ok(())
     }
 
-    fn column_row_count(data: &crate::protocol::BulkColumnData) -> usize {
-        match data {
-            crate::protocol::BulkColumnData::I32 { values, .. } => values.len(),
-            crate::protocol::BulkColumnData::I64 { values, .. } => values.len(),
-            crate::protocol::BulkColumnData::Text { rows, .. } => rows.len(),
-            crate::protocol::BulkColumnData::Binary { rows, .. } => rows.len(),
-            crate::protocol::BulkColumnData::Timestamp { values, .. } => values.len(),
-        }
-    }
-
     fn should_fallback_to_array_binding(error: &OdbcError) -> bool {
         match error {
             // Caller-side validation errors are programming bugs; do not retry.
@@ -0,0 +1,417 @@
+//! Set-based bulk UPSERT through a session temporary table.
+//!
+//! 1. Create a temp table with the payload's columns, typed from the target
+//!    (`SELECT ... WHERE 1 = 0` / `WITH NO DATA`, depending on the engine);
+//! 2. Load the payload into it with the engine's [`BulkLoader`] when it has
+//!    a session-local one (MySQL/MariaDB LOAD DATA, Oracle, Snowflake
+//!    PUT + COPY), otherwise with [`ArrayBinding`]. SQL Server BCP
+//!    opens its own connection and cannot see `#temp` tables, so SQL Server
+//!    always stages through array binding;
+//! 3. Run the single statement from [`build_bulk_upsert_sql`]
+//!    (`MERGE` or `INSERT ... SELECT ... ON CONFLICT / ON DUPLICATE KEY`);
+//! 4. Drop the temp table, whatever happened.
+//!
+//! When the upsert statement returns a result set its first row is read as
+//! `(inserted, updated)`; otherwise only the driver's affected-row count is
+//! reported (MySQL counts an updated row twice there).
+//!
+//! [`build_bulk_upsert_sql`]: crate::plugins::capabilities::Upsertable::build_bulk_upsert_sql
+
+use crate::engine::core::array_binding::ArrayBinding;
+use crate::engine::core::driver_capabilities::{
+    DriverCapabilities, ENGINE_DB2, ENGINE_MARIADB, ENGINE_MYSQL, ENGINE_ORACLE, ENGINE_POSTGRES,
+    ENGINE_SNOWFLAKE, ENGINE_SQLITE, ENGINE_SQLSERVER, ENGINE_SYBASE_ASA, ENGINE_SYBASE_ASE,
+};
+use crate::engine::identifier::{quote_identifier, IdentifierQuoting};
+use crate::error::{OdbcError, Result};
+use crate::plugins::capabilities::bulk_loader::{BulkLoadOptions, BulkLoader};
+use crate::plugins::{
+    mariadb::MariaDbPlugin, mysql::MySqlPlugin, oracle::OraclePlugin, snowflake::SnowflakePlugin,
+    PluginRegistry,
+};
+use crate::protocol::BulkInsertPayload;
+use odbc_api::{Connection, Cursor};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static STAGING_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Outcome of [`BulkUpsert::execute`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkUpsertReport {
+    /// Rows loaded into the temp table.
+    pub rows_staged: usize,
+    /// Rows the upsert statement touched, when the driver reports it.
+    pub rows_affected: Option<u64>,
+    /// Rows inserted; only for engines whose statement returns the split.
+    pub inserted: Option<u64>,
+    /// Rows updated; only for engines whose statement returns the split.
+    pub updated: Option<u64>,
+}
+
+/// Bulk UPSERT of a [`BulkInsertPayload`] into `payload.table`.
+///
+/// Conflict and update columns follow the same rules as
+/// [`build_upsert_sql`]: `update_columns` defaults to every
+/// payload column that is not a conflict column.
+///
+/// [`build_upsert_sql`]: crate::plugins::capabilities::Upsertable::build_upsert_sql
+#[derive(Debug, Clone)]
+pub struct BulkUpsert {
+    conflict_columns: Vec<String>,
+    update_columns: Option<Vec<String>>,
+    options: BulkLoadOptions,
+}
+
+impl BulkUpsert {
+    pub fn new(conflict_columns: &[&str]) -> Self {
+        Self {
+            conflict_columns: conflict_columns.iter().map(|c| c.to_string()).collect(),
+            update_columns: None,
+            options: BulkLoadOptions::default(),
+        }
+    }
+
+    /// Columns to overwrite on conflict; an empty list means "insert only".
+    pub fn with_update_columns(mut self, columns: &[&str]) -> Self {
+        self.update_columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Options handed to the staging loader; `timeout_secs` also bounds the
+    /// upsert statement.
+    pub fn with_options(mut self, options: BulkLoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Stages `payload` and merges it into `payload.table`.
+    ///
+    /// Takes the payload by value so the staging load can retarget it at the
+    /// temp table without copying the column data.
+    pub fn execute(
+        &self,
+        conn: &Connection<'static>,
+        mut payload: BulkInsertPayload,
+    ) -> Result<BulkUpsertReport> {
+        payload.validate_shape()?;
+        if payload.row_count == 0 {
+            return Ok(BulkUpsertReport {
+                rows_affected: Some(0),
+                ..BulkUpsertReport::default()
+            });
+        }
+
+        let engine = DriverCapabilities::detect(conn)?.engine;
+        let stage = StagingTable::new(
+            &engine,
+            &format!(
+                "odbc_upsert_{}_{}",
+                std::process::id(),
+                STAGING_SEQ.fetch_add(1, Ordering::Relaxed)
+            ),
+        )?;
+
+        let columns: Vec<&str> = payload.columns.iter().map(|c| c.name.as_str()).collect();
+        let conflict: Vec<&str> = self.conflict_columns.iter().map(String::as_str).collect();
+        let update: Option<Vec<&str>> = self
+            .update_columns
+            .as_ref()
+            .map(|v| v.iter().map(String::as_str).collect());
+        // Built before any DDL so bad column lists and unsupported engines
+        // fail without leaving anything behind.
+        let upsert_sql = PluginRegistry::new().build_bulk_upsert_sql(
+            &engine,
+            &payload.table,
+            &stage.quoted,
+            &columns,
+            &conflict,
+            update.as_deref(),
+        )?;
+        let create_sql = create_staging_sql(&engine, &payload.table, &stage.quoted, &columns)?;
+
+        let timeout = self.options.timeout_secs.map(|s| s as usize);
+        execute(conn, &create_sql, timeout)?;
+        let result = (|| {
+            payload.table = stage.name.clone();
+            let rows_staged = load_payload(conn, &engine, &stage.quoted, &payload, &self.options)?;
+            let report = run_upsert(conn, &upsert_sql, timeout)?;
+            Ok(BulkUpsertReport {
+                rows_staged,
+                ..report
+            })
+        })();
+        for sql in drop_staging_sql(&engine, &stage.quoted) {
+            if let Err(e) = execute(conn, &sql, timeout) {
+                log::warn!(
+                    "Bulk upsert: failed to drop staging table {}: {e}",
+                    stage.name
+                );
+            }
+        }
+        result
+    }
+}
+
+/// Temp table name as loaders see it (`name`) and as SQL references it (`quoted`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct StagingTable {
+    pub(crate) name: String,
+    pub(crate) quoted: String,
+}
+
+impl StagingTable {
+    pub(crate) fn new(engine: &str, base: &str) -> Result<Self> {
+        let style = quoting_for(engine);
+        let quoted = quote_identifier(base, style)?;
+        Ok(match engine {
+            // `#` marks a session-local table; it is not a valid bare identifier
+            // character, so only the pre-quoted name is usable.
+            ENGINE_SQLSERVER => Self {
+                name: format!("#{base}"),
+                quoted: format!("[#{base}]"),
+            },
+            ENGINE_SYBASE_ASE | ENGINE_SYBASE_ASA => Self {
+                name: format!("#{base}"),
+                quoted: format!("#{base}"),
+            },
+            // Declared temporary tables always live in the SESSION schema.
+            ENGINE_DB2 => Self {
+                name: format!("SESSION.{base}"),
+                quoted: format!("SESSION.{quoted}"),
+            },
+            _ => Self {
+                name: base.to_string(),
+                quoted,
+            },
+        })
+    }
+}
+
+/// Loads `payload` into `qtable` (already quoted; `payload.table` must name
+/// the same table for the native loaders) with the engine's session loader,
+/// falling back to [`ArrayBinding`] when there is none or it declines.
+pub(crate) fn load_payload(
+    conn: &Connection<'static>,
+    engine: &str,
+    qtable: &str,
+    payload: &BulkInsertPayload,
+    options: &BulkLoadOptions,
+) -> Result<usize> {
+    if let Some(loader) = session_loader(engine).filter(|l| l.supports_native_bulk()) {
+        match loader.execute_bulk_native(conn, payload, options) {
+            Err(OdbcError::UnsupportedFeature(reason)) => {
+                log::debug!("{} loader unavailable ({reason})", loader.technique());
+            }
+            other => return other,
+        }
+    }
+    ArrayBinding::new(options.batch_size).bulk_insert_generic_into(conn, qtable, payload)
+}
+
+/// Loaders that write through the caller's connection, so they can reach a
+/// session temp table.
+fn session_loader(engine: &str) -> Option<Box<dyn BulkLoader>> {
+    match engine {
+        ENGINE_MYSQL => Some(Box::new(MySqlPlugin::new())),
+        ENGINE_MARIADB => Some(Box::new(MariaDbPlugin::new())),
+        ENGINE_ORACLE => Some(Box::new(OraclePlugin::new())),
+        ENGINE_SNOWFLAKE => Some(Box::new(SnowflakePlugin::new())),
+        _ => None,
+    }
+}
+
+fn quoting_for(engine: &str) -> IdentifierQuoting {
+    match engine {
+        ENGINE_SQLSERVER => IdentifierQuoting::Brackets,
+        ENGINE_MYSQL | ENGINE_MARIADB => IdentifierQuoting::Backtick,
+        _ => IdentifierQuoting::DoubleQuote,
+    }
+}
+
+fn quote_qualified(qualified: &str, style: IdentifierQuoting) -> Result<String> {
+    let parts = qualified
+        .split('.')
+        .map(|p| quote_identifier(p, style))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(parts.join("."))
+}
+
+/// DDL creating an empty temp table `stage` shaped like `columns` of `target`.
+pub fn create_staging_sql(
+    engine: &str,
+    target: &str,
+    stage: &str,
+    columns: &[&str],
+) -> Result<String> {
+    let style = quoting_for(engine);
+    let qtarget = quote_qualified(target, style)?;
+    let cols = columns
+        .iter()
+        .map(|c| quote_identifier(c, style))
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    let select = format!("SELECT {cols} FROM {qtarget}");
+    Ok(match engine {
+        ENGINE_SQLSERVER => format!("SELECT {cols} INTO {stage} FROM {qtarget} WHERE 1 = 0"),
+        ENGINE_POSTGRES | ENGINE_MYSQL | ENGINE_MARIADB | ENGINE_SNOWFLAKE => {
+            format!("CREATE TEMPORARY TABLE {stage} AS {select} WHERE 1 = 0")
+        }
+        ENGINE_SQLITE => format!("CREATE TEMP TABLE {stage} AS {select} WHERE 1 = 0"),
+        ENGINE_ORACLE => format!(
+            "CREATE GLOBAL TEMPORARY TABLE {stage} ON COMMIT PRESERVE ROWS AS {select} WHERE 1 = 0"
+        ),
+        ENGINE_DB2 => format!(
+            "DECLARE GLOBAL TEMPORARY TABLE {stage} AS ({select}) WITH NO DATA \
+             ON COMMIT PRESERVE ROWS NOT LOGGED"
+        ),
+        other => {
+            return Err(OdbcError::UnsupportedFeature(format!(
+                "Bulk upsert has no temp-table support for engine {other:?}"
+            )))
+        }
+    })
+}
+
+/// Statements removing the temp table. Oracle refuses to drop a global
+/// temporary table that still holds session rows, hence the TRUNCATE.
+pub fn drop_staging_sql(engine: &str, stage: &str) -> Vec<String> {
+    match engine {
+        ENGINE_ORACLE => vec![
+            format!("TRUNCATE TABLE {stage}"),
+            format!("DROP TABLE {stage}"),
+        ],
+        ENGINE_MYSQL | ENGINE_MARIADB => vec![format!("DROP TEMPORARY TABLE {stage}")],
+        _ => vec![format!("DROP TABLE {stage}")],
+    }
+}
+
+fn execute(conn: &Connection<'static>, sql: &str, timeout: Option<usize>) -> Result<()> {
+    conn.execute(sql, (), timeout).map_err(OdbcError::from)?;
+    Ok(())
+}
+
+/// Runs the upsert; reads `(inserted, updated)` from the first row when the
+/// statement returns one, else falls back to the affected-row count.
+fn run_upsert(
+    conn: &Connection<'static>,
+    sql: &str,
+    timeout: Option<usize>,
+) -> Result<BulkUpsertReport> {
+    let mut stmt = conn.preallocate().map_err(OdbcError::from)?;
+    if let Some(secs) = timeout {
+        stmt.set_query_timeout_sec(secs).map_err(OdbcError::from)?;
+    }
+    let counts = match stmt.execute(sql, ()).map_err(OdbcError::from)? {
+        Some(mut cursor) => read_counts(&mut cursor)?,
+        None => None,
+    };
+    if let Some((inserted, updated)) = counts {
+        return Ok(BulkUpsertReport {
+            rows_affected: Some(inserted + updated.unwrap_or(0)),
+            inserted: Some(inserted),
+            updated,
+            ..BulkUpsertReport::default()
+        });
+    }
+    let affected = stmt.row_count().map_err(OdbcError::from)?;
+    Ok(BulkUpsertReport {
+        rows_affected: affected.map(|n| n as u64),
+        ..BulkUpsertReport::default()
+    })
+}
+
+/// First two cells of the first row as counts; the second is absent when the
+/// engine only reports inserts (Snowflake MERGE without `WHEN MATCHED`).
+fn read_counts(cursor: &mut impl Cursor) -> Result<Option<(u64, Option<u64>)>> {
+    let cols = cursor.num_result_cols().map_err(OdbcError::from)?;
+    let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? else {
+        return Ok(None);
+    };
+    let mut buf = Vec::new();
+    let mut cell = |col: u16| -> Result<Option<u64>> {
+        buf.clear();
+        if !row.get_text(col, &mut buf).map_err(OdbcError::from)? {
+            return Ok(Some(0));
+        }
+        Ok(std::str::from_utf8(&buf)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok()))
+    };
+    let Some(inserted) = cell(1)? else {
+        return Ok(None);
+    };
+    let updated = if cols >= 2 { cell(2)? } else { None };
+    Ok(Some((inserted, updated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staging_table_names_follow_engine_conventions() {
+        let ss = StagingTable::new(ENGINE_SQLSERVER, "odbc_upsert_1_0").unwrap();
+        assert_eq!(ss.name, "#odbc_upsert_1_0");
+        assert_eq!(ss.quoted, "[#odbc_upsert_1_0]");
+        let db2 = StagingTable::new(ENGINE_DB2, "odbc_upsert_1_0").unwrap();
+        assert_eq!(db2.quoted, "SESSION.\"odbc_upsert_1_0\"");
+        let my = StagingTable::new(ENGINE_MYSQL, "odbc_upsert_1_0").unwrap();
+        assert_eq!(my.name, "odbc_upsert_1_0");
+        assert_eq!(my.quoted, "`odbc_upsert_1_0`");
+        let ase = StagingTable::new(ENGINE_SYBASE_ASE, "odbc_upsert_1_0").unwrap();
+        assert_eq!(ase.quoted, "#odbc_upsert_1_0");
+    }
+
+    #[test]
+    fn create_staging_sql_per_engine() {
+        let cols = ["id", "name"];
+        assert_eq!(
+            create_staging_sql(ENGINE_SQLSERVER, "dbo.t", "[#s]", &cols).unwrap(),
+            "SELECT [id], [name] INTO [#s] FROM [dbo].[t] WHERE 1 = 0"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_POSTGRES, "t", "\"s\"", &cols).unwrap(),
+            "CREATE TEMPORARY TABLE \"s\" AS SELECT \"id\", \"name\" FROM \"t\" WHERE 1 = 0"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_MARIADB, "t", "`s`", &cols).unwrap(),
+            "CREATE TEMPORARY TABLE `s` AS SELECT `id`, `name` FROM `t` WHERE 1 = 0"
+        );
+        assert!(create_staging_sql(ENGINE_ORACLE, "t", "\"s\"", &cols)
+            .unwrap()
+            .starts_with("CREATE GLOBAL TEMPORARY TABLE \"s\" ON COMMIT PRESERVE ROWS AS SELECT"));
+        assert_eq!(
+            create_staging_sql(ENGINE_DB2, "t", "SESSION.\"s\"", &cols).unwrap(),
+            "DECLARE GLOBAL TEMPORARY TABLE SESSION.\"s\" AS (SELECT \"id\", \"name\" FROM \"t\") \
+             WITH NO DATA ON COMMIT PRESERVE ROWS NOT LOGGED"
+        );
+        assert!(create_staging_sql(ENGINE_POSTGRES, "t", "\"s\"", &["x; --"]).is_err());
+        assert!(matches!(
+            create_staging_sql(ENGINE_SYBASE_ASE, "t", "s", &cols),
+            Err(OdbcError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn drop_staging_sql_per_engine() {
+        assert_eq!(
+            drop_staging_sql(ENGINE_ORACLE, "\"s\""),
+            vec!["TRUNCATE TABLE \"s\"", "DROP TABLE \"s\""]
+        );
+        assert_eq!(
+            drop_staging_sql(ENGINE_MYSQL, "`s`"),
+            vec!["DROP TEMPORARY TABLE `s`"]
+        );
+        assert_eq!(
+            drop_staging_sql(ENGINE_SQLITE, "\"s\""),
+            vec!["DROP TABLE \"s\""]
+        );
+    }
+
+    #[test]
+    fn builder_keeps_column_rules() {
+        let upsert = BulkUpsert::new(&["id"]).with_update_columns(&[]);
+        assert_eq!(upsert.conflict_columns, vec!["id".to_string()]);
+        assert_eq!(upsert.update_columns, Some(Vec::new()));
+    }
+}
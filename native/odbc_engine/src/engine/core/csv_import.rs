@@ -0,0 +1,1028 @@
+//! CSV/TSV file import straight into a table.
+//!
+//! [`CsvImporter`] streams a delimited file from disk, parses up to
+//! `batch_size` records at a time into a [`BulkInsertPayload`] and hands each
+//! chunk to the engine's session bulk loader (MySQL/MariaDB LOAD DATA,
+//! Oracle, Snowflake PUT + COPY) or to array binding. Memory stays
+//! bounded by one chunk plus one record of at most `max_record_len` bytes.
+//!
+//! Records follow RFC 4180 when `quote` is set: a field enclosed in quotes may
+//! contain the delimiter, line breaks and doubled quotes. An unquoted field
+//! equal to `null_marker` is NULL; a quoted one never is. Lines that cannot be
+//! used — unbalanced quotes, a wrong field count, a value that does not parse
+//! as its column type — are skipped and reported with their line number.
+//! Blank lines are ignored.
+//!
+//! Text cells are read as UTF-8 (a leading BOM is dropped). Type hints map to
+//! [`BulkColumnType`]s; columns without a hint load as text and are converted
+//! by the database. Parsing is connection-free ([`CsvImporter::import_with`]),
+//! so everything but the load itself is unit-tested.
+
+use crate::engine::core::bulk_upsert::load_payload;
+use crate::engine::core::driver_capabilities::DriverCapabilities;
+use crate::engine::identifier::{quote_qualified_default, validate_identifier};
+use crate::error::{OdbcError, Result};
+use crate::plugins::capabilities::bulk_loader::BulkLoadOptions;
+use crate::protocol::bulk_insert::null_bitmap_size;
+use crate::protocol::{
+    BulkColumnData, BulkColumnSpec, BulkColumnType, BulkDate, BulkInsertPayload, BulkTime,
+    BulkTimestamp,
+};
+use odbc_api::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Malformed lines kept in [`CsvImportReport::malformed`]; the rest are only counted.
+pub const MAX_REPORTED_MALFORMED: usize = 1_000;
+
+/// Parsing and batching options for [`CsvImporter`].
+#[derive(Debug, Clone)]
+pub struct CsvImportOptions {
+    /// Field separator.
+    pub delimiter: u8,
+    /// Quote character; `None` reads every field verbatim.
+    pub quote: Option<u8>,
+    /// The first record holds column names.
+    pub has_header: bool,
+    /// Unquoted field value read as NULL.
+    pub null_marker: String,
+    /// Records per loaded chunk.
+    pub batch_size: usize,
+    /// Abort once more than this many lines were malformed; `None` never aborts.
+    /// Chunks loaded before that point stay loaded.
+    pub max_errors: Option<u64>,
+    /// Longest record accepted, in bytes; longer ones are reported as malformed.
+    pub max_record_len: usize,
+}
+
+impl CsvImportOptions {
+    /// Comma-separated, `"`-quoted, header row, empty unquoted field = NULL.
+    pub fn csv() -> Self {
+        Self {
+            delimiter: b',',
+            quote: Some(b'"'),
+            has_header: true,
+            null_marker: String::new(),
+            batch_size: 10_000,
+            max_errors: None,
+            max_record_len: 1 << 20,
+        }
+    }
+
+    /// Tab-separated, unquoted, header row, `\N` = NULL (the PostgreSQL/MySQL
+    /// text format).
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: b'\t',
+            quote: None,
+            null_marker: "\\N".to_string(),
+            ..Self::csv()
+        }
+    }
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+/// Reported after each loaded chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvImportProgress {
+    /// Physical lines consumed so far (header included).
+    pub lines_read: u64,
+    pub bytes_read: u64,
+    /// File size, when known.
+    pub total_bytes: Option<u64>,
+    pub rows_loaded: u64,
+    pub rows_rejected: u64,
+}
+
+/// A line [`CsvImporter`] skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CsvMalformedLine {
+    /// 1-based line on which the record starts.
+    pub line: u64,
+    pub message: String,
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CsvImportReport {
+    pub lines_read: u64,
+    pub rows_loaded: u64,
+    pub batches: u64,
+    /// Every malformed line, including those beyond [`MAX_REPORTED_MALFORMED`].
+    pub malformed_count: u64,
+    /// The first [`MAX_REPORTED_MALFORMED`] malformed lines.
+    pub malformed: Vec<CsvMalformedLine>,
+}
+
+/// Streams a delimited file into `table`.
+#[derive(Debug, Clone)]
+pub struct CsvImporter {
+    table: String,
+    options: CsvImportOptions,
+    columns: Option<Vec<String>>,
+    type_hints: HashMap<String, BulkColumnType>,
+}
+
+impl CsvImporter {
+    pub fn new(table: &str, options: CsvImportOptions) -> Self {
+        Self {
+            table: table.to_string(),
+            options,
+            columns: None,
+            type_hints: HashMap::new(),
+        }
+    }
+
+    /// Target column for each field, in file order. Required without a
+    /// header; with one, it replaces the names the header gives.
+    pub fn with_columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Parses `column` as `col_type` instead of text.
+    pub fn with_type(mut self, column: &str, col_type: BulkColumnType) -> Self {
+        self.type_hints.insert(column.to_string(), col_type);
+        self
+    }
+
+    /// Imports the file at `path` through `conn`.
+    pub fn import_file(
+        &self,
+        conn: &Connection<'static>,
+        path: &Path,
+        on_progress: &mut dyn FnMut(&CsvImportProgress),
+    ) -> Result<CsvImportReport> {
+        let file = File::open(path).map_err(|e| {
+            OdbcError::ValidationError(format!("Cannot open {}: {e}", path.display()))
+        })?;
+        let total_bytes = file.metadata().ok().map(|m| m.len());
+        let engine = DriverCapabilities::detect(conn)?.engine;
+        let qtable = quote_qualified_default(&self.table)?;
+        let load_options = BulkLoadOptions::new(self.options.batch_size);
+        self.import_with(
+            file,
+            total_bytes,
+            &mut |payload| load_payload(conn, &engine, &qtable, payload, &load_options),
+            on_progress,
+        )
+    }
+
+    /// Parses `input` and passes each chunk to `load`, which returns the rows
+    /// it wrote.
+    pub fn import_with(
+        &self,
+        input: impl Read,
+        total_bytes: Option<u64>,
+        load: &mut dyn FnMut(&BulkInsertPayload) -> Result<usize>,
+        on_progress: &mut dyn FnMut(&CsvImportProgress),
+    ) -> Result<CsvImportReport> {
+        validate_options(&self.options)?;
+        quote_qualified_default(&self.table)?;
+        let mut reader = RecordReader::new(BufReader::new(input), &self.options);
+        let mut report = CsvImportReport::default();
+
+        let header = if self.options.has_header {
+            match reader.next_record()? {
+                Some(ReadOutcome::Record { fields, .. }) => {
+                    Some(fields.into_iter().map(|f| f.text).collect::<Vec<_>>())
+                }
+                Some(ReadOutcome::Malformed { line, message }) => {
+                    return Err(OdbcError::ValidationError(format!(
+                        "Malformed CSV header on line {line}: {message}"
+                    )))
+                }
+                None => return Ok(report),
+            }
+        } else {
+            None
+        };
+        let names = match (&self.columns, header) {
+            (Some(columns), _) => columns.clone(),
+            (None, Some(header)) => header.iter().map(|h| h.trim().to_string()).collect(),
+            (None, None) => {
+                return Err(OdbcError::ValidationError(
+                    "CSV import without a header row needs explicit column names".to_string(),
+                ))
+            }
+        };
+        let types = self.column_types(&names)?;
+
+        let mut chunk = Chunk::new(&self.table, &names, &types, self.options.batch_size);
+        while let Some(outcome) = reader.next_record()? {
+            report.lines_read = reader.line;
+            let (line, fields) = match outcome {
+                ReadOutcome::Record { line, fields } => (line, fields),
+                ReadOutcome::Malformed { line, message } => {
+                    self.reject(&mut report, line, message)?;
+                    continue;
+                }
+            };
+            if fields.len() != names.len() {
+                let message = format!("expected {} fields, found {}", names.len(), fields.len());
+                self.reject(&mut report, line, message)?;
+                continue;
+            }
+            let row = fields
+                .iter()
+                .zip(names.iter().zip(&types))
+                .map(|(field, (name, ty))| {
+                    parse_cell(field, ty, &self.options.null_marker)
+                        .map_err(|e| format!("column {name}: {e}"))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>();
+            match row {
+                Ok(row) => chunk.push(row),
+                Err(message) => {
+                    self.reject(&mut report, line, message)?;
+                    continue;
+                }
+            }
+            if chunk.rows == self.options.batch_size {
+                self.flush(
+                    &mut chunk,
+                    &mut report,
+                    &reader,
+                    total_bytes,
+                    load,
+                    on_progress,
+                )?;
+            }
+        }
+        report.lines_read = reader.line;
+        if chunk.rows > 0 {
+            self.flush(
+                &mut chunk,
+                &mut report,
+                &reader,
+                total_bytes,
+                load,
+                on_progress,
+            )?;
+        }
+        Ok(report)
+    }
+
+    fn column_types(&self, names: &[String]) -> Result<Vec<BulkColumnType>> {
+        for name in names {
+            validate_identifier(name)?;
+        }
+        if let Some(unknown) = self.type_hints.keys().find(|k| !names.contains(k)) {
+            return Err(OdbcError::ValidationError(format!(
+                "Type hint for {unknown:?}, which is not an imported column"
+            )));
+        }
+        Ok(names
+            .iter()
+            .map(|n| {
+                self.type_hints
+                    .get(n)
+                    .cloned()
+                    .unwrap_or(BulkColumnType::Text)
+            })
+            .collect())
+    }
+
+    fn reject(&self, report: &mut CsvImportReport, line: u64, message: String) -> Result<()> {
+        report.malformed_count += 1;
+        if report.malformed.len() < MAX_REPORTED_MALFORMED {
+            report.malformed.push(CsvMalformedLine { line, message });
+        }
+        match self.options.max_errors {
+            Some(max) if report.malformed_count > max => Err(OdbcError::ValidationError(format!(
+                "CSV import aborted after {} malformed line(s) (limit {max}); first on line {}",
+                report.malformed_count,
+                report.malformed.first().map_or(line, |m| m.line)
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn flush<R: BufRead>(
+        &self,
+        chunk: &mut Chunk,
+        report: &mut CsvImportReport,
+        reader: &RecordReader<R>,
+        total_bytes: Option<u64>,
+        load: &mut dyn FnMut(&BulkInsertPayload) -> Result<usize>,
+        on_progress: &mut dyn FnMut(&CsvImportProgress),
+    ) -> Result<()> {
+        let payload = chunk.take_payload();
+        report.rows_loaded += load(&payload)? as u64;
+        report.batches += 1;
+        on_progress(&CsvImportProgress {
+            lines_read: reader.line,
+            bytes_read: reader.bytes_read,
+            total_bytes,
+            rows_loaded: report.rows_loaded,
+            rows_rejected: report.malformed_count,
+        });
+        Ok(())
+    }
+}
+
+fn validate_options(options: &CsvImportOptions) -> Result<()> {
+    if options.batch_size == 0 {
+        return Err(OdbcError::ValidationError(
+            "CSV import batch_size must be > 0".to_string(),
+        ));
+    }
+    let special = [b'\n', b'\r'];
+    if special.contains(&options.delimiter)
+        || options
+            .quote
+            .is_some_and(|q| q == options.delimiter || special.contains(&q))
+    {
+        return Err(OdbcError::ValidationError(
+            "CSV delimiter and quote must differ and must not be line breaks".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// One parsed field; `quoted` fields are never NULL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ReadOutcome {
+    Record { line: u64, fields: Vec<Field> },
+    Malformed { line: u64, message: String },
+}
+
+/// Splits a byte stream into records, one physical line at a time.
+struct RecordReader<R> {
+    inner: R,
+    delimiter: u8,
+    quote: Option<u8>,
+    max_record_len: usize,
+    /// Physical lines consumed.
+    line: u64,
+    bytes_read: u64,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    fn new(inner: R, options: &CsvImportOptions) -> Self {
+        Self {
+            inner,
+            delimiter: options.delimiter,
+            quote: options.quote,
+            max_record_len: options.max_record_len.max(1),
+            line: 0,
+            bytes_read: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Reads the next physical line into `buf`, keeping at most the bytes a
+    /// record may still take; `None` at end of input, `Some(true)` when the
+    /// line was cut short.
+    fn read_line(&mut self, room: usize) -> Result<Option<bool>> {
+        self.buf.clear();
+        let mut truncated = false;
+        let mut any = false;
+        loop {
+            let available = self.inner.fill_buf().map_err(io_error)?;
+            if available.is_empty() {
+                break;
+            }
+            any = true;
+            let (take, done) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => (i + 1, true),
+                None => (available.len(), false),
+            };
+            let keep = take.min(room.saturating_sub(self.buf.len()));
+            truncated |= keep < take;
+            self.buf.extend_from_slice(&available[..keep]);
+            self.inner.consume(take);
+            self.bytes_read += take as u64;
+            if done {
+                break;
+            }
+        }
+        if !any {
+            return Ok(None);
+        }
+        self.line += 1;
+        if self.line == 1 && self.buf.starts_with(b"\xEF\xBB\xBF") {
+            self.buf.drain(..3);
+        }
+        Ok(Some(truncated))
+    }
+
+    fn next_record(&mut self) -> Result<Option<ReadOutcome>> {
+        let mut fields = Vec::new();
+        let mut field = Vec::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut after_quote = false;
+        let mut start_line = 0;
+        let mut record_len = 0usize;
+
+        loop {
+            let Some(truncated) = self.read_line(self.max_record_len - record_len)? else {
+                return Ok((start_line != 0).then(|| ReadOutcome::Malformed {
+                    line: start_line,
+                    message: "unterminated quoted field".to_string(),
+                }));
+            };
+            if start_line == 0 {
+                if matches!(self.buf.as_slice(), b"\n" | b"\r\n") {
+                    continue;
+                }
+                start_line = self.line;
+            }
+            if truncated {
+                return Ok(Some(ReadOutcome::Malformed {
+                    line: start_line,
+                    message: format!("record longer than {} bytes", self.max_record_len),
+                }));
+            }
+            record_len += self.buf.len();
+
+            // Where the line terminator starts; it only ends the record
+            // outside quotes.
+            let body_end = self.buf.len()
+                - if self.buf.ends_with(b"\r\n") {
+                    2
+                } else if self.buf.ends_with(b"\n") {
+                    1
+                } else {
+                    0
+                };
+            for (i, &b) in self.buf.iter().enumerate() {
+                if in_quotes {
+                    if Some(b) == self.quote {
+                        in_quotes = false;
+                        after_quote = true;
+                    } else {
+                        field.push(b);
+                    }
+                } else if i >= body_end {
+                    break;
+                } else if after_quote {
+                    if Some(b) == self.quote {
+                        field.push(b);
+                        in_quotes = true;
+                        after_quote = false;
+                    } else if b == self.delimiter {
+                        fields.push(finish_field(&mut field, quoted, fields.len()));
+                        quoted = false;
+                        after_quote = false;
+                    } else {
+                        return Ok(Some(ReadOutcome::Malformed {
+                            line: start_line,
+                            message: format!(
+                                "unexpected {:?} after closing quote in field {}",
+                                b as char,
+                                fields.len() + 1
+                            ),
+                        }));
+                    }
+                } else if b == self.delimiter {
+                    fields.push(finish_field(&mut field, quoted, fields.len()));
+                } else if Some(b) == self.quote && field.is_empty() && !quoted {
+                    quoted = true;
+                    in_quotes = true;
+                } else {
+                    field.push(b);
+                }
+            }
+            if !in_quotes {
+                fields.push(finish_field(&mut field, quoted, fields.len()));
+                let line = start_line;
+                return Ok(Some(
+                    match fields
+                        .into_iter()
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                    {
+                        Ok(fields) => ReadOutcome::Record { line, fields },
+                        Err(message) => ReadOutcome::Malformed { line, message },
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Moves the bytes of `field` into a [`Field`]; invalid UTF-8 is kept as an
+/// error so the record is reported instead of aborting the import.
+fn finish_field(
+    field: &mut Vec<u8>,
+    quoted: bool,
+    index: usize,
+) -> std::result::Result<Field, String> {
+    String::from_utf8(std::mem::take(field))
+        .map(|text| Field { text, quoted })
+        .map_err(|_| format!("invalid UTF-8 in field {}", index + 1))
+}
+
+fn io_error(e: std::io::Error) -> OdbcError {
+    OdbcError::InternalError(format!("CSV read failed: {e}"))
+}
+
+/// One typed value of a record.
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Null,
+    I32(i32),
+    I64(i64),
+    Bytes(Vec<u8>),
+    Wide(Vec<u16>),
+    Timestamp(BulkTimestamp),
+    Bool(bool),
+    F64(f64),
+    F32(f32),
+    Date(BulkDate),
+    Time(BulkTime),
+    Uuid([u8; 16]),
+}
+
+fn parse_cell(
+    field: &Field,
+    ty: &BulkColumnType,
+    null_marker: &str,
+) -> std::result::Result<Cell, String> {
+    if !field.quoted && field.text == null_marker {
+        return Ok(Cell::Null);
+    }
+    let text = field.text.as_str();
+    let trimmed = text.trim();
+    let invalid = |what: &str| format!("invalid {what} {text:?}");
+    Ok(match ty {
+        BulkColumnType::Text | BulkColumnType::Decimal => Cell::Bytes(text.as_bytes().to_vec()),
+        BulkColumnType::NText => Cell::Wide(text.encode_utf16().collect()),
+        BulkColumnType::I32 => Cell::I32(trimmed.parse().map_err(|_| invalid("integer"))?),
+        BulkColumnType::I64 => Cell::I64(trimmed.parse().map_err(|_| invalid("integer"))?),
+        BulkColumnType::F64 => Cell::F64(trimmed.parse().map_err(|_| invalid("number"))?),
+        BulkColumnType::F32 => Cell::F32(trimmed.parse().map_err(|_| invalid("number"))?),
+        BulkColumnType::Bool => Cell::Bool(parse_bool(trimmed).ok_or_else(|| invalid("boolean"))?),
+        BulkColumnType::Binary => Cell::Bytes(parse_hex(trimmed).ok_or_else(|| invalid("hex"))?),
+        BulkColumnType::Date => Cell::Date(parse_date(trimmed).ok_or_else(|| invalid("date"))?),
+        BulkColumnType::Time => Cell::Time(parse_time(trimmed).ok_or_else(|| invalid("time"))?),
+        BulkColumnType::Timestamp => {
+            Cell::Timestamp(parse_timestamp(trimmed).ok_or_else(|| invalid("timestamp"))?)
+        }
+        BulkColumnType::Uuid => Cell::Uuid(parse_uuid(trimmed).ok_or_else(|| invalid("UUID"))?),
+    })
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "1" | "t" | "true" | "y" | "yes" => Some(true),
+        "0" | "f" | "false" | "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Hex digits, optionally prefixed with `0x` or `\x`.
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("\\x"))
+        .unwrap_or(s);
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// `YYYY-MM-DD`.
+fn parse_date(s: &str) -> Option<BulkDate> {
+    let mut parts = s.splitn(3, '-');
+    let year: i16 = parts.next()?.parse().ok()?;
+    let month: u16 = parts.next()?.parse().ok()?;
+    let day: u16 = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some(BulkDate { year, month, day })
+}
+
+/// `HH:MM:SS`, ignoring any fractional part.
+fn parse_time(s: &str) -> Option<BulkTime> {
+    let (time, _) = split_fraction(s)?;
+    let mut parts = time.splitn(3, ':');
+    let hour: u16 = parts.next()?.parse().ok()?;
+    let minute: u16 = parts.next()?.parse().ok()?;
+    let second: u16 = parts.next()?.parse().ok()?;
+    (hour < 24 && minute < 60 && second < 62).then_some(BulkTime {
+        hour,
+        minute,
+        second,
+    })
+}
+
+/// `YYYY-MM-DD[ T]HH:MM:SS[.fraction]`, or a bare date at midnight.
+fn parse_timestamp(s: &str) -> Option<BulkTimestamp> {
+    let (date, time) = match s.find([' ', 'T']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    let date = parse_date(date)?;
+    let (clock, fraction) = match time {
+        Some(t) => (parse_time(t)?, split_fraction(t)?.1),
+        None => (
+            BulkTime {
+                hour: 0,
+                minute: 0,
+                second: 0,
+            },
+            0,
+        ),
+    };
+    Some(BulkTimestamp {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+        hour: clock.hour,
+        minute: clock.minute,
+        second: clock.second,
+        fraction,
+    })
+}
+
+/// Splits `HH:MM:SS.fffffffff` into the clock part and nanoseconds.
+fn split_fraction(s: &str) -> Option<(&str, u32)> {
+    let Some((clock, digits)) = s.split_once('.') else {
+        return Some((s, 0));
+    };
+    if digits.is_empty() || digits.len() > 9 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value: u32 = digits.parse().ok()?;
+    Some((clock, value * 10u32.pow(9 - digits.len() as u32)))
+}
+
+/// 32 hex digits, hyphens anywhere ignored.
+fn parse_uuid(s: &str) -> Option<[u8; 16]> {
+    let digits: String = s.chars().filter(|&c| c != '-').collect();
+    let bytes = parse_hex(&digits)?;
+    bytes.try_into().ok()
+}
+
+/// Column-major buffer for the records of one chunk.
+struct Chunk {
+    table: String,
+    names: Vec<String>,
+    types: Vec<BulkColumnType>,
+    cells: Vec<Vec<Cell>>,
+    rows: usize,
+}
+
+impl Chunk {
+    fn new(table: &str, names: &[String], types: &[BulkColumnType], capacity: usize) -> Self {
+        Self {
+            table: table.to_string(),
+            names: names.to_vec(),
+            types: types.to_vec(),
+            cells: names.iter().map(|_| Vec::with_capacity(capacity)).collect(),
+            rows: 0,
+        }
+    }
+
+    fn push(&mut self, row: Vec<Cell>) {
+        for (column, cell) in self.cells.iter_mut().zip(row) {
+            column.push(cell);
+        }
+        self.rows += 1;
+    }
+
+    fn take_payload(&mut self) -> BulkInsertPayload {
+        let rows = std::mem::take(&mut self.rows);
+        let mut columns = Vec::with_capacity(self.names.len());
+        let mut column_data = Vec::with_capacity(self.names.len());
+        for ((name, ty), cells) in self.names.iter().zip(&self.types).zip(&mut self.cells) {
+            let data = column_data_from(ty, std::mem::take(cells));
+            let max_len = match &data {
+                BulkColumnData::Text { max_len, .. }
+                | BulkColumnData::Binary { max_len, .. }
+                | BulkColumnData::NText { max_len, .. } => *max_len,
+                _ => 0,
+            };
+            columns.push(BulkColumnSpec {
+                name: name.clone(),
+                col_type: ty.clone(),
+                nullable: true,
+                max_len,
+            });
+            column_data.push(data);
+        }
+        BulkInsertPayload {
+            table: self.table.clone(),
+            columns,
+            row_count: rows as u32,
+            column_data,
+        }
+    }
+}
+
+fn column_data_from(ty: &BulkColumnType, cells: Vec<Cell>) -> BulkColumnData {
+    let mut bitmap = vec![0u8; null_bitmap_size(cells.len())];
+    let mut any_null = false;
+    for (row, cell) in cells.iter().enumerate() {
+        if *cell == Cell::Null {
+            bitmap[row / 8] |= 1 << (row % 8);
+            any_null = true;
+        }
+    }
+    let null_bitmap = any_null.then_some(bitmap);
+    let zero_date = BulkDate {
+        year: 1,
+        month: 1,
+        day: 1,
+    };
+    let zero_time = BulkTime {
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    macro_rules! values {
+        ($variant:ident, $default:expr) => {
+            cells
+                .into_iter()
+                .map(|c| match c {
+                    Cell::$variant(v) => v,
+                    _ => $default,
+                })
+                .collect()
+        };
+    }
+    let bytes = |cells: Vec<Cell>| -> (Vec<Vec<u8>>, usize) {
+        let rows: Vec<Vec<u8>> = cells
+            .into_iter()
+            .map(|c| match c {
+                Cell::Bytes(b) => b,
+                _ => Vec::new(),
+            })
+            .collect();
+        let max_len = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        (rows, max_len)
+    };
+
+    match ty {
+        BulkColumnType::Text | BulkColumnType::Decimal => {
+            let (rows, max_len) = bytes(cells);
+            BulkColumnData::Text {
+                rows,
+                max_len,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::Binary => {
+            let (rows, max_len) = bytes(cells);
+            BulkColumnData::Binary {
+                rows,
+                max_len,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::NText => {
+            let rows: Vec<Vec<u16>> = values!(Wide, Vec::new());
+            let max_len = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+            BulkColumnData::NText {
+                rows,
+                max_len,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::I32 => BulkColumnData::I32 {
+            values: values!(I32, 0),
+            null_bitmap,
+        },
+        BulkColumnType::I64 => BulkColumnData::I64 {
+            values: values!(I64, 0),
+            null_bitmap,
+        },
+        BulkColumnType::F64 => BulkColumnData::F64 {
+            values: values!(F64, 0.0),
+            null_bitmap,
+        },
+        BulkColumnType::F32 => BulkColumnData::F32 {
+            values: values!(F32, 0.0),
+            null_bitmap,
+        },
+        BulkColumnType::Bool => BulkColumnData::Bool {
+            values: values!(Bool, false),
+            null_bitmap,
+        },
+        BulkColumnType::Date => BulkColumnData::Date {
+            values: values!(Date, zero_date),
+            null_bitmap,
+        },
+        BulkColumnType::Time => BulkColumnData::Time {
+            values: values!(Time, zero_time),
+            null_bitmap,
+        },
+        BulkColumnType::Timestamp => BulkColumnData::Timestamp {
+            values: values!(
+                Timestamp,
+                BulkTimestamp {
+                    year: 1,
+                    month: 1,
+                    day: 1,
+                    hour: 0,
+                    minute: 0,
+                    second: 0,
+                    fraction: 0,
+                }
+            ),
+            null_bitmap,
+        },
+        BulkColumnType::Uuid => BulkColumnData::Uuid {
+            values: values!(Uuid, [0u8; 16]),
+            null_bitmap,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::bulk_insert::is_null;
+
+    fn run(
+        importer: &CsvImporter,
+        input: &str,
+    ) -> (
+        Result<CsvImportReport>,
+        Vec<BulkInsertPayload>,
+        Vec<CsvImportProgress>,
+    ) {
+        let mut chunks = Vec::new();
+        let mut progress = Vec::new();
+        let report = importer.import_with(
+            input.as_bytes(),
+            Some(input.len() as u64),
+            &mut |p| {
+                chunks.push(p.clone());
+                Ok(p.row_count as usize)
+            },
+            &mut |p| progress.push(*p),
+        );
+        (report, chunks, progress)
+    }
+
+    fn text_rows(data: &BulkColumnData) -> Vec<Option<String>> {
+        let BulkColumnData::Text {
+            rows, null_bitmap, ..
+        } = data
+        else {
+            panic!("text column expected, got {data:?}");
+        };
+        rows.iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let null = null_bitmap.as_deref().is_some_and(|b| is_null(b, i));
+                (!null).then(|| String::from_utf8(r.clone()).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_rfc4180_quoting_nulls_and_line_endings() {
+        let input =
+            "\u{feff}id,name\r\n1,\"a, \"\"quoted\"\"\"\r\n2,\r\n3,\"\"\n\n4,\"multi\nline\"";
+        let importer =
+            CsvImporter::new("t", CsvImportOptions::csv()).with_type("id", BulkColumnType::I32);
+        let (report, chunks, _) = run(&importer, input);
+        let report = report.unwrap();
+        assert_eq!(report.rows_loaded, 4);
+        assert_eq!(report.malformed_count, 0);
+        assert_eq!(report.lines_read, 7);
+
+        let payload = &chunks[0];
+        assert_eq!(payload.columns[0].name, "id");
+        let BulkColumnData::I32 { values, .. } = &payload.column_data[0] else {
+            panic!("i32 column expected");
+        };
+        assert_eq!(values, &vec![1, 2, 3, 4]);
+        assert_eq!(
+            text_rows(&payload.column_data[1]),
+            vec![
+                Some("a, \"quoted\"".to_string()),
+                None,
+                Some(String::new()),
+                Some("multi\nline".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_lines_and_keeps_going() {
+        let input = "id,v\n1,a\n2\nx,b\n3,\"c\"d\n4,d\n5,\"open\n";
+        let importer =
+            CsvImporter::new("t", CsvImportOptions::csv()).with_type("id", BulkColumnType::I64);
+        let (report, chunks, _) = run(&importer, input);
+        let report = report.unwrap();
+        assert_eq!(report.rows_loaded, 2);
+        assert_eq!(chunks[0].row_count, 2);
+        let lines: Vec<u64> = report.malformed.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![3, 4, 5, 7]);
+        assert!(report.malformed[0]
+            .message
+            .contains("expected 2 fields, found 1"));
+        assert!(report.malformed[1].message.contains("column id"));
+        assert!(report.malformed[2].message.contains("after closing quote"));
+        assert!(report.malformed[3].message.contains("unterminated"));
+    }
+
+    #[test]
+    fn loads_in_batches_and_reports_progress() {
+        let mut options = CsvImportOptions::csv();
+        options.batch_size = 2;
+        let importer = CsvImporter::new("t", options);
+        let (report, chunks, progress) = run(&importer, "a\n1\n2\n3\n4\n5\n");
+        assert_eq!(report.unwrap().batches, 3);
+        assert_eq!(
+            chunks.iter().map(|c| c.row_count).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        let last = progress.last().unwrap();
+        assert_eq!(last.rows_loaded, 5);
+        assert_eq!(last.bytes_read, 12);
+        assert_eq!(last.total_bytes, Some(12));
+    }
+
+    #[test]
+    fn aborts_past_max_errors() {
+        let mut options = CsvImportOptions::csv();
+        options.max_errors = Some(1);
+        let importer = CsvImporter::new("t", options).with_type("a", BulkColumnType::I32);
+        let (report, _, _) = run(&importer, "a\nx\n1\ny\n2\n");
+        let err = report.unwrap_err().to_string();
+        assert!(err.contains("first on line 2"), "{err}");
+    }
+
+    #[test]
+    fn tsv_without_header_needs_columns() {
+        let mut options = CsvImportOptions::tsv();
+        options.has_header = false;
+        let (report, _, _) = run(&CsvImporter::new("t", options.clone()), "1\tx\n");
+        assert!(report.is_err());
+
+        let importer = CsvImporter::new("t", options).with_columns(&["id", "name"]);
+        let (report, chunks, _) = run(&importer, "1\t\"x\"\n2\t\\N\n");
+        assert_eq!(report.unwrap().rows_loaded, 2);
+        assert_eq!(
+            text_rows(&chunks[0].column_data[1]),
+            vec![Some("\"x\"".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_names_hints_and_long_records() {
+        let (report, _, _) = run(&CsvImporter::new("t", CsvImportOptions::csv()), "a;b\n1\n");
+        assert!(report.is_err());
+        let importer =
+            CsvImporter::new("t", CsvImportOptions::csv()).with_type("zz", BulkColumnType::I32);
+        let (report, _, _) = run(&importer, "a\n1\n");
+        assert!(report.is_err());
+
+        let mut options = CsvImportOptions::csv();
+        options.max_record_len = 8;
+        let (report, _, _) = run(&CsvImporter::new("t", options), "a\n0123456789\n1\n");
+        let report = report.unwrap();
+        assert_eq!(report.rows_loaded, 1);
+        assert_eq!(report.malformed[0].line, 2);
+    }
+
+    #[test]
+    fn typed_values_parse() {
+        assert_eq!(parse_hex("0x0aFF"), Some(vec![0x0a, 0xff]));
+        assert_eq!(parse_hex("abc"), None);
+        assert_eq!(parse_bool("Yes"), Some(true));
+        assert_eq!(parse_bool("maybe"), None);
+        assert_eq!(
+            parse_timestamp("2024-02-29T12:30:05.25"),
+            Some(BulkTimestamp {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 12,
+                minute: 30,
+                second: 5,
+                fraction: 250_000_000,
+            })
+        );
+        assert_eq!(parse_timestamp("2024-02-29").map(|t| t.hour), Some(0));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_time("23:59:60").map(|t| t.second), Some(60));
+        let uuid = parse_uuid("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+        assert_eq!(uuid[0], 0x00);
+        assert_eq!(uuid[15], 0xff);
+    }
+}
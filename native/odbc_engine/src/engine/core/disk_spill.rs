@@ -1,10 +1,71 @@
 use crate::error::{OdbcError, Result};
-use std::fs::File;
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use crate::protocol::columnar::CompressionType;
+use crate::protocol::compression::{compress, decompress_with_limit};
+use crate::protocol::encoder::PatchWrite;
+use crate::security::secure_buffer::SecureBuffer;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Once;
+use std::time::Duration;
 
 const DEFAULT_THRESHOLD_MB: usize = 100;
 const WRITE_CHUNK_SIZE: usize = 64 * 1024;
+/// Spilled bytes per compressed / encrypted frame.
+const FRAME_BYTES: usize = 256 * 1024;
+const SPILL_FILE_PREFIX: &str = "odbc_spill_";
+/// Where the owner of a spill file cannot be checked, files from other
+/// processes older than this are treated as left behind by a crash.
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+const ORPHAN_MIN_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Distinguishes spill files created in the same millisecond.
+static SPILL_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+static ORPHAN_SWEEP: Once = Once::new();
+
+/// How spilled bytes are stored on disk. Compression and encryption apply to
+/// frames of up to 256 KiB, so [`DiskSpillStream::read_at`] and
+/// [`DiskSpillStream::patch`] only decode the frames they touch. Bytes still
+/// below the memory threshold are kept as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpillOptions {
+    pub compression: CompressionType,
+    /// AES-256-GCM with a random key that only lives in process memory, so
+    /// the file is unreadable once the stream is gone.
+    pub encrypt: bool,
+}
+
+impl SpillOptions {
+    /// Raw bytes, the historical behaviour.
+    pub const PLAIN: Self = Self {
+        compression: CompressionType::None,
+        encrypt: false,
+    };
+
+    pub fn with_compression(mut self, compression: CompressionType) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_encryption(mut self, encrypt: bool) -> Self {
+        self.encrypt = encrypt;
+        self
+    }
+
+    fn is_plain(&self) -> bool {
+        *self == Self::PLAIN
+    }
+}
+
+impl Default for SpillOptions {
+    fn default() -> Self {
+        Self::PLAIN
+    }
+}
 
 /// Adapter that implements `Write` and forwards to `DiskSpillStream::write_chunk`.
 /// Buffers up to 64KB before calling write_chunk.
@@ -45,16 +106,45 @@ impl Write for DiskSpillWriter<'_> {
     }
 }
 
+/// Lets [`crate::protocol::encoder::RowStreamEncoder`] encode straight into the spill.
+impl PatchWrite for DiskSpillWriter<'_> {
+    fn written_len(&self) -> u64 {
+        self.spill.written_len() + self.buffer.len() as u64
+    }
+
+    fn patch(&mut self, offset: u64, bytes: &[u8]) -> std::io::Result<()> {
+        self.flush()?;
+        self.spill
+            .patch(offset, bytes)
+            .map_err(std::io::Error::other)
+    }
+}
+
 pub struct DiskSpillStream {
     threshold_bytes: usize,
     temp_dir: PathBuf,
     temp_path: Option<PathBuf>,
     file: Option<BufWriter<File>>,
     memory_buffer: Vec<u8>,
+    /// Bytes accepted by [`Self::write_chunk`] since creation.
+    written: u64,
+    /// Set unless the options are [`SpillOptions::PLAIN`]; the file then
+    /// holds frames instead of raw bytes.
+    framed: Option<FramedSpill>,
 }
 
 impl DiskSpillStream {
+    /// Spill stored as raw bytes ([`SpillOptions::PLAIN`]).
     pub fn new(threshold_mb: usize) -> Self {
+        Self::with_options(threshold_mb, SpillOptions::PLAIN)
+    }
+
+    /// Spill with the default memory threshold, stored per `options`.
+    pub fn with_default_threshold(options: SpillOptions) -> Self {
+        Self::with_options(DEFAULT_THRESHOLD_MB, options)
+    }
+
+    pub fn with_options(threshold_mb: usize, options: SpillOptions) -> Self {
         let threshold_bytes = (threshold_mb.max(1)) * 1024 * 1024;
         Self {
             threshold_bytes,
@@ -62,59 +152,154 @@ impl DiskSpillStream {
             temp_path: None,
             file: None,
             memory_buffer: Vec::new(),
+            written: 0,
+            framed: (!options.is_plain()).then(|| FramedSpill::new(options)),
         }
     }
 
+    /// True once the bytes went past the threshold into a temp file.
+    pub fn is_on_disk(&self) -> bool {
+        self.file.is_some()
+    }
+
     pub fn threshold_mb(&self) -> usize {
         self.threshold_bytes / (1024 * 1024)
     }
 
     pub fn write_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        self.written += chunk.len() as u64;
         if self.file.is_some() {
-            self.file
-                .as_mut()
-                .ok_or_else(|| OdbcError::InternalError("spill file missing".to_string()))?
-                .write_all(chunk)
-                .map_err(|e| OdbcError::InternalError(format!("spill write: {}", e)))?;
-            return Ok(());
+            return self.write_to_disk(chunk);
         }
 
         let would_exceed =
             self.memory_buffer.len().saturating_add(chunk.len()) > self.threshold_bytes;
         if would_exceed && !self.memory_buffer.is_empty() {
             self.spill_to_disk()?;
-            self.file
-                .as_mut()
-                .ok_or_else(|| OdbcError::InternalError("spill file missing".to_string()))?
-                .write_all(chunk)
-                .map_err(|e| OdbcError::InternalError(format!("spill write: {}", e)))?;
+            self.write_to_disk(chunk)?;
         } else {
             self.memory_buffer.extend_from_slice(chunk);
         }
         Ok(())
     }
 
+    fn write_to_disk(&mut self, chunk: &[u8]) -> Result<()> {
+        let w = self
+            .file
+            .as_mut()
+            .ok_or_else(|| OdbcError::InternalError("spill file missing".to_string()))?;
+        match self.framed.as_mut() {
+            Some(framed) => framed.append(w, chunk),
+            None => w
+                .write_all(chunk)
+                .map_err(|e| OdbcError::InternalError(format!("spill write: {}", e))),
+        }
+    }
+
+    pub fn written_len(&self) -> u64 {
+        self.written
+    }
+
+    /// Overwrites already-written bytes at `offset`, whether they are still in
+    /// memory or on disk (used to back-patch message headers).
+    pub fn patch(&mut self, offset: u64, bytes: &[u8]) -> Result<()> {
+        let in_bounds = offset
+            .checked_add(bytes.len() as u64)
+            .is_some_and(|end| end <= self.written);
+        if !in_bounds {
+            return Err(OdbcError::InternalError(format!(
+                "spill patch at {offset} past end {}",
+                self.written
+            )));
+        }
+        let io_err = |e: std::io::Error| OdbcError::InternalError(format!("spill patch: {}", e));
+        match (
+            self.file.as_mut(),
+            self.framed.as_mut(),
+            self.temp_path.as_ref(),
+        ) {
+            (Some(w), Some(framed), Some(path)) => framed.patch(w, path, offset, bytes)?,
+            (Some(w), _, _) => {
+                w.flush().map_err(io_err)?;
+                let f = w.get_mut();
+                f.seek(SeekFrom::Start(offset)).map_err(io_err)?;
+                f.write_all(bytes).map_err(io_err)?;
+                f.seek(SeekFrom::End(0)).map_err(io_err)?;
+            }
+            _ => {
+                let start = offset as usize;
+                self.memory_buffer[start..start + bytes.len()].copy_from_slice(bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `len` bytes written at `offset`, from memory or the spill file.
+    pub fn read_at(&mut self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let in_bounds = offset
+            .checked_add(len as u64)
+            .is_some_and(|end| end <= self.written);
+        if !in_bounds {
+            return Err(OdbcError::InternalError(format!(
+                "spill read at {offset} past end {}",
+                self.written
+            )));
+        }
+        let io_err = |e: std::io::Error| OdbcError::InternalError(format!("spill read: {}", e));
+        match (self.file.as_mut(), self.temp_path.as_ref()) {
+            (Some(w), Some(path)) if self.framed.is_some() => {
+                w.flush().map_err(io_err)?;
+                match self.framed.as_mut() {
+                    Some(framed) => framed.read(path, offset, len),
+                    None => Err(OdbcError::InternalError("spill frames missing".to_string())),
+                }
+            }
+            (Some(w), Some(path)) => {
+                w.flush().map_err(io_err)?;
+                let mut f = File::open(path).map_err(io_err)?;
+                f.seek(SeekFrom::Start(offset)).map_err(io_err)?;
+                let mut buf = vec![0u8; len];
+                f.read_exact(&mut buf).map_err(io_err)?;
+                Ok(buf)
+            }
+            _ => {
+                let start = offset as usize;
+                Ok(self.memory_buffer[start..start + len].to_vec())
+            }
+        }
+    }
+
     fn spill_to_disk(&mut self) -> Result<()> {
         let name = format!(
-            "odbc_spill_{}.bin",
+            "{SPILL_FILE_PREFIX}{}_{}_{}.bin",
+            std::process::id(),
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
-                .as_millis()
+                .as_millis(),
+            SPILL_FILE_SEQ.fetch_add(1, Ordering::Relaxed)
         );
+        ORPHAN_SWEEP.call_once(|| {
+            let removed = remove_orphaned_spill_files(&self.temp_dir);
+            if removed > 0 {
+                log::info!("DiskSpillStream: removed {removed} spill file(s) left by earlier runs");
+            }
+        });
         let path = self.temp_dir.join(name);
-        let f = File::create(&path)
+        let f = create_spill_file(&path)
             .map_err(|e| OdbcError::InternalError(format!("spill create: {}", e)))?;
-        let mut w = BufWriter::new(f);
-        w.write_all(&self.memory_buffer)
-            .map_err(|e| OdbcError::InternalError(format!("spill write: {}", e)))?;
-        self.memory_buffer.clear();
-        self.file = Some(w);
+        self.file = Some(BufWriter::new(f));
         self.temp_path = Some(path);
-        Ok(())
+        let buffered = std::mem::take(&mut self.memory_buffer);
+        self.write_to_disk(&buffered)
     }
 
     pub fn read_back(&mut self) -> Result<Vec<u8>> {
+        if self.framed.is_some() && self.file.is_some() {
+            let data = self.read_at(0, self.written as usize)?;
+            self.remove_file();
+            return Ok(data);
+        }
         if let Some(ref mut w) = self.file {
             w.flush()
                 .map_err(|e| OdbcError::InternalError(format!("spill flush: {}", e)))?;
@@ -124,7 +309,7 @@ impl DiskSpillStream {
         if let Some(p) = path {
             let data = std::fs::read(&p)
                 .map_err(|e| OdbcError::InternalError(format!("spill read: {}", e)))?;
-            let _ = std::fs::remove_file(p);
+            let _ = remove_spill_file(&p, true);
             Ok(data)
         } else {
             Ok(std::mem::take(&mut self.memory_buffer))
@@ -133,8 +318,14 @@ impl DiskSpillStream {
 
     /// Prepares for streaming read. If data was spilled to disk, returns the path
     /// (caller must delete when done). Otherwise returns the in-memory buffer.
-    /// Flushes and closes the writer when spilled.
+    /// Flushes and closes the writer when spilled. Compressed or encrypted
+    /// spills have no raw file to hand out; read them with [`Self::read_at`].
     pub fn finish_for_streaming_read(&mut self) -> Result<SpillReadSource> {
+        if self.framed.is_some() && self.file.is_some() {
+            return Err(OdbcError::UnsupportedFeature(
+                "compressed or encrypted spill files are only readable through read_at".to_string(),
+            ));
+        }
         if let Some(ref mut w) = self.file {
             w.flush()
                 .map_err(|e| OdbcError::InternalError(format!("spill flush: {}", e)))?;
@@ -168,10 +359,20 @@ impl Drop for DiskSpillStream {
     /// M4 fix: ensure any unread spill file is removed when the stream is
     /// dropped (e.g. on panic / early `?`). Best-effort: errors are logged.
     fn drop(&mut self) {
+        self.remove_file();
+    }
+}
+
+impl DiskSpillStream {
+    /// Closes and securely removes the spill file, if any.
+    fn remove_file(&mut self) {
         // Drop the writer first so the OS releases the handle on Windows.
         drop(self.file.take());
         if let Some(path) = self.temp_path.take() {
-            if let Err(e) = std::fs::remove_file(&path) {
+            // Encrypted frames are useless without the key, which dies with
+            // `self`; only clear-text files are overwritten first.
+            let overwrite = !self.framed.as_ref().is_some_and(FramedSpill::is_encrypted);
+            if let Err(e) = remove_spill_file(&path, overwrite) {
                 if e.kind() != std::io::ErrorKind::NotFound {
                     log::warn!(
                         "DiskSpillStream::drop: failed to remove temp file {}: {e}",
@@ -183,6 +384,304 @@ impl Drop for DiskSpillStream {
     }
 }
 
+/// Creates a new spill file readable and writable by its owner only. On
+/// Windows the per-user temp directory already restricts access.
+pub(crate) fn create_spill_file(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Creates `path` (which must not exist yet) accessible to its owner only.
+pub(crate) fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+/// Unlinks a spill file, first overwriting its bytes with zeros when
+/// `overwrite` is set. Best effort on copy-on-write file systems and SSDs,
+/// where old blocks may survive the overwrite.
+fn remove_spill_file(path: &Path, overwrite: bool) -> std::io::Result<()> {
+    if overwrite {
+        if let Ok(mut f) = OpenOptions::new().write(true).open(path) {
+            let zeros = vec![0u8; WRITE_CHUNK_SIZE];
+            let mut left = f.metadata()?.len();
+            while left > 0 {
+                let n = left.min(zeros.len() as u64) as usize;
+                f.write_all(&zeros[..n])?;
+                left -= n as u64;
+            }
+            f.sync_data()?;
+        }
+    }
+    std::fs::remove_file(path)
+}
+
+/// Securely removes spill files that processes which no longer run left in
+/// the temp directory, e.g. after a crash. Runs once per process on the
+/// first spill to disk; hosts may call it at startup as well. Returns how
+/// many files were removed.
+pub fn recover_orphaned_spill_files() -> usize {
+    remove_orphaned_spill_files(&std::env::temp_dir())
+}
+
+fn remove_orphaned_spill_files(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let own_pid = std::process::id();
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .filter(|n| n.ends_with(".bin"))
+            .and_then(|n| n.strip_prefix(SPILL_FILE_PREFIX))
+            .and_then(|rest| rest.split('_').next())
+            .and_then(|pid| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if pid == own_pid || !meta.is_file() || !spill_owner_is_gone(pid, &meta) {
+            continue;
+        }
+        if remove_spill_file(&entry.path(), true).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+#[cfg(target_os = "linux")]
+fn spill_owner_is_gone(pid: u32, _meta: &std::fs::Metadata) -> bool {
+    !Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn spill_owner_is_gone(_pid: u32, meta: &std::fs::Metadata) -> bool {
+    meta.modified()
+        .ok()
+        .and_then(|m| m.elapsed().ok())
+        .is_some_and(|age| age > ORPHAN_MIN_AGE)
+}
+
+/// One sealed frame: `len` spilled bytes from `start`, stored as
+/// `stored_len` bytes at `file_offset`.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    start: u64,
+    len: usize,
+    file_offset: u64,
+    stored_len: usize,
+    nonce: u64,
+}
+
+/// On-disk layout of a compressed and/or encrypted spill: frames appended to
+/// the file and indexed in memory. A patched frame is re-sealed and appended
+/// again; its old bytes stay in the file, unreferenced.
+struct FramedSpill {
+    compression: CompressionType,
+    /// AES-256 key; lives on the heap, so moving the spill leaves no copy
+    /// behind, and is wiped on drop.
+    key: Option<SecureBuffer>,
+    /// Next AES-GCM nonce; every seal takes a fresh one.
+    next_nonce: u64,
+    frames: Vec<Frame>,
+    /// Spilled bytes after the last frame, not sealed yet.
+    tail: Vec<u8>,
+    file_len: u64,
+    /// Last frame decoded, for readers walking the spill in small steps.
+    cached: Option<(usize, Vec<u8>)>,
+}
+
+impl FramedSpill {
+    fn new(options: SpillOptions) -> Self {
+        let key = options.encrypt.then(|| {
+            let mut key = vec![0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            SecureBuffer::new(key)
+        });
+        Self {
+            compression: options.compression,
+            key,
+            next_nonce: 0,
+            frames: Vec::new(),
+            tail: Vec::new(),
+            file_len: 0,
+            cached: None,
+        }
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    fn sealed_len(&self) -> u64 {
+        self.frames.last().map_or(0, |f| f.start + f.len as u64)
+    }
+
+    fn append(&mut self, w: &mut BufWriter<File>, chunk: &[u8]) -> Result<()> {
+        self.tail.extend_from_slice(chunk);
+        while self.tail.len() >= FRAME_BYTES {
+            let rest = self.tail.split_off(FRAME_BYTES);
+            let plain = std::mem::replace(&mut self.tail, rest);
+            let start = self.sealed_len();
+            let frame = self.write_frame(w, start, &plain)?;
+            self.frames.push(frame);
+        }
+        Ok(())
+    }
+
+    /// Seals `plain` and appends it to the file.
+    fn write_frame(&mut self, w: &mut BufWriter<File>, start: u64, plain: &[u8]) -> Result<Frame> {
+        let nonce = self.next_nonce;
+        self.next_nonce += 1;
+        let mut stored = compress(plain, self.compression)?;
+        if let Some(key) = &self.key {
+            stored = cipher(key.as_slice())?
+                .encrypt(
+                    &frame_nonce(nonce),
+                    Payload {
+                        msg: &stored,
+                        aad: &start.to_le_bytes(),
+                    },
+                )
+                .map_err(|_| OdbcError::InternalError("spill encryption failed".to_string()))?;
+        }
+        w.write_all(&stored)
+            .map_err(|e| OdbcError::InternalError(format!("spill write: {}", e)))?;
+        let frame = Frame {
+            start,
+            len: plain.len(),
+            file_offset: self.file_len,
+            stored_len: stored.len(),
+            nonce,
+        };
+        self.file_len += stored.len() as u64;
+        Ok(frame)
+    }
+
+    /// Decoded bytes of frame `idx`; the file must be flushed.
+    fn frame_bytes(&mut self, path: &Path, idx: usize) -> Result<&[u8]> {
+        if self
+            .cached
+            .as_ref()
+            .is_none_or(|(cached, _)| *cached != idx)
+        {
+            let frame = self.frames[idx];
+            let io_err = |e: std::io::Error| OdbcError::InternalError(format!("spill read: {}", e));
+            let mut f = File::open(path).map_err(io_err)?;
+            f.seek(SeekFrom::Start(frame.file_offset)).map_err(io_err)?;
+            let mut stored = vec![0u8; frame.stored_len];
+            f.read_exact(&mut stored).map_err(io_err)?;
+            if let Some(key) = &self.key {
+                stored = cipher(key.as_slice())?
+                    .decrypt(
+                        &frame_nonce(frame.nonce),
+                        Payload {
+                            msg: &stored,
+                            aad: &frame.start.to_le_bytes(),
+                        },
+                    )
+                    .map_err(|_| {
+                        OdbcError::InternalError("spill frame failed authentication".to_string())
+                    })?;
+            }
+            let plain = decompress_with_limit(&stored, self.compression, frame.len)?;
+            if plain.len() != frame.len {
+                return Err(OdbcError::InternalError(format!(
+                    "spill frame decoded to {} bytes, expected {}",
+                    plain.len(),
+                    frame.len
+                )));
+            }
+            self.cached = Some((idx, plain));
+        }
+        Ok(self.cached.as_ref().map_or(&[][..], |(_, plain)| plain))
+    }
+
+    fn read(&mut self, path: &Path, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let end = offset + len as u64;
+        let sealed = self.sealed_len();
+        let mut out = Vec::with_capacity(len);
+        let mut pos = offset;
+        let mut idx = self
+            .frames
+            .partition_point(|f| f.start + f.len as u64 <= pos);
+        while pos < end.min(sealed) {
+            let start = self.frames[idx].start;
+            let plain = self.frame_bytes(path, idx)?;
+            let from = (pos - start) as usize;
+            let to = ((end - start) as usize).min(plain.len());
+            out.extend_from_slice(&plain[from..to]);
+            pos = start + to as u64;
+            idx += 1;
+        }
+        if pos < end {
+            let from = (pos - sealed) as usize;
+            out.extend_from_slice(&self.tail[from..from + (end - pos) as usize]);
+        }
+        Ok(out)
+    }
+
+    fn patch(
+        &mut self,
+        w: &mut BufWriter<File>,
+        path: &Path,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<()> {
+        let end = offset + bytes.len() as u64;
+        let sealed = self.sealed_len();
+        let mut idx = self
+            .frames
+            .partition_point(|f| f.start + f.len as u64 <= offset);
+        while idx < self.frames.len() && self.frames[idx].start < end {
+            w.flush()
+                .map_err(|e| OdbcError::InternalError(format!("spill patch: {}", e)))?;
+            let start = self.frames[idx].start;
+            let mut plain = self.frame_bytes(path, idx)?.to_vec();
+            let from = offset.max(start);
+            let to = end.min(start + plain.len() as u64);
+            plain[(from - start) as usize..(to - start) as usize]
+                .copy_from_slice(&bytes[(from - offset) as usize..(to - offset) as usize]);
+            self.frames[idx] = self.write_frame(w, start, &plain)?;
+            self.cached = Some((idx, plain));
+            idx += 1;
+        }
+        if end > sealed {
+            let from = offset.max(sealed);
+            self.tail[(from - sealed) as usize..(end - sealed) as usize]
+                .copy_from_slice(&bytes[(from - offset) as usize..]);
+        }
+        Ok(())
+    }
+}
+
+fn cipher(key: &[u8]) -> Result<Aes256Gcm> {
+    Aes256Gcm::new_from_slice(key)
+        .map_err(|_| OdbcError::InternalError("invalid spill key".to_string()))
+}
+
+fn frame_nonce(counter: u64) -> Nonce<aes_gcm::aead::consts::U12> {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    nonce.into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,4 +716,155 @@ mod tests {
         assert_eq!(out.len(), 1 + 2 * 1024 * 1024);
         assert_eq!(out[0], 42);
     }
+
+    #[test]
+    fn test_disk_spill_patch_in_memory_and_on_disk() {
+        let mut s = DiskSpillStream::new(100);
+        s.write_chunk(b"hello").unwrap();
+        s.patch(1, b"EL").unwrap();
+        assert!(s.patch(4, b"xy").is_err());
+        assert_eq!(s.read_back().unwrap(), b"hELlo");
+
+        let mut s = DiskSpillStream::new(1);
+        s.write_chunk(&[0; 4]).unwrap();
+        s.write_chunk(&vec![7u8; 2 * 1024 * 1024]).unwrap();
+        s.patch(0, &[1, 2, 3, 4]).unwrap();
+        s.write_chunk(&[9]).unwrap();
+        assert_eq!(s.written_len(), 4 + 2 * 1024 * 1024 + 1);
+        let out = s.read_back().unwrap();
+        assert_eq!(&out[..5], &[1, 2, 3, 4, 7]);
+        assert_eq!(out.last(), Some(&9));
+    }
+
+    #[test]
+    fn test_disk_spill_read_at_in_memory_and_on_disk() {
+        let mut s = DiskSpillStream::new(100);
+        s.write_chunk(b"hello").unwrap();
+        assert_eq!(s.read_at(1, 3).unwrap(), b"ell");
+        assert!(s.read_at(3, 3).is_err());
+
+        let mut s = DiskSpillStream::new(1);
+        s.write_chunk(&[1, 2]).unwrap();
+        s.write_chunk(&vec![7u8; 2 * 1024 * 1024]).unwrap();
+        s.write_chunk(&[9]).unwrap();
+        assert_eq!(s.read_at(0, 3).unwrap(), vec![1, 2, 7]);
+        assert_eq!(s.read_at(2 + 2 * 1024 * 1024, 1).unwrap(), vec![9]);
+    }
+
+    fn spill_pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| b"customer-pii-"[i % 13]).collect()
+    }
+
+    #[test]
+    fn test_disk_spill_framed_read_patch_and_read_back() {
+        for options in [
+            SpillOptions::PLAIN.with_compression(CompressionType::Zstd),
+            SpillOptions::PLAIN.with_compression(CompressionType::Lz4),
+            SpillOptions::PLAIN.with_encryption(true),
+            SpillOptions::PLAIN
+                .with_compression(CompressionType::Zstd)
+                .with_encryption(true),
+        ] {
+            let mut expected = spill_pattern(3 * FRAME_BYTES + 100);
+            let mut s = DiskSpillStream::with_options(1, options);
+            s.write_chunk(&expected[..10]).unwrap();
+            s.write_chunk(&expected[10..]).unwrap();
+            let tail = vec![5u8; 2 * 1024 * 1024];
+            s.write_chunk(&tail).unwrap();
+            expected.extend_from_slice(&tail);
+            assert!(s.is_on_disk());
+
+            let edge = FRAME_BYTES as u64 - 3;
+            assert_eq!(
+                s.read_at(edge, 8).unwrap(),
+                &expected[edge as usize..edge as usize + 8]
+            );
+            s.patch(edge, b"ABCDEF").unwrap();
+            expected[edge as usize..edge as usize + 6].copy_from_slice(b"ABCDEF");
+            let last = expected.len() - 4;
+            s.patch(last as u64, &[1, 2, 3, 4]).unwrap();
+            expected[last..].copy_from_slice(&[1, 2, 3, 4]);
+            assert_eq!(
+                s.read_at(edge - 10, 20).unwrap(),
+                &expected[edge as usize - 10..edge as usize + 10]
+            );
+            assert!(s.read_at(expected.len() as u64 - 1, 2).is_err());
+            assert!(s.finish_for_streaming_read().is_err());
+            assert_eq!(s.read_back().unwrap(), expected, "{options:?}");
+        }
+    }
+
+    #[test]
+    fn test_disk_spill_compressed_and_encrypted_file_contents() {
+        let data = spill_pattern(4 * 1024 * 1024);
+        let mut compressed = DiskSpillStream::with_options(
+            1,
+            SpillOptions::PLAIN.with_compression(CompressionType::Zstd),
+        );
+        compressed.write_chunk(&data[..1]).unwrap();
+        compressed.write_chunk(&data[1..]).unwrap();
+        compressed.read_at(0, 1).unwrap();
+        let path = compressed.temp_path.clone().unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() < data.len() as u64 / 10);
+
+        let mut encrypted =
+            DiskSpillStream::with_options(1, SpillOptions::PLAIN.with_encryption(true));
+        encrypted.write_chunk(&data[..1]).unwrap();
+        encrypted.write_chunk(&data[1..]).unwrap();
+        encrypted.read_at(0, 1).unwrap();
+        let on_disk = std::fs::read(encrypted.temp_path.as_ref().unwrap()).unwrap();
+        assert!(!on_disk.windows(13).any(|w| w == b"customer-pii-"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_spill_file_is_owner_only_and_removed_on_drop() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut s = DiskSpillStream::with_options(1, SpillOptions::PLAIN);
+        s.write_chunk(&[1]).unwrap();
+        s.write_chunk(&vec![2u8; 2 * 1024 * 1024]).unwrap();
+        let path = s.temp_path.clone().unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        drop(s);
+        assert!(!path.exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_remove_orphaned_spill_files_keeps_live_owners() {
+        let dir = std::env::temp_dir().join(format!("odbc_engine_orphans_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let orphan = dir.join(format!("{SPILL_FILE_PREFIX}{}_1_0.bin", u32::MAX));
+        let own = dir.join(format!("{SPILL_FILE_PREFIX}{}_1_0.bin", std::process::id()));
+        let other = dir.join("unrelated.bin");
+        for path in [&orphan, &own, &other] {
+            std::fs::write(path, b"data").unwrap();
+        }
+        assert_eq!(remove_orphaned_spill_files(&dir), 1);
+        assert!(!orphan.exists());
+        assert!(own.exists() && other.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_row_stream_encoder_writes_through_spill() {
+        use crate::protocol::encoder::RowStreamEncoder;
+        use crate::protocol::{OdbcType, RowBuffer, RowBufferEncoder};
+
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("blob".to_string(), OdbcType::Binary);
+        for i in 0..3u8 {
+            buffer.add_row(vec![Some(vec![i; 512 * 1024])]);
+        }
+        let mut spill = DiskSpillStream::new(1);
+        let mut encoder = RowStreamEncoder::new(DiskSpillWriter::new(&mut spill), &buffer).unwrap();
+        encoder.write_rows(&buffer).unwrap();
+        encoder.finish().unwrap().flush().unwrap();
+        assert_eq!(
+            spill.read_back().unwrap(),
+            RowBufferEncoder::encode(&buffer)
+        );
+    }
 }
@@ -1,21 +1,28 @@
+use super::disk_spill::SpillOptions;
+use super::driver_capabilities::DriverCapabilities;
 use super::prepared_cache::PreparedStatementCache;
-use crate::engine::cell_reader::CellReader;
-use crate::engine::sqlserver_json::coalesce_for_json_rows;
+use super::typed_params::{input_parameters, TemporalBinding};
+use crate::engine::block_fetch::{block_fetch_rows, fetch_all_rows, RowFetcher};
+use crate::engine::sqlserver_json::{coalesce_for_json_rows, is_for_json_result};
+use crate::engine::stream_buffer::StreamBufferPolicy;
 use crate::error::{OdbcError, Result};
 use crate::handles::CachedConnection;
 use crate::observability::{Metrics, SpanGuard, StructuredLogger, Tracer};
 use crate::plugins::{DriverPlugin, PluginRegistry};
 use crate::protocol::bound_param::BoundParam;
+use crate::protocol::native_cell::native_column_type;
 use crate::protocol::{
-    encode_multi, row_buffer_to_columnar, ColumnarEncoder, MultiResultItem, OdbcType, ParamValue,
-    RowBuffer, RowBufferEncoder,
+    encode_multi, row_buffer_to_columnar, CellEncoding, ColumnarEncoder, MultiResultItem, OdbcType,
+    OutputFormat, ParamValue, RowBuffer, RowBufferEncoder,
 };
 use crate::security::AuditLogger;
+use crate::versioning::protocol_version::ProtocolVersion;
 use log::Level;
 use odbc_api::handles::{AsStatementRef, SqlResult, Statement};
-use odbc_api::{Connection, Cursor, CursorImpl, IntoParameter, ResultSetMetadata};
+use odbc_api::{Connection, Cursor, CursorImpl, DataType, ResultSetMetadata};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Returns true when the underlying ODBC error means "no more result sets",
 /// i.e. SQLSTATE 02000 ("no data") which corresponds to the SQL_NO_DATA return code.
@@ -27,12 +34,151 @@ fn is_no_more_results(err: &OdbcError) -> bool {
     s == [b'0', b'2', b'0', b'0', b'0']
 }
 
+/// Per-connection settings that shape how a result is fetched and encoded.
+/// Detected from the driver when a connection is opened (see
+/// [`crate::handles::HandleManager::result_options`]); the protocol version
+/// and output format are negotiated by the caller of that connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultOptions {
+    /// Rowset cap for block fetches, see
+    /// [`DriverCapabilities::max_row_array_size`].
+    pub max_row_array_size: u32,
+    /// Result protocol negotiated for the connection; `1.0` until the caller
+    /// asks for another. Row-major or columnar still follows the engine, see
+    /// [`ExecutionEngine::set_protocol_version`].
+    pub protocol: ProtocolVersion,
+    /// Byte format of the results, see [`ExecutionEngine::set_output_format`].
+    pub format: OutputFormat,
+    /// How streams and scrollable cursors on the connection store their
+    /// spill files.
+    pub spill: SpillOptions,
+    /// How far the worker of batched and async streams on the connection may
+    /// run ahead of the consumer.
+    pub buffer: StreamBufferPolicy,
+    /// How time fractions and UTC offsets of parameters are bound, from the
+    /// detected driver.
+    pub temporal: TemporalBinding,
+}
+
+impl ResultOptions {
+    pub fn for_driver(capabilities: &DriverCapabilities) -> Self {
+        Self {
+            max_row_array_size: capabilities.max_row_array_size,
+            protocol: ProtocolVersion::v1(),
+            format: OutputFormat::OdbcBinary,
+            spill: SpillOptions::PLAIN,
+            buffer: StreamBufferPolicy::DEFAULT,
+            temporal: TemporalBinding::for_driver(capabilities),
+        }
+    }
+
+    pub fn with_spill_options(self, spill: SpillOptions) -> Self {
+        Self { spill, ..self }
+    }
+
+    /// Same options with streams buffered per `buffer`. Fails when the
+    /// policy is invalid, see [`StreamBufferPolicy::validate`].
+    pub fn with_buffer_policy(self, buffer: StreamBufferPolicy) -> Result<Self> {
+        buffer.validate()?;
+        Ok(Self { buffer, ..self })
+    }
+
+    /// Same options with results encoded for `version`. Fails when the
+    /// version is not supported.
+    pub fn with_protocol_version(self, version: ProtocolVersion) -> Result<Self> {
+        if !version.is_supported() {
+            return Err(OdbcError::ValidationError(format!(
+                "Unsupported protocol version {}.{}",
+                version.major, version.minor
+            )));
+        }
+        Ok(Self {
+            protocol: version,
+            ..self
+        })
+    }
+
+    /// Same options with results encoded as `format`. Fails when `format`
+    /// is not built in.
+    pub fn with_output_format(self, format: OutputFormat) -> Result<Self> {
+        Ok(Self {
+            format: format.ensure_available()?,
+            ..self
+        })
+    }
+
+    /// Rows per block fetch: `fetch_size` capped by `max_row_array_size`.
+    pub fn block_rows(&self, fetch_size: Option<u32>) -> usize {
+        block_fetch_rows(self.max_row_array_size, fetch_size)
+    }
+}
+
+impl Default for ResultOptions {
+    fn default() -> Self {
+        Self::for_driver(&DriverCapabilities::default())
+    }
+}
+
+/// Connection a query runs on, with the [`ResultOptions`] of that
+/// connection. A bare `&Connection` uses the engine's own options (see
+/// [`ExecutionEngine::set_connection_string`]).
+#[derive(Clone, Copy)]
+pub struct QueryConnection<'a> {
+    conn: &'a Connection<'static>,
+    options: Option<ResultOptions>,
+}
+
+impl<'a> QueryConnection<'a> {
+    pub fn new(conn: &'a Connection<'static>, options: ResultOptions) -> Self {
+        Self {
+            conn,
+            options: Some(options),
+        }
+    }
+
+    pub fn connection(&self) -> &'a Connection<'static> {
+        self.conn
+    }
+}
+
+impl<'a> From<&'a Connection<'static>> for QueryConnection<'a> {
+    fn from(conn: &'a Connection<'static>) -> Self {
+        Self {
+            conn,
+            options: None,
+        }
+    }
+}
+
+impl<'a> From<&'a CachedConnection> for QueryConnection<'a> {
+    fn from(conn: &'a CachedConnection) -> Self {
+        Self::from(conn.connection())
+    }
+}
+
+impl<'a> From<&'a MutexGuard<'_, CachedConnection>> for QueryConnection<'a> {
+    fn from(conn: &'a MutexGuard<'_, CachedConnection>) -> Self {
+        Self::from(conn.connection())
+    }
+}
+
 pub struct ExecutionEngine {
     prepared_cache: Arc<PreparedStatementCache>,
     use_columnar: bool,
     use_compression: bool,
     plugin_registry: Option<Arc<PluginRegistry>>,
     active_plugin: Arc<Mutex<Option<Arc<dyn DriverPlugin>>>>,
+    /// Rowset cap for queries run on a bare `&Connection`; follows the
+    /// driver detected in [`Self::set_connection_string`].
+    max_row_array_size: AtomicU32,
+    /// Minor protocol revision set by [`Self::set_protocol_version`].
+    protocol_minor: AtomicU16,
+    /// [`OutputFormat`] discriminant set by [`Self::set_output_format`].
+    output_format: AtomicU8,
+    /// Whether parameters on a bare `&Connection` bind temporals the SQL
+    /// Server way, see [`TemporalBinding`]; follows the driver detected in
+    /// [`Self::set_connection_string`].
+    sqlserver_temporals: AtomicBool,
     metrics: Arc<Metrics>,
     tracer: Arc<Tracer>,
     logger: Arc<StructuredLogger>,
@@ -50,6 +196,10 @@ impl ExecutionEngine {
             use_compression: false,
             plugin_registry: Some(Arc::new(PluginRegistry::default())),
             active_plugin: Arc::new(Mutex::new(None)),
+            max_row_array_size: AtomicU32::new(DriverCapabilities::default().max_row_array_size),
+            protocol_minor: AtomicU16::new(0),
+            output_format: AtomicU8::new(OutputFormat::OdbcBinary as u8),
+            sqlserver_temporals: AtomicBool::new(false),
             metrics,
             tracer: Arc::new(Tracer::new()),
             logger: Arc::new(StructuredLogger::default()),
@@ -67,6 +217,10 @@ impl ExecutionEngine {
             use_compression,
             plugin_registry: Some(Arc::new(PluginRegistry::default())),
             active_plugin: Arc::new(Mutex::new(None)),
+            max_row_array_size: AtomicU32::new(DriverCapabilities::default().max_row_array_size),
+            protocol_minor: AtomicU16::new(0),
+            output_format: AtomicU8::new(OutputFormat::OdbcBinary as u8),
+            sqlserver_temporals: AtomicBool::new(false),
             metrics,
             tracer: Arc::new(Tracer::new()),
             logger: Arc::new(StructuredLogger::default()),
@@ -84,6 +238,10 @@ impl ExecutionEngine {
             use_compression: false,
             plugin_registry: Some(registry),
             active_plugin: Arc::new(Mutex::new(None)),
+            max_row_array_size: AtomicU32::new(DriverCapabilities::default().max_row_array_size),
+            protocol_minor: AtomicU16::new(0),
+            output_format: AtomicU8::new(OutputFormat::OdbcBinary as u8),
+            sqlserver_temporals: AtomicBool::new(false),
             metrics,
             tracer: Arc::new(Tracer::new()),
             logger: Arc::new(StructuredLogger::default()),
@@ -91,7 +249,16 @@ impl ExecutionEngine {
         }
     }
 
+    /// Selects the plugin for `connection_string` and the options used for
+    /// queries that do not carry their connection's [`ResultOptions`].
     pub fn set_connection_string(&self, connection_string: &str) {
+        let caps = DriverCapabilities::detect_from_connection_string(connection_string);
+        self.max_row_array_size
+            .store(caps.max_row_array_size, Ordering::Relaxed);
+        self.sqlserver_temporals.store(
+            TemporalBinding::for_driver(&caps) == TemporalBinding::SqlServer,
+            Ordering::Relaxed,
+        );
         if let Some(ref registry) = self.plugin_registry {
             if let Some(plugin) = registry.get_for_connection(connection_string) {
                 if let Ok(mut active) = self.active_plugin.lock() {
@@ -101,15 +268,117 @@ impl ExecutionEngine {
         }
     }
 
-    pub fn execute_query(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    /// Selects the result protocol version. Minor `1` (`1.1` / `2.1`) turns on
+    /// native cells for the types in [`crate::protocol::native_cell`]; minor
+    /// `2` additionally switches columnar results to the array layout of
+    /// `protocol::columnar_v2`. The major still follows [`Self::with_columnar`].
+    pub fn set_protocol_version(&self, version: ProtocolVersion) -> Result<()> {
+        let version = self
+            .default_options()
+            .with_protocol_version(version)?
+            .protocol;
+        self.protocol_minor.store(version.minor, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Columnar-or-row-major version results are encoded with under `options`.
+    fn protocol_version(&self, options: &ResultOptions) -> ProtocolVersion {
+        let major = if self.use_columnar { 2 } else { 1 };
+        ProtocolVersion::new(major, options.protocol.minor)
+    }
+
+    fn cell_encoding(&self, options: &ResultOptions) -> CellEncoding {
+        CellEncoding::for_version(&self.protocol_version(options))
+    }
+
+    /// Selects the byte format of query results. [`OutputFormat::ArrowIpc`]
+    /// replaces the binary protocol with an Arrow IPC stream (schema message,
+    /// one record batch, end-of-stream) and needs the `arrow-ipc` feature.
+    pub fn set_output_format(&self, format: OutputFormat) -> Result<()> {
+        let format = format.ensure_available()?;
+        self.output_format.store(format as u8, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn output_format(&self) -> OutputFormat {
+        OutputFormat::from_code(self.output_format.load(Ordering::Relaxed).into())
+            .unwrap_or_default()
+    }
+
+    /// Options for queries run on a bare `&Connection`.
+    pub fn default_options(&self) -> ResultOptions {
+        ResultOptions {
+            max_row_array_size: self.max_row_array_size.load(Ordering::Relaxed),
+            protocol: ProtocolVersion::new(
+                if self.use_columnar { 2 } else { 1 },
+                self.protocol_minor.load(Ordering::Relaxed),
+            ),
+            format: self.output_format(),
+            spill: SpillOptions::PLAIN,
+            buffer: StreamBufferPolicy::DEFAULT,
+            temporal: if self.sqlserver_temporals.load(Ordering::Relaxed) {
+                TemporalBinding::SqlServer
+            } else {
+                TemporalBinding::Standard
+            },
+        }
+    }
+
+    fn resolve<'a>(&self, conn: QueryConnection<'a>) -> (&'a Connection<'static>, ResultOptions) {
+        (
+            conn.conn,
+            conn.options.unwrap_or_else(|| self.default_options()),
+        )
+    }
+
+    /// Encodes one result set in the selected output format and protocol.
+    #[cfg_attr(not(feature = "arrow-ipc"), allow(unused_variables))]
+    fn encode_result(&self, row_buffer: &RowBuffer, options: &ResultOptions) -> Result<Vec<u8>> {
+        #[cfg(feature = "arrow-ipc")]
+        if options.format == OutputFormat::ArrowIpc {
+            return crate::protocol::arrow_stream::ArrowStreamEncoder::encode(row_buffer);
+        }
+        if self.use_columnar {
+            #[cfg(feature = "columnar-v2")]
+            if self.protocol_version(options).has_columnar_arrays() {
+                return crate::protocol::columnar_v2::ColumnarV2Encoder::encode(
+                    row_buffer,
+                    self.use_compression,
+                );
+            }
+            let columnar_buffer = row_buffer_to_columnar(row_buffer);
+            ColumnarEncoder::encode(&columnar_buffer, self.use_compression)
+        } else {
+            Ok(RowBufferEncoder::encode(row_buffer))
+        }
+    }
+
+    /// Results that carry OUT-parameter or ref-cursor footers stay on the
+    /// binary protocol: those trailers have no place in an Arrow stream.
+    fn ensure_footer_format(options: &ResultOptions) -> Result<()> {
+        if options.format == OutputFormat::OdbcBinary {
+            Ok(())
+        } else {
+            Err(OdbcError::UnsupportedFeature(
+                "OUT parameters and ref cursors cannot be returned as Arrow IPC".to_string(),
+            ))
+        }
+    }
+
+    pub fn execute_query<'a>(
+        &self,
+        conn: impl Into<QueryConnection<'a>>,
+        sql: &str,
+    ) -> Result<Vec<u8>> {
         use std::time::Instant;
+        let (conn, options) = self.resolve(conn.into());
         let start_time = Instant::now();
         let _span = SpanGuard::new(Arc::clone(&self.tracer), sql.to_string());
         let mut metadata = HashMap::new();
         metadata.insert("span_id".to_string(), _span.span_id().to_string());
         self.logger.log_query(Level::Info, sql, &metadata);
 
-        let result = self.execute_query_inner(conn, sql);
+        let result = self.execute_query_inner(conn, sql, &options);
 
         let latency = start_time.elapsed();
         self.metrics.record_query(latency);
@@ -122,7 +391,12 @@ impl ExecutionEngine {
         result
     }
 
-    fn execute_query_inner(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    fn execute_query_inner(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
         let optimized_sql = if let Ok(active) = self.active_plugin.lock() {
             if let Some(ref plugin) = *active {
                 plugin.optimize_query(sql)
@@ -139,62 +413,12 @@ impl ExecutionEngine {
 
         let cursor = stmt.execute(()).map_err(OdbcError::from)?;
 
-        let mut row_buffer = RowBuffer::new();
-
-        if let Some(mut cursor) = cursor {
-            let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-            let cols_u16: u16 = cols_i16
-                .try_into()
-                .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-            let cols_usize: usize = cols_u16.into();
-
-            let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-
-            for col_idx in 1..=cols_u16 {
-                let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-                let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-                let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-                let odbc_type = if let Ok(active) = self.active_plugin.lock() {
-                    if let Some(ref plugin) = *active {
-                        plugin.map_type(sql_type_code)
-                    } else {
-                        OdbcType::from_odbc_sql_type(sql_type_code)
-                    }
-                } else {
-                    OdbcType::from_odbc_sql_type(sql_type_code)
-                };
-                row_buffer.add_column(col_name.to_string(), odbc_type);
-                column_types.push(odbc_type);
-            }
-
-            let mut cell_reader = CellReader::new();
-            while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-                let mut row_data = Vec::with_capacity(column_types.len());
-
-                for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                    let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column number".to_string())
-                    })?;
-
-                    let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-
-                    row_data.push(cell_data);
-                }
-
-                row_buffer.add_row(row_data);
-            }
-        }
-
-        // Reassemble SQL Server FOR JSON multi-row chunks into a single
-        // logical cell. No-op for any other result shape. See
-        // `engine::sqlserver_json` for the rationale (closes #2).
-        coalesce_for_json_rows(&mut row_buffer);
-
-        if self.use_columnar {
-            let columnar_buffer = row_buffer_to_columnar(&row_buffer);
-            ColumnarEncoder::encode(&columnar_buffer, self.use_compression)
-        } else {
-            Ok(RowBufferEncoder::encode(&row_buffer))
+        match cursor {
+            Some(mut cursor) => self.encode_result_set(&mut cursor, None, options),
+            None => self.encode_result(
+                &RowBuffer::with_cell_encoding(self.cell_encoding(options)),
+                options,
+            ),
         }
     }
 
@@ -203,6 +427,7 @@ impl ExecutionEngine {
         &self,
         cached: &mut CachedConnection,
         sql: &str,
+        options: &ResultOptions,
     ) -> Result<Vec<u8>> {
         use std::time::Instant;
 
@@ -224,7 +449,13 @@ impl ExecutionEngine {
 
         self.prepared_cache.get_or_insert(&optimized_sql);
 
-        let result = cached.execute_query_no_params(&optimized_sql);
+        let result = cached.execute_query_no_params(&optimized_sql, |cursor| match cursor {
+            Some(mut cursor) => self.encode_result_set(&mut cursor, None, options),
+            None => self.encode_result(
+                &RowBuffer::with_cell_encoding(self.cell_encoding(options)),
+                options,
+            ),
+        });
 
         let latency = start_time.elapsed();
         self.metrics.record_query(latency);
@@ -237,24 +468,25 @@ impl ExecutionEngine {
         result
     }
 
-    pub fn execute_query_with_params(
+    pub fn execute_query_with_params<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[ParamValue],
     ) -> Result<Vec<u8>> {
         self.execute_query_with_params_and_timeout(conn, sql, params, None, None)
     }
 
-    pub fn execute_query_with_params_and_timeout(
+    pub fn execute_query_with_params_and_timeout<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[ParamValue],
         timeout_sec: Option<usize>,
         fetch_size: Option<u32>,
     ) -> Result<Vec<u8>> {
         use std::time::Instant;
+        let (conn, options) = self.resolve(conn.into());
 
         let start_time = Instant::now();
         let _span = SpanGuard::new(Arc::clone(&self.tracer), sql.to_string());
@@ -262,8 +494,14 @@ impl ExecutionEngine {
         metadata.insert("span_id".to_string(), _span.span_id().to_string());
         self.logger.log_query(Level::Info, sql, &metadata);
 
-        let result =
-            self.execute_query_with_params_inner(conn, sql, params, timeout_sec, fetch_size);
+        let result = self.execute_query_with_params_inner(
+            conn,
+            sql,
+            params,
+            timeout_sec,
+            fetch_size,
+            &options,
+        );
 
         self.metrics.record_query(start_time.elapsed());
 
@@ -276,15 +514,16 @@ impl ExecutionEngine {
     }
 
     /// Positional `?` with `INPUT` / `OUTPUT` / `INOUT` (DRT1 wire from Dart). Integer/BigInt OUT only (MVP).
-    pub fn execute_query_with_bound_params_and_timeout(
+    pub fn execute_query_with_bound_params_and_timeout<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         bound: &[BoundParam],
         timeout_sec: Option<usize>,
-        _fetch_size: Option<u32>,
+        fetch_size: Option<u32>,
     ) -> Result<Vec<u8>> {
         use std::time::Instant;
+        let (conn, options) = self.resolve(conn.into());
 
         use super::output_aware_params::bound_to_slots;
         let start_time = Instant::now();
@@ -296,6 +535,8 @@ impl ExecutionEngine {
         let result: Result<Vec<u8>> = (|| {
             use super::ref_cursor_oracle::bound_has_ref_cursor;
 
+            Self::ensure_footer_format(&options)?;
+
             if bound_has_ref_cursor(bound) {
                 if !self.is_oracle_plugin_active() {
                     return Err(OdbcError::ValidationError(
@@ -305,10 +546,16 @@ impl ExecutionEngine {
                             .to_string(),
                     ));
                 }
-                return self.execute_oracle_ref_cursor_path(conn, sql, bound, timeout_sec);
+                return self.execute_oracle_ref_cursor_path(
+                    conn,
+                    sql,
+                    bound,
+                    timeout_sec,
+                    &options,
+                );
             }
 
-            let mut odbc_params = bound_to_slots(bound)?;
+            let mut odbc_params = bound_to_slots(bound, options.temporal)?;
             // SQL Server (and other drivers) may only populate `OUTPUT` bind buffers after every
             // sp batch result set has been advanced with `SQLMoreResults`, mirroring
             // `execute_multi_result_inner` and `execute_oracle_ref_cursor_path` (which both call
@@ -322,7 +569,7 @@ impl ExecutionEngine {
             if let Some(s) = timeout_sec {
                 prealloc.set_query_timeout_sec(s).map_err(OdbcError::from)?;
             }
-            let mut row_buffer = RowBuffer::new();
+            let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding(&options));
             // Keep the cursor binding adjacent to the `if let` that consumes it. Any `let` in
             // between (e.g. `row_buffer`) can extend the borrow in NLL to the end of the outer
             // closure, blocking `row_count` / `more_results` on the same `Preallocated` handle.
@@ -331,48 +578,7 @@ impl ExecutionEngine {
                     .execute(sql, &mut odbc_params)
                     .map_err(OdbcError::from)?;
                 if let Some(mut cursor) = initial_cursor {
-                    let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-                    let cols_u16: u16 = cols_i16.try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column count".to_string())
-                    })?;
-                    let cols_usize: usize = cols_u16.into();
-
-                    let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-
-                    for col_idx in 1..=cols_u16 {
-                        let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-                        let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-                        let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-                        let odbc_type = if let Ok(active) = self.active_plugin.lock() {
-                            if let Some(ref plugin) = *active {
-                                plugin.map_type(sql_type_code)
-                            } else {
-                                OdbcType::from_odbc_sql_type(sql_type_code)
-                            }
-                        } else {
-                            OdbcType::from_odbc_sql_type(sql_type_code)
-                        };
-                        row_buffer.add_column(col_name.to_string(), odbc_type);
-                        column_types.push(odbc_type);
-                    }
-
-                    let mut cell_reader = CellReader::new();
-                    while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-                        let mut row_data = Vec::with_capacity(column_types.len());
-
-                        for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                            let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                                OdbcError::InternalError("Invalid column number".to_string())
-                            })?;
-
-                            let cell_data =
-                                cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-
-                            row_data.push(cell_data);
-                        }
-
-                        row_buffer.add_row(row_data);
-                    }
+                    self.read_result_set(&mut cursor, fetch_size, &options, &mut row_buffer)?;
                     let _stmt_ref = cursor.into_stmt();
                     true
                 } else {
@@ -411,7 +617,7 @@ impl ExecutionEngine {
             // accordingly so existing callers that only use the first result set keep
             // working without change.
             let mut drain: Vec<MultiResultItem> = Vec::new();
-            self.drive_more_results(&mut prealloc, &mut drain)?;
+            self.drive_more_results(&mut prealloc, &mut drain, &options)?;
 
             coalesce_for_json_rows(&mut row_buffer);
 
@@ -465,118 +671,86 @@ impl ExecutionEngine {
         sql: &str,
         params: &[ParamValue],
         timeout_sec: Option<usize>,
-        _fetch_size: Option<u32>,
+        fetch_size: Option<u32>,
+        options: &ResultOptions,
     ) -> Result<Vec<u8>> {
-        let optional_strings = crate::protocol::param_values_to_strings(params)?;
-
-        let cursor = match optional_strings.len() {
-            0 => conn
-                .execute(sql, (), timeout_sec)
-                .map_err(OdbcError::from)?,
-            1 => {
-                let p0 = optional_strings[0].as_deref().into_parameter();
-                conn.execute(sql, (&p0,), timeout_sec)
-                    .map_err(OdbcError::from)?
-            }
-            2 => {
-                let p0 = optional_strings[0].as_deref().into_parameter();
-                let p1 = optional_strings[1].as_deref().into_parameter();
-                conn.execute(sql, (&p0, &p1), timeout_sec)
-                    .map_err(OdbcError::from)?
-            }
-            3 => {
-                let p0 = optional_strings[0].as_deref().into_parameter();
-                let p1 = optional_strings[1].as_deref().into_parameter();
-                let p2 = optional_strings[2].as_deref().into_parameter();
-                conn.execute(sql, (&p0, &p1, &p2), timeout_sec)
-                    .map_err(OdbcError::from)?
-            }
-            4 => {
-                let p0 = optional_strings[0].as_deref().into_parameter();
-                let p1 = optional_strings[1].as_deref().into_parameter();
-                let p2 = optional_strings[2].as_deref().into_parameter();
-                let p3 = optional_strings[3].as_deref().into_parameter();
-                conn.execute(sql, (&p0, &p1, &p2, &p3), timeout_sec)
-                    .map_err(OdbcError::from)?
-            }
-            5 => {
-                let p0 = optional_strings[0].as_deref().into_parameter();
-                let p1 = optional_strings[1].as_deref().into_parameter();
-                let p2 = optional_strings[2].as_deref().into_parameter();
-                let p3 = optional_strings[3].as_deref().into_parameter();
-                let p4 = optional_strings[4].as_deref().into_parameter();
-                conn.execute(sql, (&p0, &p1, &p2, &p3, &p4), timeout_sec)
-                    .map_err(OdbcError::from)?
-            }
-            n => {
-                return Err(OdbcError::ValidationError(format!(
-                    "At most 5 parameters supported, got {}. \
-                    For more parameters or proper NULL handling, \
-                    use bulk insert operations or direct prepared statements.",
-                    n
-                )))
-            }
-        };
+        let input_params = input_parameters(params, options.temporal)?;
+        let cursor = conn
+            .execute(sql, input_params.as_slice(), timeout_sec)
+            .map_err(OdbcError::from)?;
+
+        match cursor {
+            Some(mut cursor) => self.encode_result_set(&mut cursor, fetch_size, options),
+            None => self.encode_result(
+                &RowBuffer::with_cell_encoding(self.cell_encoding(options)),
+                options,
+            ),
+        }
+    }
 
-        let mut row_buffer = RowBuffer::new();
+    /// Executes statement `stmt_id` through the handle it holds on `cached`,
+    /// rebinding `params` instead of preparing `sql` again. A connection
+    /// error (SQLSTATE class `08`) drops every handle held by `cached`.
+    #[cfg(feature = "statement-handle-reuse")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_prepared(
+        &self,
+        cached: &mut CachedConnection,
+        stmt_id: u32,
+        sql: &str,
+        params: &[ParamValue],
+        timeout_sec: Option<usize>,
+        fetch_size: Option<u32>,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
+        use std::time::Instant;
 
-        if let Some(mut cursor) = cursor {
-            let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-            let cols_u16: u16 = cols_i16
-                .try_into()
-                .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-            let cols_usize: usize = cols_u16.into();
+        let start_time = Instant::now();
+        let _span = SpanGuard::new(Arc::clone(&self.tracer), sql.to_string());
+        let mut metadata = HashMap::new();
+        metadata.insert("span_id".to_string(), _span.span_id().to_string());
+        self.logger.log_query(Level::Info, sql, &metadata);
 
-            let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
+        self.prepared_cache.get_or_insert(sql);
+        self.prepared_cache.record_execution();
 
-            for col_idx in 1..=cols_u16 {
-                let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-                let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-                let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-                let odbc_type = if let Ok(active) = self.active_plugin.lock() {
-                    if let Some(ref plugin) = *active {
-                        plugin.map_type(sql_type_code)
-                    } else {
-                        OdbcType::from_odbc_sql_type(sql_type_code)
-                    }
-                } else {
-                    OdbcType::from_odbc_sql_type(sql_type_code)
-                };
-                row_buffer.add_column(col_name.to_string(), odbc_type);
-                column_types.push(odbc_type);
+        let result = (|| {
+            let input_params = input_parameters(params, options.temporal)?;
+            let stmt = cached.prepared_statement(stmt_id, sql)?;
+            stmt.set_query_timeout_sec(timeout_sec.unwrap_or(0))
+                .map_err(OdbcError::from)?;
+            let cursor = stmt
+                .execute(input_params.as_slice())
+                .map_err(OdbcError::from)?;
+            match cursor {
+                Some(mut cursor) => self.encode_result_set(&mut cursor, fetch_size, options),
+                None => self.encode_result(
+                    &RowBuffer::with_cell_encoding(self.cell_encoding(options)),
+                    options,
+                ),
             }
+        })();
 
-            let mut cell_reader = CellReader::new();
-            while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-                let mut row_data = Vec::with_capacity(column_types.len());
-
-                for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                    let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column number".to_string())
-                    })?;
-
-                    let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-
-                    row_data.push(cell_data);
-                }
+        self.metrics.record_query(start_time.elapsed());
 
-                row_buffer.add_row(row_data);
+        if let Err(ref e) = result {
+            if e.is_connection_error() {
+                cached.invalidate_statements();
             }
+            self.metrics.record_error();
+            self.audit_logger.log_error(None, &e.to_string());
         }
 
-        // FOR JSON normalisation — see execute_query_inner above (closes #2).
-        coalesce_for_json_rows(&mut row_buffer);
-
-        if self.use_columnar {
-            let columnar_buffer = row_buffer_to_columnar(&row_buffer);
-            ColumnarEncoder::encode(&columnar_buffer, self.use_compression)
-        } else {
-            Ok(RowBufferEncoder::encode(&row_buffer))
-        }
+        result
     }
 
-    pub fn execute_multi_result(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    pub fn execute_multi_result<'a>(
+        &self,
+        conn: impl Into<QueryConnection<'a>>,
+        sql: &str,
+    ) -> Result<Vec<u8>> {
         use std::time::Instant;
+        let (conn, options) = self.resolve(conn.into());
 
         let start_time = Instant::now();
         let _span = SpanGuard::new(Arc::clone(&self.tracer), sql.to_string());
@@ -584,7 +758,7 @@ impl ExecutionEngine {
         metadata.insert("span_id".to_string(), _span.span_id().to_string());
         self.logger.log_query(Level::Info, sql, &metadata);
 
-        let result = self.execute_multi_result_inner(conn, sql);
+        let result = self.execute_multi_result_inner(conn, sql, &options);
 
         self.metrics.record_query(start_time.elapsed());
 
@@ -599,13 +773,14 @@ impl ExecutionEngine {
     /// Execute a multi-result batch with `?` positional parameters.
     /// Same wire format as [`execute_multi_result`]; supports up to 5 params
     /// (M5 in v3.2.0).
-    pub fn execute_multi_result_with_params(
+    pub fn execute_multi_result_with_params<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[ParamValue],
     ) -> Result<Vec<u8>> {
         use std::time::Instant;
+        let (conn, options) = self.resolve(conn.into());
 
         let start_time = Instant::now();
         let _span = SpanGuard::new(Arc::clone(&self.tracer), sql.to_string());
@@ -613,7 +788,7 @@ impl ExecutionEngine {
         metadata.insert("span_id".to_string(), _span.span_id().to_string());
         self.logger.log_query(Level::Info, sql, &metadata);
 
-        let result = self.execute_multi_result_with_params_inner(conn, sql, params);
+        let result = self.execute_multi_result_with_params_inner(conn, sql, params, &options);
 
         self.metrics.record_query(start_time.elapsed());
 
@@ -625,7 +800,12 @@ impl ExecutionEngine {
         result
     }
 
-    fn execute_multi_result_inner(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    fn execute_multi_result_inner(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
         let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
         let mut all_items: Vec<MultiResultItem> = Vec::new();
 
@@ -636,7 +816,7 @@ impl ExecutionEngine {
         let had_initial_cursor = {
             let initial_cursor = stmt.execute(()).map_err(OdbcError::from)?;
             if let Some(mut cursor) = initial_cursor {
-                let encoded = self.encode_cursor(&mut cursor)?;
+                let encoded = self.encode_cursor(&mut cursor, options)?;
                 all_items.push(MultiResultItem::ResultSet(encoded));
                 // Consume cursor *without* close_cursor (preserves pending
                 // result sets for SQLMoreResults below).
@@ -656,7 +836,7 @@ impl ExecutionEngine {
             all_items.push(MultiResultItem::RowCount(rc));
         }
 
-        self.drive_more_results(&mut stmt, &mut all_items)?;
+        self.drive_more_results(&mut stmt, &mut all_items, options)?;
         Ok(encode_multi(&all_items))
     }
 
@@ -665,57 +845,19 @@ impl ExecutionEngine {
         conn: &Connection<'static>,
         sql: &str,
         params: &[ParamValue],
+        options: &ResultOptions,
     ) -> Result<Vec<u8>> {
-        let optional_strings = crate::protocol::param_values_to_strings(params)?;
+        let input_params = input_parameters(params, options.temporal)?;
         let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
         let mut all_items: Vec<MultiResultItem> = Vec::new();
 
         let had_initial_cursor = {
-            let initial_cursor = match optional_strings.len() {
-                0 => stmt.execute(()).map_err(OdbcError::from)?,
-                1 => {
-                    let p0 = optional_strings[0].as_deref().into_parameter();
-                    stmt.execute((&p0,)).map_err(OdbcError::from)?
-                }
-                2 => {
-                    let p0 = optional_strings[0].as_deref().into_parameter();
-                    let p1 = optional_strings[1].as_deref().into_parameter();
-                    stmt.execute((&p0, &p1)).map_err(OdbcError::from)?
-                }
-                3 => {
-                    let p0 = optional_strings[0].as_deref().into_parameter();
-                    let p1 = optional_strings[1].as_deref().into_parameter();
-                    let p2 = optional_strings[2].as_deref().into_parameter();
-                    stmt.execute((&p0, &p1, &p2)).map_err(OdbcError::from)?
-                }
-                4 => {
-                    let p0 = optional_strings[0].as_deref().into_parameter();
-                    let p1 = optional_strings[1].as_deref().into_parameter();
-                    let p2 = optional_strings[2].as_deref().into_parameter();
-                    let p3 = optional_strings[3].as_deref().into_parameter();
-                    stmt.execute((&p0, &p1, &p2, &p3))
-                        .map_err(OdbcError::from)?
-                }
-                5 => {
-                    let p0 = optional_strings[0].as_deref().into_parameter();
-                    let p1 = optional_strings[1].as_deref().into_parameter();
-                    let p2 = optional_strings[2].as_deref().into_parameter();
-                    let p3 = optional_strings[3].as_deref().into_parameter();
-                    let p4 = optional_strings[4].as_deref().into_parameter();
-                    stmt.execute((&p0, &p1, &p2, &p3, &p4))
-                        .map_err(OdbcError::from)?
-                }
-                n => {
-                    return Err(OdbcError::ValidationError(format!(
-                        "At most 5 parameters supported in execute_multi_result_with_params, \
-                         got {}",
-                        n
-                    )))
-                }
-            };
+            let initial_cursor = stmt
+                .execute(input_params.as_slice())
+                .map_err(OdbcError::from)?;
 
             if let Some(mut cursor) = initial_cursor {
-                let encoded = self.encode_cursor(&mut cursor)?;
+                let encoded = self.encode_cursor(&mut cursor, options)?;
                 all_items.push(MultiResultItem::ResultSet(encoded));
                 // Same SQLCloseCursor avoidance as in `execute_multi_result_inner`.
                 let _stmt_ref = cursor.into_stmt();
@@ -734,7 +876,7 @@ impl ExecutionEngine {
             all_items.push(MultiResultItem::RowCount(rc));
         }
 
-        self.drive_more_results(&mut stmt, &mut all_items)?;
+        self.drive_more_results(&mut stmt, &mut all_items, options)?;
         Ok(encode_multi(&all_items))
     }
 
@@ -755,6 +897,7 @@ impl ExecutionEngine {
         &self,
         stmt: &mut S,
         all_items: &mut Vec<MultiResultItem>,
+        options: &ResultOptions,
     ) -> Result<()>
     where
         S: AsStatementRef,
@@ -805,7 +948,7 @@ impl ExecutionEngine {
                 // pending result sets after this one are not discarded by
                 // `SQLCloseCursor`.
                 let mut cursor = unsafe { CursorImpl::new(stmt.as_stmt_ref()) };
-                let encoded = self.encode_cursor(&mut cursor)?;
+                let encoded = self.encode_cursor(&mut cursor, options)?;
                 all_items.push(MultiResultItem::ResultSet(encoded));
                 let _stmt_ref = cursor.into_stmt();
             } else {
@@ -837,6 +980,7 @@ impl ExecutionEngine {
         sql: &str,
         bound: &[BoundParam],
         timeout_sec: Option<usize>,
+        options: &ResultOptions,
     ) -> Result<Vec<u8>> {
         use super::output_aware_params::bound_to_slots;
         use super::ref_cursor_oracle::{
@@ -849,7 +993,7 @@ impl ExecutionEngine {
             .count();
         let stripped = strip_ref_cursor_placeholders(sql, bound)?;
         let filtered = filter_non_ref_cursor_params(bound);
-        let mut odbc_params = bound_to_slots(&filtered)?;
+        let mut odbc_params = bound_to_slots(&filtered, options.temporal)?;
 
         let mut prep = conn.prepare(&stripped).map_err(OdbcError::from)?;
         if let Some(s) = timeout_sec {
@@ -862,11 +1006,11 @@ impl ExecutionEngine {
             // the underlying statement.
             let first = prep.execute(&mut odbc_params).map_err(OdbcError::from)?;
             if let Some(mut c) = first {
-                ref_blobs.push(self.encode_cursor_v1(&mut c)?);
+                ref_blobs.push(self.encode_cursor_v1(&mut c, options)?);
                 let _ = c.into_stmt();
             }
         }
-        self.drive_more_ref_cursor_blobs(&mut prep, &mut ref_blobs)?;
+        self.drive_more_ref_cursor_blobs(&mut prep, &mut ref_blobs, options)?;
 
         if ref_blobs.len() != ref_count {
             return Err(OdbcError::ValidationError(format!(
@@ -877,7 +1021,7 @@ impl ExecutionEngine {
         }
 
         let out_vals = odbc_params.output_footer_values();
-        let mut main_buffer = RowBuffer::new();
+        let mut main_buffer = RowBuffer::with_cell_encoding(self.cell_encoding(options));
         coalesce_for_json_rows(&mut main_buffer);
         let main_body = if self.use_columnar {
             let columnar_buffer = row_buffer_to_columnar(&main_buffer);
@@ -892,7 +1036,12 @@ impl ExecutionEngine {
     /// Like [`Self::drive_more_results`], but only collects cursor result
     /// sets, encoded as v1 (for the `RC1\0` trailer), skipping row-count-only
     /// steps while still advancing.
-    fn drive_more_ref_cursor_blobs<S>(&self, stmt: &mut S, out: &mut Vec<Vec<u8>>) -> Result<()>
+    fn drive_more_ref_cursor_blobs<S>(
+        &self,
+        stmt: &mut S,
+        out: &mut Vec<Vec<u8>>,
+        options: &ResultOptions,
+    ) -> Result<()>
     where
         S: AsStatementRef,
     {
@@ -932,7 +1081,7 @@ impl ExecutionEngine {
                 .map_err(OdbcError::from)?;
             if cols > 0 {
                 let mut cursor = unsafe { CursorImpl::new(stmt.as_stmt_ref()) };
-                out.push(self.encode_cursor_v1(&mut cursor)?);
+                out.push(self.encode_cursor_v1(&mut cursor, options)?);
                 let _ = cursor.into_stmt();
             } else {
                 let _ = stmt
@@ -946,40 +1095,13 @@ impl ExecutionEngine {
 
     /// Same as [`Self::encode_cursor`], but always row-major v1 (required
     /// for `RC1\0` embedded messages on the wire).
-    fn encode_cursor_v1<C: Cursor + ResultSetMetadata>(&self, cursor: &mut C) -> Result<Vec<u8>> {
-        let mut row_buffer = RowBuffer::new();
-        let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-        let cols_u16: u16 = cols_i16
-            .try_into()
-            .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-        let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_u16 as usize);
-        for col_idx in 1..=cols_u16 {
-            let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-            let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-            let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-            let odbc_type = if let Ok(active) = self.active_plugin.lock() {
-                if let Some(ref plugin) = *active {
-                    plugin.map_type(sql_type_code)
-                } else {
-                    OdbcType::from_odbc_sql_type(sql_type_code)
-                }
-            } else {
-                OdbcType::from_odbc_sql_type(sql_type_code)
-            };
-            row_buffer.add_column(col_name.to_string(), odbc_type);
-            column_types.push(odbc_type);
-        }
-        let mut cell_reader = CellReader::new();
-        while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-            let mut row_data = Vec::with_capacity(column_types.len());
-            for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                let col_number: u16 = (col_idx + 1)
-                    .try_into()
-                    .map_err(|_| OdbcError::InternalError("Invalid column number".to_string()))?;
-                row_data.push(cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?);
-            }
-            row_buffer.add_row(row_data);
-        }
+    fn encode_cursor_v1<C: Cursor + ResultSetMetadata>(
+        &self,
+        cursor: &mut C,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
+        let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding(options));
+        self.read_result_set(cursor, None, options, &mut row_buffer)?;
         coalesce_for_json_rows(&mut row_buffer);
         Ok(RowBufferEncoder::encode(&row_buffer))
     }
@@ -992,14 +1114,58 @@ impl ExecutionEngine {
     /// pending result sets) or to consume it via `cursor.into_stmt()` (which
     /// preserves them for `SQLMoreResults`). The multi-result path uses the
     /// latter.
-    fn encode_cursor<C: Cursor + ResultSetMetadata>(&self, cursor: &mut C) -> Result<Vec<u8>> {
-        let mut row_buffer = RowBuffer::new();
+    fn encode_cursor<C: Cursor + ResultSetMetadata>(
+        &self,
+        cursor: &mut C,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
+        self.encode_result_set(cursor, None, options)
+    }
+
+    /// Describes the current result set of `cursor` and encodes all of its
+    /// rows. Row-major binary results are written rowset by rowset through
+    /// [`RowFetcher::encode_into`], so only one fetch block is held next to
+    /// the output. Columnar and Arrow results, and SQL Server `FOR JSON`
+    /// results (whose chunks are reassembled into one cell, see
+    /// `engine::sqlserver_json`, closes #2), are read in full first.
+    fn encode_result_set<C: Cursor>(
+        &self,
+        cursor: &mut C,
+        fetch_size: Option<u32>,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
+        let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding(options));
+        let (column_types, data_types) = self.describe_columns(cursor, &mut row_buffer)?;
+        let block_rows = options.block_rows(fetch_size);
+        let mut fetcher = RowFetcher::new(cursor, &data_types, &column_types, block_rows)?;
+        if !self.use_columnar
+            && options.format == OutputFormat::OdbcBinary
+            && !is_for_json_result(&row_buffer)
+        {
+            return fetcher.encode_into(&mut row_buffer, block_rows, Vec::new());
+        }
+        fetcher.fetch_all(&mut row_buffer)?;
+        coalesce_for_json_rows(&mut row_buffer);
+        self.encode_result(&row_buffer, options)
+    }
+
+    /// Resolves column names and wire types of the current result set,
+    /// honouring the active plugin's type mapping (refined to the native-cell
+    /// types when `row_buffer` uses [`CellEncoding::Native`]), and registers
+    /// them on `row_buffer`. Returns the wire types alongside the ODBC data types
+    /// (the latter size the block-fetch buffers).
+    fn describe_columns<C: ResultSetMetadata>(
+        &self,
+        cursor: &mut C,
+        row_buffer: &mut RowBuffer,
+    ) -> Result<(Vec<OdbcType>, Vec<DataType>)> {
         let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
         let cols_u16: u16 = cols_i16
             .try_into()
             .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
         let cols_usize: usize = cols_u16.into();
         let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
+        let mut data_types: Vec<DataType> = Vec::with_capacity(cols_usize);
 
         for col_idx in 1..=cols_u16 {
             let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
@@ -1014,32 +1180,37 @@ impl ExecutionEngine {
             } else {
                 OdbcType::from_odbc_sql_type(sql_type_code)
             };
-            row_buffer.add_column(col_name.to_string(), odbc_type);
+            let odbc_type = if row_buffer.cell_encoding.is_native() {
+                native_column_type(&col_type).unwrap_or(odbc_type)
+            } else {
+                odbc_type
+            };
+            row_buffer.add_described_column(col_name.to_string(), odbc_type, col_type);
             column_types.push(odbc_type);
+            data_types.push(col_type);
         }
+        Ok((column_types, data_types))
+    }
 
-        let mut cell_reader = CellReader::new();
-        while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-            let mut row_data = Vec::with_capacity(column_types.len());
-            for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                let col_number: u16 = (col_idx + 1)
-                    .try_into()
-                    .map_err(|_| OdbcError::InternalError("Invalid column number".to_string()))?;
-                let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-                row_data.push(cell_data);
-            }
-            row_buffer.add_row(row_data);
-        }
-
-        // FOR JSON normalisation — see execute_query_inner above (closes #2).
-        coalesce_for_json_rows(&mut row_buffer);
-
-        if self.use_columnar {
-            let columnar_buffer = row_buffer_to_columnar(&row_buffer);
-            ColumnarEncoder::encode(&columnar_buffer, self.use_compression)
-        } else {
-            Ok(RowBufferEncoder::encode(&row_buffer))
-        }
+    /// Describes the current result set and reads all of its rows into
+    /// `row_buffer`, block-fetching when every column is bounded (see
+    /// [`crate::engine::block_fetch`]).
+    fn read_result_set<C: Cursor>(
+        &self,
+        cursor: &mut C,
+        fetch_size: Option<u32>,
+        options: &ResultOptions,
+        row_buffer: &mut RowBuffer,
+    ) -> Result<()> {
+        let (column_types, data_types) = self.describe_columns(cursor, row_buffer)?;
+        fetch_all_rows(
+            cursor,
+            &data_types,
+            &column_types,
+            options.block_rows(fetch_size),
+            row_buffer,
+        )?;
+        Ok(())
     }
 
     pub fn get_metrics(&self) -> Arc<Metrics> {
@@ -1069,6 +1240,131 @@ mod tests {
         assert!(engine.plugin_registry.is_some());
     }
 
+    #[test]
+    fn test_set_protocol_version_selects_cell_encoding() {
+        let engine = ExecutionEngine::new(10);
+        assert_eq!(
+            engine.cell_encoding(&engine.default_options()),
+            CellEncoding::Text
+        );
+        engine
+            .set_protocol_version(ProtocolVersion::v1_native())
+            .unwrap();
+        assert_eq!(
+            engine.cell_encoding(&engine.default_options()),
+            CellEncoding::Native
+        );
+        engine.set_protocol_version(ProtocolVersion::v2()).unwrap();
+        assert_eq!(
+            engine.cell_encoding(&engine.default_options()),
+            CellEncoding::Text
+        );
+        assert!(engine
+            .set_protocol_version(ProtocolVersion::new(3, 0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_output_format_follows_feature() {
+        let engine = ExecutionEngine::new(10);
+        assert_eq!(engine.output_format(), OutputFormat::OdbcBinary);
+        let result = engine.set_output_format(OutputFormat::ArrowIpc);
+        assert_eq!(result.is_ok(), cfg!(feature = "arrow-ipc"));
+        assert!(engine.set_output_format(OutputFormat::OdbcBinary).is_ok());
+        assert_eq!(engine.output_format(), OutputFormat::OdbcBinary);
+    }
+
+    #[test]
+    fn test_result_options_protocol_defaults_to_v1() {
+        let options = ResultOptions::default();
+        assert_eq!(options.protocol, ProtocolVersion::v1());
+        let native = options
+            .with_protocol_version(ProtocolVersion::v1_native())
+            .unwrap();
+        assert_eq!(native.protocol, ProtocolVersion::v1_native());
+        assert!(options
+            .with_protocol_version(ProtocolVersion::new(9, 0))
+            .is_err());
+        let engine = ExecutionEngine::new(10);
+        assert_eq!(engine.cell_encoding(&options), CellEncoding::Text);
+        assert_eq!(engine.cell_encoding(&native), CellEncoding::Native);
+    }
+
+    #[test]
+    fn test_result_options_output_format_follows_feature() {
+        let options = ResultOptions::default();
+        assert_eq!(options.format, OutputFormat::OdbcBinary);
+        let arrow = options.with_output_format(OutputFormat::ArrowIpc);
+        assert_eq!(arrow.is_ok(), cfg!(feature = "arrow-ipc"));
+        if let Ok(arrow) = arrow {
+            assert_eq!(arrow.format, OutputFormat::ArrowIpc);
+            assert_eq!(arrow.max_row_array_size, options.max_row_array_size);
+        }
+    }
+
+    #[test]
+    fn test_result_options_buffer_policy_is_validated() {
+        use crate::engine::stream_buffer::StreamOverflow;
+        let options = ResultOptions::default();
+        assert_eq!(options.buffer, StreamBufferPolicy::DEFAULT);
+        let policy = StreamBufferPolicy {
+            max_batches: 4,
+            max_bytes: Some(1 << 20),
+            overflow: StreamOverflow::Spill,
+        };
+        assert_eq!(options.with_buffer_policy(policy).unwrap().buffer, policy);
+        assert!(options
+            .with_buffer_policy(StreamBufferPolicy {
+                max_batches: 0,
+                ..policy
+            })
+            .is_err());
+    }
+
+    #[cfg(feature = "arrow-ipc")]
+    #[test]
+    fn test_arrow_output_replaces_binary_protocol() {
+        let engine = ExecutionEngine::new(10);
+        engine.set_output_format(OutputFormat::ArrowIpc).unwrap();
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("id".to_string(), OdbcType::Integer);
+        buffer.add_row(vec![Some(1i32.to_le_bytes().to_vec())]);
+        let options = engine.default_options();
+        let bytes = engine.encode_result(&buffer, &options).unwrap();
+        let reader = arrow_ipc::reader::StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        assert_eq!(reader.schema().field(0).name(), "id");
+        assert_eq!(reader.map(|b| b.unwrap().num_rows()).sum::<usize>(), 1);
+        assert!(ExecutionEngine::ensure_footer_format(&options).is_err());
+    }
+
+    #[cfg(feature = "columnar-v2")]
+    #[test]
+    fn test_set_protocol_version_columnar_arrays() {
+        let engine = ExecutionEngine::with_columnar(10, false);
+        engine
+            .set_protocol_version(ProtocolVersion::v2_arrays())
+            .unwrap();
+        assert!(engine
+            .protocol_version(&engine.default_options())
+            .has_columnar_arrays());
+        assert_eq!(
+            engine.cell_encoding(&engine.default_options()),
+            CellEncoding::Native
+        );
+        // Row-major engines only take the native cells from a 2.2 request.
+        let row_major = ExecutionEngine::new(10);
+        row_major
+            .set_protocol_version(ProtocolVersion::v2_arrays())
+            .unwrap();
+        assert!(!row_major
+            .protocol_version(&row_major.default_options())
+            .has_columnar_arrays());
+        assert_eq!(
+            row_major.cell_encoding(&row_major.default_options()),
+            CellEncoding::Native
+        );
+    }
+
     #[test]
     fn test_execution_engine_with_columnar() {
         let engine = ExecutionEngine::with_columnar(50, true);
@@ -1163,6 +1459,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_connection_string_updates_block_fetch_rows() {
+        let engine = ExecutionEngine::new(100);
+        assert_eq!(engine.default_options().block_rows(None), 1000);
+
+        engine.set_connection_string("Driver={Oracle in OraClient};DBQ=xe;");
+        let options = engine.default_options();
+        assert_eq!(options.block_rows(None), 5000);
+        assert_eq!(options.block_rows(Some(250)), 250);
+        assert_eq!(options.block_rows(Some(9000)), 5000);
+    }
+
+    #[test]
+    fn test_result_options_follow_driver_capabilities() {
+        let oracle = ResultOptions::for_driver(&DriverCapabilities::from_driver_name("oracle"));
+        assert_eq!(oracle.max_row_array_size, 5000);
+        assert_eq!(oracle.temporal, TemporalBinding::Standard);
+        assert_eq!(
+            ResultOptions::default().max_row_array_size,
+            DriverCapabilities::default().max_row_array_size
+        );
+        let sqlserver =
+            ResultOptions::for_driver(&DriverCapabilities::from_driver_name("sqlserver"));
+        assert_eq!(sqlserver.temporal, TemporalBinding::SqlServer);
+
+        let engine = ExecutionEngine::new(10);
+        assert_eq!(engine.default_options().temporal, TemporalBinding::Standard);
+        engine.set_connection_string("Driver={ODBC Driver 18 for SQL Server};Server=x;");
+        assert_eq!(
+            engine.default_options().temporal,
+            TemporalBinding::SqlServer
+        );
+    }
+
     #[test]
     fn test_set_connection_string_postgres() {
         let engine = ExecutionEngine::new(100);
@@ -1,37 +1,61 @@
 pub mod array_binding;
 pub mod batch_executor;
 pub mod bulk_copy;
+pub mod bulk_upsert;
 pub mod connection_manager;
+pub mod csv_import;
 pub mod disk_spill;
 pub mod driver_capabilities;
 pub mod execution_engine;
 pub mod memory_engine;
 pub mod metadata_cache;
+pub mod mysql_load_data;
 mod output_aware_params;
 pub mod parallel_insert;
+#[cfg(feature = "parquet")]
+pub mod parquet_io;
 pub mod pipeline;
 pub mod prepared_cache;
 pub mod protocol_engine;
+pub mod query_export;
 mod ref_cursor_oracle;
 pub mod security_layer;
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+pub mod snowflake_stage;
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 pub mod sqlserver_bcp;
+mod typed_params;
 
-pub use array_binding::ArrayBinding;
+pub use array_binding::{ArrayBinding, BulkDmlOutcome, BulkInsertOutcome};
 pub use batch_executor::{BatchExecutor, BatchParam, BatchQuery};
 pub use bulk_copy::{BulkCopyExecutor, BulkCopyFormat};
+pub use bulk_upsert::{BulkUpsert, BulkUpsertReport};
 pub use connection_manager::ConnectionManager;
-pub use disk_spill::{DiskSpillStream, DiskSpillWriter, SpillReadSource};
+pub use csv_import::{
+    CsvImportOptions, CsvImportProgress, CsvImportReport, CsvImporter, CsvMalformedLine,
+};
+pub use disk_spill::{
+    recover_orphaned_spill_files, DiskSpillStream, DiskSpillWriter, SpillOptions, SpillReadSource,
+};
 pub use driver_capabilities::{
     DriverCapabilities, ENGINE_BIGQUERY, ENGINE_DB2, ENGINE_MARIADB, ENGINE_MONGODB, ENGINE_MYSQL,
     ENGINE_ORACLE, ENGINE_POSTGRES, ENGINE_REDSHIFT, ENGINE_SNOWFLAKE, ENGINE_SQLITE,
     ENGINE_SQLSERVER, ENGINE_SYBASE_ASA, ENGINE_SYBASE_ASE, ENGINE_UNKNOWN,
 };
-pub use execution_engine::ExecutionEngine;
+pub use execution_engine::{ExecutionEngine, QueryConnection, ResultOptions};
 pub use memory_engine::MemoryEngine;
 pub use metadata_cache::{ColumnMetadata, MetadataCache, TableSchema};
+pub use mysql_load_data::MySqlLoadDataLoader;
 pub use parallel_insert::ParallelBulkInsert;
+#[cfg(feature = "parquet")]
+pub use parquet_io::{
+    ParquetCodec, ParquetExportOptions, ParquetExportReport, ParquetExporter, ParquetImportOptions,
+    ParquetImportProgress, ParquetImportReport, ParquetImporter,
+};
 pub use pipeline::{QueryPipeline, QueryPlan};
 pub use prepared_cache::{PreparedStatementCache, PreparedStatementMetrics};
 pub use protocol_engine::{ProtocolEngine, ProtocolVersion};
+pub use query_export::{ExportFormat, ExportOptions, ExportReport, QueryExporter};
 pub use security_layer::{SecureBuffer, SecurityLayer};
+pub use snowflake_stage::{SnowflakeCopyReport, SnowflakeOnError, SnowflakeStageLoader};
+pub(crate) use typed_params::input_parameters;
+pub use typed_params::TemporalBinding;
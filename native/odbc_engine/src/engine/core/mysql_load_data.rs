@@ -0,0 +1,738 @@
+//! MySQL / MariaDB `LOAD DATA LOCAL INFILE` bulk loader.
+//!
+//! Each batch of `BulkLoadOptions::batch_size` rows is written to a temp file
+//! (`options.delimiter`-separated, `\n`-terminated, backslash-escaped) and loaded with
+//! one `LOAD DATA LOCAL INFILE` statement. Every field is read into a user variable and
+//! assigned through `SET`, which lets the loader:
+//!
+//! - honour a custom `options.null_marker` (`NULLIF(@v, marker)`; the default `\N` is
+//!   already NULL under `ESCAPED BY '\\'`);
+//! - ship binary columns as hex and decode them server-side with `UNHEX`, so the
+//!   file's character set never touches blob bytes.
+//!
+//! `LOCAL` needs `local_infile` enabled on both the server and the client/DSN. When
+//! either side refuses it on the first batch the loader returns
+//! `OdbcError::UnsupportedFeature`; [`MySqlLoadDataLoader::load_or_insert_values`] then
+//! retries through [`insert_values_batched`] (multi-row `INSERT ... VALUES`).
+//!
+//! Each temp file is created exclusively in a fresh owner-only directory and removed,
+//! with its directory, after every batch. On failure they are kept (and the path
+//! logged) only when `options.keep_temp_on_failure` is set.
+
+use crate::engine::core::disk_spill::{create_private_dir, create_spill_file};
+use crate::engine::core::{input_parameters, TemporalBinding};
+use crate::engine::identifier::{quote_identifier, IdentifierQuoting};
+use crate::error::{OdbcError, Result};
+use crate::plugins::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress};
+use crate::protocol::param_value::{ParamDate, ParamTime, ParamTimestamp};
+use crate::protocol::{format_uuid, BulkColumnData, BulkInsertPayload, ParamValue};
+use odbc_api::Connection;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// MySQL rejects statements with more than 65 535 placeholders.
+const MAX_PLACEHOLDERS: usize = 65_535;
+
+/// Server / client error codes meaning `LOCAL` loading is disabled, not that the data
+/// is bad: 1148 `ER_NOT_ALLOWED_COMMAND`, 2068 `CR_LOAD_DATA_LOCAL_INFILE_REJECTED`,
+/// 3948 `ER_CLIENT_LOCAL_FILES_DISABLED`, 4166 MariaDB `ER_LOAD_INFILE_CAPABILITY_DISABLED`.
+const LOCAL_INFILE_REFUSED_CODES: &[i32] = &[1148, 2068, 3948, 4166];
+
+static TEMP_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Loads [`BulkInsertPayload`]s with `LOAD DATA LOCAL INFILE`.
+#[derive(Debug, Clone, Default)]
+pub struct MySqlLoadDataLoader {
+    temp_dir: Option<PathBuf>,
+}
+
+impl MySqlLoadDataLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory for the per-batch data files (default: `std::env::temp_dir()`).
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    /// Loads `payload` one file per batch, calling `on_batch` after each one succeeds.
+    /// Returns the rows the server reports as inserted.
+    pub fn load(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        payload.validate_shape()?;
+        validate_text_options(options)?;
+        let total_rows = payload.row_count as usize;
+        if total_rows == 0 {
+            return Ok(0);
+        }
+
+        let dir = self.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let batch_size = options.batch_size.max(1);
+        let mut start = 0usize;
+        let mut loaded = 0usize;
+        let mut batch_index = 0usize;
+        while start < total_rows {
+            let end = (start + batch_size).min(total_rows);
+            let mut file = TempLoadFile::create(&dir)?;
+            let result = file
+                .write_rows(payload, options, start..end)
+                .and_then(|()| {
+                    let sql = load_data_sql(payload, &file.path, options)?;
+                    execute_counting(conn, &sql, options.timeout_secs)
+                });
+            let affected = match result {
+                Ok(affected) => affected.unwrap_or(end - start),
+                Err(err) => {
+                    if options.keep_temp_on_failure {
+                        file.keep();
+                    }
+                    if start == 0 && is_local_infile_refused(&err) {
+                        return Err(OdbcError::UnsupportedFeature(format!(
+                            "LOAD DATA LOCAL INFILE refused: {err}"
+                        )));
+                    }
+                    return Err(err);
+                }
+            };
+
+            loaded += affected;
+            on_batch(&BulkLoadProgress {
+                batch_index,
+                batch_rows: affected,
+                rows_loaded: loaded,
+                total_rows,
+            });
+            start = end;
+            batch_index += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// [`Self::load`], falling back to [`insert_values_batched`] when `LOCAL` loading
+    /// is refused.
+    pub fn load_or_insert_values(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        match self.load(conn, payload, options, on_batch) {
+            Err(OdbcError::UnsupportedFeature(reason)) => {
+                log::warn!(
+                    "{reason}; falling back to multi-row INSERT ... VALUES. Enable \
+                     local_infile on the server and in the ODBC DSN to use the native path."
+                );
+                insert_values_batched(conn, payload, options, on_batch)
+            }
+            other => other,
+        }
+    }
+}
+
+/// True when `err` says the server or driver has `LOCAL` loading disabled.
+pub fn is_local_infile_refused(err: &OdbcError) -> bool {
+    if let OdbcError::Structured {
+        native_code,
+        message,
+        ..
+    } = err
+    {
+        if LOCAL_INFILE_REFUSED_CODES.contains(native_code) {
+            return true;
+        }
+        let message = message.to_ascii_lowercase();
+        return message.contains("local infile") || message.contains("local data is disabled");
+    }
+    false
+}
+
+/// `LOAD DATA LOCAL INFILE '<path>' INTO TABLE ... (@c0, ...) SET ...` for `payload`.
+pub fn load_data_sql(
+    payload: &BulkInsertPayload,
+    path: &Path,
+    options: &BulkLoadOptions,
+) -> Result<String> {
+    let table = quote_table(&payload.table)?;
+    // MySQL accepts forward slashes on Windows too, which avoids doubling escapes.
+    let path = path.to_string_lossy().replace('\\', "/");
+    let vars = (0..payload.columns.len())
+        .map(|i| format!("@c{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut assignments = Vec::with_capacity(payload.columns.len());
+    for (i, (spec, data)) in payload.columns.iter().zip(&payload.column_data).enumerate() {
+        let col = quote_identifier(&spec.name, IdentifierQuoting::Backtick)?;
+        let value = if options.null_marker == "\\N" {
+            format!("@c{i}")
+        } else {
+            format!("NULLIF(@c{i}, {})", mysql_literal(&options.null_marker))
+        };
+        let value = match data {
+            BulkColumnData::Binary { .. } => format!("UNHEX({value})"),
+            _ => value,
+        };
+        assignments.push(format!("{col} = {value}"));
+    }
+    Ok(format!(
+        "LOAD DATA LOCAL INFILE {} INTO TABLE {table} CHARACTER SET utf8mb4 \
+         FIELDS TERMINATED BY {} ESCAPED BY '\\\\' LINES TERMINATED BY '\\n' \
+         ({vars}) SET {}",
+        mysql_literal(&path),
+        mysql_literal(&options.delimiter.to_string()),
+        assignments.join(", ")
+    ))
+}
+
+/// Inserts `payload` with multi-row `INSERT ... VALUES (?, ...), (?, ...)` statements of
+/// up to `options.batch_size` rows (capped by MySQL's placeholder limit).
+pub fn insert_values_batched(
+    conn: &Connection<'static>,
+    payload: &BulkInsertPayload,
+    options: &BulkLoadOptions,
+    on_batch: &mut dyn FnMut(&BulkLoadProgress),
+) -> Result<usize> {
+    payload.validate_shape()?;
+    let total_rows = payload.row_count as usize;
+    let n_cols = payload.columns.len();
+    if total_rows == 0 || n_cols == 0 {
+        return Ok(0);
+    }
+
+    let rows_per_stmt = options.batch_size.clamp(1, MAX_PLACEHOLDERS / n_cols);
+    let mut start = 0usize;
+    let mut batch_index = 0usize;
+    while start < total_rows {
+        let end = (start + rows_per_stmt).min(total_rows);
+        let sql = insert_values_sql(payload, end - start)?;
+        let mut values = Vec::with_capacity((end - start) * n_cols);
+        for row in start..end {
+            for (spec, data) in payload.columns.iter().zip(&payload.column_data) {
+                values.push(param_value(&spec.name, data, row)?);
+            }
+        }
+        let params = input_parameters(&values, TemporalBinding::Standard)?;
+        let timeout = options.timeout_secs.map(|s| s as usize);
+        conn.execute(&sql, params.as_slice(), timeout)
+            .map_err(OdbcError::from)?;
+
+        on_batch(&BulkLoadProgress {
+            batch_index,
+            batch_rows: end - start,
+            rows_loaded: end,
+            total_rows,
+        });
+        start = end;
+        batch_index += 1;
+    }
+    Ok(total_rows)
+}
+
+/// `INSERT INTO ... VALUES` with `rows` placeholder tuples; binary columns go through
+/// `UNHEX(?)` because byte parameters are bound as hex text.
+pub fn insert_values_sql(payload: &BulkInsertPayload, rows: usize) -> Result<String> {
+    let table = quote_table(&payload.table)?;
+    let cols = payload
+        .columns
+        .iter()
+        .map(|c| quote_identifier(&c.name, IdentifierQuoting::Backtick))
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    let tuple = format!(
+        "({})",
+        payload
+            .column_data
+            .iter()
+            .map(|d| match d {
+                BulkColumnData::Binary { .. } => "UNHEX(?)",
+                _ => "?",
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let tuples = vec![tuple; rows.max(1)].join(", ");
+    Ok(format!("INSERT INTO {table} ({cols}) VALUES {tuples}"))
+}
+
+fn quote_table(table: &str) -> Result<String> {
+    table
+        .split('.')
+        .map(|part| quote_identifier(part, IdentifierQuoting::Backtick))
+        .collect::<Result<Vec<_>>>()
+        .map(|parts| parts.join("."))
+}
+
+/// Single-quoted MySQL string literal (backslash escapes, the server default).
+fn mysql_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\'' => out.push_str("\\'"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            _ => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+fn validate_text_options(options: &BulkLoadOptions) -> Result<()> {
+    let d = options.delimiter;
+    if !d.is_ascii() || matches!(d, '\\' | '\n' | '\r' | '\0') {
+        return Err(OdbcError::ValidationError(format!(
+            "LOAD DATA delimiter must be a single-byte character other than backslash, \
+             NUL, newline or carriage return (got {d:?})"
+        )));
+    }
+    if options.null_marker.contains(['\n', '\r', d]) {
+        return Err(OdbcError::ValidationError(
+            "LOAD DATA NULL marker cannot contain the delimiter or a line break".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Appends row `row` of `payload` in the layout [`load_data_sql`] declares.
+pub fn encode_row(
+    out: &mut Vec<u8>,
+    payload: &BulkInsertPayload,
+    row: usize,
+    options: &BulkLoadOptions,
+) {
+    let delimiter = options.delimiter as u8;
+    for (i, data) in payload.column_data.iter().enumerate() {
+        if i > 0 {
+            out.push(delimiter);
+        }
+        if data.is_null_at(row) {
+            out.extend_from_slice(options.null_marker.as_bytes());
+            continue;
+        }
+        match data {
+            BulkColumnData::I32 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::I64 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::Text { rows, .. } => escape_field(out, &rows[row], delimiter),
+            BulkColumnData::Binary { rows, .. } => {
+                for b in &rows[row] {
+                    out.extend_from_slice(format!("{b:02X}").as_bytes());
+                }
+            }
+            BulkColumnData::Timestamp { values, .. } => {
+                out.extend_from_slice(values[row].to_iso_micros().as_bytes())
+            }
+            BulkColumnData::Bool { values, .. } => out.push(if values[row] { b'1' } else { b'0' }),
+            BulkColumnData::F64 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::F32 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::Date { values, .. } => {
+                out.extend_from_slice(values[row].to_iso().as_bytes())
+            }
+            BulkColumnData::Time { values, .. } => {
+                out.extend_from_slice(values[row].to_iso().as_bytes())
+            }
+            BulkColumnData::Uuid { values, .. } => {
+                out.extend_from_slice(format_uuid(&values[row]).as_bytes())
+            }
+            BulkColumnData::NText { rows, .. } => escape_field(
+                out,
+                String::from_utf16_lossy(&rows[row]).as_bytes(),
+                delimiter,
+            ),
+        }
+    }
+    out.push(b'\n');
+}
+
+fn escape_field(out: &mut Vec<u8>, value: &[u8], delimiter: u8) {
+    for &b in value {
+        match b {
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b'\n' => out.extend_from_slice(b"\\n"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            0 => out.extend_from_slice(b"\\0"),
+            _ if b == delimiter => out.extend_from_slice(&[b'\\', b]),
+            _ => out.push(b),
+        }
+    }
+}
+
+fn param_value(column: &str, data: &BulkColumnData, row: usize) -> Result<ParamValue> {
+    if data.is_null_at(row) {
+        return Ok(ParamValue::Null);
+    }
+    Ok(match data {
+        BulkColumnData::I32 { values, .. } => ParamValue::Integer(values[row]),
+        BulkColumnData::I64 { values, .. } => ParamValue::BigInt(values[row]),
+        BulkColumnData::Text { rows, .. } => {
+            ParamValue::String(String::from_utf8(rows[row].clone()).map_err(|_| {
+                OdbcError::ValidationError(format!("column {column} row {row} is not valid UTF-8"))
+            })?)
+        }
+        BulkColumnData::Binary { rows, .. } => ParamValue::Binary(rows[row].clone()),
+        BulkColumnData::Timestamp { values, .. } => {
+            let ts = values[row];
+            ParamValue::Timestamp(ParamTimestamp {
+                date: ParamDate {
+                    year: ts.year,
+                    month: ts.month as u8,
+                    day: ts.day as u8,
+                },
+                time: ParamTime {
+                    hour: ts.hour as u8,
+                    minute: ts.minute as u8,
+                    second: ts.second as u8,
+                    nanos: ts.fraction,
+                },
+                offset_minutes: None,
+            })
+        }
+        BulkColumnData::Bool { values, .. } => ParamValue::Boolean(values[row]),
+        BulkColumnData::F64 { values, .. } => ParamValue::Double(values[row]),
+        BulkColumnData::F32 { values, .. } => ParamValue::Double(f64::from(values[row])),
+        BulkColumnData::Date { values, .. } => {
+            let d = values[row];
+            ParamValue::Date(ParamDate {
+                year: d.year,
+                month: d.month as u8,
+                day: d.day as u8,
+            })
+        }
+        BulkColumnData::Time { values, .. } => {
+            let t = values[row];
+            ParamValue::Time(ParamTime {
+                hour: t.hour as u8,
+                minute: t.minute as u8,
+                second: t.second as u8,
+                nanos: 0,
+            })
+        }
+        // MySQL has no UUID type; CHAR(36) is the common column shape.
+        BulkColumnData::Uuid { values, .. } => ParamValue::String(format_uuid(&values[row])),
+        BulkColumnData::NText { rows, .. } => {
+            ParamValue::String(String::from_utf16(&rows[row]).map_err(|_| {
+                OdbcError::ValidationError(format!("column {column} row {row} is not valid UTF-16"))
+            })?)
+        }
+    })
+}
+
+/// Runs `sql` and returns the driver's affected-row count, when it reports one.
+fn execute_counting(
+    conn: &Connection<'static>,
+    sql: &str,
+    timeout_secs: Option<u32>,
+) -> Result<Option<usize>> {
+    let mut stmt = conn.preallocate().map_err(OdbcError::from)?;
+    if let Some(secs) = timeout_secs {
+        stmt.set_query_timeout_sec(secs as usize)
+            .map_err(OdbcError::from)?;
+    }
+    stmt.execute(sql, ()).map_err(OdbcError::from)?;
+    stmt.row_count().map_err(OdbcError::from)
+}
+
+/// A per-batch data file in its own owner-only directory, both removed on drop
+/// unless [`Self::keep`] was called.
+struct TempLoadFile {
+    dir: PathBuf,
+    path: PathBuf,
+    /// Handle the file was created with, consumed by [`Self::write_rows`].
+    file: Option<File>,
+    keep: bool,
+}
+
+impl TempLoadFile {
+    /// Creates `<dir>/odbc_load_<pid>_<n>/batch.tsv`. The directory is created
+    /// with mode `0700` and the file exclusively with mode `0600` (on Unix), so
+    /// other local users can neither read the rows nor plant a file the server
+    /// would load instead.
+    fn create(dir: &Path) -> Result<Self> {
+        let io_err = |e: std::io::Error| OdbcError::InternalError(format!("load file create: {e}"));
+        let private_dir = dir.join(format!(
+            "odbc_load_{}_{}",
+            std::process::id(),
+            TEMP_FILE_SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        create_private_dir(&private_dir).map_err(io_err)?;
+        let mut load_file = Self {
+            path: private_dir.join("batch.tsv"),
+            dir: private_dir,
+            file: None,
+            keep: false,
+        };
+        load_file.file = Some(create_spill_file(&load_file.path).map_err(io_err)?);
+        Ok(load_file)
+    }
+
+    fn write_rows(
+        &mut self,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        rows: std::ops::Range<usize>,
+    ) -> Result<()> {
+        let io_err = |e: std::io::Error| OdbcError::InternalError(format!("load file write: {e}"));
+        let file = self
+            .file
+            .take()
+            .ok_or_else(|| OdbcError::InternalError("load file already written".to_string()))?;
+        let mut w = BufWriter::new(file);
+        let mut line = Vec::new();
+        for row in rows {
+            line.clear();
+            encode_row(&mut line, payload, row, options);
+            w.write_all(&line).map_err(io_err)?;
+        }
+        w.flush().map_err(io_err)
+    }
+
+    fn keep(&mut self) {
+        self.keep = true;
+        log::warn!(
+            "LOAD DATA failed; keeping data file {} for inspection",
+            self.path.display()
+        );
+    }
+}
+
+impl Drop for TempLoadFile {
+    fn drop(&mut self) {
+        self.file = None;
+        if self.keep {
+            return;
+        }
+        let warn = |path: &Path, r: std::io::Result<()>| {
+            if let Err(e) = r {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!(
+                        "TempLoadFile::drop: failed to remove {}: {e}",
+                        path.display()
+                    );
+                }
+            }
+        };
+        warn(&self.path, std::fs::remove_file(&self.path));
+        warn(&self.dir, std::fs::remove_dir(&self.dir));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{BulkColumnSpec, BulkColumnType, BulkTimestamp};
+
+    fn spec(name: &str, col_type: BulkColumnType) -> BulkColumnSpec {
+        BulkColumnSpec {
+            name: name.to_string(),
+            col_type,
+            nullable: true,
+            max_len: 0,
+        }
+    }
+
+    fn payload() -> BulkInsertPayload {
+        BulkInsertPayload {
+            table: "shop.items".to_string(),
+            columns: vec![
+                spec("id", BulkColumnType::I64),
+                spec("name", BulkColumnType::Text),
+                spec("blob", BulkColumnType::Binary),
+                spec("at", BulkColumnType::Timestamp),
+            ],
+            row_count: 2,
+            column_data: vec![
+                BulkColumnData::I64 {
+                    values: vec![1, 2],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Text {
+                    rows: vec![b"a\tb\\c\nd\0".to_vec(), Vec::new()],
+                    max_len: 8,
+                    null_bitmap: Some(vec![0b10]),
+                },
+                BulkColumnData::Binary {
+                    rows: vec![vec![0x00, 0xab], vec![0x10]],
+                    max_len: 2,
+                    null_bitmap: None,
+                },
+                BulkColumnData::Timestamp {
+                    values: vec![
+                        BulkTimestamp {
+                            year: 2024,
+                            month: 2,
+                            day: 29,
+                            hour: 1,
+                            minute: 2,
+                            second: 3,
+                            fraction: 4_000,
+                        };
+                        2
+                    ],
+                    null_bitmap: Some(vec![0b10]),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_rows_are_escaped_and_nulls_marked() {
+        let payload = payload();
+        let options = BulkLoadOptions::default();
+        let mut out = Vec::new();
+        encode_row(&mut out, &payload, 0, &options);
+        encode_row(&mut out, &payload, 1, &options);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\ta\\\tb\\\\c\\nd\\0\t00AB\t2024-02-29 01:02:03.000004\n2\t\\N\t10\t\\N\n"
+        );
+    }
+
+    #[test]
+    fn test_load_data_sql_binds_through_variables() {
+        let payload = payload();
+        let options = BulkLoadOptions::default();
+        let sql = load_data_sql(&payload, Path::new("C:\\tmp\\it's.tsv"), &options).unwrap();
+        assert_eq!(
+            sql,
+            "LOAD DATA LOCAL INFILE 'C:/tmp/it\\'s.tsv' INTO TABLE `shop`.`items` \
+             CHARACTER SET utf8mb4 FIELDS TERMINATED BY '\\t' ESCAPED BY '\\\\' \
+             LINES TERMINATED BY '\\n' (@c0, @c1, @c2, @c3) SET `id` = @c0, \
+             `name` = @c1, `blob` = UNHEX(@c2), `at` = @c3"
+        );
+    }
+
+    #[test]
+    fn test_custom_null_marker_uses_nullif() {
+        let payload = payload();
+        let options = BulkLoadOptions {
+            null_marker: "NULL".to_string(),
+            delimiter: ',',
+            ..BulkLoadOptions::default()
+        };
+        let sql = load_data_sql(&payload, Path::new("/tmp/x.tsv"), &options).unwrap();
+        assert!(sql.contains("FIELDS TERMINATED BY ','"));
+        assert!(sql.contains("`id` = NULLIF(@c0, 'NULL')"));
+        assert!(sql.contains("`blob` = UNHEX(NULLIF(@c2, 'NULL'))"));
+
+        let mut out = Vec::new();
+        encode_row(&mut out, &payload, 1, &options);
+        assert_eq!(out, b"2,NULL,10,NULL\n");
+    }
+
+    #[test]
+    fn test_invalid_text_options_are_rejected() {
+        for delimiter in ['\\', '\n', '\0', 'ç'] {
+            let options = BulkLoadOptions {
+                delimiter,
+                ..BulkLoadOptions::default()
+            };
+            assert!(validate_text_options(&options).is_err(), "{delimiter:?}");
+        }
+        let options = BulkLoadOptions {
+            null_marker: "a\tb".to_string(),
+            ..BulkLoadOptions::default()
+        };
+        assert!(validate_text_options(&options).is_err());
+    }
+
+    #[test]
+    fn test_insert_values_sql_repeats_tuples() {
+        let sql = insert_values_sql(&payload(), 2).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO `shop`.`items` (`id`, `name`, `blob`, `at`) VALUES \
+             (?, ?, UNHEX(?), ?), (?, ?, UNHEX(?), ?)"
+        );
+    }
+
+    #[test]
+    fn test_param_values_follow_column_types() {
+        let payload = payload();
+        let names = |row| {
+            payload
+                .columns
+                .iter()
+                .zip(&payload.column_data)
+                .map(|(s, d)| param_value(&s.name, d, row).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let row1 = names(1);
+        assert_eq!(row1[0], ParamValue::BigInt(2));
+        assert_eq!(row1[1], ParamValue::Null);
+        assert_eq!(row1[2], ParamValue::Binary(vec![0x10]));
+        assert_eq!(row1[3], ParamValue::Null);
+        assert!(matches!(names(0)[3], ParamValue::Timestamp(ts) if ts.time.nanos == 4_000));
+    }
+
+    #[test]
+    fn test_refusal_detection() {
+        let err = |code: i32, msg: &str| OdbcError::Structured {
+            sqlstate: *b"42000",
+            native_code: code,
+            message: msg.to_string(),
+        };
+        assert!(is_local_infile_refused(&err(3948, "")));
+        assert!(is_local_infile_refused(&err(1148, "")));
+        assert!(is_local_infile_refused(&err(
+            0,
+            "The used command is not allowed because the MariaDB server or client has \
+             disabled the local infile capability"
+        )));
+        assert!(!is_local_infile_refused(&err(1062, "Duplicate entry")));
+        assert!(!is_local_infile_refused(&OdbcError::InternalError(
+            "local infile".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_temp_file_cleanup_honours_keep() {
+        let dir = std::env::temp_dir();
+        let payload = payload();
+        let options = BulkLoadOptions::default();
+
+        let mut file = TempLoadFile::create(&dir).unwrap();
+        file.write_rows(&payload, &options, 0..2).unwrap();
+        let (path, private_dir) = (file.path.clone(), file.dir.clone());
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+        drop(file);
+        assert!(!path.exists());
+        assert!(!private_dir.exists());
+
+        let mut file = TempLoadFile::create(&dir).unwrap();
+        file.keep();
+        let (path, private_dir) = (file.path.clone(), file.dir.clone());
+        drop(file);
+        assert!(path.exists());
+        std::fs::remove_dir_all(private_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_temp_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let file = TempLoadFile::create(&std::env::temp_dir()).unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&file.dir), 0o700);
+        assert_eq!(mode(&file.path), 0o600);
+    }
+}
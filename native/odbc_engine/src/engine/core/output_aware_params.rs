@@ -5,8 +5,10 @@ use crate::protocol::bound_param::{BoundParam, ParamDirection};
 use crate::protocol::param_value::ParamValue;
 use crate::protocol::param_values_to_strings;
 
+use super::typed_params::{binds_natively, input_parameter, TemporalBinding};
 use odbc_api::buffers::Indicator;
 use odbc_api::handles::Statement;
+use odbc_api::parameter::InputParameter;
 use odbc_api::ParameterCollection;
 use odbc_api::{sys::ParamType, Nullable};
 #[cfg(windows)]
@@ -32,6 +34,8 @@ pub(crate) struct OutputAwareParams {
 /// Discriminated bind storage.
 pub(crate) enum ParamSlot {
     InText(TextBox),
+    /// Typed input bound with its native C type (see `typed_params`).
+    InNative(Box<dyn InputParameter>),
     OutI32(Nullable<i32>),
     OutI64(Nullable<i64>),
     InOutI32(Nullable<i32>),
@@ -138,8 +142,11 @@ fn decimal_payload(d: &str) -> Result<&str> {
     Ok(d)
 }
 
-/// Maps [BoundParam] to ODBC bind slots.
-pub(crate) fn bound_to_slots(bound: &[BoundParam]) -> Result<OutputAwareParams> {
+/// Maps [BoundParam] to ODBC bind slots, typed inputs bound per `temporal`.
+pub(crate) fn bound_to_slots(
+    bound: &[BoundParam],
+    temporal: TemporalBinding,
+) -> Result<OutputAwareParams> {
     let mut slots = Vec::with_capacity(bound.len());
     for bp in bound {
         let slot = match (bp.direction, &bp.value) {
@@ -149,6 +156,9 @@ pub(crate) fn bound_to_slots(bound: &[BoundParam]) -> Result<OutputAwareParams>
                      valid for ParamDirection::output",
                 ));
             }
+            (ParamDirection::Input, v) if binds_natively(v) => {
+                ParamSlot::InNative(input_parameter(v, temporal)?)
+            }
             (ParamDirection::Input, v) => ParamSlot::InText(in_text_from_param_value(v)?),
             (ParamDirection::Output, ParamValue::RefCursorOut)
             | (ParamDirection::InOut, ParamValue::RefCursorOut) => {
@@ -195,6 +205,22 @@ pub(crate) fn bound_to_slots(bound: &[BoundParam]) -> Result<OutputAwareParams>
                      implemented; use Integer, BigInt, String, or Decimal (see TYPE_MAPPING §3.1)",
                 ));
             }
+            (
+                ParamDirection::Output | ParamDirection::InOut,
+                ParamValue::Boolean(_)
+                | ParamValue::Double(_)
+                | ParamValue::Date(_)
+                | ParamValue::Time(_)
+                | ParamValue::Timestamp(_)
+                | ParamValue::Uuid(_)
+                | ParamValue::Json(_),
+            ) => {
+                return Err(validation_directed(
+                    "typed_out_inout_not_implemented: OUT/INOUT for Boolean, Double, Date, Time, \
+                     Timestamp, Uuid and Json is not implemented; bind them as input or use \
+                     String for OUT/INOUT (see TYPE_MAPPING §3.1)",
+                ));
+            }
         };
         slots.push(slot);
     }
@@ -227,7 +253,7 @@ impl OutputAwareParams {
         let mut v = Vec::new();
         for s in &self.slots {
             match s {
-                ParamSlot::InText(_) => {}
+                ParamSlot::InText(_) | ParamSlot::InNative(_) => {}
                 ParamSlot::OutI32(n) | ParamSlot::InOutI32(n) => {
                     let t = *n;
                     v.push(
@@ -268,6 +294,9 @@ unsafe impl ParameterCollection for OutputAwareParams {
                 ParamSlot::InText(t) => {
                     unsafe { stmt.bind_input_parameter(num, t) }.into_result(stmt)?;
                 }
+                ParamSlot::InNative(p) => {
+                    unsafe { stmt.bind_input_parameter(num, p) }.into_result(stmt)?;
+                }
                 ParamSlot::OutI32(n) => {
                     unsafe { stmt.bind_parameter(num, ParamType::Output, n) }.into_result(stmt)?;
                 }
@@ -314,7 +343,7 @@ mod tests {
             ParamDirection::Output,
             ParamValue::String(String::new()),
         )];
-        let o = bound_to_slots(&p).expect("out string");
+        let o = bound_to_slots(&p, TemporalBinding::Standard).expect("out string");
         assert_eq!(o.slots.len(), 1);
         match &o.slots[0] {
             ParamSlot::OutText(_) => {}
@@ -328,7 +357,7 @@ mod tests {
             ParamDirection::InOut,
             ParamValue::String("a".to_string()),
         )];
-        let o = bound_to_slots(&p).expect("inout string");
+        let o = bound_to_slots(&p, TemporalBinding::Standard).expect("inout string");
         assert_eq!(o.slots.len(), 1);
         match &o.slots[0] {
             ParamSlot::InOutText(_) => {}
@@ -339,7 +368,7 @@ mod tests {
     #[test]
     fn bound_to_slots_rejects_binary_out_with_stable_prefix() {
         let p = [bp(ParamDirection::Output, ParamValue::Binary(vec![0u8]))];
-        let e = match bound_to_slots(&p) {
+        let e = match bound_to_slots(&p, TemporalBinding::Standard) {
             Ok(_) => panic!("expected error"),
             Err(e) => e,
         };
@@ -352,10 +381,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bound_to_slots_binds_typed_input_natively() {
+        let p = [
+            bp(ParamDirection::Input, ParamValue::Boolean(true)),
+            bp(ParamDirection::Input, ParamValue::Json("{}".to_string())),
+        ];
+        let o = bound_to_slots(&p, TemporalBinding::Standard).expect("typed inputs");
+        assert!(matches!(o.slots[0], ParamSlot::InNative(_)));
+        assert!(matches!(o.slots[1], ParamSlot::InText(_)));
+        assert!(o.output_footer_values().is_empty());
+    }
+
+    #[test]
+    fn bound_to_slots_rejects_typed_out_with_stable_prefix() {
+        let p = [bp(ParamDirection::Output, ParamValue::Double(0.0))];
+        let e = match bound_to_slots(&p, TemporalBinding::Standard) {
+            Ok(_) => panic!("expected error"),
+            Err(e) => e,
+        };
+        let OdbcError::ValidationError(m) = e else {
+            panic!("expected ValidationError, got {e:?}");
+        };
+        assert!(
+            m.starts_with(super::ERR_PREFIX) && m.contains("typed_out_inout_not_implemented"),
+            "{m}"
+        );
+    }
+
     #[test]
     fn bound_to_slots_rejects_ref_cursor_out_until_bind() {
         let p = [bp(ParamDirection::Output, ParamValue::RefCursorOut)];
-        let e = match bound_to_slots(&p) {
+        let e = match bound_to_slots(&p, TemporalBinding::Standard) {
             Ok(_) => panic!("expected error"),
             Err(e) => e,
         };
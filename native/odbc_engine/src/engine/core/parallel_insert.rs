@@ -1,12 +1,26 @@
-use super::array_binding::ArrayBinding;
+use super::array_binding::{ArrayBinding, BulkInsertOutcome};
+use super::bulk_upsert::{drop_staging_sql, StagingTable};
+use super::driver_capabilities::{
+    DriverCapabilities, ENGINE_DB2, ENGINE_MARIADB, ENGINE_MYSQL, ENGINE_ORACLE, ENGINE_POSTGRES,
+    ENGINE_REDSHIFT, ENGINE_SNOWFLAKE, ENGINE_SQLITE, ENGINE_SQLSERVER, ENGINE_SYBASE_ASA,
+    ENGINE_SYBASE_ASE,
+};
+use crate::engine::identifier::{
+    quote_identifier_default, quote_qualified_default, MAX_IDENTIFIER_LEN,
+};
 use crate::error::{OdbcError, Result};
-use crate::pool::ConnectionPool;
+use crate::pool::{ConnectionPool, PooledConnectionWrapper};
+use crate::protocol::{BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload};
+use odbc_api::Connection;
 use rayon::prelude::*;
 use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 const DEFAULT_BATCH_SIZE: usize = 10_000;
 
+static STAGING_SEQ: AtomicU64 = AtomicU64::new(0);
+
 /// Atomicity contract for `insert_i32_parallel`.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ParallelMode {
@@ -19,6 +33,13 @@ pub enum ParallelMode {
     /// On failure, that chunk is rolled back; other chunks already committed
     /// stay committed (per-chunk atomicity, not global).
     PerChunkTransactional,
+    /// The whole load commits or nothing does. Rows load into a session
+    /// temporary table (`#stage`, `CREATE TEMP TABLE`, global temporary or
+    /// declared temporary, depending on the engine) built from the payload's
+    /// column specs; once every row is staged a single transaction copies
+    /// them into the target. Temp tables are private to their session, so
+    /// this mode runs on one pooled connection and ignores `parallelism`.
+    AllOrNothing,
 }
 
 pub(crate) fn validate_i32_parallel_input(columns: &[&str], data: &[Vec<i32>]) -> Result<()> {
@@ -97,6 +118,9 @@ impl ParallelBulkInsert {
         if n_rows == 0 {
             return Ok(0);
         }
+        if self.mode == ParallelMode::AllOrNothing {
+            return self.insert_payload_parallel(&i32_payload(table, columns, data));
+        }
 
         let ranges = chunk_ranges(n_rows, self.parallelism);
 
@@ -106,7 +130,7 @@ impl ParallelBulkInsert {
         let columns: Arc<Vec<String>> =
             Arc::new(columns.iter().map(|s| (*s).to_string()).collect());
         let batch_size = self.batch_size;
-        let mode = self.mode;
+        let transactional = self.mode == ParallelMode::PerChunkTransactional;
 
         let results: Vec<Result<usize>> = ranges
             .into_par_iter()
@@ -115,44 +139,9 @@ impl ParallelBulkInsert {
                 let ab = ArrayBinding::new(batch_size);
                 let cols: Vec<&str> = columns.iter().map(String::as_str).collect();
 
-                match mode {
-                    ParallelMode::Independent => {
-                        let conn = pool.get()?;
-                        let odbc_conn = conn.get_connection();
-                        ab.bulk_insert_i32(odbc_conn, &table, &cols, &chunk)
-                    }
-                    ParallelMode::PerChunkTransactional => {
-                        // Per-chunk atomicity: open a transaction on the borrowed
-                        // connection, run the insert, then commit or rollback.
-                        // Note: `bulk_insert_i32` borrows the connection
-                        // immutably while we need a brief mutable borrow for
-                        // autocommit toggles. We perform them around the call.
-                        let mut conn_mut = pool.get()?;
-                        conn_mut
-                            .get_connection_mut()
-                            .set_autocommit(false)
-                            .map_err(OdbcError::from)?;
-                        let result =
-                            ab.bulk_insert_i32(conn_mut.get_connection(), &table, &cols, &chunk);
-                        match result {
-                            Ok(n) => {
-                                conn_mut
-                                    .get_connection_mut()
-                                    .commit()
-                                    .map_err(OdbcError::from)?;
-                                let _ = conn_mut.get_connection_mut().set_autocommit(true);
-                                Ok(n)
-                            }
-                            Err(e) => {
-                                if let Err(re) = conn_mut.get_connection_mut().rollback() {
-                                    log::error!("ParallelBulkInsert: rollback after failure: {re}");
-                                }
-                                let _ = conn_mut.get_connection_mut().set_autocommit(true);
-                                Err(e)
-                            }
-                        }
-                    }
-                }
+                run_chunk(&pool, transactional, |conn| {
+                    ab.bulk_insert_i32(conn, &table, &cols, &chunk)
+                })
             })
             .collect();
 
@@ -167,6 +156,388 @@ impl ParallelBulkInsert {
 
         collect_partial_failure(total, errors)
     }
+
+    /// Parallel insert of any [`BulkInsertPayload`] across pool connections,
+    /// under the configured [`ParallelMode`]. Returns the rows inserted.
+    pub fn insert_payload_parallel(&self, payload: &BulkInsertPayload) -> Result<usize> {
+        payload.validate_shape()?;
+        if payload.row_count == 0 || payload.columns.is_empty() {
+            return Ok(0);
+        }
+        match self.mode {
+            ParallelMode::Independent => self.load_chunks(payload, &payload.table, false),
+            ParallelMode::PerChunkTransactional => self.load_chunks(payload, &payload.table, true),
+            ParallelMode::AllOrNothing => self.load_via_staging(payload),
+        }
+    }
+
+    /// Loads `payload` into `table` chunk by chunk, one pool connection each.
+    fn load_chunks(
+        &self,
+        payload: &BulkInsertPayload,
+        table: &str,
+        transactional: bool,
+    ) -> Result<usize> {
+        let results: Vec<Result<usize>> =
+            chunk_ranges(payload.row_count as usize, self.parallelism)
+                .into_par_iter()
+                .map(|range| {
+                    let mut chunk = payload.slice_rows(range);
+                    chunk.table = table.to_string();
+                    let ab = ArrayBinding::new(self.batch_size);
+                    run_chunk(&self.pool, transactional, |conn| {
+                        ab.bulk_insert_generic(conn, &chunk)
+                    })
+                })
+                .collect();
+
+        let mut total = 0_usize;
+        let mut errors: Vec<(usize, String)> = Vec::new();
+        for (chunk_idx, r) in results.into_iter().enumerate() {
+            match r {
+                Ok(n) => total += n,
+                Err(e) => errors.push((chunk_idx, e.to_string())),
+            }
+        }
+
+        collect_partial_failure(total, errors)
+    }
+
+    fn load_via_staging(&self, payload: &BulkInsertPayload) -> Result<usize> {
+        let target = quote_qualified_default(&payload.table)?;
+        let mut quoted_cols = Vec::with_capacity(payload.columns.len());
+        for c in &payload.columns {
+            quoted_cols.push(quote_identifier_default(&c.name)?);
+        }
+        let cols = quoted_cols.join(", ");
+
+        let mut conn = self.pool.get()?;
+        let engine = DriverCapabilities::detect(conn.get_connection())?.engine;
+        let stage = StagingTable::new(&engine, &staging_table_name(&engine, &payload.table))?;
+        execute(
+            conn.get_connection(),
+            &create_staging_sql(&engine, &stage.quoted, &payload.columns)?,
+        )?;
+
+        let result = ArrayBinding::new(self.batch_size)
+            .bulk_insert_generic_into(conn.get_connection(), &stage.quoted, payload)
+            .and_then(|staged| {
+                let sql = format!(
+                    "INSERT INTO {target} ({cols}) SELECT {cols} FROM {}",
+                    stage.quoted
+                );
+                in_transaction(&mut conn, |c| execute(c, &sql))?;
+                Ok(staged)
+            });
+
+        for sql in drop_staging_sql(&engine, &stage.quoted) {
+            if let Err(e) = execute(conn.get_connection(), &sql) {
+                log::warn!(
+                    "ParallelBulkInsert: failed to drop staging table {}: {e}",
+                    stage.name
+                );
+            }
+        }
+        result
+    }
+
+    /// Parallel insert of a generic payload where rows the driver rejects are
+    /// reported instead of failing their chunk (see
+    /// [`ArrayBinding::bulk_insert_generic_with_rejects`]).
+    ///
+    /// Rejected `row_index` values refer to `payload`, not to the chunk that
+    /// carried the row. Under [`ParallelMode::PerChunkTransactional`] a chunk
+    /// with rejected rows still commits the rows that went in. Chunks that
+    /// fail as a whole (connection loss, bad SQL) surface as
+    /// `BulkPartialFailure` like in [`insert_i32_parallel`](Self::insert_i32_parallel).
+    /// [`ParallelMode::AllOrNothing`] is rejected: skipping bad rows and
+    /// all-or-nothing loading contradict each other.
+    pub fn insert_payload_with_rejects(
+        &self,
+        payload: &BulkInsertPayload,
+    ) -> Result<BulkInsertOutcome> {
+        if self.mode == ParallelMode::AllOrNothing {
+            return Err(OdbcError::ValidationError(
+                "continue-on-error inserts cannot run in AllOrNothing mode".to_string(),
+            ));
+        }
+        payload.validate_shape()?;
+        let n_rows = payload.row_count as usize;
+        if n_rows == 0 || payload.columns.is_empty() {
+            return Ok(BulkInsertOutcome::default());
+        }
+
+        let pool = Arc::clone(&self.pool);
+        let batch_size = self.batch_size;
+        let transactional = self.mode == ParallelMode::PerChunkTransactional;
+
+        let results: Vec<Result<(usize, BulkInsertOutcome)>> =
+            chunk_ranges(n_rows, self.parallelism)
+                .into_par_iter()
+                .map(|range| {
+                    let offset = range.start;
+                    let chunk = payload.slice_rows(range);
+                    let ab = ArrayBinding::new(batch_size);
+                    run_chunk(&pool, transactional, |conn| {
+                        ab.bulk_insert_generic_with_rejects(conn, &chunk)
+                    })
+                    .map(|outcome| (offset, outcome))
+                })
+                .collect();
+
+        let mut merged = BulkInsertOutcome::default();
+        let mut errors: Vec<(usize, String)> = Vec::new();
+        for (chunk_idx, r) in results.into_iter().enumerate() {
+            match r {
+                Ok((offset, outcome)) => {
+                    merged.inserted += outcome.inserted;
+                    merged
+                        .rejected
+                        .extend(outcome.rejected.into_iter().map(|mut row| {
+                            row.row_index += offset as u32;
+                            row
+                        }));
+                }
+                Err(e) => errors.push((chunk_idx, e.to_string())),
+            }
+        }
+
+        collect_partial_failure(merged.inserted, errors)?;
+        Ok(merged)
+    }
+}
+
+/// Runs `insert` on a pooled connection, inside its own transaction when
+/// `transactional` is set.
+fn run_chunk<T>(
+    pool: &ConnectionPool,
+    transactional: bool,
+    insert: impl FnOnce(&Connection<'static>) -> Result<T>,
+) -> Result<T> {
+    let mut conn = pool.get()?;
+    if !transactional {
+        return insert(conn.get_connection());
+    }
+    in_transaction(&mut conn, insert)
+}
+
+/// Runs `insert` inside a transaction on `conn_mut`, then commits or rolls
+/// back and restores autocommit.
+fn in_transaction<T>(
+    conn_mut: &mut PooledConnectionWrapper,
+    insert: impl FnOnce(&Connection<'static>) -> Result<T>,
+) -> Result<T> {
+    // The insert borrows the connection immutably while we need a brief
+    // mutable borrow for autocommit toggles. We perform them around the call.
+    conn_mut
+        .get_connection_mut()
+        .set_autocommit(false)
+        .map_err(OdbcError::from)?;
+    let result = insert(conn_mut.get_connection());
+    match result {
+        Ok(n) => {
+            conn_mut
+                .get_connection_mut()
+                .commit()
+                .map_err(OdbcError::from)?;
+            let _ = conn_mut.get_connection_mut().set_autocommit(true);
+            Ok(n)
+        }
+        Err(e) => {
+            if let Err(re) = conn_mut.get_connection_mut().rollback() {
+                log::error!("ParallelBulkInsert: rollback after failure: {re}");
+            }
+            let _ = conn_mut.get_connection_mut().set_autocommit(true);
+            Err(e)
+        }
+    }
+}
+
+fn execute(conn: &Connection<'static>, sql: &str) -> Result<()> {
+    conn.execute(sql, (), None).map_err(OdbcError::from)?;
+    Ok(())
+}
+
+/// Unquoted staging table name derived from the last segment of `table`,
+/// unique per process. The table part is truncated so the name (with the
+/// `#` SQL Server and Sybase prepend) fits the engine's identifier limit;
+/// the hex pid/sequence suffix keeps truncated names unique.
+fn staging_table_name(engine: &str, table: &str) -> String {
+    let suffix = format!(
+        "_stg{:x}_{:x}",
+        std::process::id(),
+        STAGING_SEQ.fetch_add(1, Ordering::Relaxed)
+    );
+    let base = table.rsplit('.').next().unwrap_or(table);
+    let room = max_staging_name_len(engine)
+        .saturating_sub(suffix.len())
+        .max(1);
+    // Identifiers are validated as ASCII, so any byte offset is a boundary.
+    let base = if base.is_ascii() && base.len() > room {
+        &base[..room]
+    } else {
+        base
+    };
+    format!("{base}{suffix}")
+}
+
+/// Longest staging name the engine accepts, `#` prefix excluded.
+fn max_staging_name_len(engine: &str) -> usize {
+    match engine {
+        ENGINE_POSTGRES | ENGINE_REDSHIFT => 63,
+        ENGINE_MYSQL | ENGINE_MARIADB => 64,
+        // `#temp` names are capped at 116 characters, `#` included.
+        ENGINE_SQLSERVER => 115,
+        ENGINE_DB2 | ENGINE_SQLITE | ENGINE_SNOWFLAKE => MAX_IDENTIFIER_LEN,
+        // Oracle before 12.2 and unknown engines: the SQL-92 limit.
+        _ => 30,
+    }
+}
+
+/// DDL creating the session temporary table `stage` (already quoted) with
+/// one column per spec. Types come from the specs rather than from the
+/// target: `SELECT INTO` / CTAS would copy `IDENTITY` and defaults along.
+fn create_staging_sql(engine: &str, stage: &str, columns: &[BulkColumnSpec]) -> Result<String> {
+    let defs = columns
+        .iter()
+        .map(|spec| {
+            let name = quote_identifier_default(&spec.name)?;
+            let null = match (spec.nullable, engine) {
+                (false, _) => " NOT NULL",
+                // Sybase columns default to NOT NULL.
+                (true, ENGINE_SQLSERVER | ENGINE_SYBASE_ASE | ENGINE_SYBASE_ASA) => " NULL",
+                (true, _) => "",
+            };
+            Ok(format!(
+                "{name} {}{null}",
+                staging_column_type(engine, spec)
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    Ok(match engine {
+        ENGINE_SQLSERVER | ENGINE_SYBASE_ASE | ENGINE_SYBASE_ASA => {
+            format!("CREATE TABLE {stage} ({defs})")
+        }
+        ENGINE_POSTGRES | ENGINE_SQLITE | ENGINE_REDSHIFT => {
+            format!("CREATE TEMP TABLE {stage} ({defs})")
+        }
+        ENGINE_MYSQL | ENGINE_MARIADB | ENGINE_SNOWFLAKE => {
+            format!("CREATE TEMPORARY TABLE {stage} ({defs})")
+        }
+        ENGINE_DB2 => format!(
+            "DECLARE GLOBAL TEMPORARY TABLE {stage} ({defs}) ON COMMIT PRESERVE ROWS NOT LOGGED"
+        ),
+        _ => format!("CREATE GLOBAL TEMPORARY TABLE {stage} ({defs}) ON COMMIT PRESERVE ROWS"),
+    })
+}
+
+/// Column type able to hold what [`ArrayBinding`] binds for `spec`.
+/// Decimals are bound as text, so they stage as text (except on PostgreSQL,
+/// which has no implicit text-to-numeric cast for the final copy).
+fn staging_column_type(engine: &str, spec: &BulkColumnSpec) -> String {
+    let n = spec.max_len.max(1);
+    let sqlserver = engine == ENGINE_SQLSERVER;
+    let sybase = matches!(engine, ENGINE_SYBASE_ASE | ENGINE_SYBASE_ASA);
+    let mysql = matches!(engine, ENGINE_MYSQL | ENGINE_MARIADB);
+    let oracle = engine == ENGINE_ORACLE;
+    let pg = matches!(engine, ENGINE_POSTGRES | ENGINE_REDSHIFT);
+    match spec.col_type {
+        BulkColumnType::I32 if oracle => "NUMBER(10)".to_string(),
+        BulkColumnType::I32 => "INTEGER".to_string(),
+        BulkColumnType::I64 if oracle => "NUMBER(19)".to_string(),
+        BulkColumnType::I64 => "BIGINT".to_string(),
+        BulkColumnType::Decimal if engine == ENGINE_POSTGRES => "NUMERIC".to_string(),
+        BulkColumnType::Text | BulkColumnType::Decimal => match engine {
+            ENGINE_SQLITE => "TEXT".to_string(),
+            _ if sqlserver => bounded("VARCHAR", n, 8000, "VARCHAR(MAX)"),
+            _ if sybase => bounded("VARCHAR", n, 8000, "TEXT"),
+            _ if oracle => bounded("VARCHAR2", n, 4000, "CLOB"),
+            _ if mysql => bounded("VARCHAR", n, 16383, "LONGTEXT"),
+            ENGINE_POSTGRES => bounded("VARCHAR", n, 10_485_760, "TEXT"),
+            ENGINE_REDSHIFT => bounded("VARCHAR", n, 65535, "VARCHAR(MAX)"),
+            ENGINE_SNOWFLAKE => format!("VARCHAR({n})"),
+            ENGINE_DB2 => bounded("VARCHAR", n, 32672, "CLOB"),
+            _ => bounded("VARCHAR", n, 4000, "CLOB"),
+        },
+        BulkColumnType::NText => match engine {
+            ENGINE_SQLITE => "TEXT".to_string(),
+            _ if sqlserver => bounded("NVARCHAR", n, 4000, "NVARCHAR(MAX)"),
+            _ if sybase => bounded("NVARCHAR", n, 4000, "UNITEXT"),
+            _ if oracle => bounded("NVARCHAR2", n, 2000, "NCLOB"),
+            _ if mysql => bounded("NVARCHAR", n, 16383, "LONGTEXT"),
+            _ if pg => bounded("VARCHAR", n, 65535, "TEXT"),
+            ENGINE_SNOWFLAKE => format!("VARCHAR({n})"),
+            ENGINE_DB2 => bounded("VARGRAPHIC", n, 16336, "DBCLOB"),
+            _ => bounded("NVARCHAR", n, 2000, "NCLOB"),
+        },
+        BulkColumnType::Binary => match engine {
+            ENGINE_SQLITE => "BLOB".to_string(),
+            ENGINE_POSTGRES => "BYTEA".to_string(),
+            ENGINE_SNOWFLAKE => "BINARY".to_string(),
+            ENGINE_REDSHIFT => format!("VARBYTE({})", n.min(1_024_000)),
+            _ if sqlserver => bounded("VARBINARY", n, 8000, "VARBINARY(MAX)"),
+            _ if sybase => bounded("VARBINARY", n, 8000, "IMAGE"),
+            _ if oracle => bounded("RAW", n, 2000, "BLOB"),
+            _ if mysql => bounded("VARBINARY", n, 16383, "LONGBLOB"),
+            ENGINE_DB2 => bounded("VARBINARY", n, 32672, "BLOB"),
+            _ => bounded("VARBINARY", n, 4000, "BLOB"),
+        },
+        BulkColumnType::Timestamp if sqlserver => "DATETIME2(7)".to_string(),
+        BulkColumnType::Timestamp if sybase => "DATETIME".to_string(),
+        BulkColumnType::Timestamp if mysql => "DATETIME(6)".to_string(),
+        BulkColumnType::Timestamp => "TIMESTAMP".to_string(),
+        BulkColumnType::Bool if sqlserver || sybase => "BIT".to_string(),
+        BulkColumnType::Bool if oracle => "NUMBER(1)".to_string(),
+        BulkColumnType::Bool if engine == ENGINE_DB2 => "SMALLINT".to_string(),
+        BulkColumnType::Bool => "BOOLEAN".to_string(),
+        BulkColumnType::F64 if sqlserver || sybase => "FLOAT".to_string(),
+        BulkColumnType::F64 if oracle => "BINARY_DOUBLE".to_string(),
+        BulkColumnType::F64 if mysql => "DOUBLE".to_string(),
+        BulkColumnType::F64 => "DOUBLE PRECISION".to_string(),
+        BulkColumnType::F32 if oracle => "BINARY_FLOAT".to_string(),
+        BulkColumnType::F32 if mysql => "FLOAT".to_string(),
+        BulkColumnType::F32 => "REAL".to_string(),
+        BulkColumnType::Date => "DATE".to_string(),
+        // Oracle has no TIME type; its DATE carries a time of day.
+        BulkColumnType::Time if oracle => "DATE".to_string(),
+        BulkColumnType::Time => "TIME".to_string(),
+        BulkColumnType::Uuid if sqlserver => "UNIQUEIDENTIFIER".to_string(),
+        BulkColumnType::Uuid if engine == ENGINE_POSTGRES => "UUID".to_string(),
+        BulkColumnType::Uuid => "CHAR(36)".to_string(),
+    }
+}
+
+/// `{sized}({n})` up to `limit`, else the engine's unbounded type.
+fn bounded(sized: &str, n: usize, limit: usize, unbounded: &str) -> String {
+    if n <= limit {
+        format!("{sized}({n})")
+    } else {
+        unbounded.to_string()
+    }
+}
+
+fn i32_payload(table: &str, columns: &[&str], data: Vec<Vec<i32>>) -> BulkInsertPayload {
+    BulkInsertPayload {
+        table: table.to_string(),
+        row_count: data[0].len() as u32,
+        columns: columns
+            .iter()
+            .map(|name| BulkColumnSpec {
+                name: (*name).to_string(),
+                col_type: BulkColumnType::I32,
+                nullable: false,
+                max_len: 0,
+            })
+            .collect(),
+        column_data: data
+            .into_iter()
+            .map(|values| BulkColumnData::I32 {
+                values,
+                null_bitmap: None,
+            })
+            .collect(),
+    }
 }
 
 fn chunk_ranges(row_count: usize, parallelism: usize) -> Vec<Range<usize>> {
@@ -299,6 +670,127 @@ mod tests {
         }
     }
 
+    fn spec(
+        name: &str,
+        col_type: BulkColumnType,
+        nullable: bool,
+        max_len: usize,
+    ) -> BulkColumnSpec {
+        BulkColumnSpec {
+            name: name.to_string(),
+            col_type,
+            nullable,
+            max_len,
+        }
+    }
+
+    #[test]
+    fn test_staging_table_name_is_unique_and_unqualified() {
+        let a = staging_table_name(ENGINE_SQLSERVER, "dbo.orders");
+        let b = staging_table_name(ENGINE_SQLSERVER, "dbo.orders");
+        assert_ne!(a, b);
+        assert!(a.starts_with("orders_stg"));
+        assert!(quote_identifier_default(&a).is_ok());
+    }
+
+    #[test]
+    fn test_staging_table_name_fits_engine_limits() {
+        let long = format!("sales.{}", "order_line_items_".repeat(8));
+        for (engine, limit) in [
+            (ENGINE_ORACLE, 30),
+            (ENGINE_POSTGRES, 63),
+            (ENGINE_MYSQL, 64),
+            (ENGINE_SQLSERVER, 115),
+        ] {
+            let a = staging_table_name(engine, &long);
+            let b = staging_table_name(engine, &long);
+            assert!(a.len() <= limit, "{engine}: {a}");
+            assert_ne!(a, b);
+            assert!(a.starts_with("order_line_items_"));
+            assert!(quote_identifier_default(&a).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_create_staging_sql_per_engine() {
+        let cols = [
+            spec("id", BulkColumnType::I64, false, 0),
+            spec("name", BulkColumnType::NText, true, 50),
+        ];
+        assert_eq!(
+            create_staging_sql(ENGINE_SQLSERVER, "[#s]", &cols).unwrap(),
+            "CREATE TABLE [#s] (\"id\" BIGINT NOT NULL, \"name\" NVARCHAR(50) NULL)"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_POSTGRES, "\"s\"", &cols).unwrap(),
+            "CREATE TEMP TABLE \"s\" (\"id\" BIGINT NOT NULL, \"name\" VARCHAR(50))"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_MYSQL, "`s`", &cols[..1]).unwrap(),
+            "CREATE TEMPORARY TABLE `s` (\"id\" BIGINT NOT NULL)"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_ORACLE, "\"s\"", &cols).unwrap(),
+            "CREATE GLOBAL TEMPORARY TABLE \"s\" (\"id\" NUMBER(19) NOT NULL, \
+             \"name\" NVARCHAR2(50)) ON COMMIT PRESERVE ROWS"
+        );
+        assert_eq!(
+            create_staging_sql(ENGINE_DB2, "SESSION.\"s\"", &cols).unwrap(),
+            "DECLARE GLOBAL TEMPORARY TABLE SESSION.\"s\" (\"id\" BIGINT NOT NULL, \
+             \"name\" VARGRAPHIC(50)) ON COMMIT PRESERVE ROWS NOT LOGGED"
+        );
+        for engine in [
+            ENGINE_SQLSERVER,
+            ENGINE_SYBASE_ASE,
+            ENGINE_DB2,
+            ENGINE_POSTGRES,
+        ] {
+            let sql = create_staging_sql(engine, "s", &cols).unwrap();
+            assert!(!sql.contains("SELECT"), "{engine}: {sql}");
+        }
+        assert!(create_staging_sql(
+            ENGINE_POSTGRES,
+            "s",
+            &[spec("x; --", BulkColumnType::I32, true, 0)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_staging_column_type_bounds_and_fallbacks() {
+        let text = |n| spec("t", BulkColumnType::Text, true, n);
+        assert_eq!(
+            staging_column_type(ENGINE_SQLSERVER, &text(10)),
+            "VARCHAR(10)"
+        );
+        assert_eq!(
+            staging_column_type(ENGINE_SQLSERVER, &text(9000)),
+            "VARCHAR(MAX)"
+        );
+        assert_eq!(staging_column_type(ENGINE_ORACLE, &text(5000)), "CLOB");
+        assert_eq!(staging_column_type(ENGINE_MYSQL, &text(0)), "VARCHAR(1)");
+        let decimal = spec("d", BulkColumnType::Decimal, true, 40);
+        assert_eq!(staging_column_type(ENGINE_POSTGRES, &decimal), "NUMERIC");
+        assert_eq!(
+            staging_column_type(ENGINE_SQLSERVER, &decimal),
+            "VARCHAR(40)"
+        );
+        let uuid = spec("u", BulkColumnType::Uuid, true, 0);
+        assert_eq!(
+            staging_column_type(ENGINE_SQLSERVER, &uuid),
+            "UNIQUEIDENTIFIER"
+        );
+        assert_eq!(staging_column_type(ENGINE_MYSQL, &uuid), "CHAR(36)");
+    }
+
+    #[test]
+    fn test_i32_payload_matches_columns() {
+        let payload = i32_payload("t", &["a", "b"], vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(payload.row_count, 2);
+        assert_eq!(payload.columns[1].name, "b");
+        assert!(payload.validate_shape().is_ok());
+    }
+
     #[test]
     #[ignore]
     fn test_parallel_bulk_insert_new() {
@@ -0,0 +1,821 @@
+//! Parquet export of query results and Parquet import into tables (feature
+//! `parquet`).
+//!
+//! [`ParquetExporter`] feeds the batches of
+//! [`StreamingExecutor::execute_streaming_rows`] to a Parquet writer: one row
+//! group per `row_group_rows` rows, snappy, zstd or no compression. Column
+//! types follow the Arrow mapping of [`crate::protocol::arrow_stream`], which
+//! Parquet stores with these logical types:
+//!
+//! | `OdbcType`                          | Parquet logical type               |
+//! | ----------------------------------- | ---------------------------------- |
+//! | `SmallInt` / `Integer` / `BigInt`   | `INT(16/32/64, signed)`            |
+//! | `Decimal` (precision ≤ 38), `Money` | `DECIMAL(p, s)`                    |
+//! | `Date`                              | `DATE`                             |
+//! | `Time`                              | `TIME(MICROS, local)`              |
+//! | `Timestamp`                         | `TIMESTAMP(MICROS, local)`         |
+//! | `DatetimeOffset`, `TimestampWithTz` | `TIMESTAMP(MICROS, UTC-adjusted)`  |
+//! | `Uuid`                              | `UUID`                             |
+//! | `Json`                              | `JSON`                             |
+//! | text types                          | `STRING`                           |
+//!
+//! [`ParquetImporter`] reads a file back as [`BulkInsertPayload`] batches of
+//! `batch_rows` rows, loaded through the engine's session bulk loader or array
+//! binding, or handed to any [`crate::plugins::capabilities::BulkLoader`]
+//! through [`ParquetImporter::read_file`]. Narrow integers widen to `I32` /
+//! `I64`, decimals load as text, timestamps keep nanoseconds (UTC-adjusted
+//! ones load as UTC wall time), times drop their fraction.
+
+use crate::engine::core::bulk_upsert::load_payload;
+use crate::engine::core::driver_capabilities::DriverCapabilities;
+use crate::engine::core::typed_params::TemporalBinding;
+use crate::engine::identifier::{quote_qualified_default, validate_identifier};
+use crate::engine::streaming::StreamingExecutor;
+use crate::error::{OdbcError, Result};
+use crate::plugins::capabilities::bulk_loader::BulkLoadOptions;
+use crate::protocol::arrow_stream::{arrow_schema, record_batch};
+use crate::protocol::bulk_insert::null_bitmap_size;
+use crate::protocol::native_cell::civil_from_days;
+use crate::protocol::{
+    BulkColumnData, BulkColumnSpec, BulkColumnType, BulkDate, BulkInsertPayload, BulkTime,
+    BulkTimestamp, ParamValue,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Float32Type, Float64Type, Int32Type, Int64Type, Time64MicrosecondType,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_cast::cast::{cast_with_options, CastOptions};
+use arrow_schema::{DataType as ArrowType, Field, TimeUnit};
+use odbc_api::Connection;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// Placeholder value of NULL timestamp rows.
+const EPOCH: BulkTimestamp = BulkTimestamp {
+    year: 1970,
+    month: 1,
+    day: 1,
+    hour: 0,
+    minute: 0,
+    second: 0,
+    fraction: 0,
+};
+
+fn parquet_error(e: ParquetError) -> OdbcError {
+    OdbcError::InternalError(format!("Parquet: {e}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetCodec {
+    Uncompressed,
+    Snappy,
+    Zstd,
+}
+
+impl ParquetCodec {
+    fn compression(self) -> Compression {
+        match self {
+            Self::Uncompressed => Compression::UNCOMPRESSED,
+            Self::Snappy => Compression::SNAPPY,
+            Self::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParquetExportOptions {
+    /// Rows per row group; the last one may be smaller.
+    pub row_group_rows: usize,
+    pub codec: ParquetCodec,
+    /// Rows fetched per batch.
+    pub fetch_size: usize,
+}
+
+impl Default for ParquetExportOptions {
+    fn default() -> Self {
+        Self {
+            row_group_rows: 100_000,
+            codec: ParquetCodec::Snappy,
+            fetch_size: 1_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParquetExportReport {
+    pub rows: u64,
+    pub row_groups: u64,
+    pub bytes_written: u64,
+    pub elapsed_ms: u64,
+}
+
+pub struct ParquetExporter {
+    options: ParquetExportOptions,
+    params: Vec<ParamValue>,
+    temporal: TemporalBinding,
+}
+
+impl ParquetExporter {
+    pub fn new(options: ParquetExportOptions) -> Self {
+        Self {
+            options,
+            params: Vec::new(),
+            temporal: TemporalBinding::Standard,
+        }
+    }
+
+    /// Binds `params` to the query's positional `?` markers.
+    pub fn with_params(mut self, params: Vec<ParamValue>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Binds time fractions and UTC offsets in the params per `temporal`,
+    /// see [`StreamingExecutor::with_temporal_binding`].
+    pub fn with_temporal_binding(mut self, temporal: TemporalBinding) -> Self {
+        self.temporal = temporal;
+        self
+    }
+
+    /// Runs `sql` and writes its result to `path`, replacing the file. On
+    /// error or cancellation the partial file is removed.
+    pub fn export_to_file(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        path: &Path,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<ParquetExportReport> {
+        let file = File::create(path).map_err(|e| {
+            OdbcError::ValidationError(format!("Cannot create {}: {e}", path.display()))
+        })?;
+        let result = self.export_to(conn, sql, file, cancel);
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    /// Runs `sql` and writes its result to `out` as one Parquet file.
+    pub fn export_to<W: Write + Send>(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        out: W,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<ParquetExportReport> {
+        let started = Instant::now();
+        let executor = StreamingExecutor::new(self.options.fetch_size.max(1))
+            .with_params(self.params.clone())
+            .with_temporal_binding(self.temporal);
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(self.options.row_group_rows.max(1))
+            .set_compression(self.options.codec.compression())
+            .build();
+        let mut out = Some(out);
+        let mut writer = None;
+        let mut rows = 0u64;
+        executor.execute_streaming_rows(
+            conn,
+            sql,
+            self.options.fetch_size,
+            |batch| {
+                if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+                    return Err(OdbcError::Cancelled);
+                }
+                let (writer, schema) = match &mut writer {
+                    Some((writer, schema)) => (writer, schema),
+                    None => {
+                        let schema = Arc::new(arrow_schema(batch));
+                        let sink = out.take().ok_or_else(|| {
+                            OdbcError::InternalError("Parquet writer already closed".to_string())
+                        })?;
+                        let (writer, schema) = writer.insert((
+                            ArrowWriter::try_new(sink, schema.clone(), Some(properties.clone()))
+                                .map_err(parquet_error)?,
+                            schema,
+                        ));
+                        (writer, schema)
+                    }
+                };
+                if batch.row_count() > 0 {
+                    writer
+                        .write(&record_batch(schema, batch)?)
+                        .map_err(parquet_error)?;
+                    rows += batch.row_count() as u64;
+                }
+                Ok(())
+            },
+            None,
+        )?;
+
+        let (writer, _) = writer.ok_or_else(|| {
+            OdbcError::ValidationError("Parquet export: statement returned no result set".into())
+        })?;
+        let (row_groups, bytes_written) = close_writer(writer)?;
+        Ok(ParquetExportReport {
+            rows,
+            row_groups,
+            bytes_written,
+            elapsed_ms: started.elapsed().as_millis().try_into().unwrap_or(u64::MAX),
+        })
+    }
+}
+
+/// Writes the footer and returns the file's row groups and byte size.
+fn close_writer<W: Write + Send>(mut writer: ArrowWriter<W>) -> Result<(u64, u64)> {
+    let metadata = writer.finish().map_err(parquet_error)?;
+    writer
+        .inner_mut()
+        .flush()
+        .map_err(|e| OdbcError::InternalError(format!("Parquet flush failed: {e}")))?;
+    Ok((
+        metadata.row_groups.len() as u64,
+        writer.bytes_written() as u64,
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub struct ParquetImportOptions {
+    /// Rows per loaded batch.
+    pub batch_rows: usize,
+}
+
+impl Default for ParquetImportOptions {
+    fn default() -> Self {
+        Self { batch_rows: 10_000 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ParquetImportProgress {
+    pub rows_loaded: u64,
+    /// Rows in the file, from its footer.
+    pub total_rows: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParquetImportReport {
+    pub rows_read: u64,
+    pub rows_loaded: u64,
+    pub batches: u64,
+}
+
+/// Loads a Parquet file into `table`.
+#[derive(Debug, Clone)]
+pub struct ParquetImporter {
+    table: String,
+    options: ParquetImportOptions,
+}
+
+impl ParquetImporter {
+    pub fn new(table: &str, options: ParquetImportOptions) -> Self {
+        Self {
+            table: table.to_string(),
+            options,
+        }
+    }
+
+    /// Imports the file at `path` through `conn`.
+    pub fn import_file(
+        &self,
+        conn: &Connection<'static>,
+        path: &Path,
+        on_progress: &mut dyn FnMut(&ParquetImportProgress),
+    ) -> Result<ParquetImportReport> {
+        let engine = DriverCapabilities::detect(conn)?.engine;
+        let qtable = quote_qualified_default(&self.table)?;
+        let load_options = BulkLoadOptions::new(self.options.batch_rows.max(1));
+        self.read_file(
+            path,
+            &mut |payload| load_payload(conn, &engine, &qtable, payload, &load_options),
+            on_progress,
+        )
+    }
+
+    /// Reads `path` and passes each batch to `load`, which returns the rows it
+    /// wrote.
+    pub fn read_file(
+        &self,
+        path: &Path,
+        load: &mut dyn FnMut(&BulkInsertPayload) -> Result<usize>,
+        on_progress: &mut dyn FnMut(&ParquetImportProgress),
+    ) -> Result<ParquetImportReport> {
+        quote_qualified_default(&self.table)?;
+        let file = File::open(path).map_err(|e| {
+            OdbcError::ValidationError(format!("Cannot open {}: {e}", path.display()))
+        })?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(parquet_error)?;
+        let total_rows = u64::try_from(builder.metadata().file_metadata().num_rows()).unwrap_or(0);
+        for field in builder.schema().fields() {
+            validate_identifier(field.name())?;
+            bulk_type(field)?;
+        }
+        let reader = builder
+            .with_batch_size(self.options.batch_rows.max(1))
+            .build()
+            .map_err(parquet_error)?;
+
+        let mut report = ParquetImportReport::default();
+        for batch in reader {
+            let batch =
+                batch.map_err(|e| OdbcError::InternalError(format!("Parquet read failed: {e}")))?;
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            let payload = record_batch_to_payload(&self.table, &batch)?;
+            report.rows_read += batch.num_rows() as u64;
+            report.rows_loaded += load(&payload)? as u64;
+            report.batches += 1;
+            on_progress(&ParquetImportProgress {
+                rows_loaded: report.rows_loaded,
+                total_rows,
+            });
+        }
+        Ok(report)
+    }
+}
+
+/// Bulk column type an Arrow field loads as.
+fn bulk_type(field: &Field) -> Result<BulkColumnType> {
+    let ty = match field.data_type() {
+        ArrowType::Boolean => BulkColumnType::Bool,
+        ArrowType::Int8 | ArrowType::Int16 | ArrowType::Int32 => BulkColumnType::I32,
+        ArrowType::UInt8 | ArrowType::UInt16 => BulkColumnType::I32,
+        ArrowType::Int64 | ArrowType::UInt32 | ArrowType::UInt64 => BulkColumnType::I64,
+        ArrowType::Float16 | ArrowType::Float32 => BulkColumnType::F32,
+        ArrowType::Float64 => BulkColumnType::F64,
+        ArrowType::Decimal128(..) | ArrowType::Decimal256(..) => BulkColumnType::Decimal,
+        ArrowType::Utf8 | ArrowType::LargeUtf8 | ArrowType::Utf8View => BulkColumnType::Text,
+        ArrowType::FixedSizeBinary(16) if field.extension_type_name() == Some("arrow.uuid") => {
+            BulkColumnType::Uuid
+        }
+        ArrowType::Binary
+        | ArrowType::LargeBinary
+        | ArrowType::BinaryView
+        | ArrowType::FixedSizeBinary(_) => BulkColumnType::Binary,
+        ArrowType::Date32 | ArrowType::Date64 => BulkColumnType::Date,
+        ArrowType::Time32(_) | ArrowType::Time64(_) => BulkColumnType::Time,
+        ArrowType::Timestamp(..) => BulkColumnType::Timestamp,
+        other => {
+            return Err(OdbcError::UnsupportedFeature(format!(
+                "Parquet column '{}' has unsupported type {other}",
+                field.name()
+            )))
+        }
+    };
+    Ok(ty)
+}
+
+/// Converts one Arrow record batch into a payload for `table`.
+pub fn record_batch_to_payload(table: &str, batch: &RecordBatch) -> Result<BulkInsertPayload> {
+    let schema = batch.schema();
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    let mut column_data = Vec::with_capacity(batch.num_columns());
+    for (field, array) in schema.fields().iter().zip(batch.columns()) {
+        let col_type = bulk_type(field)?;
+        let data = column_data_from(field, array, col_type.clone())?;
+        let max_len = match &data {
+            BulkColumnData::Text { max_len, .. } | BulkColumnData::Binary { max_len, .. } => {
+                *max_len
+            }
+            _ => 0,
+        };
+        columns.push(BulkColumnSpec {
+            name: field.name().clone(),
+            col_type,
+            nullable: field.is_nullable(),
+            max_len,
+        });
+        column_data.push(data);
+    }
+    Ok(BulkInsertPayload {
+        table: table.to_string(),
+        columns,
+        row_count: u32::try_from(batch.num_rows()).map_err(|_| {
+            OdbcError::ValidationError("Parquet batch exceeds u32::MAX rows".to_string())
+        })?,
+        column_data,
+    })
+}
+
+fn cast_to(field: &Field, array: &ArrayRef, target: &ArrowType) -> Result<ArrayRef> {
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    cast_with_options(array, target, &options)
+        .map_err(|e| OdbcError::ValidationError(format!("Parquet column '{}': {e}", field.name())))
+}
+
+fn null_bitmap(array: &dyn Array) -> Option<Vec<u8>> {
+    if array.null_count() == 0 {
+        return None;
+    }
+    let mut bitmap = vec![0u8; null_bitmap_size(array.len())];
+    for row in (0..array.len()).filter(|&row| array.is_null(row)) {
+        bitmap[row / 8] |= 1 << (row % 8);
+    }
+    Some(bitmap)
+}
+
+/// Byte rows of a variable-width column; NULLs become empty rows.
+fn byte_rows<'a>(array: &'a dyn Array, value: impl Fn(usize) -> &'a [u8]) -> (Vec<Vec<u8>>, usize) {
+    let rows: Vec<Vec<u8>> = (0..array.len())
+        .map(|row| {
+            if array.is_null(row) {
+                Vec::new()
+            } else {
+                value(row).to_vec()
+            }
+        })
+        .collect();
+    let max_len = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    (rows, max_len)
+}
+
+/// Calendar timestamp `nanos` nanoseconds after the Unix epoch.
+fn timestamp_from_nanos(nanos: i128) -> Option<BulkTimestamp> {
+    let days = nanos.div_euclid(NANOS_PER_DAY);
+    let in_day = nanos.rem_euclid(NANOS_PER_DAY);
+    let (year, month, day) = civil_from_days(i64::try_from(days).ok()?);
+    let secs = in_day / 1_000_000_000;
+    Some(BulkTimestamp {
+        year: i16::try_from(year).ok()?,
+        month: month as u16,
+        day: day as u16,
+        hour: (secs / 3600) as u16,
+        minute: (secs / 60 % 60) as u16,
+        second: (secs % 60) as u16,
+        fraction: (in_day % 1_000_000_000) as u32,
+    })
+}
+
+fn column_data_from(
+    field: &Field,
+    array: &ArrayRef,
+    col_type: BulkColumnType,
+) -> Result<BulkColumnData> {
+    let null_bitmap = null_bitmap(array.as_ref());
+    let data = match col_type {
+        BulkColumnType::Bool => BulkColumnData::Bool {
+            values: array
+                .as_boolean()
+                .iter()
+                .map(|v| v.unwrap_or(false))
+                .collect(),
+            null_bitmap,
+        },
+        BulkColumnType::I32 => BulkColumnData::I32 {
+            values: cast_to(field, array, &ArrowType::Int32)?
+                .as_primitive::<Int32Type>()
+                .values()
+                .to_vec(),
+            null_bitmap,
+        },
+        BulkColumnType::I64 => BulkColumnData::I64 {
+            values: cast_to(field, array, &ArrowType::Int64)?
+                .as_primitive::<Int64Type>()
+                .values()
+                .to_vec(),
+            null_bitmap,
+        },
+        BulkColumnType::F32 => BulkColumnData::F32 {
+            values: cast_to(field, array, &ArrowType::Float32)?
+                .as_primitive::<Float32Type>()
+                .values()
+                .to_vec(),
+            null_bitmap,
+        },
+        BulkColumnType::F64 => BulkColumnData::F64 {
+            values: array.as_primitive::<Float64Type>().values().to_vec(),
+            null_bitmap,
+        },
+        BulkColumnType::Text | BulkColumnType::Decimal => {
+            let text = cast_to(field, array, &ArrowType::Utf8)?;
+            let strings = text.as_string::<i32>();
+            let (rows, max_len) = byte_rows(strings, |row| strings.value(row).as_bytes());
+            BulkColumnData::Text {
+                rows,
+                max_len,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::Binary => {
+            let binary = match array.data_type() {
+                ArrowType::FixedSizeBinary(_) => {
+                    let fixed = array.as_fixed_size_binary();
+                    let (rows, max_len) = byte_rows(fixed, |row| fixed.value(row));
+                    return Ok(BulkColumnData::Binary {
+                        rows,
+                        max_len,
+                        null_bitmap,
+                    });
+                }
+                _ => cast_to(field, array, &ArrowType::Binary)?,
+            };
+            let bytes = binary.as_binary::<i32>();
+            let (rows, max_len) = byte_rows(bytes, |row| bytes.value(row));
+            BulkColumnData::Binary {
+                rows,
+                max_len,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::Uuid => {
+            let fixed = array.as_fixed_size_binary();
+            BulkColumnData::Uuid {
+                values: (0..fixed.len())
+                    .map(|row| {
+                        if fixed.is_null(row) {
+                            [0u8; 16]
+                        } else {
+                            fixed.value(row).try_into().unwrap_or([0u8; 16])
+                        }
+                    })
+                    .collect(),
+                null_bitmap,
+            }
+        }
+        BulkColumnType::Date => BulkColumnData::Date {
+            values: cast_to(field, array, &ArrowType::Date32)?
+                .as_primitive::<Date32Type>()
+                .values()
+                .iter()
+                .map(|&days| {
+                    let (year, month, day) = civil_from_days(days.into());
+                    BulkDate {
+                        year: i16::try_from(year).unwrap_or(i16::MAX),
+                        month: month as u16,
+                        day: day as u16,
+                    }
+                })
+                .collect(),
+            null_bitmap,
+        },
+        BulkColumnType::Time => BulkColumnData::Time {
+            values: cast_to(field, array, &ArrowType::Time64(TimeUnit::Microsecond))?
+                .as_primitive::<Time64MicrosecondType>()
+                .values()
+                .iter()
+                .map(|&micros| {
+                    let secs = micros.rem_euclid(86_400_000_000) / 1_000_000;
+                    BulkTime {
+                        hour: (secs / 3600) as u16,
+                        minute: (secs / 60 % 60) as u16,
+                        second: (secs % 60) as u16,
+                    }
+                })
+                .collect(),
+            null_bitmap,
+        },
+        BulkColumnType::Timestamp => {
+            let ArrowType::Timestamp(unit, _) = array.data_type() else {
+                return Err(OdbcError::InternalError(format!(
+                    "Parquet column '{}' is not a timestamp",
+                    field.name()
+                )));
+            };
+            let per_unit: i128 = match unit {
+                TimeUnit::Second => 1_000_000_000,
+                TimeUnit::Millisecond => 1_000_000,
+                TimeUnit::Microsecond => 1_000,
+                TimeUnit::Nanosecond => 1,
+            };
+            let raw = cast_to(field, array, &ArrowType::Int64)?;
+            let values = raw
+                .as_primitive::<Int64Type>()
+                .values()
+                .iter()
+                .enumerate()
+                .map(|(row, &v)| {
+                    if raw.is_null(row) {
+                        return Ok(EPOCH);
+                    }
+                    timestamp_from_nanos(i128::from(v) * per_unit).ok_or_else(|| {
+                        OdbcError::ValidationError(format!(
+                            "Parquet column '{}': timestamp out of range in row {row}",
+                            field.name()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            BulkColumnData::Timestamp {
+                values,
+                null_bitmap,
+            }
+        }
+        BulkColumnType::NText => {
+            return Err(OdbcError::InternalError(
+                "Parquet import never produces NText".to_string(),
+            ))
+        }
+    };
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{OdbcType, RowBuffer};
+    use arrow_schema::SchemaRef;
+    use odbc_api::DataType;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::path::PathBuf;
+
+    fn text(s: &str) -> Option<Vec<u8>> {
+        Some(s.as_bytes().to_vec())
+    }
+
+    fn sample() -> RowBuffer {
+        let mut rows = RowBuffer::new();
+        rows.add_described_column("id".to_string(), OdbcType::Integer, DataType::Integer);
+        rows.add_described_column(
+            "price".to_string(),
+            OdbcType::Decimal,
+            DataType::Decimal {
+                precision: 10,
+                scale: 2,
+            },
+        );
+        rows.add_described_column(
+            "created_at".to_string(),
+            OdbcType::Timestamp,
+            DataType::Timestamp { precision: 6 },
+        );
+        rows.add_described_column("born".to_string(), OdbcType::Date, DataType::Date);
+        rows.add_column("token".to_string(), OdbcType::Uuid);
+        rows.add_column("name".to_string(), OdbcType::Varchar);
+        rows.add_row(vec![
+            text("1"),
+            text("12.50"),
+            text("2024-02-29 12:30:00.123456"),
+            text("1999-12-31"),
+            text("6f9619ff-8b86-d011-b42d-00c04fc964ff"),
+            text("alpha"),
+        ]);
+        rows.add_row(vec![text("2"), None, None, None, None, text("")]);
+        rows
+    }
+
+    fn write_file(name: &str, rows: &[RowBuffer], options: &ParquetExportOptions) -> PathBuf {
+        let schema: SchemaRef = Arc::new(arrow_schema(&rows[0]));
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(options.row_group_rows)
+            .set_compression(options.codec.compression())
+            .build();
+        let path = std::env::temp_dir().join(format!(
+            "odbc_parquet_io_{name}_{}.parquet",
+            std::process::id()
+        ));
+        let out = File::create(&path).unwrap();
+        let mut writer = ArrowWriter::try_new(out, schema.clone(), Some(properties)).unwrap();
+        for batch in rows {
+            writer
+                .write(&record_batch(&schema, batch).unwrap())
+                .unwrap();
+        }
+        close_writer(writer).unwrap();
+        path
+    }
+
+    fn file_reader(path: &Path) -> SerializedFileReader<File> {
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(path);
+        reader
+    }
+
+    #[test]
+    fn logical_types_follow_odbc_types() {
+        use parquet::basic::LogicalType;
+
+        let path = write_file("logical", &[sample()], &ParquetExportOptions::default());
+        let reader = file_reader(&path);
+        let schema = reader.metadata().file_metadata().schema_descr_ptr();
+        let logical: Vec<Option<LogicalType>> =
+            schema.columns().iter().map(|c| c.logical_type()).collect();
+        assert!(matches!(
+            logical[1],
+            Some(LogicalType::Decimal {
+                precision: 10,
+                scale: 2
+            })
+        ));
+        assert!(matches!(logical[2], Some(LogicalType::Timestamp { .. })));
+        assert_eq!(logical[3], Some(LogicalType::Date));
+        assert_eq!(logical[4], Some(LogicalType::Uuid));
+        assert_eq!(logical[5], Some(LogicalType::String));
+    }
+
+    #[test]
+    fn one_row_group_per_n_rows() {
+        let options = ParquetExportOptions {
+            row_group_rows: 3,
+            codec: ParquetCodec::Zstd,
+            ..Default::default()
+        };
+        let path = write_file(
+            "groups",
+            &[sample(), sample(), sample(), sample()],
+            &options,
+        );
+        let reader = file_reader(&path);
+        let groups: Vec<i64> = reader
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|g| g.num_rows())
+            .collect();
+        assert_eq!(groups, vec![3, 3, 2]);
+    }
+
+    #[test]
+    fn file_reads_back_as_payload_batches() {
+        let path = write_file(
+            "payload",
+            &[sample(), sample()],
+            &ParquetExportOptions::default(),
+        );
+
+        let mut payloads = Vec::new();
+        let mut progress = Vec::new();
+        let report = ParquetImporter::new("t", ParquetImportOptions { batch_rows: 3 })
+            .read_file(
+                &path,
+                &mut |p| {
+                    payloads.push(p.clone());
+                    Ok(p.row_count as usize)
+                },
+                &mut |p| progress.push(*p),
+            )
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(report.rows_loaded, 4);
+        assert_eq!(report.batches, 2);
+        assert_eq!(progress.last().unwrap().total_rows, 4);
+        let first = &payloads[0];
+        assert_eq!(first.row_count, 3);
+        let types: Vec<BulkColumnType> = first.columns.iter().map(|c| c.col_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                BulkColumnType::I32,
+                BulkColumnType::Decimal,
+                BulkColumnType::Timestamp,
+                BulkColumnType::Date,
+                BulkColumnType::Uuid,
+                BulkColumnType::Text,
+            ]
+        );
+        let BulkColumnData::Text {
+            rows, null_bitmap, ..
+        } = &first.column_data[1]
+        else {
+            panic!("decimal should load as text");
+        };
+        assert_eq!(rows[0], b"12.50");
+        assert_eq!(null_bitmap.as_deref(), Some(&[0b010][..]));
+        let BulkColumnData::Timestamp { values, .. } = &first.column_data[2] else {
+            panic!("timestamp column");
+        };
+        assert_eq!(
+            values[0],
+            BulkTimestamp {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 12,
+                minute: 30,
+                second: 0,
+                fraction: 123_456_000,
+            }
+        );
+        let BulkColumnData::Uuid { values, .. } = &first.column_data[4] else {
+            panic!("uuid column");
+        };
+        assert_eq!(values[0][..4], [0x6f, 0x96, 0x19, 0xff]);
+    }
+
+    #[test]
+    fn timestamp_before_epoch_splits_days_correctly() {
+        let ts = timestamp_from_nanos(-1).unwrap();
+        assert_eq!(
+            (ts.year, ts.month, ts.day, ts.hour, ts.second, ts.fraction),
+            (1969, 12, 31, 23, 59, 999_999_999)
+        );
+    }
+}
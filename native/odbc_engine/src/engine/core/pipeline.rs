@@ -1,9 +1,9 @@
-use super::execution_engine::ExecutionEngine;
+use super::execution_engine::{ExecutionEngine, QueryConnection, ResultOptions};
 use crate::error::{OdbcError, Result};
 use crate::handles::CachedConnection;
 use crate::observability::Metrics;
-use crate::protocol::ParamValue;
-use odbc_api::Connection;
+use crate::protocol::{OutputFormat, ParamValue};
+use crate::versioning::protocol_version::ProtocolVersion;
 use std::sync::Arc;
 
 pub struct QueryPlan {
@@ -54,11 +54,19 @@ impl QueryPipeline {
         Ok(QueryPlan::new(sql.to_string()))
     }
 
-    pub fn execute(&self, conn: &Connection<'static>, plan: QueryPlan) -> Result<Vec<u8>> {
+    pub fn execute<'a>(
+        &self,
+        conn: impl Into<QueryConnection<'a>>,
+        plan: QueryPlan,
+    ) -> Result<Vec<u8>> {
         self.execution_engine.execute_query(conn, plan.sql())
     }
 
-    pub fn execute_direct(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    pub fn execute_direct<'a>(
+        &self,
+        conn: impl Into<QueryConnection<'a>>,
+        sql: &str,
+    ) -> Result<Vec<u8>> {
         let plan = self.parse_sql(sql)?;
         self.execute(conn, plan)
     }
@@ -68,14 +76,16 @@ impl QueryPipeline {
         &self,
         cached: &mut CachedConnection,
         sql: &str,
+        options: &ResultOptions,
     ) -> Result<Vec<u8>> {
         self.parse_sql(sql)?;
-        self.execution_engine.execute_query_cached(cached, sql)
+        self.execution_engine
+            .execute_query_cached(cached, sql, options)
     }
 
-    pub fn execute_with_params(
+    pub fn execute_with_params<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[ParamValue],
     ) -> Result<Vec<u8>> {
@@ -84,9 +94,9 @@ impl QueryPipeline {
             .execute_query_with_params(conn, sql, params)
     }
 
-    pub fn execute_with_params_and_timeout(
+    pub fn execute_with_params_and_timeout<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[ParamValue],
         timeout_sec: Option<usize>,
@@ -102,9 +112,9 @@ impl QueryPipeline {
         )
     }
 
-    pub fn execute_with_bound_params_and_timeout(
+    pub fn execute_with_bound_params_and_timeout<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         bound: &[crate::protocol::bound_param::BoundParam],
         timeout_sec: Option<usize>,
@@ -115,14 +125,43 @@ impl QueryPipeline {
             .execute_query_with_bound_params_and_timeout(conn, sql, bound, timeout_sec, fetch_size)
     }
 
-    pub fn execute_multi(&self, conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+    /// Executes an `odbc_prepare` statement through its reusable handle.
+    #[cfg(feature = "statement-handle-reuse")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_prepared(
+        &self,
+        cached: &mut CachedConnection,
+        stmt_id: u32,
+        sql: &str,
+        params: &[ParamValue],
+        timeout_sec: Option<usize>,
+        fetch_size: Option<u32>,
+        options: &ResultOptions,
+    ) -> Result<Vec<u8>> {
+        self.parse_sql(sql)?;
+        self.execution_engine.execute_prepared(
+            cached,
+            stmt_id,
+            sql,
+            params,
+            timeout_sec,
+            fetch_size,
+            options,
+        )
+    }
+
+    pub fn execute_multi<'a>(
+        &self,
+        conn: impl Into<QueryConnection<'a>>,
+        sql: &str,
+    ) -> Result<Vec<u8>> {
         self.parse_sql(sql)?;
         self.execution_engine.execute_multi_result(conn, sql)
     }
 
-    pub fn execute_multi_with_params(
+    pub fn execute_multi_with_params<'a>(
         &self,
-        conn: &Connection<'static>,
+        conn: impl Into<QueryConnection<'a>>,
         sql: &str,
         params: &[crate::protocol::ParamValue],
     ) -> Result<Vec<u8>> {
@@ -134,6 +173,16 @@ impl QueryPipeline {
     pub fn get_metrics(&self) -> Arc<Metrics> {
         self.execution_engine.get_metrics()
     }
+
+    /// See [`ExecutionEngine::set_protocol_version`].
+    pub fn set_protocol_version(&self, version: ProtocolVersion) -> Result<()> {
+        self.execution_engine.set_protocol_version(version)
+    }
+
+    /// See [`ExecutionEngine::set_output_format`].
+    pub fn set_output_format(&self, format: OutputFormat) -> Result<()> {
+        self.execution_engine.set_output_format(format)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,780 @@
+//! Snowflake staged bulk loader (`PUT` + `COPY INTO`).
+//!
+//! The payload is split into `BulkLoadOptions::batch_size`-row CSV files, each
+//! zstd-compressed into a private directory (owner-only on Unix) under the temp
+//! directory, then:
+//!
+//! 1. `CREATE TEMPORARY STAGE` — dropped with the session, and explicitly afterwards;
+//! 2. `PUT file://... @stage SOURCE_COMPRESSION = ZSTD AUTO_COMPRESS = FALSE` per file;
+//! 3. `COPY INTO table (cols) FROM @stage ... ON_ERROR = ... PURGE = TRUE`.
+//!
+//! COPY returns one row per staged file (`file`, `status`, `rows_parsed`,
+//! `rows_loaded`, `errors_seen`, `first_error`, ...); [`parse_copy_result`] turns those
+//! into a [`SnowflakeCopyReport`] with the inserted-row count and the rejected files.
+//!
+//! CSV layout: `options.delimiter`-separated, text always enclosed in `"` (embedded
+//! quotes doubled), NULL written unenclosed as `options.null_marker`, binary as hex,
+//! timestamps as `YYYY-MM-DD HH:MM:SS.ffffff`. The SQL builders and [`stage_files`]
+//! need no connection, so everything but the round trip is unit-tested.
+
+use super::disk_spill::{create_private_dir, create_spill_file};
+use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
+use crate::error::{OdbcError, Result};
+use crate::plugins::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress};
+use crate::protocol::{format_uuid, BulkColumnData, BulkInsertPayload};
+use odbc_api::{Connection, Cursor, ResultSetMetadata};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const ZSTD_LEVEL: i32 = 3;
+
+static STAGE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Result-set cells as text, `None` for SQL NULL.
+type TextRow = Vec<Option<String>>;
+
+/// `ON_ERROR` policy for `COPY INTO`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnowflakeOnError {
+    /// Fail the whole COPY on the first bad row (Snowflake's default for bulk loads).
+    #[default]
+    AbortStatement,
+    /// Load every parseable row and report the rest.
+    Continue,
+    /// Skip any file that contains an error.
+    SkipFile,
+}
+
+impl SnowflakeOnError {
+    fn as_sql(self) -> &'static str {
+        match self {
+            Self::AbortStatement => "ABORT_STATEMENT",
+            Self::Continue => "CONTINUE",
+            Self::SkipFile => "SKIP_FILE",
+        }
+    }
+}
+
+/// One CSV file written by [`stage_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedFile {
+    pub path: PathBuf,
+    pub rows: usize,
+}
+
+/// Outcome of one staged file, from the `COPY INTO` result set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyFileResult {
+    pub file: String,
+    /// `LOADED`, `LOAD_FAILED`, `PARTIALLY_LOADED`, ...
+    pub status: String,
+    pub rows_parsed: u64,
+    pub rows_loaded: u64,
+    pub errors_seen: u64,
+    pub first_error: Option<String>,
+    pub first_error_line: Option<u64>,
+    pub first_error_column: Option<String>,
+}
+
+impl CopyFileResult {
+    /// Rows of this file that did not make it into the table.
+    pub fn rows_rejected(&self) -> u64 {
+        self.errors_seen
+            .max(self.rows_parsed.saturating_sub(self.rows_loaded))
+    }
+}
+
+/// Parsed `COPY INTO` result.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnowflakeCopyReport {
+    pub files: Vec<CopyFileResult>,
+}
+
+impl SnowflakeCopyReport {
+    pub fn rows_loaded(&self) -> u64 {
+        self.files.iter().map(|f| f.rows_loaded).sum()
+    }
+
+    pub fn rows_rejected(&self) -> u64 {
+        self.files.iter().map(CopyFileResult::rows_rejected).sum()
+    }
+
+    /// Files with at least one rejected row.
+    pub fn rejected_files(&self) -> impl Iterator<Item = &CopyFileResult> {
+        self.files.iter().filter(|f| f.rows_rejected() > 0)
+    }
+}
+
+/// Loads [`BulkInsertPayload`]s through a temporary internal stage.
+#[derive(Debug, Clone, Default)]
+pub struct SnowflakeStageLoader {
+    temp_dir: Option<PathBuf>,
+    on_error: SnowflakeOnError,
+}
+
+impl SnowflakeStageLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory under which each load creates its private directory for the
+    /// compressed CSV files (default: `std::env::temp_dir()`).
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_on_error(mut self, on_error: SnowflakeOnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Stages and copies `payload`; `on_batch` is called once per file COPY reports.
+    pub fn load(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<SnowflakeCopyReport> {
+        payload.validate_shape()?;
+        validate_csv_options(options)?;
+        if payload.row_count == 0 {
+            return Ok(SnowflakeCopyReport::default());
+        }
+
+        let stage = format!(
+            "ODBC_BULK_{}_{}",
+            std::process::id(),
+            STAGE_SEQ.fetch_add(1, Ordering::Relaxed)
+        );
+        let dir = self.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let mut files = stage_files(payload, options, &dir, &stage)?;
+        let timeout = options.timeout_secs.map(|s| s as usize);
+
+        let result = (|| {
+            execute(conn, &create_stage_sql(&stage), timeout)?;
+            for file in &files.files {
+                execute(conn, &put_sql(&file.path, &stage), timeout)?;
+            }
+            let sql = copy_into_sql(payload, &stage, options, self.on_error)?;
+            let (columns, rows) = query_text_rows(conn, &sql, timeout)?;
+            parse_copy_result(&columns, &rows)
+        })();
+        if let Err(e) = execute(conn, &drop_stage_sql(&stage), timeout) {
+            log::warn!("Snowflake bulk load: failed to drop stage {stage}: {e}");
+        }
+
+        let report = match result {
+            Ok(report) => report,
+            Err(err) => {
+                if options.keep_temp_on_failure {
+                    files.keep();
+                    log::warn!(
+                        "Snowflake bulk load failed; keeping {} staged file(s) in {}",
+                        files.files.len(),
+                        files.dir.display()
+                    );
+                }
+                return Err(err);
+            }
+        };
+
+        let total_rows = payload.row_count as usize;
+        let mut loaded = 0usize;
+        for (batch_index, file) in report.files.iter().enumerate() {
+            loaded += file.rows_loaded as usize;
+            on_batch(&BulkLoadProgress {
+                batch_index,
+                batch_rows: file.rows_loaded as usize,
+                rows_loaded: loaded,
+                total_rows,
+            });
+        }
+        for file in report.rejected_files() {
+            log::warn!(
+                "Snowflake COPY rejected {} row(s) from {} ({}): {}",
+                file.rows_rejected(),
+                file.file,
+                file.status,
+                file.first_error.as_deref().unwrap_or("no error detail")
+            );
+        }
+        Ok(report)
+    }
+}
+
+/// The files written by [`stage_files`] and their private directory. Dropping
+/// removes both unless [`keep`](Self::keep) was called.
+#[derive(Debug)]
+pub struct StagedFiles {
+    pub dir: PathBuf,
+    pub files: Vec<StagedFile>,
+    keep: bool,
+}
+
+impl StagedFiles {
+    /// Leaves the files on disk when dropped, for post-mortem inspection.
+    pub fn keep(&mut self) {
+        self.keep = true;
+    }
+}
+
+impl Drop for StagedFiles {
+    fn drop(&mut self) {
+        if self.keep {
+            return;
+        }
+        let warn = |path: &Path, r: std::io::Result<()>| {
+            if let Err(e) = r {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!(
+                        "StagedFiles::drop: failed to remove {}: {e}",
+                        path.display()
+                    );
+                }
+            }
+        };
+        for file in &self.files {
+            warn(&file.path, std::fs::remove_file(&file.path));
+        }
+        warn(&self.dir, std::fs::remove_dir(&self.dir));
+    }
+}
+
+/// Writes `payload` as zstd-compressed CSV files of at most `options.batch_size` rows,
+/// named `<prefix>_<n>.csv.zst` in a new directory `<dir>/<prefix>`. The directory is
+/// owner-only and each file is created exclusively with mode `0600` (on Unix), so
+/// other local users can neither read the rows nor plant files in their place. On
+/// error everything written so far is removed.
+pub fn stage_files(
+    payload: &BulkInsertPayload,
+    options: &BulkLoadOptions,
+    dir: &Path,
+    prefix: &str,
+) -> Result<StagedFiles> {
+    let io_err = |e: std::io::Error| OdbcError::InternalError(format!("stage file write: {e}"));
+    let total_rows = payload.row_count as usize;
+    let batch_size = options.batch_size.max(1);
+    let private_dir = dir.join(prefix);
+    create_private_dir(&private_dir).map_err(io_err)?;
+    let mut staged = StagedFiles {
+        dir: private_dir,
+        files: Vec::with_capacity(total_rows.div_ceil(batch_size)),
+        keep: false,
+    };
+    let mut line = Vec::new();
+    let mut start = 0usize;
+    while start < total_rows {
+        let end = (start + batch_size).min(total_rows);
+        let path = staged
+            .dir
+            .join(format!("{prefix}_{}.csv.zst", staged.files.len()));
+        let file = create_spill_file(&path).map_err(io_err)?;
+        staged.files.push(StagedFile {
+            path,
+            rows: end - start,
+        });
+        let mut encoder =
+            zstd::stream::write::Encoder::new(BufWriter::new(file), ZSTD_LEVEL).map_err(io_err)?;
+        for row in start..end {
+            line.clear();
+            encode_csv_row(&mut line, payload, row, options);
+            encoder.write_all(&line).map_err(io_err)?;
+        }
+        encoder.finish().map_err(io_err)?.flush().map_err(io_err)?;
+        start = end;
+    }
+    Ok(staged)
+}
+
+/// Appends row `row` of `payload` as one CSV record.
+pub fn encode_csv_row(
+    out: &mut Vec<u8>,
+    payload: &BulkInsertPayload,
+    row: usize,
+    options: &BulkLoadOptions,
+) {
+    for (i, data) in payload.column_data.iter().enumerate() {
+        if i > 0 {
+            out.push(options.delimiter as u8);
+        }
+        if data.is_null_at(row) {
+            out.extend_from_slice(options.null_marker.as_bytes());
+            continue;
+        }
+        match data {
+            BulkColumnData::I32 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::I64 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::Text { rows, .. } => quote_csv(out, &rows[row]),
+            BulkColumnData::Binary { rows, .. } => {
+                for b in &rows[row] {
+                    out.extend_from_slice(format!("{b:02X}").as_bytes());
+                }
+            }
+            BulkColumnData::Timestamp { values, .. } => {
+                out.extend_from_slice(values[row].to_iso_micros().as_bytes())
+            }
+            BulkColumnData::Bool { values, .. } => {
+                out.extend_from_slice(if values[row] { b"TRUE" } else { b"FALSE" })
+            }
+            BulkColumnData::F64 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::F32 { values, .. } => {
+                out.extend_from_slice(values[row].to_string().as_bytes())
+            }
+            BulkColumnData::Date { values, .. } => {
+                out.extend_from_slice(values[row].to_iso().as_bytes())
+            }
+            BulkColumnData::Time { values, .. } => {
+                out.extend_from_slice(values[row].to_iso().as_bytes())
+            }
+            BulkColumnData::Uuid { values, .. } => {
+                out.extend_from_slice(format_uuid(&values[row]).as_bytes())
+            }
+            BulkColumnData::NText { rows, .. } => {
+                quote_csv(out, String::from_utf16_lossy(&rows[row]).as_bytes())
+            }
+        }
+    }
+    out.push(b'\n');
+}
+
+fn quote_csv(out: &mut Vec<u8>, value: &[u8]) {
+    out.push(b'"');
+    for &b in value {
+        if b == b'"' {
+            out.push(b'"');
+        }
+        out.push(b);
+    }
+    out.push(b'"');
+}
+
+pub fn create_stage_sql(stage: &str) -> String {
+    format!("CREATE TEMPORARY STAGE IF NOT EXISTS {stage}")
+}
+
+pub fn drop_stage_sql(stage: &str) -> String {
+    format!("DROP STAGE IF EXISTS {stage}")
+}
+
+/// `PUT` for one already-compressed file.
+pub fn put_sql(path: &Path, stage: &str) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    format!(
+        "PUT {} @{stage} SOURCE_COMPRESSION = ZSTD AUTO_COMPRESS = FALSE OVERWRITE = TRUE",
+        sf_literal(&format!("file://{path}"))
+    )
+}
+
+/// `COPY INTO` from every file in `stage`, purging them once loaded.
+pub fn copy_into_sql(
+    payload: &BulkInsertPayload,
+    stage: &str,
+    options: &BulkLoadOptions,
+    on_error: SnowflakeOnError,
+) -> Result<String> {
+    let qtable = quote_qualified_default(&payload.table)?;
+    let cols = payload
+        .columns
+        .iter()
+        .map(|c| quote_identifier_default(&c.name))
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    Ok(format!(
+        "COPY INTO {qtable} ({cols}) FROM @{stage} FILE_FORMAT = (TYPE = CSV \
+         COMPRESSION = ZSTD FIELD_DELIMITER = {} RECORD_DELIMITER = '\\n' \
+         FIELD_OPTIONALLY_ENCLOSED_BY = '\"' ESCAPE_UNENCLOSED_FIELD = NONE \
+         NULL_IF = ({}) EMPTY_FIELD_AS_NULL = FALSE BINARY_FORMAT = HEX \
+         TIMESTAMP_FORMAT = 'YYYY-MM-DD HH24:MI:SS.FF6') ON_ERROR = {} PURGE = TRUE",
+        sf_literal(&options.delimiter.to_string()),
+        sf_literal(&options.null_marker),
+        on_error.as_sql()
+    ))
+}
+
+/// Builds the report from the `COPY INTO` result set. Columns are matched by name
+/// (case-insensitive); a result without a `file` column ("Copy executed with 0 files
+/// processed.") yields an empty report.
+pub fn parse_copy_result(columns: &[String], rows: &[TextRow]) -> Result<SnowflakeCopyReport> {
+    let index = |name: &str| columns.iter().position(|c| c.eq_ignore_ascii_case(name));
+    let Some(file_idx) = index("file") else {
+        return Ok(SnowflakeCopyReport::default());
+    };
+    let status_idx = index("status");
+    let parsed_idx = index("rows_parsed");
+    let loaded_idx = index("rows_loaded").ok_or_else(|| {
+        OdbcError::InternalError("COPY INTO result has no rows_loaded column".to_string())
+    })?;
+    let errors_idx = index("errors_seen");
+    let first_error_idx = index("first_error");
+    let line_idx = index("first_error_line");
+    let column_idx = index("first_error_column_name");
+
+    let text = |row: &[Option<String>], idx: Option<usize>| -> Option<String> {
+        idx.and_then(|i| row.get(i).cloned().flatten())
+            .filter(|s| !s.is_empty())
+    };
+    let number = |row: &[Option<String>], idx: Option<usize>| -> Result<Option<u64>> {
+        text(row, idx)
+            .map(|s| {
+                s.trim().parse::<u64>().map_err(|_| {
+                    OdbcError::InternalError(format!("COPY INTO result: bad count {s:?}"))
+                })
+            })
+            .transpose()
+    };
+
+    let mut files = Vec::with_capacity(rows.len());
+    for row in rows {
+        files.push(CopyFileResult {
+            file: text(row, Some(file_idx)).unwrap_or_default(),
+            status: text(row, status_idx).unwrap_or_default(),
+            rows_parsed: number(row, parsed_idx)?.unwrap_or(0),
+            rows_loaded: number(row, Some(loaded_idx))?.unwrap_or(0),
+            errors_seen: number(row, errors_idx)?.unwrap_or(0),
+            first_error: text(row, first_error_idx),
+            first_error_line: number(row, line_idx)?,
+            first_error_column: text(row, column_idx),
+        });
+    }
+    Ok(SnowflakeCopyReport { files })
+}
+
+/// Snowflake string literal (backslash escapes).
+fn sf_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\'' => out.push_str("\\'"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+fn validate_csv_options(options: &BulkLoadOptions) -> Result<()> {
+    let d = options.delimiter;
+    if !d.is_ascii() || matches!(d, '"' | '\n' | '\r') {
+        return Err(OdbcError::ValidationError(format!(
+            "CSV delimiter must be a single-byte character other than '\"', newline or \
+             carriage return (got {d:?})"
+        )));
+    }
+    if options.null_marker.contains(['"', '\n', '\r', d]) {
+        return Err(OdbcError::ValidationError(
+            "CSV NULL marker cannot contain quotes, the delimiter or a line break".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn execute(conn: &Connection<'static>, sql: &str, timeout: Option<usize>) -> Result<()> {
+    conn.execute(sql, (), timeout).map_err(OdbcError::from)?;
+    Ok(())
+}
+
+/// Column names and text cells of the result set `sql` produces.
+fn query_text_rows(
+    conn: &Connection<'static>,
+    sql: &str,
+    timeout: Option<usize>,
+) -> Result<(Vec<String>, Vec<TextRow>)> {
+    let Some(mut cursor) = conn.execute(sql, (), timeout).map_err(OdbcError::from)? else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let columns = cursor
+        .column_names()
+        .map_err(OdbcError::from)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(OdbcError::from)?;
+    let mut rows = Vec::new();
+    let mut buf = Vec::new();
+    while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
+        let mut cells = Vec::with_capacity(columns.len());
+        for col in 1..=columns.len() as u16 {
+            buf.clear();
+            let non_null = row.get_text(col, &mut buf).map_err(OdbcError::from)?;
+            cells.push(non_null.then(|| String::from_utf8_lossy(&buf).into_owned()));
+        }
+        rows.push(cells);
+    }
+    Ok((columns, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{BulkColumnSpec, BulkColumnType, BulkTimestamp};
+
+    fn spec(name: &str, col_type: BulkColumnType) -> BulkColumnSpec {
+        BulkColumnSpec {
+            name: name.to_string(),
+            col_type,
+            nullable: true,
+            max_len: 0,
+        }
+    }
+
+    fn payload(rows: usize) -> BulkInsertPayload {
+        BulkInsertPayload {
+            table: "PUBLIC.ITEMS".to_string(),
+            columns: vec![
+                spec("ID", BulkColumnType::I32),
+                spec("NAME", BulkColumnType::Text),
+                spec("RAW", BulkColumnType::Binary),
+                spec("AT", BulkColumnType::Timestamp),
+            ],
+            row_count: rows as u32,
+            column_data: vec![
+                BulkColumnData::I32 {
+                    values: (1..=rows as i32).collect(),
+                    null_bitmap: None,
+                },
+                BulkColumnData::Text {
+                    rows: (0..rows)
+                        .map(|i| format!("say \"hi\"\t{i}").into_bytes())
+                        .collect(),
+                    max_len: 16,
+                    null_bitmap: Some(vec![0b10; rows.div_ceil(8)]),
+                },
+                BulkColumnData::Binary {
+                    rows: vec![vec![0x0f, 0xa0]; rows],
+                    max_len: 2,
+                    null_bitmap: None,
+                },
+                BulkColumnData::Timestamp {
+                    values: vec![
+                        BulkTimestamp {
+                            year: 2024,
+                            month: 1,
+                            day: 2,
+                            hour: 3,
+                            minute: 4,
+                            second: 5,
+                            fraction: 6_000,
+                        };
+                        rows
+                    ],
+                    null_bitmap: None,
+                },
+            ],
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("odbc_sf_test_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_csv_rows_enclose_text_and_mark_nulls() {
+        let payload = payload(2);
+        let options = BulkLoadOptions::default();
+        let mut out = Vec::new();
+        encode_csv_row(&mut out, &payload, 0, &options);
+        encode_csv_row(&mut out, &payload, 1, &options);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\t\"say \"\"hi\"\"\t0\"\t0FA0\t2024-01-02 03:04:05.000006\n\
+             2\t\\N\t0FA0\t2024-01-02 03:04:05.000006\n"
+        );
+    }
+
+    #[test]
+    fn test_stage_files_split_and_compress_batches() {
+        let dir = temp_dir("split");
+        let payload = payload(5);
+        let options = BulkLoadOptions::new(2);
+        let staged = stage_files(&payload, &options, &dir, "T").unwrap();
+        assert_eq!(
+            staged.files.iter().map(|f| f.rows).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(staged.dir, dir.join("T"));
+        assert!(staged.files[0].path.ends_with("T/T_0.csv.zst"));
+
+        let mut expected = Vec::new();
+        encode_csv_row(&mut expected, &payload, 4, &options);
+        let last = zstd::decode_all(std::fs::File::open(&staged.files[2].path).unwrap()).unwrap();
+        assert_eq!(last, expected);
+
+        let files = staged.files.clone();
+        let private_dir = staged.dir.clone();
+        drop(staged);
+        assert!(files.iter().all(|f| !f.path.exists()));
+        assert!(!private_dir.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_kept_files_survive_drop() {
+        let dir = temp_dir("keep");
+        let mut staged = stage_files(&payload(1), &BulkLoadOptions::default(), &dir, "K").unwrap();
+        staged.keep();
+        let path = staged.files[0].path.clone();
+        drop(staged);
+        assert!(path.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stage_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = temp_dir("mode");
+        let staged = stage_files(&payload(1), &BulkLoadOptions::default(), &dir, "M").unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&staged.dir), 0o700);
+        assert_eq!(mode(&staged.files[0].path), 0o600);
+        drop(staged);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_stage_files_refuse_an_existing_directory() {
+        let dir = temp_dir("exists");
+        std::fs::create_dir(dir.join("E")).unwrap();
+        std::fs::write(dir.join("E").join("E_0.csv.zst"), b"planted").unwrap();
+        assert!(stage_files(&payload(1), &BulkLoadOptions::default(), &dir, "E").is_err());
+        assert_eq!(
+            std::fs::read(dir.join("E").join("E_0.csv.zst")).unwrap(),
+            b"planted"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_stage_and_put_sql() {
+        assert_eq!(
+            create_stage_sql("ODBC_BULK_1_0"),
+            "CREATE TEMPORARY STAGE IF NOT EXISTS ODBC_BULK_1_0"
+        );
+        assert_eq!(
+            put_sql(Path::new("/tmp/it's_0.csv.zst"), "S"),
+            "PUT 'file:///tmp/it\\'s_0.csv.zst' @S SOURCE_COMPRESSION = ZSTD \
+             AUTO_COMPRESS = FALSE OVERWRITE = TRUE"
+        );
+        assert!(put_sql(Path::new("C:\\temp\\a.csv.zst"), "S").starts_with("PUT 'file://C:/temp/a"));
+        assert_eq!(drop_stage_sql("S"), "DROP STAGE IF EXISTS S");
+    }
+
+    #[test]
+    fn test_copy_into_sql_carries_format_and_on_error() {
+        let options = BulkLoadOptions::default();
+        let sql = copy_into_sql(&payload(1), "S", &options, SnowflakeOnError::Continue).unwrap();
+        assert!(sql.starts_with(
+            "COPY INTO \"PUBLIC\".\"ITEMS\" (\"ID\", \"NAME\", \"RAW\", \"AT\") FROM @S"
+        ));
+        assert!(sql.contains("FIELD_DELIMITER = '\\t'"));
+        assert!(sql.contains("NULL_IF = ('\\\\N')"));
+        assert!(sql.contains("COMPRESSION = ZSTD"));
+        assert!(sql.ends_with("ON_ERROR = CONTINUE PURGE = TRUE"));
+        let sql = copy_into_sql(&payload(1), "S", &options, SnowflakeOnError::default()).unwrap();
+        assert!(sql.contains("ON_ERROR = ABORT_STATEMENT"));
+    }
+
+    #[test]
+    fn test_invalid_csv_options_are_rejected() {
+        for delimiter in ['"', '\n', 'ß'] {
+            let options = BulkLoadOptions {
+                delimiter,
+                ..BulkLoadOptions::default()
+            };
+            assert!(validate_csv_options(&options).is_err(), "{delimiter:?}");
+        }
+        let options = BulkLoadOptions {
+            null_marker: "\"\"".to_string(),
+            ..BulkLoadOptions::default()
+        };
+        assert!(validate_csv_options(&options).is_err());
+    }
+
+    #[test]
+    fn test_parse_copy_result_counts_loaded_and_rejected() {
+        let columns: Vec<String> = [
+            "file",
+            "status",
+            "rows_parsed",
+            "rows_loaded",
+            "error_limit",
+            "errors_seen",
+            "first_error",
+            "first_error_line",
+            "first_error_character",
+            "first_error_column_name",
+        ]
+        .iter()
+        .map(|s| s.to_uppercase())
+        .collect();
+        let cell = |s: &str| Some(s.to_string());
+        let rows = vec![
+            vec![
+                cell("odbc_bulk_1_0_0.csv.zst"),
+                cell("LOADED"),
+                cell("2"),
+                cell("2"),
+                cell("2"),
+                cell("0"),
+                None,
+                None,
+                None,
+                None,
+            ],
+            vec![
+                cell("odbc_bulk_1_0_1.csv.zst"),
+                cell("PARTIALLY_LOADED"),
+                cell("3"),
+                cell("1"),
+                cell("3"),
+                cell("2"),
+                cell("Numeric value 'x' is not recognized"),
+                cell("2"),
+                cell("1"),
+                cell("\"ITEMS\"[\"ID\":1]"),
+            ],
+        ];
+        let report = parse_copy_result(&columns, &rows).unwrap();
+        assert_eq!(report.rows_loaded(), 3);
+        assert_eq!(report.rows_rejected(), 2);
+        let rejected: Vec<_> = report.rejected_files().collect();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].status, "PARTIALLY_LOADED");
+        assert_eq!(rejected[0].first_error_line, Some(2));
+        assert_eq!(
+            rejected[0].first_error_column.as_deref(),
+            Some("\"ITEMS\"[\"ID\":1]")
+        );
+    }
+
+    #[test]
+    fn test_parse_copy_result_handles_no_files_and_bad_counts() {
+        let report = parse_copy_result(
+            &["status".to_string()],
+            &[vec![Some(
+                "Copy executed with 0 files processed.".to_string(),
+            )]],
+        )
+        .unwrap();
+        assert_eq!(report, SnowflakeCopyReport::default());
+
+        let columns = vec!["file".to_string(), "rows_loaded".to_string()];
+        let rows = vec![vec![Some("f".to_string()), Some("many".to_string())]];
+        assert!(parse_copy_result(&columns, &rows).is_err());
+        assert!(parse_copy_result(&columns[..1], &[]).is_err());
+    }
+}
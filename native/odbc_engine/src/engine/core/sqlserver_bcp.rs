@@ -7,6 +7,18 @@
 //!
 //! We prioritize `sqlncli11.dll` for BCP operations. If unavailable, we attempt modern drivers but may fall back to ArrayBinding.
 //!
+//! # Linux
+//!
+//! The Linux builds of msodbcsql 17/18 export the same BCP entry points from
+//! `libmsodbcsql-<major>.<minor>.so.<rev>`. Candidates are, in order: the path
+//! in `ODBC_MSODBCSQL_PATH`, every `libmsodbcsql-*.so*` under
+//! `/opt/microsoft/msodbcsql{18,17}/lib64` (newest first), then the bare
+//! `libmsodbcsql-18.so` / `libmsodbcsql-17.so` names for the loader search path.
+//! Loading the same file the driver manager already opened reuses that image.
+//!
+//! unixODBC hands out its own connection handles, so the BCP calls receive
+//! the driver-level handle obtained through `SQLGetInfo(SQL_DRIVER_HDBC)`.
+//!
 //! # bcp_collen Usage
 //!
 //! `bcp_collen` sets the column length for **all subsequent rows** until called again. For nullable columns:
@@ -16,7 +28,7 @@
 
 use crate::error::{OdbcError, Result};
 use crate::protocol::bulk_insert::is_null;
-use crate::protocol::{BulkColumnData, BulkColumnType, BulkInsertPayload};
+use crate::protocol::{BulkColumnData, BulkColumnType, BulkDate, BulkInsertPayload, BulkTime};
 use libloading::Library;
 use odbc_api::sys::{
     ConnectionAttribute, DriverConnectOption, HDbc, Handle, HandleType, SQLAllocHandle,
@@ -25,8 +37,21 @@ use odbc_api::sys::{
 };
 use std::ffi::c_void;
 
+#[cfg(windows)]
 const CANDIDATE_LIBRARIES: &[&str] = &["sqlncli11.dll", "msodbcsql17.dll", "msodbcsql18.dll"];
 
+#[cfg(target_os = "linux")]
+const CANDIDATE_LIBRARIES: &[&str] = &["libmsodbcsql-18.so", "libmsodbcsql-17.so"];
+
+#[cfg(target_os = "linux")]
+const LINUX_DRIVER_DIRS: &[&str] = &[
+    "/opt/microsoft/msodbcsql18/lib64",
+    "/opt/microsoft/msodbcsql17/lib64",
+];
+
+#[cfg(target_os = "linux")]
+const MSODBCSQL_PATH_ENV: &str = "ODBC_MSODBCSQL_PATH";
+
 const REQUIRED_SYMBOL_SETS: &[&[&[u8]]] = &[
     &[b"bcp_initW\0", b"bcp_init\0"],
     &[b"bcp_bind\0"],
@@ -40,7 +65,34 @@ const SQL_BCP_ON: i32 = 1;
 const DB_IN: i32 = 1;
 const SQLINT4: i32 = 56;
 const SQLINT8: i32 = 127;
+const SQLBIT: i32 = 50;
+const SQLFLT4: i32 = 59;
+const SQLFLT8: i32 = 62;
+const SQLDATEN: i32 = 40;
+const SQLTIMEN: i32 = 41;
+const SQLUNIQUEID: i32 = 36;
+const SQLNCHAR: i32 = 239;
+/// `sizeof(DBDATE)`.
+const DBDATE_LEN: usize = 6;
+/// `sizeof(SQL_SS_TIME2_STRUCT)`.
+const SS_TIME2_LEN: usize = 12;
 const SQL_NULL_DATA: i32 = -1;
+#[cfg(target_os = "linux")]
+const SQL_DRIVER_HDBC: u16 = 3;
+
+#[cfg(target_os = "linux")]
+extern "system" {
+    // Declared locally because `odbc_api::sys::InfoType` has no SQL_DRIVER_HDBC
+    // variant; the symbol comes from the driver manager odbc-sys already links.
+    #[link_name = "SQLGetInfo"]
+    fn sql_get_info_raw(
+        hdbc: HDbc,
+        info_type: u16,
+        info_value: *mut c_void,
+        buffer_length: SmallInt,
+        string_length: *mut SmallInt,
+    ) -> SqlReturn;
+}
 
 type BcpInitWFn = unsafe extern "system" fn(
     hdbc: HDbc,
@@ -63,107 +115,224 @@ type BcpColLenFn = unsafe extern "system" fn(hdbc: HDbc, cb_data: i32, idx_serve
 type BcpSendRowFn = unsafe extern "system" fn(hdbc: HDbc) -> i32;
 type BcpDoneFn = unsafe extern "system" fn(hdbc: HDbc) -> i32;
 
-enum BoundColumnRef<'a> {
-    I32 {
-        values: &'a [i32],
-        null_bitmap: Option<&'a [u8]>,
-        cell: std::mem::MaybeUninit<i32>,
-    },
-    I64 {
-        values: &'a [i64],
-        null_bitmap: Option<&'a [u8]>,
-        cell: std::mem::MaybeUninit<i64>,
+/// Row source for one bound column.
+enum BoundSource<'a> {
+    I32(&'a [i32]),
+    I64(&'a [i64]),
+    Bool(&'a [bool]),
+    F64(&'a [f64]),
+    F32(&'a [f32]),
+    Date(&'a [BulkDate]),
+    Time(&'a [BulkTime]),
+    Uuid(&'a [[u8; 16]]),
+    NText {
+        rows: &'a [Vec<u16>],
+        max_len: usize,
     },
 }
 
+impl BoundSource<'_> {
+    /// BCP program-variable type and the byte capacity of its cell.
+    fn bcp_type_and_capacity(&self) -> (i32, usize) {
+        match self {
+            BoundSource::I32(_) => (SQLINT4, 4),
+            BoundSource::I64(_) => (SQLINT8, 8),
+            BoundSource::Bool(_) => (SQLBIT, 1),
+            BoundSource::F64(_) => (SQLFLT8, 8),
+            BoundSource::F32(_) => (SQLFLT4, 4),
+            BoundSource::Date(_) => (SQLDATEN, DBDATE_LEN),
+            BoundSource::Time(_) => (SQLTIMEN, SS_TIME2_LEN),
+            BoundSource::Uuid(_) => (SQLUNIQUEID, 16),
+            BoundSource::NText { max_len, .. } => (SQLNCHAR, max_len * 2),
+        }
+    }
+}
+
+struct BoundColumnRef<'a> {
+    source: BoundSource<'a>,
+    null_bitmap: Option<&'a [u8]>,
+    /// 8-byte aligned cell bound once through `bcp_bind` and rewritten per row.
+    /// Never resized, so the pointer handed to the driver stays valid.
+    cell: Vec<u64>,
+}
+
 impl<'a> BoundColumnRef<'a> {
+    fn new(source: BoundSource<'a>, null_bitmap: Option<&'a [u8]>) -> Self {
+        let (_, capacity) = source.bcp_type_and_capacity();
+        Self {
+            source,
+            null_bitmap,
+            cell: vec![0u64; capacity.div_ceil(8).max(1)],
+        }
+    }
+
     fn len(&self) -> usize {
-        match self {
-            BoundColumnRef::I32 { values, .. } => values.len(),
-            BoundColumnRef::I64 { values, .. } => values.len(),
+        match &self.source {
+            BoundSource::I32(v) => v.len(),
+            BoundSource::I64(v) => v.len(),
+            BoundSource::Bool(v) => v.len(),
+            BoundSource::F64(v) => v.len(),
+            BoundSource::F32(v) => v.len(),
+            BoundSource::Date(v) => v.len(),
+            BoundSource::Time(v) => v.len(),
+            BoundSource::Uuid(v) => v.len(),
+            BoundSource::NText { rows, .. } => rows.len(),
         }
     }
 
+    fn is_null_row(&self, row_idx: usize) -> bool {
+        self.null_bitmap.is_some_and(|bm| is_null(bm, row_idx))
+    }
+
     fn bind_args_mut(&mut self) -> (*const u8, i32, i32) {
-        match self {
-            BoundColumnRef::I32 { cell, .. } => (
-                cell.as_mut_ptr().cast::<u8>(),
-                std::mem::size_of::<i32>() as i32,
-                SQLINT4,
-            ),
-            BoundColumnRef::I64 { cell, .. } => (
-                cell.as_mut_ptr().cast::<u8>(),
-                std::mem::size_of::<i64>() as i32,
-                SQLINT8,
-            ),
-        }
+        let (e_data_type, capacity) = self.source.bcp_type_and_capacity();
+        (
+            self.cell.as_mut_ptr().cast::<u8>(),
+            capacity as i32,
+            e_data_type,
+        )
+    }
+
+    fn cell_bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.cell.len() * 8;
+        // SAFETY: `cell` owns `len` initialized bytes; u8 has no alignment requirement.
+        unsafe { std::slice::from_raw_parts_mut(self.cell.as_mut_ptr().cast::<u8>(), len) }
     }
 
     fn write_row(&mut self, row_idx: usize) {
-        match self {
-            BoundColumnRef::I32 {
-                values,
-                null_bitmap,
-                cell,
-            } => {
-                let value = if null_bitmap.is_some_and(|bm| is_null(bm, row_idx)) {
-                    0
-                } else {
-                    values[row_idx]
-                };
-                let _ = cell.write(value);
+        if self.is_null_row(row_idx) {
+            return;
+        }
+        let mut scratch = [0u8; 16];
+        let len = match &self.source {
+            BoundSource::I32(v) => put(&mut scratch, &v[row_idx].to_ne_bytes()),
+            BoundSource::I64(v) => put(&mut scratch, &v[row_idx].to_ne_bytes()),
+            BoundSource::Bool(v) => put(&mut scratch, &[u8::from(v[row_idx])]),
+            BoundSource::F64(v) => put(&mut scratch, &v[row_idx].to_ne_bytes()),
+            BoundSource::F32(v) => put(&mut scratch, &v[row_idx].to_ne_bytes()),
+            BoundSource::Date(v) => {
+                // DBDATE: year, month, day.
+                let d = v[row_idx];
+                scratch[..2].copy_from_slice(&d.year.to_ne_bytes());
+                scratch[2..4].copy_from_slice(&d.month.to_ne_bytes());
+                scratch[4..6].copy_from_slice(&d.day.to_ne_bytes());
+                DBDATE_LEN
             }
-            BoundColumnRef::I64 {
-                values,
-                null_bitmap,
-                cell,
-            } => {
-                let value = if null_bitmap.is_some_and(|bm| is_null(bm, row_idx)) {
-                    0
-                } else {
-                    values[row_idx]
-                };
-                let _ = cell.write(value);
+            BoundSource::Time(v) => {
+                // SQL_SS_TIME2_STRUCT: hour, minute, second, padding, fraction (left 0).
+                let t = v[row_idx];
+                scratch[..2].copy_from_slice(&t.hour.to_ne_bytes());
+                scratch[2..4].copy_from_slice(&t.minute.to_ne_bytes());
+                scratch[4..6].copy_from_slice(&t.second.to_ne_bytes());
+                SS_TIME2_LEN
             }
-        }
+            BoundSource::Uuid(v) => put(&mut scratch, &guid_bytes(&v[row_idx])),
+            BoundSource::NText { rows, max_len } => {
+                let row = &rows[row_idx];
+                let units = row.len().min(*max_len);
+                let cell = self.cell_bytes_mut();
+                for (i, unit) in row[..units].iter().enumerate() {
+                    cell[i * 2..i * 2 + 2].copy_from_slice(&unit.to_ne_bytes());
+                }
+                return;
+            }
+        };
+        self.cell_bytes_mut()[..len].copy_from_slice(&scratch[..len]);
     }
 
     fn row_collen_for_bcp(&self, row_idx: usize) -> i32 {
-        match self {
-            BoundColumnRef::I32 { null_bitmap, .. } => {
-                if null_bitmap.is_some_and(|bm| is_null(bm, row_idx)) {
-                    SQL_NULL_DATA
-                } else {
-                    std::mem::size_of::<i32>() as i32
-                }
-            }
-            BoundColumnRef::I64 { null_bitmap, .. } => {
-                if null_bitmap.is_some_and(|bm| is_null(bm, row_idx)) {
-                    SQL_NULL_DATA
-                } else {
-                    std::mem::size_of::<i64>() as i32
-                }
-            }
+        if self.is_null_row(row_idx) {
+            return SQL_NULL_DATA;
+        }
+        match &self.source {
+            BoundSource::NText { rows, max_len } => (rows[row_idx].len().min(*max_len) * 2) as i32,
+            other => other.bcp_type_and_capacity().1 as i32,
         }
     }
 }
 
+fn put(scratch: &mut [u8; 16], bytes: &[u8]) -> usize {
+    scratch[..bytes.len()].copy_from_slice(bytes);
+    bytes.len()
+}
+
+/// RFC 4122 byte order to the in-memory `GUID` layout (little-endian
+/// `Data1`/`Data2`/`Data3`, `Data4` unchanged).
+fn guid_bytes(uuid: &[u8; 16]) -> [u8; 16] {
+    let mut out = *uuid;
+    out[..4].reverse();
+    out[4..6].reverse();
+    out[6..8].reverse();
+    out
+}
+
+/// Checks that a SQL Server client library exporting the BCP entry points
+/// can be loaded on this host (`sqlncli11`/`msodbcsql*.dll` on Windows,
+/// `libmsodbcsql-*.so` on Linux).
 pub fn probe_native_bcp_support() -> Result<()> {
     let mut load_errors: Vec<String> = Vec::new();
 
-    for candidate in CANDIDATE_LIBRARIES {
-        match probe_library(candidate) {
+    for candidate in candidate_libraries() {
+        match probe_library(&candidate) {
             Ok(()) => return Ok(()),
             Err(err) => load_errors.push(format!("{candidate}: {err}")),
         }
     }
 
     Err(OdbcError::UnsupportedFeature(format!(
-        "Unable to load SQL Server BCP libraries from PATH. Tried: {}",
+        "Unable to load SQL Server BCP libraries. Tried: {}",
         load_errors.join(" | ")
     )))
 }
 
+#[cfg(windows)]
+fn candidate_libraries() -> Vec<String> {
+    CANDIDATE_LIBRARIES.iter().map(|s| s.to_string()).collect()
+}
+
+#[cfg(target_os = "linux")]
+fn candidate_libraries() -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    if let Ok(explicit) = std::env::var(MSODBCSQL_PATH_ENV) {
+        let explicit = explicit.trim();
+        if !explicit.is_empty() {
+            candidates.push(explicit.to_string());
+        }
+    }
+    for dir in LINUX_DRIVER_DIRS {
+        candidates.extend(msodbcsql_libraries_in(std::path::Path::new(dir)));
+    }
+    candidates.extend(CANDIDATE_LIBRARIES.iter().map(|s| s.to_string()));
+    candidates.dedup();
+    candidates
+}
+
+/// Lists `libmsodbcsql-*.so*` files in `dir`, newest version first.
+#[cfg(target_os = "linux")]
+fn msodbcsql_libraries_in(dir: &std::path::Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(Vec<u32>, String)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version = name.strip_prefix("libmsodbcsql-")?;
+            if !version.contains(".so") {
+                return None;
+            }
+            let key = version
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|part| !part.is_empty())
+                .filter_map(|part| part.parse::<u32>().ok())
+                .collect::<Vec<_>>();
+            Some((key, entry.path().to_string_lossy().to_string()))
+        })
+        .collect();
+    found.sort_by(|a, b| b.cmp(a));
+    found.into_iter().map(|(_, path)| path).collect()
+}
+
 pub fn execute_native_bcp(
     conn_str: &str,
     payload: &BulkInsertPayload,
@@ -259,11 +428,12 @@ pub fn execute_native_bcp(
         return Err(err);
     }
 
+    let bcp_hdbc = driver_connection_handle(dbc_handle);
     let table_wide = to_wide_nul(payload.table.as_str());
     let init_rc = unsafe {
         // SAFETY: BCP handle connected and BCP-enabled. Table pointer is valid NUL-terminated UTF-16.
         bcp_init_w(
-            dbc_handle,
+            bcp_hdbc,
             table_wide.as_ptr(),
             std::ptr::null(),
             std::ptr::null(),
@@ -282,7 +452,7 @@ pub fn execute_native_bcp(
         let bind_rc = unsafe {
             // SAFETY: Pointers target stable per-column memory kept alive for all rows.
             bcp_bind(
-                dbc_handle,
+                bcp_hdbc,
                 p_data,
                 0,
                 cb_data,
@@ -308,7 +478,7 @@ pub fn execute_native_bcp(
             let collen_rc = unsafe {
                 // SAFETY: Column index is 1-based and valid for the established binding layout.
                 // bcp_collen must be called for every row to set the correct length (or SQL_NULL_DATA).
-                bcp_collen(dbc_handle, collen, (idx + 1) as i32)
+                bcp_collen(bcp_hdbc, collen, (idx + 1) as i32)
             };
             if collen_rc == 0 {
                 disconnect_and_free_silent(dbc_handle, dbc, env);
@@ -321,7 +491,7 @@ pub fn execute_native_bcp(
         }
         let send_rc = unsafe {
             // SAFETY: `bcp_bind` already bound row memory and we only update bound storage.
-            bcp_sendrow(dbc_handle)
+            bcp_sendrow(bcp_hdbc)
         };
         if send_rc == 0 {
             disconnect_and_free_silent(dbc_handle, dbc, env);
@@ -334,7 +504,7 @@ pub fn execute_native_bcp(
 
     let done_rows = unsafe {
         // SAFETY: Finalizes BCP session on a valid connected BCP handle.
-        bcp_done(dbc_handle)
+        bcp_done(bcp_hdbc)
     };
     if done_rows < 0 {
         disconnect_and_free_silent(dbc_handle, dbc, env);
@@ -347,12 +517,42 @@ pub fn execute_native_bcp(
     Ok(done_rows as usize)
 }
 
+/// Returns the handle the BCP entry points expect for `dbc_handle`.
+#[cfg(windows)]
+fn driver_connection_handle(dbc_handle: HDbc) -> HDbc {
+    dbc_handle
+}
+
+/// Returns the handle the BCP entry points expect for `dbc_handle`.
+///
+/// Falls back to the driver manager handle when the manager does not
+/// answer `SQL_DRIVER_HDBC` (e.g. when the driver is linked directly).
+#[cfg(target_os = "linux")]
+fn driver_connection_handle(dbc_handle: HDbc) -> HDbc {
+    let mut driver_hdbc: *mut c_void = std::ptr::null_mut();
+    let rc = unsafe {
+        // SAFETY: SQL_DRIVER_HDBC writes one pointer-sized handle into the output buffer.
+        sql_get_info_raw(
+            dbc_handle,
+            SQL_DRIVER_HDBC,
+            (&mut driver_hdbc as *mut *mut c_void).cast::<c_void>(),
+            std::mem::size_of::<*mut c_void>() as SmallInt,
+            std::ptr::null_mut(),
+        )
+    };
+    if (rc == SqlReturn::SUCCESS || rc == SqlReturn::SUCCESS_WITH_INFO) && !driver_hdbc.is_null() {
+        HDbc(driver_hdbc.cast())
+    } else {
+        dbc_handle
+    }
+}
+
 fn load_bcp_library() -> Result<Library> {
     let mut errors: Vec<String> = Vec::new();
-    for candidate in CANDIDATE_LIBRARIES {
+    for candidate in candidate_libraries() {
         let try_load = unsafe {
             // SAFETY: Dynamic loading for optional SQL Server BCP runtime.
-            Library::new(candidate)
+            Library::new(&candidate)
         };
         match try_load {
             Ok(lib) => return Ok(lib),
@@ -378,47 +578,55 @@ fn build_bound_columns<'a>(payload: &'a BulkInsertPayload) -> Result<Vec<BoundCo
         .columns
         .iter()
         .zip(payload.column_data.iter())
-        .map(|(spec, data)| match (&spec.col_type, data) {
-            (
-                BulkColumnType::I32,
-                BulkColumnData::I32 {
-                    values,
-                    null_bitmap,
-                },
-            ) => Ok(BoundColumnRef::I32 {
-                values: values.as_slice(),
-                null_bitmap: validate_null_bitmap(
-                    null_bitmap.as_deref(),
-                    values.len(),
-                    spec.name.as_str(),
-                )?,
-                cell: std::mem::MaybeUninit::uninit(),
-            }),
-            (
-                BulkColumnType::I64,
-                BulkColumnData::I64 {
-                    values,
-                    null_bitmap,
-                },
-            ) => Ok(BoundColumnRef::I64 {
-                values: values.as_slice(),
-                null_bitmap: validate_null_bitmap(
-                    null_bitmap.as_deref(),
-                    values.len(),
-                    spec.name.as_str(),
-                )?,
-                cell: std::mem::MaybeUninit::uninit(),
-            }),
-            (BulkColumnType::I32 | BulkColumnType::I64, _) => {
-                Err(OdbcError::UnsupportedFeature(format!(
-                    "Native BCP currently requires matching payload type for '{}'",
-                    spec.name
-                )))
-            }
-            _ => Err(OdbcError::UnsupportedFeature(format!(
-                "Native BCP currently supports only I32/I64 columns; '{}' uses {:?}",
-                spec.name, spec.col_type
-            ))),
+        .map(|(spec, data)| {
+            let source = match (&spec.col_type, data) {
+                (BulkColumnType::I32, BulkColumnData::I32 { values, .. }) => {
+                    BoundSource::I32(values)
+                }
+                (BulkColumnType::I64, BulkColumnData::I64 { values, .. }) => {
+                    BoundSource::I64(values)
+                }
+                (BulkColumnType::Bool, BulkColumnData::Bool { values, .. }) => {
+                    BoundSource::Bool(values)
+                }
+                (BulkColumnType::F64, BulkColumnData::F64 { values, .. }) => {
+                    BoundSource::F64(values)
+                }
+                (BulkColumnType::F32, BulkColumnData::F32 { values, .. }) => {
+                    BoundSource::F32(values)
+                }
+                (BulkColumnType::Date, BulkColumnData::Date { values, .. }) => {
+                    BoundSource::Date(values)
+                }
+                (BulkColumnType::Time, BulkColumnData::Time { values, .. }) => {
+                    BoundSource::Time(values)
+                }
+                (BulkColumnType::Uuid, BulkColumnData::Uuid { values, .. }) => {
+                    BoundSource::Uuid(values)
+                }
+                (BulkColumnType::NText, BulkColumnData::NText { rows, max_len, .. }) => {
+                    BoundSource::NText {
+                        rows,
+                        max_len: (*max_len).max(1),
+                    }
+                }
+                (BulkColumnType::Text | BulkColumnType::Decimal | BulkColumnType::Binary, _)
+                | (BulkColumnType::Timestamp, _) => {
+                    return Err(OdbcError::UnsupportedFeature(format!(
+                        "Native BCP does not support {:?} columns yet ('{}')",
+                        spec.col_type, spec.name
+                    )))
+                }
+                _ => {
+                    return Err(OdbcError::UnsupportedFeature(format!(
+                        "Native BCP currently requires matching payload type for '{}'",
+                        spec.name
+                    )))
+                }
+            };
+            let null_bitmap =
+                validate_null_bitmap(data.null_bitmap(), data.len(), spec.name.as_str())?;
+            Ok(BoundColumnRef::new(source, null_bitmap))
         })
         .collect()
 }
@@ -538,14 +746,58 @@ fn disconnect_and_free_silent(dbc_handle: HDbc, dbc: Handle, env: Handle) {
 
 #[cfg(test)]
 mod tests {
-    use super::{build_bound_columns, to_wide_nul, trim_symbol_name};
-    use crate::protocol::{BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload};
+    use super::{
+        build_bound_columns, guid_bytes, probe_library, to_wide_nul, trim_symbol_name, SQLBIT,
+        SQLDATEN, SQLFLT8, SQLNCHAR, SQLTIMEN, SQLUNIQUEID,
+    };
+    use crate::error::OdbcError;
+    use crate::protocol::{
+        BulkColumnData, BulkColumnSpec, BulkColumnType, BulkDate, BulkInsertPayload, BulkTime,
+    };
 
     #[test]
     fn test_trim_symbol_name() {
         assert_eq!(trim_symbol_name(b"bcp_initW\0"), "bcp_initW");
     }
 
+    #[test]
+    fn test_probe_library_reports_missing_library_as_unsupported() {
+        let err = probe_library("definitely_missing_bcp_runtime_lib").unwrap_err();
+        assert!(matches!(err, OdbcError::UnsupportedFeature(_)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_msodbcsql_libraries_in_orders_newest_first() {
+        let dir = std::env::temp_dir().join(format!("odbc_bcp_probe_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "libmsodbcsql-18.3.so.2.1",
+            "libmsodbcsql-18.10.so.1.1",
+            "libmsodbcsql-17.10.so.6.1",
+            "libodbcinst.so",
+            "libmsodbcsql-18.rll",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let found = super::msodbcsql_libraries_in(&dir);
+        let names: Vec<_> = found
+            .iter()
+            .map(|p| p.rsplit('/').next().unwrap().to_string())
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            names,
+            vec![
+                "libmsodbcsql-18.10.so.1.1",
+                "libmsodbcsql-18.3.so.2.1",
+                "libmsodbcsql-17.10.so.6.1",
+            ]
+        );
+    }
+
     #[test]
     fn test_to_wide_nul_appends_terminator() {
         let wide = to_wide_nul("abc");
@@ -589,6 +841,84 @@ mod tests {
         assert_eq!(cols[1].len(), 3);
     }
 
+    #[test]
+    fn test_build_bound_columns_binds_extended_types() {
+        let spec = |name: &str, col_type| BulkColumnSpec {
+            name: name.to_string(),
+            col_type,
+            nullable: false,
+            max_len: 3,
+        };
+        let payload = BulkInsertPayload {
+            table: "dbo.t".to_string(),
+            columns: vec![
+                spec("flag", BulkColumnType::Bool),
+                spec("ratio", BulkColumnType::F64),
+                spec("day", BulkColumnType::Date),
+                spec("at", BulkColumnType::Time),
+                spec("id", BulkColumnType::Uuid),
+                spec("label", BulkColumnType::NText),
+            ],
+            row_count: 1,
+            column_data: vec![
+                BulkColumnData::Bool {
+                    values: vec![true],
+                    null_bitmap: None,
+                },
+                BulkColumnData::F64 {
+                    values: vec![0.5],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Date {
+                    values: vec![BulkDate {
+                        year: 2024,
+                        month: 1,
+                        day: 2,
+                    }],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Time {
+                    values: vec![BulkTime {
+                        hour: 1,
+                        minute: 2,
+                        second: 3,
+                    }],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Uuid {
+                    values: vec![[0u8; 16]],
+                    null_bitmap: None,
+                },
+                BulkColumnData::NText {
+                    rows: vec!["héllo".encode_utf16().collect()],
+                    max_len: 3,
+                    null_bitmap: None,
+                },
+            ],
+        };
+
+        let mut cols = build_bound_columns(&payload).expect("columns should be accepted");
+        let types: Vec<i32> = cols.iter_mut().map(|c| c.bind_args_mut().2).collect();
+        assert_eq!(
+            types,
+            vec![SQLBIT, SQLFLT8, SQLDATEN, SQLTIMEN, SQLUNIQUEID, SQLNCHAR]
+        );
+        // NText is truncated to max_len code units; fixed types report their width.
+        assert_eq!(cols[5].row_collen_for_bcp(0), 6);
+        assert_eq!(cols[3].row_collen_for_bcp(0), 12);
+        cols[0].write_row(0);
+        assert_eq!(cols[0].cell_bytes_mut()[0], 1);
+    }
+
+    #[test]
+    fn test_guid_bytes_swaps_leading_fields() {
+        let uuid = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        assert_eq!(
+            guid_bytes(&uuid),
+            [3, 2, 1, 0, 5, 4, 7, 6, 8, 9, 10, 11, 12, 13, 14, 15]
+        );
+    }
+
     #[test]
     fn test_build_bound_columns_rejects_invalid_null_bitmap_size() {
         let payload = BulkInsertPayload {
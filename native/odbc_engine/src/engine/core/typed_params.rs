@@ -0,0 +1,547 @@
+//! Native ODBC binding for [`ParamValue`] inputs.
+//!
+//! Typed variants (`Boolean`, `Double`, `Date`, `Time`, `Timestamp`, `Uuid`) are bound with their
+//! matching C type (`SQL_C_BIT`, `SQL_C_DOUBLE`, `SQL_C_TYPE_DATE`, ...) so the driver never has
+//! to parse a literal. The original variants keep their text binding, which is what callers
+//! relied on before the typed tags existed.
+//!
+//! The plain ODBC time struct has no fraction and the timestamp struct no UTC offset. Those values
+//! depend on the DBMS ([`TemporalBinding`], kept per connection in
+//! [`ResultOptions::temporal`](super::ResultOptions::temporal)): SQL Server takes its
+//! `SQL_SS_TIME2` and `SQL_SS_TIMESTAMPOFFSET` structs; elsewhere fractional times go as
+//! `SQL_TYPE_TIME` with fractional digits and offsets are rejected rather than sent as text the
+//! driver may truncate.
+
+use super::driver_capabilities::{DriverCapabilities, ENGINE_SQLSERVER};
+use crate::error::{OdbcError, Result};
+use crate::protocol::param_value::{ParamTime, ParamTimestamp, ParamValue};
+use crate::protocol::param_values_to_strings;
+
+use odbc_api::handles::{CData, HasDataType};
+use odbc_api::parameter::{CElement, InputParameter, WithDataType};
+use odbc_api::sys::{CDataType, Date, Guid, SqlDataType, Time, Timestamp};
+use odbc_api::{Bit, DataType};
+use std::ffi::c_void;
+use std::num::NonZeroUsize;
+use std::ptr::null;
+
+#[cfg(windows)]
+type TextBox = odbc_api::parameter::VarWCharBox;
+#[cfg(not(windows))]
+type TextBox = odbc_api::parameter::VarCharBox;
+
+/// `SQL_GUID` parameter (`SQL_C_GUID` value buffer).
+struct GuidParam(Guid);
+
+impl GuidParam {
+    fn from_rfc4122(bytes: &[u8; 16]) -> Self {
+        let mut d4 = [0u8; 8];
+        d4.copy_from_slice(&bytes[8..]);
+        Self(Guid {
+            d1: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            d2: u16::from_be_bytes([bytes[4], bytes[5]]),
+            d3: u16::from_be_bytes([bytes[6], bytes[7]]),
+            d4,
+        })
+    }
+}
+
+// SAFETY: `Guid` is a fixed-size `repr(C)` struct matching `SQLGUID`; no indicator is needed for
+// a non-null fixed-size value and the pointer stays valid for as long as `self` is borrowed.
+unsafe impl CData for GuidParam {
+    fn cdata_type(&self) -> CDataType {
+        CDataType::Guid
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        null()
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        &self.0 as *const Guid as *const c_void
+    }
+
+    fn buffer_length(&self) -> isize {
+        0
+    }
+}
+
+impl HasDataType for GuidParam {
+    fn data_type(&self) -> DataType {
+        DataType::Other {
+            data_type: SqlDataType::EXT_GUID,
+            column_size: NonZeroUsize::new(36),
+            decimal_digits: 0,
+        }
+    }
+}
+
+// SAFETY: fixed-size values are always complete.
+unsafe impl CElement for GuidParam {
+    fn assert_completness(&self) {}
+}
+
+/// `SQL_SS_TIME2` (SQL Server `time`).
+const SQL_SS_TIME2: i16 = -154;
+/// `SQL_SS_TIMESTAMPOFFSET` (SQL Server `datetimeoffset`).
+const SQL_SS_TIMESTAMPOFFSET: i16 = -155;
+/// Widest fraction SQL Server stores (100 ns).
+const SS_MAX_PRECISION: i16 = 7;
+
+/// `SQL_SS_TIME2_STRUCT`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SsTime2 {
+    hour: u16,
+    minute: u16,
+    second: u16,
+    fraction: u32,
+}
+
+/// `SQL_SS_TIMESTAMPOFFSET_STRUCT`: local date and time plus the offset, both offset parts
+/// carrying its sign.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SsTimestampOffset {
+    year: i16,
+    month: u16,
+    day: u16,
+    hour: u16,
+    minute: u16,
+    second: u16,
+    fraction: u32,
+    timezone_hour: i16,
+    timezone_minute: i16,
+}
+
+/// Fixed-size driver-specific struct bound with an explicit C and SQL type.
+struct StructParam<T> {
+    value: T,
+    c_type: CDataType,
+    data_type: DataType,
+}
+
+// SAFETY: `T` is one of the `repr(C)` structs above, matching the layout the driver expects for
+// `c_type`; fixed-size values need no indicator and the pointer is valid while `self` is borrowed.
+unsafe impl<T> CData for StructParam<T> {
+    fn cdata_type(&self) -> CDataType {
+        self.c_type
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        null()
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        &self.value as *const T as *const c_void
+    }
+
+    fn buffer_length(&self) -> isize {
+        std::mem::size_of::<T>() as isize
+    }
+}
+
+impl<T> HasDataType for StructParam<T> {
+    fn data_type(&self) -> DataType {
+        self.data_type
+    }
+}
+
+// SAFETY: fixed-size values are always complete.
+unsafe impl<T> CElement for StructParam<T> {
+    fn assert_completness(&self) {}
+}
+
+/// How time fractions and UTC offsets, which the standard ODBC structs cannot carry, are bound.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TemporalBinding {
+    /// `SQL_TYPE_TIME` with fractional digits; UTC offsets are unsupported.
+    #[default]
+    Standard,
+    /// SQL Server `SQL_SS_TIME2` / `SQL_SS_TIMESTAMPOFFSET`.
+    SqlServer,
+}
+
+impl TemporalBinding {
+    /// Binding for the driver described by `capabilities`.
+    pub fn for_driver(capabilities: &DriverCapabilities) -> Self {
+        match capabilities.engine.as_str() {
+            ENGINE_SQLSERVER => Self::SqlServer,
+            _ => Self::Standard,
+        }
+    }
+}
+
+/// Fractional-second digits needed to carry `nanos` without loss (0, 7 or 9).
+fn fraction_precision(nanos: u32) -> i16 {
+    if nanos == 0 {
+        0
+    } else if nanos.is_multiple_of(100) {
+        7
+    } else {
+        9
+    }
+}
+
+fn sys_time(t: &ParamTime) -> Time {
+    Time {
+        hour: t.hour.into(),
+        minute: t.minute.into(),
+        second: t.second.into(),
+    }
+}
+
+fn sys_timestamp(ts: &ParamTimestamp) -> Timestamp {
+    Timestamp {
+        year: ts.date.year,
+        month: ts.date.month.into(),
+        day: ts.date.day.into(),
+        hour: ts.time.hour.into(),
+        minute: ts.time.minute.into(),
+        second: ts.time.second.into(),
+        fraction: ts.time.nanos,
+    }
+}
+
+fn text_box_from_string(s: String) -> TextBox {
+    #[cfg(windows)]
+    {
+        TextBox::from_str_slice(&s)
+    }
+    #[cfg(not(windows))]
+    {
+        TextBox::from_string(s)
+    }
+}
+
+fn text_parameter(v: &ParamValue) -> Result<Box<dyn InputParameter>> {
+    let text = param_values_to_strings(std::slice::from_ref(v))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| OdbcError::ValidationError("param_values_to_strings empty".to_string()))?;
+    Ok(Box::new(match text {
+        None => TextBox::null(),
+        Some(s) => text_box_from_string(s),
+    }))
+}
+
+/// True when `v` is bound with a native (non-text) C type by [`input_parameter`].
+pub(crate) fn binds_natively(v: &ParamValue) -> bool {
+    matches!(
+        v,
+        ParamValue::Boolean(_)
+            | ParamValue::Double(_)
+            | ParamValue::Date(_)
+            | ParamValue::Time(_)
+            | ParamValue::Timestamp(_)
+            | ParamValue::Uuid(_)
+    )
+}
+
+fn time_parameter(t: &ParamTime, temporal: TemporalBinding) -> Box<dyn InputParameter> {
+    let precision = fraction_precision(t.nanos);
+    match temporal {
+        _ if t.nanos == 0 => Box::new(WithDataType::new(
+            sys_time(t),
+            DataType::Time { precision: 0 },
+        )),
+        TemporalBinding::SqlServer => {
+            let precision = precision.min(SS_MAX_PRECISION);
+            Box::new(StructParam {
+                value: SsTime2 {
+                    hour: t.hour.into(),
+                    minute: t.minute.into(),
+                    second: t.second.into(),
+                    fraction: t.nanos,
+                },
+                c_type: CDataType::SsTime2,
+                data_type: DataType::Other {
+                    data_type: SqlDataType(SQL_SS_TIME2),
+                    // `hh:mm:ss[.fffffff]`
+                    column_size: NonZeroUsize::new(9 + precision as usize),
+                    decimal_digits: precision,
+                },
+            })
+        }
+        // `SQL_TIME_STRUCT` has no fraction field; the timestamp struct converts to
+        // `SQL_TYPE_TIME`, its date part ignored.
+        TemporalBinding::Standard => Box::new(WithDataType::new(
+            Timestamp {
+                year: 1970,
+                month: 1,
+                day: 1,
+                hour: t.hour.into(),
+                minute: t.minute.into(),
+                second: t.second.into(),
+                fraction: t.nanos,
+            },
+            DataType::Time { precision },
+        )),
+    }
+}
+
+fn timestamp_parameter(
+    ts: &ParamTimestamp,
+    temporal: TemporalBinding,
+) -> Result<Box<dyn InputParameter>> {
+    let precision = fraction_precision(ts.time.nanos);
+    let Some(offset) = ts.offset_minutes else {
+        return Ok(Box::new(WithDataType::new(
+            sys_timestamp(ts),
+            DataType::Timestamp { precision },
+        )));
+    };
+    if temporal != TemporalBinding::SqlServer {
+        return Err(OdbcError::UnsupportedFeature(
+            "Timestamp parameters with a UTC offset bind natively on SQL Server only; \
+             convert the value to UTC or pass it as a string"
+                .to_string(),
+        ));
+    }
+    let precision = precision.min(SS_MAX_PRECISION);
+    let local = sys_timestamp(ts);
+    Ok(Box::new(StructParam {
+        value: SsTimestampOffset {
+            year: local.year,
+            month: local.month,
+            day: local.day,
+            hour: local.hour,
+            minute: local.minute,
+            second: local.second,
+            fraction: local.fraction,
+            timezone_hour: offset / 60,
+            timezone_minute: offset % 60,
+        },
+        c_type: CDataType::SsTimestampOffset,
+        data_type: DataType::Other {
+            data_type: SqlDataType(SQL_SS_TIMESTAMPOFFSET),
+            // `yyyy-mm-dd hh:mm:ss[.fffffff] +hh:mm`
+            column_size: NonZeroUsize::new(if precision == 0 {
+                26
+            } else {
+                27 + precision as usize
+            }),
+            decimal_digits: precision,
+        },
+    }))
+}
+
+/// Input parameter for one `?` placeholder.
+pub(crate) fn input_parameter(
+    v: &ParamValue,
+    temporal: TemporalBinding,
+) -> Result<Box<dyn InputParameter>> {
+    let param: Box<dyn InputParameter> = match v {
+        ParamValue::RefCursorOut => {
+            return Err(OdbcError::ValidationError(
+                "ParamValue::RefCursorOut is only valid as an output parameter".to_string(),
+            ));
+        }
+        _ if !binds_natively(v) => return text_parameter(v),
+        ParamValue::Boolean(b) => Box::new(Bit::from_bool(*b)),
+        ParamValue::Double(d) => Box::new(*d),
+        ParamValue::Date(d) => Box::new(Date {
+            year: d.year,
+            month: d.month.into(),
+            day: d.day.into(),
+        }),
+        ParamValue::Time(t) => time_parameter(t, temporal),
+        ParamValue::Timestamp(ts) => timestamp_parameter(ts, temporal)?,
+        ParamValue::Uuid(u) => Box::new(GuidParam::from_rfc4122(u)),
+        _ => return text_parameter(v),
+    };
+    Ok(param)
+}
+
+/// Input parameters for a whole `?` list, in order, with temporals bound per `temporal`.
+pub(crate) fn input_parameters(
+    params: &[ParamValue],
+    temporal: TemporalBinding,
+) -> Result<Vec<Box<dyn InputParameter>>> {
+    params
+        .iter()
+        .map(|v| input_parameter(v, temporal))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::param_value::ParamDate;
+
+    fn ts(nanos: u32, offset_minutes: Option<i16>) -> ParamValue {
+        ParamValue::Timestamp(ParamTimestamp {
+            date: ParamDate {
+                year: 2024,
+                month: 2,
+                day: 29,
+            },
+            time: ParamTime {
+                hour: 13,
+                minute: 45,
+                second: 30,
+                nanos,
+            },
+            offset_minutes,
+        })
+    }
+
+    #[test]
+    fn test_boolean_binds_as_bit() {
+        let p = input_parameter(&ParamValue::Boolean(true), TemporalBinding::Standard).unwrap();
+        assert_eq!(p.cdata_type(), CDataType::Bit);
+        assert_eq!(p.data_type(), DataType::Bit);
+    }
+
+    #[test]
+    fn test_double_binds_as_double() {
+        let p = input_parameter(&ParamValue::Double(1.5), TemporalBinding::Standard).unwrap();
+        assert_eq!(p.cdata_type(), CDataType::Double);
+        assert_eq!(p.data_type(), DataType::Double);
+    }
+
+    #[test]
+    fn test_date_binds_as_type_date() {
+        let p = input_parameter(
+            &ParamValue::Date(ParamDate {
+                year: 2024,
+                month: 1,
+                day: 31,
+            }),
+            TemporalBinding::Standard,
+        )
+        .unwrap();
+        assert_eq!(p.cdata_type(), CDataType::TypeDate);
+        assert_eq!(p.data_type(), DataType::Date);
+    }
+
+    #[test]
+    fn test_timestamp_precision_follows_fraction() {
+        let whole = input_parameter(&ts(0, None), TemporalBinding::Standard).unwrap();
+        assert_eq!(whole.cdata_type(), CDataType::TypeTimestamp);
+        assert_eq!(whole.data_type(), DataType::Timestamp { precision: 0 });
+        let hundred_ns =
+            input_parameter(&ts(123_456_700, None), TemporalBinding::Standard).unwrap();
+        assert_eq!(hundred_ns.data_type(), DataType::Timestamp { precision: 7 });
+        let ns = input_parameter(&ts(123_456_789, None), TemporalBinding::Standard).unwrap();
+        assert_eq!(ns.data_type(), DataType::Timestamp { precision: 9 });
+    }
+
+    #[test]
+    fn test_timestamp_with_offset_binds_as_ss_timestampoffset() {
+        let v = ts(123_456_700, Some(-210));
+        assert!(binds_natively(&v));
+        let p = input_parameter(&v, TemporalBinding::SqlServer).unwrap();
+        assert_eq!(p.cdata_type(), CDataType::SsTimestampOffset);
+        assert_eq!(
+            p.data_type(),
+            DataType::Other {
+                data_type: SqlDataType(SQL_SS_TIMESTAMPOFFSET),
+                column_size: NonZeroUsize::new(34),
+                decimal_digits: 7,
+            }
+        );
+        assert_eq!(
+            p.buffer_length() as usize,
+            std::mem::size_of::<SsTimestampOffset>()
+        );
+        // SAFETY: the parameter was built from an `SsTimestampOffset`.
+        let value = unsafe { &*(p.value_ptr() as *const SsTimestampOffset) };
+        assert_eq!(
+            (value.year, value.hour, value.fraction),
+            (2024, 13, 123_456_700)
+        );
+        assert_eq!((value.timezone_hour, value.timezone_minute), (-3, -30));
+    }
+
+    #[test]
+    fn test_timestamp_with_offset_is_unsupported_off_sql_server() {
+        assert!(matches!(
+            input_parameter(&ts(0, Some(60)), TemporalBinding::Standard),
+            Err(OdbcError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_time_with_fraction_binds_per_dbms() {
+        let time = |nanos| {
+            ParamValue::Time(ParamTime {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                nanos,
+            })
+        };
+        assert_eq!(
+            input_parameter(&time(0), TemporalBinding::SqlServer)
+                .unwrap()
+                .cdata_type(),
+            CDataType::TypeTime
+        );
+        assert!(binds_natively(&time(500_000_000)));
+
+        let ss = input_parameter(&time(500_000_000), TemporalBinding::SqlServer).unwrap();
+        assert_eq!(ss.cdata_type(), CDataType::SsTime2);
+        assert_eq!(
+            ss.data_type(),
+            DataType::Other {
+                data_type: SqlDataType(SQL_SS_TIME2),
+                column_size: NonZeroUsize::new(16),
+                decimal_digits: 7,
+            }
+        );
+        assert_eq!(ss.buffer_length(), 12);
+
+        let std = input_parameter(&time(123_456_789), TemporalBinding::Standard).unwrap();
+        assert_eq!(std.cdata_type(), CDataType::TypeTimestamp);
+        assert_eq!(std.data_type(), DataType::Time { precision: 9 });
+    }
+
+    #[test]
+    fn test_temporal_binding_follows_driver() {
+        let binding =
+            |name| TemporalBinding::for_driver(&DriverCapabilities::from_driver_name(name));
+        assert_eq!(binding("Microsoft SQL Server"), TemporalBinding::SqlServer);
+        assert_eq!(binding("PostgreSQL"), TemporalBinding::Standard);
+    }
+
+    #[test]
+    fn test_uuid_binds_as_guid_in_rfc4122_order() {
+        let bytes = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 1, 2, 3, 4, 5, 6, 7, 8,
+        ];
+        let g = GuidParam::from_rfc4122(&bytes);
+        assert_eq!(g.0.d1, 0x1234_5678);
+        assert_eq!(g.0.d2, 0x9abc);
+        assert_eq!(g.0.d3, 0xdef0);
+        assert_eq!(g.0.d4, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let p = input_parameter(&ParamValue::Uuid(bytes), TemporalBinding::Standard).unwrap();
+        assert_eq!(p.cdata_type(), CDataType::Guid);
+        assert_eq!(p.data_type().data_type(), SqlDataType::EXT_GUID);
+    }
+
+    #[test]
+    fn test_legacy_variants_keep_text_binding() {
+        for v in [
+            ParamValue::Integer(1),
+            ParamValue::BigInt(2),
+            ParamValue::String("x".to_string()),
+            ParamValue::Decimal("1.5".to_string()),
+            ParamValue::Null,
+            ParamValue::Json("{}".to_string()),
+        ] {
+            assert!(!binds_natively(&v), "{v:?}");
+            assert_eq!(
+                input_parameter(&v, TemporalBinding::Standard)
+                    .unwrap()
+                    .cdata_type(),
+                TextBox::null().cdata_type()
+            );
+        }
+    }
+
+    #[test]
+    fn test_ref_cursor_out_is_rejected() {
+        assert!(input_parameter(&ParamValue::RefCursorOut, TemporalBinding::Standard).is_err());
+    }
+}
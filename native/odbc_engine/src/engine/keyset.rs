@@ -0,0 +1,440 @@
+//! Keyset pagination for resumable streams.
+//!
+//! A keyset stream walks a `SELECT` in the order of a caller-declared key
+//! (one or more non-null columns that are unique together), one page per
+//! query, each page starting strictly after the last key tuple of the one
+//! before. The page SQL comes from the engine's
+//! [`KeysetPaginator`](crate::plugins::capabilities::KeysetPaginator), so
+//! quoting and `TOP` / `LIMIT` / `FETCH FIRST` follow the dialect.
+//!
+//! After every batch the stream records a [`KeysetToken`] holding that
+//! batch's last key tuple. The token does not reference the connection:
+//! a stream started from it on any connection resumes right after the last
+//! row its predecessor delivered. It is bound to the query text and key list
+//! it was issued for and rejected for any other.
+
+use crate::engine::identifier::validate_identifier;
+use crate::error::{OdbcError, Result};
+use crate::protocol::native_cell::{self, CellEncoding, NativeValue};
+use crate::protocol::param_value::try_serialize_params;
+use crate::protocol::{
+    deserialize_params, OdbcType, ParamDate, ParamTime, ParamTimestamp, ParamValue, RowBuffer,
+};
+use odbc_api::DataType;
+
+const TOKEN_MAGIC: &[u8; 4] = b"KST1";
+
+/// Key columns, page size and optional resume point of a keyset stream.
+#[derive(Debug, Clone)]
+pub struct KeysetPagination {
+    keys: Vec<String>,
+    page_rows: u32,
+    resume_from: Option<KeysetToken>,
+}
+
+impl KeysetPagination {
+    pub const DEFAULT_PAGE_ROWS: u32 = 10_000;
+
+    /// Orders by `keys`, most significant first.
+    pub fn new(keys: &[&str]) -> Result<Self> {
+        if keys.is_empty() {
+            return Err(OdbcError::ValidationError(
+                "keyset stream needs at least one key column".to_string(),
+            ));
+        }
+        for (i, key) in keys.iter().enumerate() {
+            validate_identifier(key)?;
+            if keys[..i].iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                return Err(OdbcError::ValidationError(format!(
+                    "duplicate key column {key:?}"
+                )));
+            }
+        }
+        Ok(Self {
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            page_rows: Self::DEFAULT_PAGE_ROWS,
+            resume_from: None,
+        })
+    }
+
+    /// Rows per page query; `0` keeps the default.
+    pub fn with_page_rows(mut self, page_rows: u32) -> Self {
+        if page_rows > 0 {
+            self.page_rows = page_rows;
+        }
+        self
+    }
+
+    /// Starts after the key tuple in `token` instead of at the first row.
+    pub fn resume_from(mut self, token: KeysetToken) -> Self {
+        self.resume_from = Some(token);
+        self
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    pub fn page_rows(&self) -> u32 {
+        self.page_rows
+    }
+
+    /// Key tuple the first page starts after, checking that the resume
+    /// token was issued for `sql` and these keys.
+    pub(crate) fn start_after(&self, sql: &str) -> Result<Option<Vec<ParamValue>>> {
+        let Some(token) = &self.resume_from else {
+            return Ok(None);
+        };
+        if token.fingerprint != fingerprint(sql, &self.keys)
+            || token.values.len() != self.keys.len()
+        {
+            return Err(OdbcError::ValidationError(
+                "continuation token was issued for a different query or key list".to_string(),
+            ));
+        }
+        Ok(Some(token.values.clone()))
+    }
+
+    pub(crate) fn token_for(&self, sql: &str, values: Vec<ParamValue>) -> KeysetToken {
+        KeysetToken {
+            fingerprint: fingerprint(sql, &self.keys),
+            values,
+        }
+    }
+}
+
+/// Opaque resume point of a keyset stream: the last delivered key tuple.
+///
+/// The text form is lowercase hex; callers should store and pass it back
+/// verbatim.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetToken {
+    fingerprint: u64,
+    values: Vec<ParamValue>,
+}
+
+impl KeysetToken {
+    pub fn values(&self) -> &[ParamValue] {
+        &self.values
+    }
+
+    pub fn encode(&self) -> Result<String> {
+        let mut bytes = TOKEN_MAGIC.to_vec();
+        bytes.extend_from_slice(&self.fingerprint.to_le_bytes());
+        bytes.extend(try_serialize_params(&self.values)?);
+        Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+    }
+
+    pub fn decode(text: &str) -> Result<Self> {
+        let invalid = || OdbcError::ValidationError("malformed continuation token".to_string());
+        let text = text.trim();
+        if !text.len().is_multiple_of(2) || !text.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let rest = bytes.strip_prefix(TOKEN_MAGIC).ok_or_else(invalid)?;
+        if rest.len() < 8 {
+            return Err(invalid());
+        }
+        let (fingerprint, values) = rest.split_at(8);
+        let values = deserialize_params(values).map_err(|_| invalid())?;
+        if values.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            fingerprint: u64::from_le_bytes(fingerprint.try_into().map_err(|_| invalid())?),
+            values,
+        })
+    }
+}
+
+/// FNV-1a over the query text and key list; stable across processes and
+/// builds, unlike `DefaultHasher`.
+fn fingerprint(sql: &str, keys: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    };
+    feed(sql.trim().trim_end_matches(';').trim_end().as_bytes());
+    for key in keys {
+        feed(&[0]);
+        feed(key.to_ascii_lowercase().as_bytes());
+    }
+    hash
+}
+
+/// Values for the `?` markers of the page predicate built by
+/// `KeysetPaginator::build_keyset_page_sql`: for key `i`, the first `i + 1`
+/// values of `last`.
+pub(crate) fn predicate_params(last: &[ParamValue]) -> Vec<ParamValue> {
+    (1..=last.len())
+        .flat_map(|n| last[..n].iter().cloned())
+        .collect()
+}
+
+/// Result column index of every key, matched case-insensitively.
+///
+/// Native cells hold timestamps in microseconds, so with native encoding a
+/// timestamp key declared with more than 6 fractional digits (e.g. SQL
+/// Server `datetime2(7)`) is rejected: its truncated value would make the
+/// next page start before the last row delivered. Such keys work with the
+/// text encoding, which keeps the driver's full fraction.
+pub(crate) fn key_columns(rows: &RowBuffer, keys: &[String]) -> Result<Vec<usize>> {
+    keys.iter()
+        .map(|key| {
+            let i = rows
+                .columns
+                .iter()
+                .position(|c| c.name.eq_ignore_ascii_case(key))
+                .ok_or_else(|| {
+                    OdbcError::ValidationError(format!(
+                        "key column {key:?} is not in the query result"
+                    ))
+                })?;
+            let column = &rows.columns[i];
+            if rows.cell_encoding.is_native() && column.odbc_type == OdbcType::Timestamp {
+                if let Some(DataType::Timestamp { precision }) = column.sql_type {
+                    if precision > 6 {
+                        return Err(OdbcError::UnsupportedFeature(format!(
+                            "key column {key:?} has {precision} fractional second digits; \
+                             native keyset streams keep 6, use the text encoding"
+                        )));
+                    }
+                }
+            }
+            Ok(i)
+        })
+        .collect()
+}
+
+/// Key tuple of the last row in `rows`, typed for binding.
+pub(crate) fn last_key_tuple(rows: &RowBuffer, columns: &[usize]) -> Result<Vec<ParamValue>> {
+    let row = rows
+        .rows
+        .last()
+        .ok_or_else(|| OdbcError::InternalError("no row to take a key from".to_string()))?;
+    columns
+        .iter()
+        .map(|&i| {
+            let column = &rows.columns[i];
+            let cell = row[i].as_deref().ok_or_else(|| {
+                OdbcError::ValidationError(format!("key column {:?} is NULL", column.name))
+            })?;
+            key_value(cell, column.odbc_type, rows.cell_encoding).ok_or_else(|| {
+                OdbcError::UnsupportedFeature(format!(
+                    "key column {:?} has a type keyset streams cannot bind ({:?})",
+                    column.name, column.odbc_type
+                ))
+            })
+        })
+        .collect()
+}
+
+fn key_value(cell: &[u8], odbc_type: OdbcType, encoding: CellEncoding) -> Option<ParamValue> {
+    match (odbc_type, cell.len()) {
+        (OdbcType::Integer, 4) => {
+            return Some(ParamValue::Integer(i32::from_le_bytes(
+                cell.try_into().ok()?,
+            )))
+        }
+        (OdbcType::BigInt, 8) => {
+            return Some(ParamValue::BigInt(i64::from_le_bytes(
+                cell.try_into().ok()?,
+            )))
+        }
+        (OdbcType::Binary, _) => return Some(ParamValue::Binary(cell.to_vec())),
+        _ => {}
+    }
+    if encoding.is_native() {
+        if let Some(native) = native_cell::decode(cell, odbc_type) {
+            return native_key_value(native);
+        }
+    }
+    let text = std::str::from_utf8(cell).ok()?.to_string();
+    Some(match odbc_type {
+        OdbcType::Decimal | OdbcType::Money => ParamValue::Decimal(text),
+        _ => ParamValue::String(text),
+    })
+}
+
+fn native_key_value(native: NativeValue) -> Option<ParamValue> {
+    const MICROS_PER_DAY: i64 = 86_400_000_000;
+    let date = |days: i64| {
+        let (year, month, day) = native_cell::civil_from_days(days);
+        Some(ParamDate {
+            year: i16::try_from(year).ok()?,
+            month: month as u8,
+            day: day as u8,
+        })
+    };
+    Some(match native {
+        NativeValue::Double(v) => ParamValue::Double(v),
+        NativeValue::Float(v) => ParamValue::Double(f64::from(v)),
+        NativeValue::SmallInt(v) => ParamValue::Integer(i32::from(v)),
+        NativeValue::Boolean(v) => ParamValue::Boolean(v),
+        NativeValue::Date(days) => ParamValue::Date(date(i64::from(days))?),
+        NativeValue::Timestamp(micros) => {
+            let micros_of_day = micros.rem_euclid(MICROS_PER_DAY);
+            let seconds = micros_of_day / 1_000_000;
+            ParamValue::Timestamp(ParamTimestamp {
+                date: date(micros.div_euclid(MICROS_PER_DAY))?,
+                time: ParamTime {
+                    hour: (seconds / 3600) as u8,
+                    minute: (seconds / 60 % 60) as u8,
+                    second: (seconds % 60) as u8,
+                    nanos: (micros_of_day % 1_000_000) as u32 * 1_000,
+                },
+                offset_minutes: None,
+            })
+        }
+        NativeValue::Uuid(bytes) => ParamValue::Uuid(bytes),
+        NativeValue::TimestampOffset { .. } => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(encoding: CellEncoding) -> RowBuffer {
+        let mut rows = RowBuffer::with_cell_encoding(encoding);
+        rows.add_column("Id".to_string(), OdbcType::Integer);
+        rows.add_column("created".to_string(), OdbcType::Timestamp);
+        rows.add_column("name".to_string(), OdbcType::Varchar);
+        rows
+    }
+
+    #[test]
+    fn token_round_trips_and_is_bound_to_query() {
+        let pagination = KeysetPagination::new(&["id", "name"]).unwrap();
+        let values = vec![ParamValue::BigInt(42), ParamValue::String("x".to_string())];
+        let text = pagination
+            .token_for("SELECT * FROM t", values.clone())
+            .encode()
+            .unwrap();
+        assert!(text.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let resumed = pagination
+            .clone()
+            .resume_from(KeysetToken::decode(&text).unwrap());
+        assert_eq!(
+            resumed.start_after("SELECT * FROM t;").unwrap(),
+            Some(values)
+        );
+        assert!(resumed.start_after("SELECT * FROM u").is_err());
+        let other_keys = KeysetPagination::new(&["id"])
+            .unwrap()
+            .resume_from(KeysetToken::decode(&text).unwrap());
+        assert!(other_keys.start_after("SELECT * FROM t").is_err());
+    }
+
+    #[test]
+    fn decode_rejects_malformed_tokens() {
+        for text in ["", "abc", "zz", "4b535431", "00112233445566778899"] {
+            assert!(KeysetToken::decode(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn pagination_rejects_bad_key_lists() {
+        assert!(KeysetPagination::new(&[]).is_err());
+        assert!(KeysetPagination::new(&["id", "ID"]).is_err());
+        assert!(KeysetPagination::new(&["id; --"]).is_err());
+        assert_eq!(
+            KeysetPagination::new(&["id"])
+                .unwrap()
+                .with_page_rows(0)
+                .page_rows(),
+            KeysetPagination::DEFAULT_PAGE_ROWS
+        );
+    }
+
+    #[test]
+    fn predicate_params_repeat_key_prefixes() {
+        let last = [
+            ParamValue::Integer(1),
+            ParamValue::Integer(2),
+            ParamValue::Integer(3),
+        ];
+        let params: Vec<i32> = predicate_params(&last)
+            .into_iter()
+            .map(|p| match p {
+                ParamValue::Integer(v) => v,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(params, [1, 1, 2, 1, 2, 3]);
+    }
+
+    #[test]
+    fn last_key_tuple_decodes_text_and_native_cells() {
+        let mut text = buffer(CellEncoding::Text);
+        text.add_row(vec![
+            Some(7i32.to_le_bytes().to_vec()),
+            Some(b"2024-01-02 03:04:05".to_vec()),
+            Some(b"a".to_vec()),
+        ]);
+        let columns = key_columns(&text, &["ID".to_string(), "created".to_string()]).unwrap();
+        assert_eq!(
+            last_key_tuple(&text, &columns).unwrap(),
+            [
+                ParamValue::Integer(7),
+                ParamValue::String("2024-01-02 03:04:05".to_string())
+            ]
+        );
+
+        let mut native = buffer(CellEncoding::Native);
+        let micros = (19_724 * 86_400 + 3_723) * 1_000_000 + 5;
+        native.add_row(vec![
+            Some(1i32.to_le_bytes().to_vec()),
+            Some(i64::to_le_bytes(micros).to_vec()),
+            None,
+        ]);
+        let value = last_key_tuple(&native, &[1]).unwrap().remove(0);
+        let ParamValue::Timestamp(ts) = value else {
+            panic!("expected a timestamp, got {value:?}");
+        };
+        assert_eq!((ts.date.year, ts.date.month, ts.date.day), (2024, 1, 2));
+        assert_eq!((ts.time.hour, ts.time.minute, ts.time.second), (1, 2, 3));
+        assert_eq!(ts.time.nanos, 5_000);
+
+        assert!(last_key_tuple(&native, &[2]).is_err());
+        assert!(key_columns(&native, &["missing".to_string()]).is_err());
+    }
+
+    #[test]
+    fn binary_keys_bind_raw_bytes() {
+        let mut rows = RowBuffer::with_cell_encoding(CellEncoding::Text);
+        rows.add_column("hash".to_string(), OdbcType::Binary);
+        rows.add_row(vec![Some(vec![0xFF, 0x00, 0xC3])]);
+        assert_eq!(
+            last_key_tuple(&rows, &[0]).unwrap(),
+            [ParamValue::Binary(vec![0xFF, 0x00, 0xC3])]
+        );
+    }
+
+    #[test]
+    fn native_timestamp_keys_finer_than_micros_are_rejected() {
+        let keys = ["created".to_string()];
+        for (encoding, precision, ok) in [
+            (CellEncoding::Native, 6, true),
+            (CellEncoding::Native, 7, false),
+            (CellEncoding::Text, 7, true),
+        ] {
+            let mut rows = RowBuffer::with_cell_encoding(encoding);
+            rows.add_described_column(
+                "created".to_string(),
+                OdbcType::Timestamp,
+                DataType::Timestamp { precision },
+            );
+            assert_eq!(key_columns(&rows, &keys).is_ok(), ok, "{precision}");
+        }
+    }
+}
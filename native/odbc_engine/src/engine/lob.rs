@@ -0,0 +1,540 @@
+//! Piecewise access to large BLOB / CLOB values.
+//!
+//! [`LobReader`] runs a query, stays on its first row and hands out one
+//! column through repeated `SQLGetData` calls, so the value never has to fit
+//! in memory (unlike [`crate::engine::cell_reader::CellReader`]).
+//! [`LobWriter`] binds one parameter as data-at-execution and feeds it with
+//! `SQLPutData` chunks, lifting the `MAX_PARAM_VALUE_PAYLOAD_LEN` cap of
+//! [`ParamValue`] for that parameter.
+//!
+//! Text values are exchanged as UTF-8 and sent to / read from the driver as
+//! UTF-16 (`SQL_C_WCHAR`), for the reasons given on
+//! [`crate::engine::cell_reader::CellReader::read_text`].
+
+use crate::engine::core::array_binding::check_stmt_return;
+use crate::engine::core::{input_parameters, TemporalBinding};
+use crate::error::{OdbcError, Result};
+use crate::handles::SharedConnection;
+use crate::protocol::ParamValue;
+use odbc_api::handles::{
+    AsStatementRef, DataType, DelayedInput, HasDataType, SqlResult, SqlText, Statement,
+    StatementImpl,
+};
+use odbc_api::parameter::InputParameter;
+use odbc_api::sys::{
+    len_data_at_exec, CDataType, HStmt, Len, Pointer, SQLCancel, SQLExecute, SQLGetData,
+    SQLParamData, SQLPutData, SQLRowCount, SqlReturn, DATA_AT_EXEC, NO_TOTAL, NULL_DATA,
+};
+use odbc_api::{Cursor, CursorImpl, ResultSetMetadata};
+use std::ffi::c_void;
+use std::num::NonZeroUsize;
+
+/// Bytes fetched per `SQLGetData` call.
+const LOB_PIECE_BYTES: usize = 256 * 1024;
+
+/// How a LOB value is exchanged with the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobKind {
+    /// Raw bytes (`SQL_C_BINARY`).
+    Binary,
+    /// UTF-8 text, transferred as `SQL_C_WCHAR`.
+    Text,
+}
+
+/// One column of the current row of a query, read in pieces.
+pub struct LobReader {
+    cursor: CursorImpl<StatementImpl<'static>>,
+    column: u16,
+    kind: LobKind,
+    /// Bytes fetched but not handed out yet, from `pending_at`.
+    pending: Vec<u8>,
+    pending_at: usize,
+    /// High surrogate that ended the previous text piece.
+    carry: Option<u16>,
+    is_null: bool,
+    done: bool,
+    /// Declared last so the connection outlives the statement.
+    conn: SharedConnection,
+}
+
+impl LobReader {
+    /// Executes `sql` with `params` (temporals bound per `temporal`) on
+    /// `conn` and positions on its first row; `column` is 1-based.
+    pub fn open(
+        conn: SharedConnection,
+        sql: &str,
+        params: &[ParamValue],
+        temporal: TemporalBinding,
+        column: u16,
+        kind: LobKind,
+    ) -> Result<Self> {
+        if column == 0 {
+            return Err(OdbcError::ValidationError(
+                "LOB column numbers start at 1".to_string(),
+            ));
+        }
+        let guard = conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let input_params = input_parameters(params, temporal)?;
+        let mut cursor = guard
+            .connection()
+            .execute(sql, input_params.as_slice(), None)
+            .map_err(OdbcError::from)?
+            .ok_or_else(|| {
+                OdbcError::ValidationError("statement returned no result set".to_string())
+            })?;
+        let columns = cursor.num_result_cols().map_err(OdbcError::from)?;
+        if i32::from(column) > i32::from(columns) {
+            return Err(OdbcError::ValidationError(format!(
+                "LOB column {column} out of range, result has {columns} columns"
+            )));
+        }
+        if cursor.next_row().map_err(OdbcError::from)?.is_none() {
+            return Err(OdbcError::ValidationError(
+                "query returned no rows".to_string(),
+            ));
+        }
+        // SAFETY: the statement only borrows the `Connection<'static>` owned
+        // by `conn`, which `LobReader` keeps alive and drops after the cursor.
+        // The FFI closes every LOB handle of a connection before disconnecting.
+        let cursor = unsafe {
+            std::mem::transmute::<CursorImpl<StatementImpl<'_>>, CursorImpl<StatementImpl<'static>>>(
+                cursor,
+            )
+        };
+        drop(guard);
+        Ok(Self {
+            cursor,
+            column,
+            kind,
+            pending: Vec::new(),
+            pending_at: 0,
+            carry: None,
+            is_null: false,
+            done: false,
+            conn,
+        })
+    }
+
+    /// True when the cell is SQL `NULL`.
+    pub fn is_null(&mut self) -> Result<bool> {
+        if self.pending_at == self.pending.len() && !self.done {
+            self.fetch_piece()?;
+        }
+        Ok(self.is_null)
+    }
+
+    /// Next bytes of the value, at most `max`; empty once it has been read
+    /// completely (or is `NULL`).
+    pub fn read(&mut self, max: usize) -> Result<Vec<u8>> {
+        while self.pending_at == self.pending.len() && !self.done {
+            self.fetch_piece()?;
+        }
+        let end = self.pending.len().min(self.pending_at.saturating_add(max));
+        let out = self.pending[self.pending_at..end].to_vec();
+        self.pending_at = end;
+        Ok(out)
+    }
+
+    fn fetch_piece(&mut self) -> Result<()> {
+        let _guard = self
+            .conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        self.pending.clear();
+        self.pending_at = 0;
+        let hstmt = self.cursor.as_stmt_ref().as_sys();
+        let mut indicator: Len = 0;
+        let (ret, written) = match self.kind {
+            LobKind::Binary => {
+                self.pending.resize(LOB_PIECE_BYTES, 0);
+                // SAFETY: `pending` holds `LOB_PIECE_BYTES` writable bytes.
+                let ret = unsafe {
+                    SQLGetData(
+                        hstmt,
+                        self.column,
+                        CDataType::Binary,
+                        self.pending.as_mut_ptr() as Pointer,
+                        LOB_PIECE_BYTES as Len,
+                        &mut indicator,
+                    )
+                };
+                (ret, piece_len(indicator, LOB_PIECE_BYTES))
+            }
+            LobKind::Text => {
+                let mut wide = vec![0u16; LOB_PIECE_BYTES / 2];
+                let capacity = (wide.len() - 1) * 2;
+                // SAFETY: `wide` holds `LOB_PIECE_BYTES` writable bytes.
+                let ret = unsafe {
+                    SQLGetData(
+                        hstmt,
+                        self.column,
+                        CDataType::WChar,
+                        wide.as_mut_ptr() as Pointer,
+                        LOB_PIECE_BYTES as Len,
+                        &mut indicator,
+                    )
+                };
+                let written = piece_len(indicator, capacity);
+                if ret != SqlReturn::NO_DATA && indicator != NULL_DATA {
+                    decode_wide_piece(&mut self.carry, &wide[..written / 2], &mut self.pending);
+                }
+                (ret, written)
+            }
+        };
+        if ret == SqlReturn::NO_DATA {
+            self.pending.clear();
+            if self.carry.take().is_some() {
+                self.pending
+                    .extend_from_slice(char::REPLACEMENT_CHARACTER.to_string().as_bytes());
+            }
+            self.done = true;
+            return Ok(());
+        }
+        if let Err(e) = check_stmt_return(hstmt, ret, "SQLGetData") {
+            self.pending.clear();
+            self.done = true;
+            return Err(e);
+        }
+        if indicator == NULL_DATA {
+            self.pending.clear();
+            self.is_null = true;
+            self.done = true;
+        } else if self.kind == LobKind::Binary {
+            self.pending.truncate(written);
+        }
+        Ok(())
+    }
+}
+
+/// Bytes `SQLGetData` wrote into a buffer holding `capacity` bytes of data.
+fn piece_len(indicator: Len, capacity: usize) -> usize {
+    if indicator == NO_TOTAL {
+        capacity
+    } else {
+        usize::try_from(indicator).map_or(0, |len| len.min(capacity))
+    }
+}
+
+/// Appends the UTF-8 form of `units` to `out`. A high surrogate ending the
+/// piece is held in `carry` until the next piece completes the pair.
+fn decode_wide_piece(carry: &mut Option<u16>, units: &[u16], out: &mut Vec<u8>) {
+    let mut joined: Vec<u16> = Vec::with_capacity(units.len() + 1);
+    joined.extend(carry.take());
+    joined.extend_from_slice(units);
+    if let Some(&last) = joined.last() {
+        if (0xD800..=0xDBFF).contains(&last) {
+            *carry = joined.pop();
+        }
+    }
+    let text: String = char::decode_utf16(joined)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    out.extend_from_slice(text.as_bytes());
+}
+
+/// The data-at-execution parameter of a [`LobWriter`].
+struct LobStream {
+    kind: LobKind,
+    /// Declared column size; `None` lets the driver pick its maximum.
+    length: Option<NonZeroUsize>,
+    indicator: Len,
+}
+
+impl HasDataType for LobStream {
+    fn data_type(&self) -> DataType {
+        match self.kind {
+            LobKind::Binary => DataType::LongVarbinary {
+                length: self.length,
+            },
+            LobKind::Text => DataType::WLongVarchar { length: None },
+        }
+    }
+}
+
+// SAFETY: `indicator_ptr` points into `self`, which the writer keeps boxed
+// (fixed address) and bound until the statement is dropped.
+unsafe impl DelayedInput for LobStream {
+    fn cdata_type(&self) -> CDataType {
+        match self.kind {
+            LobKind::Binary => CDataType::Binary,
+            LobKind::Text => CDataType::WChar,
+        }
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        &self.indicator as *const isize
+    }
+
+    fn stream_ptr(&mut self) -> *mut c_void {
+        self as *mut Self as *mut c_void
+    }
+}
+
+/// A statement whose LOB parameter is streamed in with `SQLPutData`.
+pub struct LobWriter {
+    stmt: StatementImpl<'static>,
+    /// Bound to the other `?` markers until the statement is dropped.
+    _params: Vec<Box<dyn InputParameter>>,
+    _stream: Box<LobStream>,
+    kind: LobKind,
+    /// Trailing bytes of an incomplete UTF-8 sequence (text only).
+    utf8_carry: Vec<u8>,
+    wrote_any: bool,
+    finished: bool,
+    /// Declared last so the connection outlives the statement.
+    conn: SharedConnection,
+}
+
+impl LobWriter {
+    /// Prepares `sql` and starts executing it. `lob_param` is the 1-based
+    /// marker streamed with [`Self::write`]; `params` fill the other markers
+    /// in order, temporals bound per `temporal`. `total_len` (binary only) is
+    /// passed on to drivers that need the length up front.
+    pub fn open(
+        conn: SharedConnection,
+        sql: &str,
+        params: &[ParamValue],
+        temporal: TemporalBinding,
+        lob_param: u16,
+        kind: LobKind,
+        total_len: Option<u64>,
+    ) -> Result<Self> {
+        if lob_param == 0 || usize::from(lob_param) > params.len() + 1 {
+            return Err(OdbcError::ValidationError(format!(
+                "LOB parameter {lob_param} out of range for {} markers",
+                params.len() + 1
+            )));
+        }
+        let indicator = match (kind, total_len) {
+            (LobKind::Binary, Some(len)) => len_data_at_exec(Len::try_from(len).map_err(|_| {
+                OdbcError::ValidationError(format!("LOB length {len} out of range"))
+            })?),
+            _ => DATA_AT_EXEC,
+        };
+        let length = total_len
+            .filter(|_| kind == LobKind::Binary)
+            .and_then(|len| usize::try_from(len).ok())
+            .and_then(NonZeroUsize::new);
+        let mut stream = Box::new(LobStream {
+            kind,
+            length,
+            indicator,
+        });
+
+        let guard = conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let input_params = input_parameters(params, temporal)?;
+        let stmt = guard
+            .connection()
+            .preallocate()
+            .map_err(OdbcError::from)?
+            .into_handle();
+        // SAFETY: as for `LobReader::open`; `LobWriter` keeps `conn` alive
+        // and drops it after the statement.
+        let mut stmt =
+            unsafe { std::mem::transmute::<StatementImpl<'_>, StatementImpl<'static>>(stmt) };
+        let hstmt = stmt.as_sys();
+        stmt_result(hstmt, stmt.prepare(&SqlText::new(sql)), "SQLPrepare")?;
+        for (index, param) in input_params.iter().enumerate() {
+            let marker = index as u16 + if index as u16 + 1 < lob_param { 1 } else { 2 };
+            // SAFETY: the parameter is boxed and moved into the writer, which
+            // drops the statement first.
+            let bound = unsafe { stmt.bind_input_parameter(marker, param) };
+            stmt_result(hstmt, bound, "SQLBindParameter")?;
+        }
+        // SAFETY: `stream` is boxed and kept by the writer, see above.
+        let bound = unsafe { stmt.bind_delayed_input_parameter(lob_param, stream.as_mut()) };
+        stmt_result(hstmt, bound, "SQLBindParameter")?;
+
+        // SAFETY: every bound parameter lives in `input_params` / `stream`.
+        let ret = unsafe { SQLExecute(hstmt) };
+        if ret != SqlReturn::NEED_DATA {
+            check_stmt_return(hstmt, ret, "SQLExecute")?;
+            return Err(OdbcError::InternalError(
+                "driver did not ask for the LOB parameter".to_string(),
+            ));
+        }
+        let mut token: Pointer = std::ptr::null_mut();
+        // SAFETY: `token` is a live out pointer.
+        let ret = unsafe { SQLParamData(hstmt, &mut token) };
+        if ret != SqlReturn::NEED_DATA {
+            check_stmt_return(hstmt, ret, "SQLParamData")?;
+            return Err(OdbcError::InternalError(
+                "driver did not ask for the LOB parameter".to_string(),
+            ));
+        }
+        drop(guard);
+        Ok(Self {
+            stmt,
+            _params: input_params,
+            _stream: stream,
+            kind,
+            utf8_carry: Vec::new(),
+            wrote_any: false,
+            finished: false,
+            conn,
+        })
+    }
+
+    /// Sends the next chunk of the value. Text chunks may split UTF-8
+    /// sequences; the remainder is sent with the next chunk.
+    pub fn write(&mut self, chunk: &[u8]) -> Result<()> {
+        if self.finished {
+            return Err(OdbcError::ValidationError(
+                "LOB upload already finished".to_string(),
+            ));
+        }
+        let piece = match self.kind {
+            LobKind::Binary => chunk.to_vec(),
+            LobKind::Text => utf8_to_wide_piece(&mut self.utf8_carry, chunk)?,
+        };
+        if piece.is_empty() {
+            return Ok(());
+        }
+        self.put(&piece)?;
+        self.wrote_any = true;
+        Ok(())
+    }
+
+    /// Ends the value and completes execution; returns the affected row
+    /// count (-1 if the driver does not know).
+    pub fn finish(&mut self) -> Result<i64> {
+        if self.finished {
+            return Err(OdbcError::ValidationError(
+                "LOB upload already finished".to_string(),
+            ));
+        }
+        if !self.utf8_carry.is_empty() {
+            return Err(OdbcError::ValidationError(
+                "LOB text ends with an incomplete UTF-8 sequence".to_string(),
+            ));
+        }
+        if !self.wrote_any {
+            self.put(&[])?;
+        }
+        // Only now: until SQLParamData runs the statement still waits for
+        // data, and dropping the writer must cancel it.
+        self.finished = true;
+        let _guard = self
+            .conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let hstmt = self.stmt.as_sys();
+        let mut token: Pointer = std::ptr::null_mut();
+        // SAFETY: bound parameters are alive; `token` is a live out pointer.
+        let ret = unsafe { SQLParamData(hstmt, &mut token) };
+        if ret == SqlReturn::NEED_DATA {
+            return Err(OdbcError::InternalError(
+                "driver asked for another data-at-execution parameter".to_string(),
+            ));
+        }
+        if ret != SqlReturn::NO_DATA {
+            check_stmt_return(hstmt, ret, "SQLParamData")?;
+        }
+        let mut rows: Len = -1;
+        // SAFETY: `rows` is a live out pointer.
+        let _ = unsafe { SQLRowCount(hstmt, &mut rows) };
+        Ok(rows as i64)
+    }
+
+    fn put(&mut self, piece: &[u8]) -> Result<()> {
+        let _guard = self
+            .conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let hstmt = self.stmt.as_sys();
+        // SAFETY: `piece` is valid for `piece.len()` bytes for the call.
+        let ret = unsafe { SQLPutData(hstmt, piece.as_ptr() as Pointer, piece.len() as Len) };
+        check_stmt_return(hstmt, ret, "SQLPutData")
+    }
+}
+
+impl Drop for LobWriter {
+    /// An upload that was not finished is cancelled, so the statement never
+    /// executes with a partial value.
+    fn drop(&mut self) {
+        if !self.finished {
+            // SAFETY: the statement handle is still allocated.
+            let _ = unsafe { SQLCancel(self.stmt.as_sys()) };
+        }
+    }
+}
+
+/// UTF-16LE bytes of the complete UTF-8 sequences in `carry` + `chunk`;
+/// an incomplete trailing sequence is left in `carry`.
+fn utf8_to_wide_piece(carry: &mut Vec<u8>, chunk: &[u8]) -> Result<Vec<u8>> {
+    carry.extend_from_slice(chunk);
+    let valid = match std::str::from_utf8(carry) {
+        Ok(text) => text.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(e) => {
+            return Err(OdbcError::ValidationError(format!(
+                "LOB text is not valid UTF-8: {e}"
+            )))
+        }
+    };
+    let rest = carry.split_off(valid);
+    let text = std::str::from_utf8(carry).unwrap_or_default();
+    let wide = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    *carry = rest;
+    Ok(wide)
+}
+
+/// Maps an odbc-api statement call to our error type with its diagnostics.
+fn stmt_result(hstmt: HStmt, result: SqlResult<()>, context: &str) -> Result<()> {
+    match result {
+        SqlResult::Success(()) | SqlResult::SuccessWithInfo(()) => Ok(()),
+        SqlResult::Error { .. } => check_stmt_return(hstmt, SqlReturn::ERROR, context),
+        _ => Err(OdbcError::InternalError(format!(
+            "{context} returned an unexpected status"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_len() {
+        assert_eq!(piece_len(10, 100), 10);
+        assert_eq!(piece_len(500, 100), 100);
+        assert_eq!(piece_len(NO_TOTAL, 100), 100);
+        assert_eq!(piece_len(NULL_DATA, 100), 0);
+    }
+
+    #[test]
+    fn test_decode_wide_piece_carries_split_surrogate_pairs() {
+        let wide: Vec<u16> = "a😀b".encode_utf16().collect();
+        let mut carry = None;
+        let mut out = Vec::new();
+        decode_wide_piece(&mut carry, &wide[..2], &mut out);
+        assert_eq!(out, b"a");
+        assert_eq!(carry, Some(wide[1]));
+        decode_wide_piece(&mut carry, &wide[2..], &mut out);
+        assert_eq!(carry, None);
+        assert_eq!(String::from_utf8(out).unwrap(), "a😀b");
+    }
+
+    #[test]
+    fn test_utf8_to_wide_piece_carries_split_sequences() {
+        let text = "só 😀";
+        let bytes = text.as_bytes();
+        let mut carry = Vec::new();
+        let mut wide = utf8_to_wide_piece(&mut carry, &bytes[..2]).unwrap();
+        assert_eq!(carry, &bytes[1..2]);
+        wide.extend(utf8_to_wide_piece(&mut carry, &bytes[2..5]).unwrap());
+        wide.extend(utf8_to_wide_piece(&mut carry, &bytes[5..]).unwrap());
+        assert!(carry.is_empty());
+        let units: Vec<u16> = wide
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(String::from_utf16(&units).unwrap(), text);
+
+        assert!(utf8_to_wide_piece(&mut Vec::new(), &[0xFF, b'a']).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod block_fetch;
 pub mod catalog;
 pub mod cell_reader;
 pub mod connection;
@@ -5,9 +6,13 @@ pub mod core;
 pub mod dbms_info;
 pub mod environment;
 pub mod identifier;
+pub mod keyset;
+pub mod lob;
 pub mod query;
+pub mod scrollable;
 pub mod sqlserver_json;
 pub mod statement;
+pub mod stream_buffer;
 pub mod streaming;
 pub mod transaction;
 pub mod xa_transaction;
@@ -33,18 +38,23 @@ pub use identifier::{
     quote_identifier, quote_identifier_default, quote_qualified_default, validate_identifier,
     IdentifierQuoting, MAX_IDENTIFIER_LEN,
 };
+pub use keyset::{KeysetPagination, KeysetToken};
+pub use lob::{LobKind, LobReader, LobWriter};
 pub use query::{
-    execute_multi_result, execute_multi_result_with_params, execute_query_with_cached_connection,
-    execute_query_with_connection, execute_query_with_param_buffer,
-    execute_query_with_param_buffer_and_timeout, execute_query_with_params,
-    execute_query_with_params_and_timeout, get_global_metrics,
+    execute_multi_result, execute_multi_result_with_params, execute_prepared_with_param_buffer,
+    execute_query_with_cached_connection, execute_query_with_connection,
+    execute_query_with_param_buffer, execute_query_with_param_buffer_and_timeout,
+    execute_query_with_params, execute_query_with_params_and_timeout, get_global_metrics,
 };
+pub use scrollable::{ScrollFetch, ScrollableCursor};
 pub use sqlserver_json::{
     coalesce_for_json_rows, is_for_json_result, SQLSERVER_FOR_JSON_COLUMN_NAME,
 };
 pub use statement::StatementHandle;
+pub use stream_buffer::{StreamBufferPolicy, StreamBufferStats, StreamOverflow};
 pub use streaming::{
-    start_multi_async_stream, start_multi_batched_stream, AsyncStreamStatus, AsyncStreamingState,
+    start_multi_async_stream, start_multi_async_stream_with_params, start_multi_batched_stream,
+    start_multi_batched_stream_with_params, AsyncStreamStatus, AsyncStreamingState,
     BatchedStreamingState, StreamState, StreamingExecutor, StreamingState,
     MULTI_STREAM_ITEM_TAG_RESULT_SET, MULTI_STREAM_ITEM_TAG_ROW_COUNT,
 };
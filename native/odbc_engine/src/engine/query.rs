@@ -1,10 +1,9 @@
-use crate::engine::core::QueryPipeline;
+use crate::engine::core::{QueryConnection, QueryPipeline, ResultOptions};
 use crate::error::Result;
 use crate::handles::CachedConnection;
 use crate::observability::Metrics;
-use crate::protocol::bound_param::{ParamDirection, ParamList};
+use crate::protocol::bound_param::{BoundParam, ParamDirection, ParamList};
 use crate::protocol::{deserialize_param_buffer, ParamValue};
-use odbc_api::Connection;
 use std::sync::Arc;
 
 lazy_static::lazy_static! {
@@ -15,7 +14,10 @@ pub fn get_global_metrics() -> Arc<Metrics> {
     PIPELINE.get_metrics()
 }
 
-pub fn execute_query_with_connection(conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+pub fn execute_query_with_connection<'a>(
+    conn: impl Into<QueryConnection<'a>>,
+    sql: &str,
+) -> Result<Vec<u8>> {
     PIPELINE.execute_direct(conn, sql)
 }
 
@@ -23,12 +25,13 @@ pub fn execute_query_with_connection(conn: &Connection<'static>, sql: &str) -> R
 pub fn execute_query_with_cached_connection(
     cached: &mut CachedConnection,
     sql: &str,
+    options: &ResultOptions,
 ) -> Result<Vec<u8>> {
-    PIPELINE.execute_direct_cached(cached, sql)
+    PIPELINE.execute_direct_cached(cached, sql, options)
 }
 
-pub fn execute_query_with_params(
-    conn: &Connection<'static>,
+pub fn execute_query_with_params<'a>(
+    conn: impl Into<QueryConnection<'a>>,
     sql: &str,
     params: &[ParamValue],
 ) -> Result<Vec<u8>> {
@@ -37,45 +40,43 @@ pub fn execute_query_with_params(
 
 /// Like [execute_query_with_params] but accepts a raw FFI buffer: legacy
 /// [ParamValue]… concatenation, or a DRT1 directed list (see [crate::protocol::bound_param]).
-pub fn execute_query_with_param_buffer(
-    conn: &Connection<'static>,
+pub fn execute_query_with_param_buffer<'a>(
+    conn: impl Into<QueryConnection<'a>>,
     sql: &str,
     param_bytes: &[u8],
 ) -> Result<Vec<u8>> {
-    dispatch_param_buffer(conn, sql, param_bytes, None, None)
+    dispatch_param_buffer(conn.into(), sql, param_bytes, None, None)
 }
 
 fn dispatch_param_buffer(
-    conn: &Connection<'static>,
+    conn: QueryConnection<'_>,
     sql: &str,
     param_bytes: &[u8],
     timeout_sec: Option<usize>,
     fetch_size: Option<u32>,
 ) -> Result<Vec<u8>> {
-    let list = deserialize_param_buffer(param_bytes)?;
-    match list {
-        ParamList::Legacy(p) => {
-            PIPELINE.execute_with_params_and_timeout(conn, sql, &p, timeout_sec, fetch_size)
+    match input_values(deserialize_param_buffer(param_bytes)?) {
+        Ok(p) => PIPELINE.execute_with_params_and_timeout(conn, sql, &p, timeout_sec, fetch_size),
+        Err(b) => {
+            PIPELINE.execute_with_bound_params_and_timeout(conn, sql, &b, timeout_sec, fetch_size)
         }
-        ParamList::Directed(b) => {
-            if b.iter().all(|x| x.direction == ParamDirection::Input) {
-                let p: Vec<ParamValue> = b.iter().map(|x| x.value.clone()).collect();
-                PIPELINE.execute_with_params_and_timeout(conn, sql, &p, timeout_sec, fetch_size)
-            } else {
-                PIPELINE.execute_with_bound_params_and_timeout(
-                    conn,
-                    sql,
-                    &b,
-                    timeout_sec,
-                    fetch_size,
-                )
-            }
+    }
+}
+
+/// Values of `list` when every parameter is `INPUT`; the directed list
+/// otherwise.
+fn input_values(list: ParamList) -> std::result::Result<Vec<ParamValue>, Vec<BoundParam>> {
+    match list {
+        ParamList::Legacy(p) => Ok(p),
+        ParamList::Directed(b) if b.iter().all(|x| x.direction == ParamDirection::Input) => {
+            Ok(b.into_iter().map(|x| x.value).collect())
         }
+        ParamList::Directed(b) => Err(b),
     }
 }
 
-pub fn execute_query_with_params_and_timeout(
-    conn: &Connection<'static>,
+pub fn execute_query_with_params_and_timeout<'a>(
+    conn: impl Into<QueryConnection<'a>>,
     sql: &str,
     params: &[ParamValue],
     timeout_sec: Option<usize>,
@@ -85,22 +86,72 @@ pub fn execute_query_with_params_and_timeout(
 }
 
 /// [execute_query_with_params_and_timeout] with a raw buffer (legacy or DRT1).
-pub fn execute_query_with_param_buffer_and_timeout(
-    conn: &Connection<'static>,
+pub fn execute_query_with_param_buffer_and_timeout<'a>(
+    conn: impl Into<QueryConnection<'a>>,
+    sql: &str,
+    param_bytes: &[u8],
+    timeout_sec: Option<usize>,
+    fetch_size: Option<u32>,
+) -> Result<Vec<u8>> {
+    dispatch_param_buffer(conn.into(), sql, param_bytes, timeout_sec, fetch_size)
+}
+
+/// Executes `odbc_prepare` statement `stmt_id` on `cached` with a raw
+/// parameter buffer (legacy or DRT1). With `statement-handle-reuse`,
+/// input-only parameter lists rebind the handle prepared for `stmt_id`;
+/// otherwise, and for `OUTPUT` / `INOUT` lists, `sql` is prepared per call.
+#[cfg_attr(not(feature = "statement-handle-reuse"), allow(unused_variables))]
+pub fn execute_prepared_with_param_buffer(
+    cached: &mut CachedConnection,
+    stmt_id: u32,
     sql: &str,
     param_bytes: &[u8],
     timeout_sec: Option<usize>,
     fetch_size: Option<u32>,
+    options: &ResultOptions,
 ) -> Result<Vec<u8>> {
-    dispatch_param_buffer(conn, sql, param_bytes, timeout_sec, fetch_size)
+    #[cfg(feature = "statement-handle-reuse")]
+    {
+        match input_values(deserialize_param_buffer(param_bytes)?) {
+            Ok(p) => PIPELINE.execute_prepared(
+                cached,
+                stmt_id,
+                sql,
+                &p,
+                timeout_sec,
+                fetch_size,
+                options,
+            ),
+            Err(b) => PIPELINE.execute_with_bound_params_and_timeout(
+                QueryConnection::new(cached.connection(), *options),
+                sql,
+                &b,
+                timeout_sec,
+                fetch_size,
+            ),
+        }
+    }
+    #[cfg(not(feature = "statement-handle-reuse"))]
+    {
+        dispatch_param_buffer(
+            QueryConnection::new(cached.connection(), *options),
+            sql,
+            param_bytes,
+            timeout_sec,
+            fetch_size,
+        )
+    }
 }
 
-pub fn execute_multi_result(conn: &Connection<'static>, sql: &str) -> Result<Vec<u8>> {
+pub fn execute_multi_result<'a>(
+    conn: impl Into<QueryConnection<'a>>,
+    sql: &str,
+) -> Result<Vec<u8>> {
     PIPELINE.execute_multi(conn, sql)
 }
 
-pub fn execute_multi_result_with_params(
-    conn: &Connection<'static>,
+pub fn execute_multi_result_with_params<'a>(
+    conn: impl Into<QueryConnection<'a>>,
     sql: &str,
     params: &[ParamValue],
 ) -> Result<Vec<u8>> {
@@ -0,0 +1,509 @@
+//! Scrollable cursors: fetch a block of rows at any position of a result set.
+//!
+//! [`ScrollableCursor::open`] asks the driver for a scrollable cursor
+//! (`SQL_ATTR_CURSOR_SCROLLABLE`, falling back to a static
+//! `SQL_ATTR_CURSOR_TYPE`) and positions it with `SQLFetchScroll`. That needs
+//! a result whose columns can all be bound (see [`crate::engine::block_fetch`]).
+//!
+//! When the driver only opens forward-only cursors, or a column needs
+//! `SQLGetData`, the cursor is emulated: the result is read once, forward,
+//! into a [`DiskSpillStream`] and blocks are served from there. Both modes
+//! follow the `SQLFetchScroll` rules for first / last / absolute / relative
+//! positioning, so callers see the same rows either way.
+
+use crate::engine::block_fetch::{BoundRowset, RowFetcher};
+use crate::engine::core::array_binding::check_stmt_return;
+use crate::engine::core::{input_parameters, DiskSpillStream, ResultOptions};
+use crate::engine::streaming::describe_columns;
+use crate::error::{OdbcError, Result};
+use crate::handles::SharedConnection;
+use crate::protocol::{CellEncoding, ParamValue, RowBuffer, RowBufferEncoder};
+use odbc_api::buffers::ColumnarAnyBuffer;
+use odbc_api::handles::{AsStatementRef, Statement, StatementImpl};
+use odbc_api::sys::{
+    FetchOrientation, HStmt, Integer, Len, Pointer, SQLFetchScroll, SQLGetStmtAttr, SQLSetStmtAttr,
+    SqlReturn, StatementAttribute,
+};
+use odbc_api::{Cursor, CursorImpl};
+
+const SQL_SCROLLABLE: usize = 1;
+const SQL_CURSOR_FORWARD_ONLY: usize = 0;
+const SQL_CURSOR_STATIC: usize = 3;
+
+/// Where a fetch places the block of rows it returns, as in `SQLFetchScroll`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollFetch {
+    First,
+    /// The last `rows` rows of the result.
+    Last,
+    /// Starts at 1-based row `n`; negative counts back from the last row.
+    Absolute(i64),
+    /// Starts `n` rows after (or before) the start of the current block.
+    Relative(i64),
+}
+
+impl ScrollFetch {
+    fn orientation(self) -> (FetchOrientation, i64) {
+        match self {
+            ScrollFetch::First => (FetchOrientation::First, 0),
+            ScrollFetch::Last => (FetchOrientation::Last, 0),
+            ScrollFetch::Absolute(n) => (FetchOrientation::Absolute, n),
+            ScrollFetch::Relative(n) => (FetchOrientation::Relative, n),
+        }
+    }
+}
+
+/// A result set held open for positioned block fetches. Each fetch returns
+/// the block as one [`RowBufferEncoder`] message; past either end the block
+/// is empty.
+pub struct ScrollableCursor {
+    mode: ScrollMode,
+    /// Columns of the result; rows are filled and cleared per fetch.
+    row_buffer: RowBuffer,
+}
+
+enum ScrollMode {
+    Native(NativeScroll),
+    Emulated(EmulatedScroll),
+}
+
+impl ScrollableCursor {
+    /// Executes `sql` with `params` on `conn` and keeps its result open.
+    /// Cells are encoded for the protocol in `options`, as in
+    /// [`crate::engine::StreamingExecutor::with_protocol_version`]; emulated
+    /// scrolling fetches in blocks sized by `options` into a spill stored per
+    /// its [`ResultOptions::spill`].
+    pub fn open(
+        conn: SharedConnection,
+        sql: &str,
+        params: &[ParamValue],
+        options: &ResultOptions,
+    ) -> Result<Self> {
+        let mut row_buffer =
+            RowBuffer::with_cell_encoding(CellEncoding::for_version(&options.protocol));
+        let guard = conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let input_params = input_parameters(params, options.temporal)?;
+        let mut stmt = guard.connection().preallocate().map_err(OdbcError::from)?;
+        let scrollable = request_scrollable(stmt.as_stmt_ref().as_sys());
+        let mut cursor = stmt
+            .into_cursor(sql, input_params.as_slice())
+            .map_err(OdbcError::from)?
+            .ok_or_else(|| {
+                OdbcError::ValidationError("statement returned no result set".to_string())
+            })?;
+        let (column_types, data_types) = describe_columns(&mut cursor, &mut row_buffer)?;
+
+        let rowset = BoundRowset::plan(&data_types, &column_types);
+        if let Some(rowset) = rowset.filter(|_| scrollable && is_scrollable(&mut cursor)) {
+            // SAFETY: the statement only borrows the `Connection<'static>` owned
+            // by `conn`, which `NativeScroll` keeps alive and drops after the
+            // cursor. The connection is never moved out of its `Arc`, and the
+            // FFI closes every cursor of a connection before disconnecting it.
+            let cursor = unsafe {
+                std::mem::transmute::<
+                    CursorImpl<StatementImpl<'_>>,
+                    CursorImpl<StatementImpl<'static>>,
+                >(cursor)
+            };
+            drop(guard);
+            return Ok(Self {
+                mode: ScrollMode::Native(NativeScroll {
+                    cursor,
+                    rowset,
+                    buffer: None,
+                    conn,
+                }),
+                row_buffer,
+            });
+        }
+
+        let block_rows = options.block_rows(None);
+        let mut fetcher = RowFetcher::new(&mut cursor, &data_types, &column_types, block_rows)?;
+        let mut store = EmulatedScroll::new(DiskSpillStream::with_default_threshold(options.spill));
+        loop {
+            let added = fetcher.fetch_into(&mut row_buffer, block_rows)?;
+            store.append(&row_buffer)?;
+            row_buffer.rows.clear();
+            if added < block_rows {
+                break;
+            }
+        }
+        Ok(Self {
+            mode: ScrollMode::Emulated(store),
+            row_buffer,
+        })
+    }
+
+    /// True when the driver could not scroll and the result is replayed from
+    /// a spill file.
+    pub fn is_emulated(&self) -> bool {
+        matches!(self.mode, ScrollMode::Emulated(_))
+    }
+
+    /// Moves to `fetch` and returns the block of up to `rows` rows there,
+    /// encoded with [`RowBufferEncoder`]. Native cursors may return fewer
+    /// rows per block for very wide rows (see
+    /// [`crate::engine::block_fetch::MAX_BLOCK_BUFFER_BYTES`]).
+    pub fn fetch(&mut self, fetch: ScrollFetch, rows: usize) -> Result<Vec<u8>> {
+        if rows == 0 {
+            return Err(OdbcError::ValidationError(
+                "scroll fetch needs at least one row".to_string(),
+            ));
+        }
+        self.row_buffer.rows.clear();
+        match &mut self.mode {
+            ScrollMode::Native(native) => native.fetch(fetch, rows, &mut self.row_buffer)?,
+            ScrollMode::Emulated(store) => store.fetch(fetch, rows, &mut self.row_buffer)?,
+        }
+        let encoded = RowBufferEncoder::try_encode(&self.row_buffer)
+            .map_err(|e| OdbcError::ResourceLimitReached(format!("result encoding failed: {e}")));
+        self.row_buffer.rows.clear();
+        encoded
+    }
+}
+
+/// Asks for a scrollable cursor before execution; false when the driver
+/// refuses both the scrollable flag and a static cursor type.
+fn request_scrollable(hstmt: HStmt) -> bool {
+    // SAFETY: `hstmt` is an allocated statement that has not been executed.
+    unsafe {
+        let ret = SQLSetStmtAttr(
+            hstmt,
+            StatementAttribute::CursorScrollable,
+            SQL_SCROLLABLE as Pointer,
+            0,
+        );
+        if ret == SqlReturn::SUCCESS || ret == SqlReturn::SUCCESS_WITH_INFO {
+            return true;
+        }
+        let ret = SQLSetStmtAttr(
+            hstmt,
+            StatementAttribute::CursorType,
+            SQL_CURSOR_STATIC as Pointer,
+            0,
+        );
+        ret == SqlReturn::SUCCESS || ret == SqlReturn::SUCCESS_WITH_INFO
+    }
+}
+
+/// Drivers may silently downgrade the requested cursor (`01S02`); check
+/// what was actually opened.
+fn is_scrollable(cursor: &mut impl Cursor) -> bool {
+    let mut cursor_type: usize = SQL_CURSOR_FORWARD_ONLY;
+    // SAFETY: SQL_ATTR_CURSOR_TYPE is an SQLULEN written into `cursor_type`.
+    let ret = unsafe {
+        SQLGetStmtAttr(
+            cursor.as_stmt_ref().as_sys(),
+            StatementAttribute::CursorType,
+            &mut cursor_type as *mut usize as Pointer,
+            0,
+            std::ptr::null_mut::<Integer>(),
+        )
+    };
+    (ret == SqlReturn::SUCCESS || ret == SqlReturn::SUCCESS_WITH_INFO)
+        && cursor_type != SQL_CURSOR_FORWARD_ONLY
+}
+
+/// Driver-side scrollable cursor, fetched with `SQLFetchScroll` into a
+/// buffer bound for the duration of each fetch.
+struct NativeScroll {
+    cursor: CursorImpl<StatementImpl<'static>>,
+    rowset: BoundRowset,
+    /// Requested rows and the buffer allocated for them.
+    buffer: Option<(usize, ColumnarAnyBuffer)>,
+    /// Declared last so the connection outlives the statement.
+    conn: SharedConnection,
+}
+
+impl NativeScroll {
+    fn fetch(&mut self, fetch: ScrollFetch, rows: usize, row_buffer: &mut RowBuffer) -> Result<()> {
+        let _guard = self
+            .conn
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+        let buffer = match self.buffer.take() {
+            Some((requested, buffer)) if requested == rows => buffer,
+            _ => self.rowset.buffer(rows)?,
+        };
+        let (orientation, offset) = fetch.orientation();
+        let offset = Len::try_from(offset).map_err(|_| {
+            OdbcError::ValidationError(format!("scroll offset {offset} out of range"))
+        })?;
+
+        // SAFETY: the statement is in cursor state. The borrowed `CursorImpl`
+        // is turned back into a statement reference without being dropped, so
+        // the owned cursor stays open (same pattern as `RowFetcher`).
+        let hstmt = self.cursor.as_stmt_ref().as_sys();
+        let borrowed = unsafe { CursorImpl::new(self.cursor.as_stmt_ref()) };
+        let block = borrowed.bind_buffer(buffer).map_err(OdbcError::from)?;
+        // SAFETY: `block` keeps the rowset buffer bound until `unbind` below.
+        let ret = unsafe { SQLFetchScroll(hstmt, orientation, offset) };
+        let (borrowed, buffer) = block.unbind().map_err(OdbcError::from)?;
+        let mut stmt = borrowed.into_stmt();
+        // SAFETY: no buffers are bound any more.
+        let _ = unsafe { stmt.set_row_array_size(1) };
+
+        let result = if ret == SqlReturn::NO_DATA {
+            Ok(())
+        } else {
+            check_stmt_return(hstmt, ret, "SQLFetchScroll")
+                .and_then(|()| self.rowset.append_rows(&buffer, row_buffer).map(|_| ()))
+        };
+        self.buffer = Some((rows, buffer));
+        result
+    }
+}
+
+/// Cursor position of an emulated cursor, in `SQLFetchScroll` terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    BeforeStart,
+    /// 0-based first row of the current block.
+    Block(u64),
+    AfterEnd,
+}
+
+/// Position after applying `fetch` with blocks of `rows` rows to a result
+/// of `total` rows, following the `SQLFetchScroll` cursor-positioning rules.
+fn scroll_position(current: Position, fetch: ScrollFetch, total: u64, rows: u64) -> Position {
+    let total = i128::from(total);
+    let rows = i128::from(rows);
+    // 1-based start row the fetch moves to, with the rules for landing
+    // before the first row.
+    let start = match fetch {
+        ScrollFetch::First => 1,
+        ScrollFetch::Last => (total - rows + 1).max(1),
+        ScrollFetch::Absolute(n) => {
+            let n = i128::from(n);
+            if n < 0 && -n > total {
+                if -n > rows {
+                    return Position::BeforeStart;
+                }
+                1
+            } else if n < 0 {
+                total + n + 1
+            } else if n == 0 {
+                return Position::BeforeStart;
+            } else {
+                n
+            }
+        }
+        ScrollFetch::Relative(n) => {
+            let n = i128::from(n);
+            let current = match current {
+                Position::BeforeStart if n <= 0 => return Position::BeforeStart,
+                Position::AfterEnd if n >= 0 => return Position::AfterEnd,
+                Position::BeforeStart => 0,
+                Position::Block(start) => i128::from(start) + 1,
+                Position::AfterEnd => total + 1,
+            };
+            let start = current + n;
+            if start < 1 {
+                if -n > rows {
+                    return Position::BeforeStart;
+                }
+                1
+            } else {
+                start
+            }
+        }
+    };
+    if start > total {
+        Position::AfterEnd
+    } else {
+        Position::Block((start - 1) as u64)
+    }
+}
+
+/// Forward-only result replayed from a spill: every row is written once as
+/// `[len: i32 LE, -1 = NULL][bytes]` per cell, with the offset of each row
+/// kept in memory.
+struct EmulatedScroll {
+    spill: DiskSpillStream,
+    /// Start offset of each row, plus the end of the last one.
+    row_offsets: Vec<u64>,
+    position: Position,
+}
+
+impl EmulatedScroll {
+    fn new(spill: DiskSpillStream) -> Self {
+        Self {
+            row_offsets: vec![spill.written_len()],
+            spill,
+            position: Position::BeforeStart,
+        }
+    }
+
+    fn total_rows(&self) -> u64 {
+        (self.row_offsets.len() - 1) as u64
+    }
+
+    fn append(&mut self, rows: &RowBuffer) -> Result<()> {
+        let mut record = Vec::new();
+        for row in &rows.rows {
+            record.clear();
+            for cell in row {
+                match cell {
+                    Some(bytes) => {
+                        let len = i32::try_from(bytes.len()).map_err(|_| {
+                            OdbcError::ResourceLimitReached(format!(
+                                "cell of {} bytes too large to spill",
+                                bytes.len()
+                            ))
+                        })?;
+                        record.extend_from_slice(&len.to_le_bytes());
+                        record.extend_from_slice(bytes);
+                    }
+                    None => record.extend_from_slice(&(-1i32).to_le_bytes()),
+                }
+            }
+            self.spill.write_chunk(&record)?;
+            self.row_offsets.push(self.spill.written_len());
+        }
+        Ok(())
+    }
+
+    fn fetch(&mut self, fetch: ScrollFetch, rows: usize, row_buffer: &mut RowBuffer) -> Result<()> {
+        let total = self.total_rows();
+        self.position = scroll_position(self.position, fetch, total, rows as u64);
+        let Position::Block(start) = self.position else {
+            return Ok(());
+        };
+        let first = start as usize;
+        let last = (start.saturating_add(rows as u64)).min(total) as usize;
+        let offset = self.row_offsets[first];
+        let len = (self.row_offsets[last] - offset) as usize;
+        let bytes = self.spill.read_at(offset, len)?;
+
+        let corrupt = || OdbcError::InternalError("corrupt scroll spill record".to_string());
+        let columns = row_buffer.column_count();
+        let mut at = 0usize;
+        for _ in first..last {
+            let mut row = Vec::with_capacity(columns);
+            for _ in 0..columns {
+                let len_bytes = bytes.get(at..at + 4).ok_or_else(corrupt)?;
+                let len = i32::from_le_bytes(len_bytes.try_into().map_err(|_| corrupt())?);
+                at += 4;
+                if len < 0 {
+                    row.push(None);
+                    continue;
+                }
+                let end = at + len as usize;
+                row.push(Some(bytes.get(at..end).ok_or_else(corrupt)?.to_vec()));
+                at = end;
+            }
+            row_buffer.add_row(row);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::OdbcType;
+
+    fn store_with_rows(n: i32) -> (EmulatedScroll, RowBuffer) {
+        let mut rows = RowBuffer::new();
+        rows.add_column("id".to_string(), OdbcType::Integer);
+        rows.add_column("name".to_string(), OdbcType::Varchar);
+        for i in 1..=n {
+            let name = (i % 3 != 0).then(|| format!("row{i}").into_bytes());
+            rows.add_row(vec![Some(i.to_le_bytes().to_vec()), name]);
+        }
+        let mut store = EmulatedScroll::new(DiskSpillStream::default());
+        store.append(&rows).unwrap();
+        rows.rows.clear();
+        (store, rows)
+    }
+
+    fn ids(
+        store: &mut EmulatedScroll,
+        rows: &mut RowBuffer,
+        fetch: ScrollFetch,
+        n: usize,
+    ) -> Vec<i32> {
+        rows.rows.clear();
+        store.fetch(fetch, n, rows).unwrap();
+        rows.rows
+            .iter()
+            .map(|r| i32::from_le_bytes(r[0].as_deref().unwrap().try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn scroll_position_follows_fetch_scroll_rules() {
+        use Position::*;
+        use ScrollFetch::*;
+        assert_eq!(scroll_position(BeforeStart, First, 10, 3), Block(0));
+        assert_eq!(scroll_position(BeforeStart, First, 0, 3), AfterEnd);
+        assert_eq!(scroll_position(BeforeStart, Last, 10, 3), Block(7));
+        assert_eq!(scroll_position(BeforeStart, Last, 2, 3), Block(0));
+        assert_eq!(scroll_position(BeforeStart, Absolute(4), 10, 3), Block(3));
+        assert_eq!(scroll_position(BeforeStart, Absolute(11), 10, 3), AfterEnd);
+        assert_eq!(
+            scroll_position(BeforeStart, Absolute(0), 10, 3),
+            BeforeStart
+        );
+        assert_eq!(scroll_position(BeforeStart, Absolute(-2), 10, 3), Block(8));
+        assert_eq!(
+            scroll_position(BeforeStart, Absolute(-12), 10, 3),
+            BeforeStart
+        );
+        assert_eq!(
+            scroll_position(BeforeStart, Absolute(-12), 10, 20),
+            Block(0)
+        );
+        assert_eq!(scroll_position(Block(5), Relative(2), 10, 3), Block(7));
+        assert_eq!(scroll_position(Block(5), Relative(-7), 10, 3), BeforeStart);
+        assert_eq!(scroll_position(Block(1), Relative(-3), 10, 3), Block(0));
+        assert_eq!(scroll_position(Block(8), Relative(3), 10, 3), AfterEnd);
+        assert_eq!(scroll_position(BeforeStart, Relative(2), 10, 3), Block(1));
+        assert_eq!(
+            scroll_position(BeforeStart, Relative(-1), 10, 3),
+            BeforeStart
+        );
+        assert_eq!(scroll_position(AfterEnd, Relative(-3), 10, 3), Block(7));
+        assert_eq!(scroll_position(AfterEnd, Relative(1), 10, 3), AfterEnd);
+    }
+
+    #[test]
+    fn emulated_scroll_replays_blocks_from_spill() {
+        let (mut store, mut rows) = store_with_rows(10);
+        assert_eq!(store.total_rows(), 10);
+        assert_eq!(
+            ids(&mut store, &mut rows, ScrollFetch::First, 4),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(
+            ids(&mut store, &mut rows, ScrollFetch::Relative(4), 4),
+            [5, 6, 7, 8]
+        );
+        assert_eq!(
+            ids(&mut store, &mut rows, ScrollFetch::Relative(4), 4),
+            [9, 10]
+        );
+        assert!(ids(&mut store, &mut rows, ScrollFetch::Relative(4), 4).is_empty());
+        assert_eq!(ids(&mut store, &mut rows, ScrollFetch::Last, 3), [8, 9, 10]);
+        assert_eq!(
+            ids(&mut store, &mut rows, ScrollFetch::Absolute(-4), 2),
+            [7, 8]
+        );
+
+        rows.rows.clear();
+        store.fetch(ScrollFetch::Absolute(3), 1, &mut rows).unwrap();
+        assert_eq!(rows.rows[0][1], None);
+        store.fetch(ScrollFetch::Absolute(2), 1, &mut rows).unwrap();
+        assert_eq!(rows.rows[1][1].as_deref(), Some(&b"row2"[..]));
+    }
+
+    #[test]
+    fn emulated_scroll_over_empty_result() {
+        let (mut store, mut rows) = store_with_rows(0);
+        assert!(ids(&mut store, &mut rows, ScrollFetch::First, 5).is_empty());
+        assert!(ids(&mut store, &mut rows, ScrollFetch::Last, 5).is_empty());
+        assert!(ids(&mut store, &mut rows, ScrollFetch::Relative(-1), 5).is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//! Bounded hand-off between a stream worker and the consumer fetching its
+//! chunks (`odbc_stream_fetch`).
+//!
+//! The worker queues each encoded batch. Once the consumer lags by
+//! [`StreamBufferPolicy::max_batches`] batches or
+//! [`StreamBufferPolicy::max_bytes`] bytes, the worker either waits for it
+//! ([`StreamOverflow::Block`], the cursor stays open meanwhile) or appends the
+//! batch to a [`DiskSpillStream`] and keeps fetching ([`StreamOverflow::Spill`]).
+//! Batches always come out in order; a spill file is dropped as soon as the
+//! consumer has read everything in it.
+
+use crate::engine::core::{DiskSpillStream, SpillOptions};
+use crate::engine::streaming::BatchedMessage;
+use crate::error::{OdbcError, Result};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::mpsc::{RecvError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// Threshold of the spill's own in-memory buffer; beyond it spilled batches
+/// go to a temp file.
+const SPILL_THRESHOLD_MB: usize = 1;
+
+/// What a stream worker does with a batch that does not fit the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamOverflow {
+    /// Wait until the consumer has fetched enough.
+    #[default]
+    Block,
+    /// Write the batch to a temp file and keep fetching.
+    Spill,
+}
+
+/// Memory budget of one stream's batch buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamBufferPolicy {
+    /// Batches held in memory (at least 1).
+    pub max_batches: usize,
+    /// Bytes held in memory; `None` = no byte limit. A batch larger than the
+    /// budget is still accepted when nothing else is buffered.
+    pub max_bytes: Option<u64>,
+    pub overflow: StreamOverflow,
+}
+
+impl StreamBufferPolicy {
+    /// One batch in flight, worker blocks: the historical behaviour.
+    pub const DEFAULT: Self = Self {
+        max_batches: 1,
+        max_bytes: None,
+        overflow: StreamOverflow::Block,
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        if self.max_batches == 0 {
+            return Err(OdbcError::ValidationError(
+                "stream buffer max_batches must be at least 1".to_string(),
+            ));
+        }
+        if self.max_bytes == Some(0) {
+            return Err(OdbcError::ValidationError(
+                "stream buffer max_bytes must be positive (or unset)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn fits(&self, queue: &Queue, len: usize) -> bool {
+        if queue.memory_batches == 0 {
+            return true;
+        }
+        queue.memory_batches < self.max_batches
+            && self
+                .max_bytes
+                .is_none_or(|max| queue.stats.buffered_bytes + len as u64 <= max)
+    }
+}
+
+impl Default for StreamBufferPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Buffer statistics of one stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StreamBufferStats {
+    /// Batches and bytes waiting in memory now.
+    pub buffered_batches: u64,
+    pub buffered_bytes: u64,
+    /// Most batches / bytes ever waiting in memory at once.
+    pub high_water_batches: u64,
+    pub high_water_bytes: u64,
+    /// Batches and bytes written to the spill so far.
+    pub spilled_batches: u64,
+    pub spilled_bytes: u64,
+    /// Spilled bytes not fetched yet.
+    pub spill_pending_bytes: u64,
+    /// Times the worker had to wait for the consumer.
+    pub producer_waits: u64,
+}
+
+enum Entry {
+    Message(BatchedMessage),
+    Spilled { offset: u64, len: usize },
+}
+
+struct Queue {
+    entries: VecDeque<Entry>,
+    memory_batches: usize,
+    /// Queued entries that live in `spill`.
+    spilled_entries: usize,
+    spill: Option<DiskSpillStream>,
+    /// How batches that overflow to `spill` are stored.
+    spill_options: SpillOptions,
+    stats: StreamBufferStats,
+    sender_alive: bool,
+    receiver_alive: bool,
+}
+
+impl Queue {
+    fn push_memory(&mut self, message: BatchedMessage) {
+        if let BatchedMessage::Batch(bytes) = &message {
+            self.memory_batches += 1;
+            let stats = &mut self.stats;
+            stats.buffered_bytes += bytes.len() as u64;
+            stats.buffered_batches = self.memory_batches as u64;
+            stats.high_water_batches = stats.high_water_batches.max(stats.buffered_batches);
+            stats.high_water_bytes = stats.high_water_bytes.max(stats.buffered_bytes);
+        }
+        self.entries.push_back(Entry::Message(message));
+    }
+
+    fn push_spilled(&mut self, bytes: &[u8]) -> Result<()> {
+        let spill = self.spill.get_or_insert_with(|| {
+            DiskSpillStream::with_options(SPILL_THRESHOLD_MB, self.spill_options)
+        });
+        let offset = spill.written_len();
+        spill.write_chunk(bytes)?;
+        self.entries.push_back(Entry::Spilled {
+            offset,
+            len: bytes.len(),
+        });
+        self.spilled_entries += 1;
+        self.stats.spilled_batches += 1;
+        self.stats.spilled_bytes += bytes.len() as u64;
+        self.stats.spill_pending_bytes += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<BatchedMessage> {
+        let message = match self.entries.pop_front()? {
+            Entry::Message(message) => {
+                if let BatchedMessage::Batch(bytes) = &message {
+                    self.memory_batches -= 1;
+                    self.stats.buffered_bytes -= bytes.len() as u64;
+                    self.stats.buffered_batches = self.memory_batches as u64;
+                }
+                message
+            }
+            Entry::Spilled { offset, len } => {
+                self.spilled_entries -= 1;
+                self.stats.spill_pending_bytes -= len as u64;
+                let read = match self.spill.as_mut() {
+                    Some(spill) => spill.read_at(offset, len),
+                    None => Err(OdbcError::InternalError("stream spill missing".to_string())),
+                };
+                if self.spilled_entries == 0 {
+                    // Everything spilled was fetched: drop (and delete) the file.
+                    self.spill = None;
+                }
+                match read {
+                    Ok(bytes) => BatchedMessage::Batch(bytes),
+                    Err(e) => BatchedMessage::Error(e.to_string()),
+                }
+            }
+        };
+        Some(message)
+    }
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+    policy: StreamBufferPolicy,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Worker side of a stream buffer.
+pub(crate) struct StreamSender {
+    shared: Arc<Shared>,
+}
+
+/// Consumer side of a stream buffer; `recv` / `try_recv` behave like the
+/// `mpsc` receiver they replace.
+pub(crate) struct StreamReceiver {
+    shared: Arc<Shared>,
+}
+
+pub(crate) fn stream_channel(
+    policy: StreamBufferPolicy,
+    spill_options: SpillOptions,
+) -> (StreamSender, StreamReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(Queue {
+            entries: VecDeque::new(),
+            memory_batches: 0,
+            spilled_entries: 0,
+            spill: None,
+            spill_options,
+            stats: StreamBufferStats::default(),
+            sender_alive: true,
+            receiver_alive: true,
+        }),
+        changed: Condvar::new(),
+        policy,
+    });
+    (
+        StreamSender {
+            shared: Arc::clone(&shared),
+        },
+        StreamReceiver { shared },
+    )
+}
+
+impl StreamSender {
+    /// Queues `message`. A batch over budget waits or spills per the policy;
+    /// end-of-stream messages are always accepted. Fails once the receiver is
+    /// gone.
+    pub(crate) fn send(&self, message: BatchedMessage) -> Result<()> {
+        let policy = self.shared.policy;
+        let mut queue = self.shared.lock();
+        let mut waited = false;
+        loop {
+            if !queue.receiver_alive {
+                return Err(OdbcError::InternalError(
+                    "Stream consumer closed".to_string(),
+                ));
+            }
+            let len = match &message {
+                BatchedMessage::Batch(bytes) => bytes.len(),
+                _ => 0,
+            };
+            let is_batch = matches!(message, BatchedMessage::Batch(_));
+            if !is_batch || policy.fits(&queue, len) {
+                queue.push_memory(message);
+                break;
+            }
+            match policy.overflow {
+                StreamOverflow::Block => {
+                    if !waited {
+                        queue.stats.producer_waits += 1;
+                        waited = true;
+                    }
+                    queue = self
+                        .shared
+                        .changed
+                        .wait(queue)
+                        .unwrap_or_else(|e| e.into_inner());
+                }
+                StreamOverflow::Spill => {
+                    let BatchedMessage::Batch(bytes) = &message else {
+                        unreachable!("only batches are spilled");
+                    };
+                    queue.push_spilled(bytes)?;
+                    break;
+                }
+            }
+        }
+        drop(queue);
+        self.shared.changed.notify_all();
+        Ok(())
+    }
+}
+
+impl Drop for StreamSender {
+    fn drop(&mut self) {
+        self.shared.lock().sender_alive = false;
+        self.shared.changed.notify_all();
+    }
+}
+
+impl StreamReceiver {
+    /// Next message, waiting for the worker if none is queued.
+    pub(crate) fn recv(&self) -> std::result::Result<BatchedMessage, RecvError> {
+        let mut queue = self.shared.lock();
+        loop {
+            if let Some(message) = queue.pop() {
+                drop(queue);
+                self.shared.changed.notify_all();
+                return Ok(message);
+            }
+            if !queue.sender_alive {
+                return Err(RecvError);
+            }
+            queue = self
+                .shared
+                .changed
+                .wait(queue)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    pub(crate) fn try_recv(&self) -> std::result::Result<BatchedMessage, TryRecvError> {
+        let mut queue = self.shared.lock();
+        match queue.pop() {
+            Some(message) => {
+                drop(queue);
+                self.shared.changed.notify_all();
+                Ok(message)
+            }
+            None if queue.sender_alive => Err(TryRecvError::Empty),
+            None => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> StreamBufferStats {
+        self.shared.lock().stats
+    }
+}
+
+impl Drop for StreamReceiver {
+    fn drop(&mut self) {
+        let mut queue = self.shared.lock();
+        queue.receiver_alive = false;
+        queue.entries.clear();
+        queue.spill = None;
+        drop(queue);
+        self.shared.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn batch(len: usize, fill: u8) -> BatchedMessage {
+        BatchedMessage::Batch(vec![fill; len])
+    }
+
+    fn recv_batch(rx: &StreamReceiver) -> Vec<u8> {
+        match rx.recv() {
+            Ok(BatchedMessage::Batch(bytes)) => bytes,
+            _ => panic!("expected a batch"),
+        }
+    }
+
+    #[test]
+    fn block_policy_holds_worker_at_batch_limit() {
+        let (tx, rx) = stream_channel(
+            StreamBufferPolicy {
+                max_batches: 2,
+                ..StreamBufferPolicy::DEFAULT
+            },
+            SpillOptions::PLAIN,
+        );
+        let worker = std::thread::spawn(move || {
+            for i in 0..5u8 {
+                tx.send(batch(10, i)).unwrap();
+            }
+            tx.send(BatchedMessage::Done).unwrap();
+        });
+        let started = std::time::Instant::now();
+        while rx.stats().producer_waits == 0 && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(rx.stats().buffered_batches, 2);
+        assert_eq!(rx.stats().producer_waits, 1);
+
+        for i in 0..5u8 {
+            assert_eq!(recv_batch(&rx), vec![i; 10]);
+        }
+        assert!(matches!(rx.recv(), Ok(BatchedMessage::Done)));
+        worker.join().unwrap();
+        let stats = rx.stats();
+        assert_eq!(stats.high_water_batches, 2);
+        assert_eq!(stats.high_water_bytes, 20);
+        assert_eq!(stats.buffered_bytes, 0);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+
+    #[test]
+    fn byte_budget_admits_one_oversized_batch() {
+        let (tx, rx) = stream_channel(
+            StreamBufferPolicy {
+                max_batches: 10,
+                max_bytes: Some(8),
+                overflow: StreamOverflow::Block,
+            },
+            SpillOptions::PLAIN,
+        );
+        tx.send(batch(32, 1)).unwrap();
+        assert_eq!(rx.stats().buffered_bytes, 32);
+        let policy = tx.shared.policy;
+        assert!(!policy.fits(&tx.shared.lock(), 1));
+        assert_eq!(recv_batch(&rx).len(), 32);
+    }
+
+    #[test]
+    fn spill_policy_keeps_order_and_drops_file_when_drained() {
+        let (tx, rx) = stream_channel(
+            StreamBufferPolicy {
+                max_batches: 1,
+                max_bytes: None,
+                overflow: StreamOverflow::Spill,
+            },
+            SpillOptions::PLAIN,
+        );
+        for i in 0..4u8 {
+            tx.send(batch(100, i)).unwrap();
+        }
+        tx.send(BatchedMessage::Done).unwrap();
+        drop(tx);
+
+        let stats = rx.stats();
+        assert_eq!(stats.buffered_batches, 1);
+        assert_eq!(stats.spilled_batches, 3);
+        assert_eq!(stats.spill_pending_bytes, 300);
+        assert_eq!(stats.producer_waits, 0);
+        for i in 0..4u8 {
+            assert_eq!(recv_batch(&rx), vec![i; 100]);
+        }
+        assert!(rx.shared.lock().spill.is_none());
+        assert_eq!(rx.stats().spill_pending_bytes, 0);
+        assert!(matches!(rx.recv(), Ok(BatchedMessage::Done)));
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn spill_uses_the_options_of_its_stream() {
+        let (tx, rx) = stream_channel(
+            StreamBufferPolicy {
+                max_batches: 1,
+                max_bytes: None,
+                overflow: StreamOverflow::Spill,
+            },
+            SpillOptions::PLAIN.with_encryption(true),
+        );
+        for i in 0..3u8 {
+            tx.send(batch(100, i)).unwrap();
+        }
+        assert!(rx.shared.lock().spill_options.encrypt);
+        for i in 0..3u8 {
+            assert_eq!(recv_batch(&rx), vec![i; 100]);
+        }
+    }
+
+    #[test]
+    fn dropped_receiver_releases_blocked_worker() {
+        let (tx, rx) = stream_channel(StreamBufferPolicy::DEFAULT, SpillOptions::PLAIN);
+        tx.send(batch(1, 0)).unwrap();
+        let worker = std::thread::spawn(move || tx.send(batch(1, 1)));
+        std::thread::sleep(Duration::from_millis(20));
+        drop(rx);
+        assert!(worker.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn policy_rejects_empty_budget() {
+        let zero_batches = StreamBufferPolicy {
+            max_batches: 0,
+            ..StreamBufferPolicy::DEFAULT
+        };
+        assert!(zero_batches.validate().is_err());
+        let zero_bytes = StreamBufferPolicy {
+            max_bytes: Some(0),
+            ..StreamBufferPolicy::DEFAULT
+        };
+        assert!(zero_bytes.validate().is_err());
+        assert!(StreamBufferPolicy::DEFAULT.validate().is_ok());
+    }
+}
@@ -1,14 +1,24 @@
-use crate::engine::cell_reader::CellReader;
-use crate::engine::core::{DiskSpillStream, DiskSpillWriter};
-use crate::engine::sqlserver_json::coalesce_for_json_rows;
+use crate::engine::block_fetch::{block_fetch_rows, fetch_all_rows, RowFetcher};
+use crate::engine::core::{
+    input_parameters, DiskSpillStream, DiskSpillWriter, DriverCapabilities, ResultOptions,
+    SpillOptions, TemporalBinding,
+};
+use crate::engine::keyset::{self, KeysetPagination};
+use crate::engine::sqlserver_json::{coalesce_for_json_rows, is_for_json_result};
+use crate::engine::stream_buffer::{
+    stream_channel, StreamBufferPolicy, StreamBufferStats, StreamReceiver,
+};
 use crate::error::{OdbcError, Result};
 use crate::handles::SharedHandleManager;
-use crate::protocol::{OdbcType, RowBuffer, RowBufferEncoder};
+use crate::plugins::PluginRegistry;
+use crate::protocol::native_cell::native_column_type;
+use crate::protocol::{
+    CellEncoding, OdbcType, OutputFormat, ParamValue, RowBuffer, RowBufferEncoder,
+};
+use crate::versioning::protocol_version::ProtocolVersion;
 use odbc_api::handles::{AsStatementRef, SqlResult, Statement};
-use odbc_api::{Connection, Cursor, CursorImpl, ResultSetMetadata};
-use std::fs::File;
-use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use odbc_api::{Connection, Cursor, CursorImpl, DataType, ResultSetMetadata};
+use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
@@ -33,6 +43,13 @@ pub const MULTI_STREAM_ITEM_TAG_ROW_COUNT: u8 = 1;
 
 pub struct StreamingExecutor {
     chunk_size: usize,
+    max_row_array_size: u32,
+    cell_encoding: CellEncoding,
+    output_format: OutputFormat,
+    params: Vec<ParamValue>,
+    buffer_policy: StreamBufferPolicy,
+    spill_options: SpillOptions,
+    temporal: TemporalBinding,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,61 +63,120 @@ pub enum AsyncStreamStatus {
 
 impl StreamingExecutor {
     pub fn new(chunk_size: usize) -> Self {
-        Self { chunk_size }
+        Self {
+            chunk_size,
+            max_row_array_size: DriverCapabilities::default().max_row_array_size,
+            cell_encoding: CellEncoding::Text,
+            output_format: OutputFormat::OdbcBinary,
+            params: Vec::new(),
+            buffer_policy: StreamBufferPolicy::DEFAULT,
+            spill_options: SpillOptions::PLAIN,
+            temporal: TemporalBinding::Standard,
+        }
     }
 
-    pub fn execute_streaming(
-        &self,
-        conn: &Connection<'static>,
-        sql: &str,
-    ) -> Result<StreamingState> {
-        let mut row_buffer = RowBuffer::new();
-        let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
+    /// Binds `params` to the positional `?` markers of every query this
+    /// executor streams.
+    pub fn with_params(mut self, params: Vec<ParamValue>) -> Self {
+        self.params = params;
+        self
+    }
 
-        let cursor = stmt.execute(()).map_err(OdbcError::from)?;
+    /// Emits row buffers for `version`; `1.1` switches the types in
+    /// [`crate::protocol::native_cell`] to fixed-width cells.
+    pub fn with_protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.cell_encoding = CellEncoding::for_version(&version);
+        self
+    }
 
-        if let Some(mut cursor) = cursor {
-            let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-            let cols_u16: u16 = cols_i16
-                .try_into()
-                .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-            let cols_usize: usize = cols_u16.into();
-
-            let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-
-            for col_idx in 1..=cols_u16 {
-                let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-                let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-                let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-                let odbc_type = OdbcType::from_odbc_sql_type(sql_type_code);
-                row_buffer.add_column(col_name.to_string(), odbc_type);
-                column_types.push(odbc_type);
+    /// Emits results as `format`. With [`OutputFormat::ArrowIpc`] the
+    /// concatenated chunks of a stream form one Arrow IPC stream: the schema
+    /// message, a record batch per fetched batch, then end-of-stream.
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Bounds the batches a background stream worker may run ahead of its
+    /// consumer (see [`crate::engine::stream_buffer`]).
+    pub fn with_buffer_policy(mut self, policy: StreamBufferPolicy) -> Self {
+        self.buffer_policy = policy;
+        self
+    }
+
+    /// Stores the spill files of this executor's streams (result spills and
+    /// overflowing stream buffers) per `options`. Default: raw bytes.
+    pub fn with_spill_options(mut self, options: SpillOptions) -> Self {
+        self.spill_options = options;
+        self
+    }
+
+    /// Binds time fractions and UTC offsets in `params` per `temporal`,
+    /// normally the connection's [`ResultOptions::temporal`]. Default:
+    /// [`TemporalBinding::Standard`].
+    pub fn with_temporal_binding(mut self, temporal: TemporalBinding) -> Self {
+        self.temporal = temporal;
+        self
+    }
+
+    /// Encodes a fully materialised result in the selected output format.
+    fn encode(&self, row_buffer: &RowBuffer) -> Result<Vec<u8>> {
+        match self.output_format.ensure_available()? {
+            #[cfg(feature = "arrow-ipc")]
+            OutputFormat::ArrowIpc => {
+                crate::protocol::arrow_stream::ArrowStreamEncoder::encode(row_buffer)
             }
+            _ => encode_row_buffer(row_buffer),
+        }
+    }
 
-            let mut cell_reader = CellReader::new();
-            while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-                let mut row_data = Vec::with_capacity(column_types.len());
+    /// Caps block-fetch rowsets at the driver's
+    /// [`DriverCapabilities::max_row_array_size`] (default: the generic
+    /// driver value).
+    pub fn with_max_row_array_size(mut self, max_row_array_size: u32) -> Self {
+        self.max_row_array_size = max_row_array_size;
+        self
+    }
 
-                for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                    let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column number".to_string())
-                    })?;
+    fn block_rows(&self, fetch_size: Option<u32>) -> usize {
+        block_fetch_rows(self.max_row_array_size, fetch_size)
+    }
 
-                    let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
+    /// True when the result can be encoded while it is fetched: binary
+    /// output that needs no `FOR JSON` coalescing across rows.
+    fn encodes_single_pass(&self, row_buffer: &RowBuffer) -> bool {
+        self.output_format == OutputFormat::OdbcBinary && !is_for_json_result(row_buffer)
+    }
 
-                    row_data.push(cell_data);
-                }
+    pub fn execute_streaming(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+    ) -> Result<StreamingState> {
+        let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding);
+        let input_params = input_parameters(&self.params, self.temporal)?;
+        let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
 
-                row_buffer.add_row(row_data);
-            }
+        let cursor = stmt
+            .execute(input_params.as_slice())
+            .map_err(OdbcError::from)?;
 
-            // FOR JSON normalisation — buffer-mode streaming materialises
-            // the full result before encoding, so it's safe (and necessary,
-            // for the SQL Server FOR JSON shape) to coalesce here. See
-            // `engine::sqlserver_json` (closes #2).
-            coalesce_for_json_rows(&mut row_buffer);
+        if let Some(mut cursor) = cursor {
+            let (column_types, data_types) = describe_columns(&mut cursor, &mut row_buffer)?;
+            let block_rows = self.block_rows(None);
+            let mut fetcher = RowFetcher::new(&mut cursor, &data_types, &column_types, block_rows)?;
 
-            let encoded = encode_row_buffer(&row_buffer)?;
+            let encoded = if self.encodes_single_pass(&row_buffer) {
+                fetcher.encode_into(&mut row_buffer, block_rows, Vec::new())?
+            } else {
+                fetcher.fetch_all(&mut row_buffer)?;
+                // FOR JSON normalisation — buffer-mode streaming materialises
+                // the full result before encoding, so it's safe (and necessary,
+                // for the SQL Server FOR JSON shape) to coalesce here. See
+                // `engine::sqlserver_json` (closes #2).
+                coalesce_for_json_rows(&mut row_buffer);
+                self.encode(&row_buffer)?
+            };
             Ok(StreamingState {
                 data: encoded,
                 offset: 0,
@@ -114,88 +190,71 @@ impl StreamingExecutor {
     /// Buffer-mode streaming with optional spill-to-disk. When `spill_threshold_mb > 0`,
     /// encodes to `DiskSpillStream`; if data exceeds threshold, spills to temp file
     /// and returns `StreamState::FileBacked` for chunked read without loading full result.
+    /// Binary results are encoded into the spill as rows are fetched, so no full
+    /// `RowBuffer` is ever held.
     pub fn execute_streaming_with_spill(
         &self,
         conn: &Connection<'static>,
         sql: &str,
         spill_threshold_mb: Option<usize>,
     ) -> Result<StreamState> {
-        let mut row_buffer = RowBuffer::new();
+        let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding);
+        let input_params = input_parameters(&self.params, self.temporal)?;
         let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
 
-        let cursor = stmt.execute(()).map_err(OdbcError::from)?;
+        let cursor = stmt
+            .execute(input_params.as_slice())
+            .map_err(OdbcError::from)?;
 
         if let Some(mut cursor) = cursor {
-            let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-            let cols_u16: u16 = cols_i16
-                .try_into()
-                .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-            let cols_usize: usize = cols_u16.into();
-
-            let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-
-            for col_idx in 1..=cols_u16 {
-                let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-                let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-                let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-                let odbc_type = OdbcType::from_odbc_sql_type(sql_type_code);
-                row_buffer.add_column(col_name.to_string(), odbc_type);
-                column_types.push(odbc_type);
+            let (column_types, data_types) = describe_columns(&mut cursor, &mut row_buffer)?;
+            let block_rows = self.block_rows(None);
+            let mut fetcher = RowFetcher::new(&mut cursor, &data_types, &column_types, block_rows)?;
+
+            let single_pass = self.encodes_single_pass(&row_buffer);
+            if !single_pass {
+                fetcher.fetch_all(&mut row_buffer)?;
+                // FOR JSON normalisation — see execute_streaming above (closes #2).
+                coalesce_for_json_rows(&mut row_buffer);
             }
 
-            let mut cell_reader = CellReader::new();
-            while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-                let mut row_data = Vec::with_capacity(column_types.len());
-
-                for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                    let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column number".to_string())
-                    })?;
-
-                    let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-
-                    row_data.push(cell_data);
-                }
-
-                row_buffer.add_row(row_data);
-            }
-
-            // FOR JSON normalisation — see execute_streaming above (closes #2).
-            coalesce_for_json_rows(&mut row_buffer);
-
             let chunk_size = self.chunk_size;
 
             if let Some(threshold_mb) = spill_threshold_mb.filter(|&t| t > 0) {
-                let mut spill = DiskSpillStream::new(threshold_mb);
+                let mut spill = DiskSpillStream::with_options(threshold_mb, self.spill_options);
                 let mut writer = DiskSpillWriter::new(&mut spill);
-                RowBufferEncoder::encode_to_writer(&row_buffer, &mut writer)
-                    .map_err(|e| OdbcError::InternalError(format!("encode to spill: {}", e)))?;
+                if single_pass {
+                    writer = fetcher.encode_into(&mut row_buffer, block_rows, writer)?;
+                } else {
+                    writer
+                        .write_all(&self.encode(&row_buffer)?)
+                        .map_err(|e| OdbcError::InternalError(format!("encode to spill: {}", e)))?;
+                }
                 writer
                     .flush()
                     .map_err(|e| OdbcError::InternalError(format!("spill flush: {}", e)))?;
 
-                match spill.finish_for_streaming_read()? {
-                    crate::engine::core::SpillReadSource::File(path) => {
-                        let total_len = std::fs::metadata(&path)
-                            .map(|m| m.len() as usize)
-                            .unwrap_or(0);
-                        Ok(StreamState::FileBacked(StreamingStateFileBacked {
-                            path,
-                            offset: 0,
-                            chunk_size,
-                            total_len,
-                        }))
-                    }
-                    crate::engine::core::SpillReadSource::Memory(data) => {
-                        Ok(StreamState::InMemory(StreamingState {
-                            data,
-                            offset: 0,
-                            chunk_size,
-                        }))
-                    }
+                if spill.is_on_disk() {
+                    let total_len = spill.written_len() as usize;
+                    Ok(StreamState::FileBacked(StreamingStateFileBacked {
+                        spill: Box::new(spill),
+                        offset: 0,
+                        chunk_size,
+                        total_len,
+                    }))
+                } else {
+                    Ok(StreamState::InMemory(StreamingState {
+                        data: spill.read_back()?,
+                        offset: 0,
+                        chunk_size,
+                    }))
                 }
             } else {
-                let encoded = encode_row_buffer(&row_buffer)?;
+                let encoded = if single_pass {
+                    fetcher.encode_into(&mut row_buffer, block_rows, Vec::new())?
+                } else {
+                    self.encode(&row_buffer)?
+                };
                 Ok(StreamState::InMemory(StreamingState {
                     data: encoded,
                     offset: 0,
@@ -229,35 +288,62 @@ impl StreamingExecutor {
     ) -> Result<()>
     where
         F: FnMut(Vec<u8>) -> Result<()>,
+    {
+        let mut encoder: Option<BatchEncoder> = None;
+        self.execute_streaming_rows(
+            conn,
+            sql,
+            fetch_size,
+            |rows| {
+                let encoder = match &mut encoder {
+                    Some(encoder) => encoder,
+                    None => encoder.insert(BatchEncoder::new(self.output_format, rows)?),
+                };
+                on_batch(encoder.encode(rows)?)
+            },
+            cancel_requested,
+        )?;
+
+        if let Some(tail) = encoder.map(BatchEncoder::finish).transpose()?.flatten() {
+            on_batch(tail)?;
+        }
+        Ok(())
+    }
+
+    /// The fetch loop behind [`Self::execute_streaming_batched`], handing
+    /// `on_rows` each batch of up to `fetch_size` decoded rows instead of an
+    /// encoded message. The columns are described on every batch; an empty
+    /// result still yields one batch with no rows. Statements that return no
+    /// cursor never call `on_rows`.
+    pub fn execute_streaming_rows<F>(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        fetch_size: usize,
+        mut on_rows: F,
+        cancel_requested: Option<Arc<AtomicBool>>,
+    ) -> Result<()>
+    where
+        F: FnMut(&RowBuffer) -> Result<()>,
     {
         let batch_size = fetch_size.max(1);
+        let input_params = input_parameters(&self.params, self.temporal)?;
         let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
-        let cursor = stmt.execute(()).map_err(OdbcError::from)?;
+        let cursor = stmt
+            .execute(input_params.as_slice())
+            .map_err(OdbcError::from)?;
 
         let mut cursor = match cursor {
             Some(c) => c,
             None => return Ok(()),
         };
 
-        let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-        let cols_u16: u16 = cols_i16
-            .try_into()
-            .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-        let cols_usize: usize = cols_u16.into();
-        let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-        let mut row_buffer = RowBuffer::new();
-
-        for col_idx in 1..=cols_u16 {
-            let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-            let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-            let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-            let odbc_type = OdbcType::from_odbc_sql_type(sql_type_code);
-            row_buffer.add_column(col_name.to_string(), odbc_type);
-            column_types.push(odbc_type);
-        }
+        let mut row_buffer = RowBuffer::with_cell_encoding(self.cell_encoding);
+        let (column_types, data_types) = describe_columns(&mut cursor, &mut row_buffer)?;
+        let block_rows = self.block_rows(Some(batch_size.try_into().unwrap_or(u32::MAX)));
+        let mut fetcher = RowFetcher::new(&mut cursor, &data_types, &column_types, block_rows)?;
 
         let mut first_batch = true;
-        let mut cell_reader = CellReader::new();
         loop {
             if cancel_requested
                 .as_ref()
@@ -267,42 +353,24 @@ impl StreamingExecutor {
             }
 
             row_buffer.rows.clear();
-            let mut rows_fetched = 0;
-
-            while rows_fetched < batch_size {
-                let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? else {
-                    break;
-                };
-                let mut row_data = Vec::with_capacity(column_types.len());
-                for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                    let col_number: u16 = (col_idx + 1).try_into().map_err(|_| {
-                        OdbcError::InternalError("Invalid column number".to_string())
-                    })?;
-                    let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-                    row_data.push(cell_data);
-                }
-                row_buffer.add_row(row_data);
-                rows_fetched += 1;
-            }
+            fetcher.fetch_into(&mut row_buffer, batch_size)?;
 
             if row_buffer.row_count() == 0 {
                 if first_batch {
-                    let encoded = encode_row_buffer(&row_buffer)?;
-                    on_batch(encoded)?;
+                    on_rows(&row_buffer)?;
                 }
                 break;
             }
 
-            let encoded = encode_row_buffer(&row_buffer)?;
-            on_batch(encoded)?;
+            on_rows(&row_buffer)?;
             first_batch = false;
         }
-
         Ok(())
     }
 
     /// Starts cursor-based batched streaming via a worker thread. Uses
-    /// `execute_streaming_batched` internally; memory is bounded to one batch.
+    /// `execute_streaming_batched` internally; how far the worker runs ahead
+    /// is bounded by [`Self::with_buffer_policy`] (one batch by default).
     /// Returns state that yields chunks on `fetch_next_chunk` until done.
     /// The HandleManager lock is held only briefly to clone the connection;
     /// the per-connection lock is held for the stream duration.
@@ -316,7 +384,7 @@ impl StreamingExecutor {
     ) -> Result<BatchedStreamingState> {
         let fetch_size = fetch_size.max(1);
         let chunk_size = chunk_size.max(1);
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(1);
+        let (tx, rx) = stream_channel(self.buffer_policy, self.spill_options);
         let cancel_requested = Arc::new(AtomicBool::new(false));
 
         let conn_arc = {
@@ -330,6 +398,13 @@ impl StreamingExecutor {
                 .map_err(|e| OdbcError::InternalError(format!("Invalid connection: {}", e)))?
         };
 
+        let max_row_array_size = self.max_row_array_size;
+        let cell_encoding = self.cell_encoding;
+        let output_format = self.output_format;
+        let params = self.params.clone();
+        let buffer_policy = self.buffer_policy;
+        let spill_options = self.spill_options;
+        let temporal = self.temporal;
         let join = std::thread::spawn({
             let sql = sql.clone();
             let cancel = Arc::clone(&cancel_requested);
@@ -340,15 +415,21 @@ impl StreamingExecutor {
                     ));
                     return;
                 };
-                let executor = StreamingExecutor::new(chunk_size);
+                let executor = StreamingExecutor {
+                    chunk_size,
+                    max_row_array_size,
+                    cell_encoding,
+                    output_format,
+                    params,
+                    buffer_policy,
+                    spill_options,
+                    temporal,
+                };
                 match executor.execute_streaming_batched(
                     conn_guard.connection(),
                     &sql,
                     fetch_size,
-                    |batch| {
-                        tx.send(BatchedMessage::Batch(batch))
-                            .map_err(|e| OdbcError::InternalError(e.to_string()))
-                    },
+                    |batch| tx.send(BatchedMessage::Batch(batch)),
                     Some(cancel),
                 ) {
                     Ok(()) => {
@@ -366,17 +447,188 @@ impl StreamingExecutor {
             }
         });
 
-        Ok(BatchedStreamingState {
-            receiver: rx,
-            current_batch: None,
-            offset: 0,
+        Ok(BatchedStreamingState::new(
+            rx,
             chunk_size,
-            done: false,
-            stream_error: None,
-            cancelled: false,
             cancel_requested,
-            _join: Some(join),
-        })
+            Some(join),
+        ))
+    }
+
+    /// Starts a resumable batched stream over `sql` that walks the result
+    /// in `pagination`'s key order, one page query at a time (see
+    /// [`crate::engine::keyset`]). The page SQL is built for the engine the
+    /// connection reports. Every batch is preceded by a continuation token
+    /// holding its last key tuple, exposed through
+    /// [`BatchedStreamingState::continuation_token`] once the batch has been
+    /// fully fetched.
+    pub fn start_keyset_stream(
+        &self,
+        handles: SharedHandleManager,
+        conn_id: u32,
+        sql: String,
+        pagination: KeysetPagination,
+        fetch_size: usize,
+        chunk_size: usize,
+    ) -> Result<BatchedStreamingState> {
+        let fetch_size = fetch_size.max(1);
+        let chunk_size = chunk_size.max(1);
+        // Reject a token issued for another query before spawning anything.
+        pagination.start_after(&sql)?;
+        let (tx, rx) = stream_channel(self.buffer_policy, self.spill_options);
+        let cancel_requested = Arc::new(AtomicBool::new(false));
+
+        let conn_arc = {
+            let Ok(guard) = handles.lock() else {
+                return Err(OdbcError::InternalError(
+                    "Failed to lock HandleManager".to_string(),
+                ));
+            };
+            guard
+                .get_connection(conn_id)
+                .map_err(|e| OdbcError::InternalError(format!("Invalid connection: {}", e)))?
+        };
+
+        let executor = self.with_page_params(self.params.clone());
+        let join = std::thread::spawn({
+            let cancel = Arc::clone(&cancel_requested);
+            move || {
+                let Ok(conn_guard) = conn_arc.lock() else {
+                    let _ = tx.send(BatchedMessage::Error(
+                        "Failed to lock connection".to_string(),
+                    ));
+                    return;
+                };
+                match executor.execute_keyset_batched(
+                    conn_guard.connection(),
+                    &sql,
+                    &pagination,
+                    fetch_size,
+                    |message| tx.send(message),
+                    Some(cancel),
+                ) {
+                    Ok(()) => {
+                        let _ = tx.send(BatchedMessage::Done);
+                    }
+                    Err(e) => {
+                        let msg = e.to_string();
+                        let _ = tx.send(if msg.contains("cancelled") {
+                            BatchedMessage::Cancelled
+                        } else {
+                            BatchedMessage::Error(msg)
+                        });
+                    }
+                }
+            }
+        });
+
+        let mut state = BatchedStreamingState::new(rx, chunk_size, cancel_requested, Some(join));
+        state.resumable = true;
+        Ok(state)
+    }
+
+    /// Copy of this executor binding `params` instead of its own.
+    fn with_page_params(&self, params: Vec<ParamValue>) -> Self {
+        Self {
+            chunk_size: self.chunk_size,
+            max_row_array_size: self.max_row_array_size,
+            cell_encoding: self.cell_encoding,
+            output_format: self.output_format,
+            params,
+            buffer_policy: self.buffer_policy,
+            spill_options: self.spill_options,
+            temporal: self.temporal,
+        }
+    }
+
+    /// The page loop behind [`Self::start_keyset_stream`]. Each page binds
+    /// the executor's parameters followed by the predicate values of the
+    /// previous page's last key; a page shorter than
+    /// [`KeysetPagination::page_rows`] ends the walk. `on_message` gets a
+    /// [`BatchedMessage::Checkpoint`] ahead of every non-empty batch.
+    fn execute_keyset_batched<F>(
+        &self,
+        conn: &Connection<'static>,
+        sql: &str,
+        pagination: &KeysetPagination,
+        fetch_size: usize,
+        mut on_message: F,
+        cancel_requested: Option<Arc<AtomicBool>>,
+    ) -> Result<()>
+    where
+        F: FnMut(BatchedMessage) -> Result<()>,
+    {
+        let dbms_name = conn
+            .database_management_system_name()
+            .map_err(OdbcError::from)?;
+        let plugin_id = PluginRegistry::plugin_id_for_dbms_name(&dbms_name).ok_or_else(|| {
+            OdbcError::UnsupportedFeature(format!(
+                "No keyset pagination support for DBMS {dbms_name:?}"
+            ))
+        })?;
+        let registry = PluginRegistry::new();
+        let keys: Vec<&str> = pagination.keys().iter().map(String::as_str).collect();
+        let page_rows = pagination.page_rows();
+
+        let mut last_key = pagination.start_after(sql)?;
+        let mut encoder: Option<BatchEncoder> = None;
+        loop {
+            let page_sql = registry.build_keyset_page_sql(
+                plugin_id,
+                sql,
+                &keys,
+                last_key.is_some(),
+                page_rows,
+            )?;
+            let mut params = self.params.clone();
+            if let Some(last) = &last_key {
+                params.extend(keyset::predicate_params(last));
+            }
+
+            let mut page_count: u64 = 0;
+            let mut page_last_key = None;
+            let mut key_columns: Option<Vec<usize>> = None;
+            self.with_page_params(params).execute_streaming_rows(
+                conn,
+                &page_sql,
+                fetch_size,
+                |rows| {
+                    // Only an empty first page yields an empty batch.
+                    if rows.row_count() == 0 && encoder.is_some() {
+                        return Ok(());
+                    }
+                    let encoder = match &mut encoder {
+                        Some(encoder) => encoder,
+                        None => encoder.insert(BatchEncoder::new(self.output_format, rows)?),
+                    };
+                    if rows.row_count() > 0 {
+                        let columns = match &key_columns {
+                            Some(columns) => columns,
+                            None => {
+                                key_columns.insert(keyset::key_columns(rows, pagination.keys())?)
+                            }
+                        };
+                        let tuple = keyset::last_key_tuple(rows, columns)?;
+                        let token = pagination.token_for(sql, tuple.clone()).encode()?;
+                        on_message(BatchedMessage::Checkpoint(token))?;
+                        page_count += rows.row_count() as u64;
+                        page_last_key = Some(tuple);
+                    }
+                    on_message(BatchedMessage::Batch(encoder.encode(rows)?))
+                },
+                cancel_requested.clone(),
+            )?;
+
+            if page_count < u64::from(page_rows) {
+                break;
+            }
+            last_key = page_last_key;
+        }
+
+        if let Some(tail) = encoder.map(BatchEncoder::finish).transpose()?.flatten() {
+            on_message(BatchedMessage::Batch(tail))?;
+        }
+        Ok(())
     }
 
     /// Starts async cursor-based streaming with explicit poll support.
@@ -393,7 +645,7 @@ impl StreamingExecutor {
     ) -> Result<AsyncStreamingState> {
         let fetch_size = fetch_size.max(1);
         let chunk_size = chunk_size.max(1);
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(1);
+        let (tx, rx) = stream_channel(self.buffer_policy, self.spill_options);
         let cancel_requested = Arc::new(AtomicBool::new(false));
 
         let conn_arc = {
@@ -407,6 +659,13 @@ impl StreamingExecutor {
                 .map_err(|e| OdbcError::InternalError(format!("Invalid connection: {}", e)))?
         };
 
+        let max_row_array_size = self.max_row_array_size;
+        let cell_encoding = self.cell_encoding;
+        let output_format = self.output_format;
+        let params = self.params.clone();
+        let buffer_policy = self.buffer_policy;
+        let spill_options = self.spill_options;
+        let temporal = self.temporal;
         let join = std::thread::spawn({
             let sql = sql.clone();
             let cancel = Arc::clone(&cancel_requested);
@@ -417,15 +676,21 @@ impl StreamingExecutor {
                     ));
                     return;
                 };
-                let executor = StreamingExecutor::new(chunk_size);
+                let executor = StreamingExecutor {
+                    chunk_size,
+                    max_row_array_size,
+                    cell_encoding,
+                    output_format,
+                    params,
+                    buffer_policy,
+                    spill_options,
+                    temporal,
+                };
                 match executor.execute_streaming_batched(
                     conn_guard.connection(),
                     &sql,
                     fetch_size,
-                    |batch| {
-                        tx.send(BatchedMessage::Batch(batch))
-                            .map_err(|e| OdbcError::InternalError(e.to_string()))
-                    },
+                    |batch| tx.send(BatchedMessage::Batch(batch)),
                     Some(cancel),
                 ) {
                     Ok(()) => {
@@ -457,6 +722,48 @@ impl StreamingExecutor {
     }
 }
 
+/// Per-batch encoder of [`StreamingExecutor::execute_streaming_batched`]:
+/// a self-contained binary message per batch, or successive pieces of one
+/// Arrow IPC stream.
+enum BatchEncoder {
+    Binary,
+    #[cfg(feature = "arrow-ipc")]
+    Arrow(crate::protocol::arrow_stream::ArrowStreamEncoder),
+}
+
+impl BatchEncoder {
+    #[cfg_attr(not(feature = "arrow-ipc"), allow(unused_variables))]
+    fn new(format: OutputFormat, row_buffer: &RowBuffer) -> Result<Self> {
+        match format.ensure_available()? {
+            #[cfg(feature = "arrow-ipc")]
+            OutputFormat::ArrowIpc => Ok(Self::Arrow(
+                crate::protocol::arrow_stream::ArrowStreamEncoder::new(row_buffer)?,
+            )),
+            _ => Ok(Self::Binary),
+        }
+    }
+
+    fn encode(&mut self, row_buffer: &RowBuffer) -> Result<Vec<u8>> {
+        match self {
+            Self::Binary => encode_row_buffer(row_buffer),
+            #[cfg(feature = "arrow-ipc")]
+            Self::Arrow(encoder) => {
+                encoder.write_rows(row_buffer)?;
+                Ok(encoder.take_bytes())
+            }
+        }
+    }
+
+    /// Trailing bytes to emit after the last batch, if the format has any.
+    fn finish(self) -> Result<Option<Vec<u8>>> {
+        match self {
+            Self::Binary => Ok(None),
+            #[cfg(feature = "arrow-ipc")]
+            Self::Arrow(encoder) => encoder.finish().map(Some),
+        }
+    }
+}
+
 /// Drive a prepared statement that may yield multiple result sets and call
 /// `on_item` for **every** result set or row-count, in order. Each item is
 /// wire-framed as `[tag: u8][len: u32 LE][payload]`. Used by the streaming
@@ -467,13 +774,17 @@ impl StreamingExecutor {
 fn drive_multi_result_stream<F>(
     conn: &Connection<'static>,
     sql: &str,
+    params: &[ParamValue],
+    options: &ResultOptions,
     on_item: &mut F,
     cancel_requested: Option<Arc<AtomicBool>>,
 ) -> Result<()>
 where
     F: FnMut(Vec<u8>) -> Result<()>,
 {
+    let input_params = input_parameters(params, options.temporal)?;
     let mut stmt = conn.prepare(sql).map_err(OdbcError::from)?;
+    let block_rows = options.block_rows(None);
     let cancel_check = || {
         cancel_requested
             .as_ref()
@@ -484,12 +795,14 @@ where
     // borrow on `stmt`. Same SQLCloseCursor avoidance pattern as
     // `ExecutionEngine::execute_multi_result_inner` (M1 fix in v3.2.0).
     let had_initial_cursor = {
-        let initial_cursor = stmt.execute(()).map_err(OdbcError::from)?;
+        let initial_cursor = stmt
+            .execute(input_params.as_slice())
+            .map_err(OdbcError::from)?;
         if let Some(mut cursor) = initial_cursor {
             if cancel_check() {
                 return Err(OdbcError::Cancelled);
             }
-            let encoded = encode_cursor_to_buffer(&mut cursor)?;
+            let encoded = encode_cursor_to_buffer(&mut cursor, block_rows)?;
             on_item(frame_item(MULTI_STREAM_ITEM_TAG_RESULT_SET, encoded)?)?;
             let _stmt_ref = cursor.into_stmt();
             true
@@ -549,7 +862,7 @@ where
         if cols > 0 {
             // SAFETY: just observed cols > 0 with no other live borrow.
             let mut cursor = unsafe { CursorImpl::new(stmt.as_stmt_ref()) };
-            let encoded = encode_cursor_to_buffer(&mut cursor)?;
+            let encoded = encode_cursor_to_buffer(&mut cursor, block_rows)?;
             on_item(frame_item(MULTI_STREAM_ITEM_TAG_RESULT_SET, encoded)?)?;
             let _stmt_ref = cursor.into_stmt();
         } else {
@@ -569,45 +882,57 @@ where
 /// Read every row from `cursor` into a `RowBuffer` and encode it via
 /// `RowBufferEncoder` (binary protocol v1). Local helper to avoid coupling
 /// `StreamingExecutor` with `ExecutionEngine`.
-fn encode_cursor_to_buffer<C>(cursor: &mut C) -> Result<Vec<u8>>
+fn encode_cursor_to_buffer<C>(cursor: &mut C, block_rows: usize) -> Result<Vec<u8>>
 where
     C: Cursor + ResultSetMetadata,
 {
     let mut row_buffer = RowBuffer::new();
+    let (column_types, data_types) = describe_columns(cursor, &mut row_buffer)?;
+    fetch_all_rows(
+        cursor,
+        &data_types,
+        &column_types,
+        block_rows,
+        &mut row_buffer,
+    )?;
+
+    // FOR JSON normalisation — multi-result item is fully materialised
+    // before framing, so the same coalescing applies here (closes #2).
+    coalesce_for_json_rows(&mut row_buffer);
+
+    encode_row_buffer(&row_buffer)
+}
+
+/// Registers the result-set columns on `row_buffer` and returns their wire
+/// types plus the ODBC data types used to size block-fetch buffers. Native
+/// row buffers get the refined native-cell types.
+pub(crate) fn describe_columns<C: ResultSetMetadata>(
+    cursor: &mut C,
+    row_buffer: &mut RowBuffer,
+) -> Result<(Vec<OdbcType>, Vec<DataType>)> {
     let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
     let cols_u16: u16 = cols_i16
         .try_into()
         .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
     let cols_usize: usize = cols_u16.into();
     let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
+    let mut data_types: Vec<DataType> = Vec::with_capacity(cols_usize);
 
     for col_idx in 1..=cols_u16 {
         let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
         let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
         let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
         let odbc_type = OdbcType::from_odbc_sql_type(sql_type_code);
-        row_buffer.add_column(col_name.to_string(), odbc_type);
+        let odbc_type = if row_buffer.cell_encoding.is_native() {
+            native_column_type(&col_type).unwrap_or(odbc_type)
+        } else {
+            odbc_type
+        };
+        row_buffer.add_described_column(col_name.to_string(), odbc_type, col_type);
         column_types.push(odbc_type);
+        data_types.push(col_type);
     }
-
-    let mut cell_reader = CellReader::new();
-    while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-        let mut row_data = Vec::with_capacity(column_types.len());
-        for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-            let col_number: u16 = (col_idx + 1)
-                .try_into()
-                .map_err(|_| OdbcError::InternalError("Invalid column number".to_string()))?;
-            let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-            row_data.push(cell_data);
-        }
-        row_buffer.add_row(row_data);
-    }
-
-    // FOR JSON normalisation — multi-result item is fully materialised
-    // before framing, so the same coalescing applies here (closes #2).
-    coalesce_for_json_rows(&mut row_buffer);
-
-    encode_row_buffer(&row_buffer)
+    Ok((column_types, data_types))
 }
 
 fn encode_row_buffer(row_buffer: &RowBuffer) -> Result<Vec<u8>> {
@@ -643,12 +968,25 @@ pub fn start_multi_batched_stream(
     sql: String,
     chunk_size: usize,
 ) -> Result<BatchedStreamingState> {
-    spawn_multi_stream_worker(handles, conn_id, sql, chunk_size, /* async = */ false).map(
-        |either| match either {
-            EitherStream::Batched(b) => b,
-            EitherStream::Async(_) => unreachable!(),
-        },
+    start_multi_batched_stream_with_params(handles, conn_id, sql, Vec::new(), chunk_size)
+}
+
+/// [`start_multi_batched_stream`] with `params` bound to the batch's `?`
+/// markers.
+pub fn start_multi_batched_stream_with_params(
+    handles: SharedHandleManager,
+    conn_id: u32,
+    sql: String,
+    params: Vec<ParamValue>,
+    chunk_size: usize,
+) -> Result<BatchedStreamingState> {
+    spawn_multi_stream_worker(
+        handles, conn_id, sql, params, chunk_size, /* async = */ false,
     )
+    .map(|either| match either {
+        EitherStream::Batched(b) => b,
+        EitherStream::Async(_) => unreachable!(),
+    })
 }
 
 /// Like [`start_multi_batched_stream`] but returns an `AsyncStreamingState`
@@ -659,11 +997,24 @@ pub fn start_multi_async_stream(
     sql: String,
     chunk_size: usize,
 ) -> Result<AsyncStreamingState> {
-    spawn_multi_stream_worker(handles, conn_id, sql, chunk_size, /* async = */ true).map(|either| {
-        match either {
-            EitherStream::Batched(_) => unreachable!(),
-            EitherStream::Async(a) => a,
-        }
+    start_multi_async_stream_with_params(handles, conn_id, sql, Vec::new(), chunk_size)
+}
+
+/// [`start_multi_async_stream`] with `params` bound to the batch's `?`
+/// markers.
+pub fn start_multi_async_stream_with_params(
+    handles: SharedHandleManager,
+    conn_id: u32,
+    sql: String,
+    params: Vec<ParamValue>,
+    chunk_size: usize,
+) -> Result<AsyncStreamingState> {
+    spawn_multi_stream_worker(
+        handles, conn_id, sql, params, chunk_size, /* async = */ true,
+    )
+    .map(|either| match either {
+        EitherStream::Batched(_) => unreachable!(),
+        EitherStream::Async(a) => a,
     })
 }
 
@@ -676,23 +1027,24 @@ fn spawn_multi_stream_worker(
     handles: SharedHandleManager,
     conn_id: u32,
     sql: String,
+    params: Vec<ParamValue>,
     chunk_size: usize,
     is_async: bool,
 ) -> Result<EitherStream> {
     let chunk_size = chunk_size.max(1);
-    let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(1);
-    let cancel_requested = Arc::new(AtomicBool::new(false));
-
-    let conn_arc = {
+    let (conn_arc, options) = {
         let Ok(guard) = handles.lock() else {
             return Err(OdbcError::InternalError(
                 "Failed to lock HandleManager".to_string(),
             ));
         };
-        guard
+        let conn_arc = guard
             .get_connection(conn_id)
-            .map_err(|e| OdbcError::InternalError(format!("Invalid connection: {}", e)))?
+            .map_err(|e| OdbcError::InternalError(format!("Invalid connection: {}", e)))?;
+        (conn_arc, guard.result_options(conn_id).unwrap_or_default())
     };
+    let (tx, rx) = stream_channel(options.buffer, options.spill);
+    let cancel_requested = Arc::new(AtomicBool::new(false));
 
     let join = std::thread::spawn({
         let cancel = Arc::clone(&cancel_requested);
@@ -703,13 +1055,12 @@ fn spawn_multi_stream_worker(
                 ));
                 return;
             };
-            let mut on_item = |framed: Vec<u8>| -> Result<()> {
-                tx.send(BatchedMessage::Batch(framed))
-                    .map_err(|e| OdbcError::InternalError(e.to_string()))
-            };
+            let mut on_item = |framed: Vec<u8>| tx.send(BatchedMessage::Batch(framed));
             match drive_multi_result_stream(
                 conn_guard.connection(),
                 &sql,
+                &params,
+                &options,
                 &mut on_item,
                 Some(cancel),
             ) {
@@ -739,29 +1090,26 @@ fn spawn_multi_stream_worker(
             _join: Some(join),
         }))
     } else {
-        Ok(EitherStream::Batched(BatchedStreamingState {
-            receiver: rx,
-            current_batch: None,
-            offset: 0,
+        Ok(EitherStream::Batched(BatchedStreamingState::new(
+            rx,
             chunk_size,
-            done: false,
-            stream_error: None,
-            cancelled: false,
             cancel_requested,
-            _join: Some(join),
-        }))
+            Some(join),
+        )))
     }
 }
 
 pub(crate) enum BatchedMessage {
     Batch(Vec<u8>),
+    /// Continuation token of the batch that follows (keyset streams only).
+    Checkpoint(String),
     Done,
     Cancelled,
     Error(String),
 }
 
 pub struct BatchedStreamingState {
-    receiver: mpsc::Receiver<BatchedMessage>,
+    receiver: StreamReceiver,
     current_batch: Option<Vec<u8>>,
     offset: usize,
     chunk_size: usize,
@@ -770,9 +1118,38 @@ pub struct BatchedStreamingState {
     cancelled: bool,
     cancel_requested: Arc<AtomicBool>,
     _join: Option<JoinHandle<()>>,
+    resumable: bool,
+    /// Token received ahead of the next batch.
+    pending_token: Option<String>,
+    /// Token of `current_batch`, published once its last byte is handed out.
+    batch_token: Option<String>,
+    continuation_token: Option<String>,
 }
 
 impl BatchedStreamingState {
+    fn new(
+        receiver: StreamReceiver,
+        chunk_size: usize,
+        cancel_requested: Arc<AtomicBool>,
+        join: Option<JoinHandle<()>>,
+    ) -> Self {
+        Self {
+            receiver,
+            current_batch: None,
+            offset: 0,
+            chunk_size,
+            done: false,
+            stream_error: None,
+            cancelled: false,
+            cancel_requested,
+            _join: join,
+            resumable: false,
+            pending_token: None,
+            batch_token: None,
+            continuation_token: None,
+        }
+    }
+
     /// Requests cancellation of the batched stream. The worker checks this flag
     /// between batches and exits early when set.
     pub fn request_cancel(&self) {
@@ -788,11 +1165,16 @@ impl BatchedStreamingState {
         }
 
         let batch_len = self.current_batch.as_ref().map(|b| b.len()).unwrap_or(0);
-        if self.current_batch.is_none() || self.offset >= batch_len {
+        while self.current_batch.is_none() || self.offset >= batch_len {
             match self.receiver.recv() {
                 Ok(BatchedMessage::Batch(b)) => {
                     self.current_batch = Some(b);
                     self.offset = 0;
+                    self.batch_token = self.pending_token.take();
+                    break;
+                }
+                Ok(BatchedMessage::Checkpoint(token)) => {
+                    self.pending_token = Some(token);
                 }
                 Ok(BatchedMessage::Done) => {
                     self.done = true;
@@ -821,6 +1203,7 @@ impl BatchedStreamingState {
 
         let batch_len = self.current_batch.as_ref().map(|b| b.len()).unwrap_or(0);
         if self.offset == 0 && self.chunk_size >= batch_len {
+            self.publish_batch_token();
             return Ok(self.current_batch.take());
         }
 
@@ -833,32 +1216,48 @@ impl BatchedStreamingState {
         let end = self.offset.saturating_add(self.chunk_size).min(b.len());
         let chunk = b[self.offset..end].to_vec();
         self.offset = end;
+        if end == batch_len {
+            self.publish_batch_token();
+        }
 
         Ok(Some(chunk))
     }
 
+    fn publish_batch_token(&mut self) {
+        if let Some(token) = self.batch_token.take() {
+            self.continuation_token = Some(token);
+        }
+    }
+
+    /// True for streams started with [`StreamingExecutor::start_keyset_stream`].
+    pub fn is_resumable(&self) -> bool {
+        self.resumable
+    }
+
+    /// Continuation token of the last batch whose final chunk has been
+    /// returned by [`Self::fetch_next_chunk`]; `None` before the first one
+    /// and for streams that are not resumable.
+    pub fn continuation_token(&self) -> Option<&str> {
+        self.continuation_token.as_deref()
+    }
+
     pub fn has_more(&self) -> bool {
         !self.done
     }
 
+    /// Buffered-bytes and high-water statistics of the worker's buffer.
+    pub fn buffer_stats(&self) -> StreamBufferStats {
+        self.receiver.stats()
+    }
+
     #[cfg(test)]
-    fn from_receiver(receiver: mpsc::Receiver<BatchedMessage>, chunk_size: usize) -> Self {
-        Self {
-            receiver,
-            current_batch: None,
-            offset: 0,
-            chunk_size,
-            done: false,
-            stream_error: None,
-            cancelled: false,
-            cancel_requested: Arc::new(AtomicBool::new(false)),
-            _join: None,
-        }
+    fn from_receiver(receiver: StreamReceiver, chunk_size: usize) -> Self {
+        Self::new(receiver, chunk_size, Arc::new(AtomicBool::new(false)), None)
     }
 }
 
 pub struct AsyncStreamingState {
-    receiver: mpsc::Receiver<BatchedMessage>,
+    receiver: StreamReceiver,
     current_batch: Option<Vec<u8>>,
     offset: usize,
     chunk_size: usize,
@@ -888,6 +1287,7 @@ impl AsyncStreamingState {
                 self.current_batch = Some(b);
                 self.offset = 0;
             }
+            Ok(BatchedMessage::Checkpoint(_)) => {}
             Ok(BatchedMessage::Done) => {
                 self.done = true;
             }
@@ -939,12 +1339,14 @@ impl AsyncStreamingState {
         }
 
         let batch_len = self.current_batch_len();
-        if self.current_batch.is_none() || self.offset >= batch_len {
+        while self.current_batch.is_none() || self.offset >= batch_len {
             match self.receiver.recv() {
                 Ok(BatchedMessage::Batch(b)) => {
                     self.current_batch = Some(b);
                     self.offset = 0;
+                    break;
                 }
+                Ok(BatchedMessage::Checkpoint(_)) => {}
                 Ok(BatchedMessage::Done) => {
                     self.done = true;
                     return Ok(None);
@@ -989,8 +1391,13 @@ impl AsyncStreamingState {
         !self.done
     }
 
+    /// Buffered-bytes and high-water statistics of the worker's buffer.
+    pub fn buffer_stats(&self) -> StreamBufferStats {
+        self.receiver.stats()
+    }
+
     #[cfg(test)]
-    fn from_receiver(receiver: mpsc::Receiver<BatchedMessage>, chunk_size: usize) -> Self {
+    fn from_receiver(receiver: StreamReceiver, chunk_size: usize) -> Self {
         Self {
             receiver,
             current_batch: None,
@@ -1029,7 +1436,8 @@ impl StreamState {
 
 /// Streaming state backed by a temp file. Reads in chunks; deletes file on drop.
 pub struct StreamingStateFileBacked {
-    path: PathBuf,
+    /// Removes (and securely unlinks) the spill file when the stream is dropped.
+    spill: Box<DiskSpillStream>,
     offset: usize,
     chunk_size: usize,
     total_len: usize,
@@ -1041,18 +1449,8 @@ impl StreamingStateFileBacked {
             return Ok(None);
         }
 
-        let mut f = File::open(&self.path)
-            .map_err(|e| OdbcError::InternalError(format!("spill file read: {}", e)))?;
-        f.seek(SeekFrom::Start(self.offset as u64))
-            .map_err(|e| OdbcError::InternalError(format!("spill file seek: {}", e)))?;
-
         let to_read = (self.chunk_size).min(self.total_len - self.offset);
-        let mut buf = vec![0u8; to_read];
-        // A6 fix: a single `read()` may return fewer bytes than requested
-        // (especially on Windows for files >64 KiB). Use `read_exact` so the
-        // caller never observes a short chunk silently.
-        f.read_exact(&mut buf)
-            .map_err(|e| OdbcError::InternalError(format!("spill file read_exact: {}", e)))?;
+        let buf = self.spill.read_at(self.offset as u64, to_read)?;
         self.offset += to_read;
 
         if to_read == 0 {
@@ -1067,12 +1465,6 @@ impl StreamingStateFileBacked {
     }
 }
 
-impl Drop for StreamingStateFileBacked {
-    fn drop(&mut self) {
-        let _ = std::fs::remove_file(&self.path);
-    }
-}
-
 pub struct StreamingState {
     data: Vec<u8>,
     offset: usize,
@@ -1100,11 +1492,21 @@ impl StreamingState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::mpsc;
+    use crate::engine::stream_buffer::StreamOverflow;
+
+    fn test_channel() -> (crate::engine::stream_buffer::StreamSender, StreamReceiver) {
+        stream_channel(
+            StreamBufferPolicy {
+                max_batches: 2,
+                ..StreamBufferPolicy::DEFAULT
+            },
+            SpillOptions::PLAIN,
+        )
+    }
 
     #[test]
     fn test_batched_streaming_state_fetch_chunks() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(2);
+        let (tx, rx) = test_channel();
         let _ = tx.send(BatchedMessage::Batch(vec![1, 2, 3, 4, 5, 6]));
         let _ = tx.send(BatchedMessage::Done);
         drop(tx);
@@ -1129,7 +1531,7 @@ mod tests {
 
     #[test]
     fn test_batched_streaming_state_takes_whole_batch_when_chunk_fits() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(2);
+        let (tx, rx) = test_channel();
         let _ = tx.send(BatchedMessage::Batch(vec![1, 2, 3]));
         let _ = tx.send(BatchedMessage::Done);
         drop(tx);
@@ -1142,9 +1544,30 @@ mod tests {
         assert_eq!(state.fetch_next_chunk().unwrap(), None);
     }
 
+    #[test]
+    fn test_batched_streaming_state_publishes_token_after_batch_drained() {
+        let (tx, rx) = test_channel();
+        let _ = tx.send(BatchedMessage::Checkpoint("t1".to_string()));
+        let _ = tx.send(BatchedMessage::Batch(vec![1, 2, 3]));
+        let _ = tx.send(BatchedMessage::Checkpoint("t2".to_string()));
+        let _ = tx.send(BatchedMessage::Batch(vec![4]));
+        let _ = tx.send(BatchedMessage::Done);
+        drop(tx);
+
+        let mut state = BatchedStreamingState::from_receiver(rx, 2);
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![1, 2]));
+        assert_eq!(state.continuation_token(), None);
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![3]));
+        assert_eq!(state.continuation_token(), Some("t1"));
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![4]));
+        assert_eq!(state.continuation_token(), Some("t2"));
+        assert_eq!(state.fetch_next_chunk().unwrap(), None);
+        assert_eq!(state.continuation_token(), Some("t2"));
+    }
+
     #[test]
     fn test_batched_streaming_state_error() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(1);
+        let (tx, rx) = stream_channel(StreamBufferPolicy::DEFAULT, SpillOptions::PLAIN);
         let _ = tx.send(BatchedMessage::Error("test error".to_string()));
         drop(tx);
 
@@ -1153,9 +1576,35 @@ mod tests {
         assert!(e.to_string().contains("test error"));
     }
 
+    #[test]
+    fn test_batched_streaming_state_reads_spilled_batches_in_order() {
+        let (tx, rx) = stream_channel(
+            StreamBufferPolicy {
+                max_batches: 1,
+                max_bytes: None,
+                overflow: StreamOverflow::Spill,
+            },
+            SpillOptions::PLAIN,
+        );
+        for batch in [vec![1, 2], vec![3, 4], vec![5, 6]] {
+            tx.send(BatchedMessage::Batch(batch)).unwrap();
+        }
+        tx.send(BatchedMessage::Done).unwrap();
+        drop(tx);
+
+        let mut state = BatchedStreamingState::from_receiver(rx, 8);
+        assert_eq!(state.buffer_stats().spilled_batches, 2);
+        assert_eq!(state.buffer_stats().spill_pending_bytes, 4);
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![1, 2]));
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![3, 4]));
+        assert_eq!(state.fetch_next_chunk().unwrap(), Some(vec![5, 6]));
+        assert_eq!(state.fetch_next_chunk().unwrap(), None);
+        assert_eq!(state.buffer_stats().spill_pending_bytes, 0);
+    }
+
     #[test]
     fn test_async_streaming_state_poll_ready_then_done() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(2);
+        let (tx, rx) = test_channel();
         let _ = tx.send(BatchedMessage::Batch(vec![10, 11, 12, 13]));
         let _ = tx.send(BatchedMessage::Done);
         drop(tx);
@@ -1175,7 +1624,7 @@ mod tests {
 
     #[test]
     fn test_async_streaming_state_takes_whole_batch_when_chunk_fits() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(2);
+        let (tx, rx) = test_channel();
         let _ = tx.send(BatchedMessage::Batch(vec![7, 8, 9]));
         let _ = tx.send(BatchedMessage::Done);
         drop(tx);
@@ -1202,7 +1651,7 @@ mod tests {
 
     #[test]
     fn test_async_streaming_state_poll_error() {
-        let (tx, rx) = mpsc::sync_channel::<BatchedMessage>(1);
+        let (tx, rx) = stream_channel(StreamBufferPolicy::DEFAULT, SpillOptions::PLAIN);
         let _ = tx.send(BatchedMessage::Error("async test error".to_string()));
         drop(tx);
 
@@ -1218,12 +1667,36 @@ mod tests {
         assert_eq!(executor.chunk_size, 1024);
     }
 
+    #[test]
+    fn test_streaming_executor_with_protocol_version() {
+        let executor = StreamingExecutor::new(64);
+        assert_eq!(executor.cell_encoding, CellEncoding::Text);
+        let executor = executor.with_protocol_version(ProtocolVersion::v1_native());
+        assert_eq!(executor.cell_encoding, CellEncoding::Native);
+        let executor = executor.with_protocol_version(ProtocolVersion::v1());
+        assert_eq!(executor.cell_encoding, CellEncoding::Text);
+    }
+
     #[test]
     fn test_streaming_executor_new_with_different_chunk_size() {
         let executor = StreamingExecutor::new(512);
         assert_eq!(executor.chunk_size, 512);
     }
 
+    #[test]
+    fn test_streaming_executor_block_rows_respect_driver_cap() {
+        let executor = StreamingExecutor::new(1024);
+        assert_eq!(
+            executor.block_rows(None),
+            DriverCapabilities::default().max_row_array_size as usize
+        );
+
+        let executor = StreamingExecutor::new(1024).with_max_row_array_size(250);
+        assert_eq!(executor.block_rows(None), 250);
+        assert_eq!(executor.block_rows(Some(100)), 100);
+        assert_eq!(executor.block_rows(Some(10_000)), 250);
+    }
+
     #[test]
     fn test_streaming_state_fetch_next_chunk() {
         let data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
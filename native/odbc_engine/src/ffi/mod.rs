@@ -6,19 +6,28 @@ pub mod columnar_decompress;
 pub mod guard;
 
 use crate::async_bridge;
-#[cfg(not(feature = "sqlserver-bcp"))]
-use crate::engine::ArrayBinding;
 #[cfg(feature = "sqlserver-bcp")]
 use crate::engine::BulkCopyExecutor;
 use crate::engine::{
-    execute_multi_result, execute_multi_result_with_params, execute_query_with_cached_connection,
-    execute_query_with_connection, execute_query_with_param_buffer,
-    execute_query_with_param_buffer_and_timeout, get_global_metrics, get_type_info, list_columns,
-    list_foreign_keys, list_indexes, list_primary_keys, list_tables, recover_prepared_xids,
-    resume_prepared, AsyncStreamStatus, AsyncStreamingState, BatchedStreamingState,
-    DriverCapabilities, IsolationLevel, LockTimeout, MetadataCache, OdbcConnection,
-    OdbcEnvironment, PreparedXa, PreparingXa, SavepointDialect, StatementHandle, StreamState,
-    StreamingExecutor, Transaction, TransactionAccessMode, XaTransaction, Xid,
+    execute_multi_result, execute_multi_result_with_params, execute_prepared_with_param_buffer,
+    execute_query_with_cached_connection, execute_query_with_connection,
+    execute_query_with_param_buffer, execute_query_with_param_buffer_and_timeout,
+    get_global_metrics, get_type_info, list_columns, list_foreign_keys, list_indexes,
+    list_primary_keys, list_tables, recover_prepared_xids, resume_prepared, AsyncStreamStatus,
+    AsyncStreamingState, BatchedStreamingState, DriverCapabilities, IsolationLevel,
+    KeysetPagination, KeysetToken, LobKind, LobReader, LobWriter, LockTimeout, MetadataCache,
+    OdbcConnection, OdbcEnvironment, PreparedXa, PreparingXa, QueryConnection, ResultOptions,
+    SavepointDialect, ScrollFetch, ScrollableCursor, SpillOptions, StatementHandle,
+    StreamBufferPolicy, StreamBufferStats, StreamOverflow, StreamState, StreamingExecutor,
+    TemporalBinding, Transaction, TransactionAccessMode, XaTransaction, Xid,
+};
+use crate::engine::{
+    ArrayBinding, BulkDmlOutcome, BulkUpsert, CsvImportOptions, CsvImporter, ExportOptions,
+    ParallelBulkInsert, QueryExporter,
+};
+#[cfg(feature = "parquet")]
+use crate::engine::{
+    ParquetCodec, ParquetExportOptions, ParquetExporter, ParquetImportOptions, ParquetImporter,
 };
 use crate::error::StructuredError;
 use crate::error::{OdbcError, Result};
@@ -27,11 +36,13 @@ use crate::observability::Metrics;
 use crate::plugins::PluginRegistry;
 use crate::pool::{ConnectionPool, PooledConnectionWrapper};
 use crate::protocol::bound_param::ParamDirection;
+use crate::protocol::columnar::CompressionType;
 use crate::protocol::{
-    bound_param::ParamList, bulk_insert::is_null, deserialize_param_buffer,
-    parse_bulk_insert_payload, BulkColumnData, BulkInsertPayload, ParamValue,
+    bound_param::ParamList, deserialize_param_buffer, parse_bulk_insert_payload, BulkInsertPayload,
+    OutputFormat, ParamValue,
 };
 use crate::security::AuditLogger;
+use crate::versioning::protocol_version::ProtocolVersion;
 use log::LevelFilter;
 use rayon::prelude::*;
 use std::collections::hash_map::DefaultHasher;
@@ -84,6 +95,15 @@ impl StreamKind {
         }
     }
 
+    /// Worker buffer statistics; `None` for fully materialised streams.
+    fn buffer_stats(&self) -> Option<StreamBufferStats> {
+        match self {
+            StreamKind::Batched(s) => Some(s.buffer_stats()),
+            StreamKind::AsyncBatched(s) => Some(s.buffer_stats()),
+            StreamKind::Buffer(_) => None,
+        }
+    }
+
     fn cancel(&self) {
         match self {
             StreamKind::Batched(s) => s.request_cancel(),
@@ -213,17 +233,28 @@ impl AsyncRequestManager {
         conn_id: u32,
         sql: String,
     ) -> Option<u32> {
+        self.start_task(conn_id, move |_| run_async_query(handles, conn_id, &sql))
+    }
+
+    /// Runs `task` on the blocking pool under a new request id. The task gets
+    /// the slot's cancel flag so long-running work can stop at a safe point.
+    fn start_task<F>(&mut self, conn_id: u32, task: F) -> Option<u32>
+    where
+        F: FnOnce(&AtomicBool) -> Result<Vec<u8>> + Send + 'static,
+    {
         let request_id = self.allocate_request_id()?;
         let slot = Arc::new(AsyncRequestSlot::new(conn_id));
         let slot_for_worker = Arc::clone(&slot);
 
         let handle = match async_bridge::spawn_blocking_task(move || {
-            let result = std::panic::catch_unwind(|| run_async_query(handles, conn_id, &sql))
-                .unwrap_or_else(|_| {
-                    Err(OdbcError::InternalError(
-                        "Async request task panicked".to_string(),
-                    ))
-                });
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                task(&slot_for_worker.cancelled)
+            }))
+            .unwrap_or_else(|_| {
+                Err(OdbcError::InternalError(
+                    "Async request task panicked".to_string(),
+                ))
+            });
             let cancelled = slot_for_worker.cancelled.load(Ordering::SeqCst);
             if let Ok(mut outcome) = slot_for_worker.outcome.lock() {
                 *outcome = if cancelled {
@@ -340,11 +371,12 @@ fn run_async_query(handles: SharedHandleManager, conn_id: u32, sql: &str) -> Res
         .lock()
         .map_err(|_| OdbcError::InternalError("Failed to lock handles mutex".to_string()))?;
     let conn_arc = handles_guard.get_connection(conn_id)?;
+    let options = handles_guard.result_options(conn_id).unwrap_or_default();
     drop(handles_guard);
     let mut conn_guard = conn_arc
         .lock()
         .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
-    execute_query_with_cached_connection(&mut conn_guard, sql)
+    execute_query_with_cached_connection(&mut conn_guard, sql, &options)
 }
 
 struct GlobalState {
@@ -365,6 +397,8 @@ struct GlobalState {
     streams: HashMap<u32, StreamKind>,
     stream_connections: HashMap<u32, u32>, // Map stream_id -> conn_id
     pending_stream_chunks: HashMap<u32, PendingStreamChunk>,
+    cursors: HashMap<u32, OpenCursor>,
+    lobs: HashMap<u32, OpenLob>,
     pending_result_buffers: HashMap<PendingResultKey, PendingResultBuffer>,
     pools: HashMap<u32, Arc<ConnectionPool>>,
     pooled_connections: HashMap<u32, (u32, PooledConnectionWrapper)>, // pooled_conn_id -> (pool_id, wrapper)
@@ -378,6 +412,8 @@ struct GlobalState {
     /// uniquely identifies a branch through its whole lifecycle.
     next_xa_id: u32,
     next_stmt_id: u32,
+    next_cursor_id: u32,
+    next_lob_id: u32,
     // Legacy global error (for backward compatibility with functions without conn_id)
     last_error: Option<String>,
     last_structured_error: Option<StructuredError>,
@@ -394,6 +430,24 @@ struct PendingStreamChunk {
     has_more: bool,
 }
 
+struct OpenCursor {
+    conn_id: u32,
+    cursor: ScrollableCursor,
+    /// Block fetched for a too-small buffer, returned by the next fetch
+    /// without moving the cursor.
+    pending_block: Option<Vec<u8>>,
+}
+
+struct OpenLob {
+    conn_id: u32,
+    handle: LobHandle,
+}
+
+enum LobHandle {
+    Reader(Box<LobReader>),
+    Writer(Box<LobWriter>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum PendingResultKey {
     ExecQuery {
@@ -415,6 +469,19 @@ enum PendingResultKey {
         timeout_override_ms: u32,
         fetch_size: u32,
     },
+    /// Continue-on-error bulk insert; `owner_id` is a connection or, with
+    /// `from_pool`, a pool ID.
+    BulkInsertRejects {
+        owner_id: u32,
+        from_pool: bool,
+        payload_hash: u64,
+    },
+    /// CSV or Parquet import report; `request_hash` covers the function,
+    /// path, table and options.
+    FileImport {
+        conn_id: u32,
+        request_hash: u64,
+    },
 }
 
 struct PendingResultBuffer {
@@ -542,6 +609,8 @@ fn get_global_state() -> &'static Arc<Mutex<GlobalState>> {
             streams: HashMap::new(),
             stream_connections: HashMap::new(),
             pending_stream_chunks: HashMap::new(),
+            cursors: HashMap::new(),
+            lobs: HashMap::new(),
             pending_result_buffers: HashMap::new(),
             pools: HashMap::new(),
             pooled_connections: HashMap::new(),
@@ -552,6 +621,8 @@ fn get_global_state() -> &'static Arc<Mutex<GlobalState>> {
             next_txn_id: 1,
             next_xa_id: 1,
             next_stmt_id: 1,
+            next_cursor_id: 1,
+            next_lob_id: 1,
             last_error: None,
             last_structured_error: None,
             connection_errors: HashMap::new(),
@@ -583,6 +654,40 @@ fn read_env_u64(key: &str, default: u64) -> u64 {
 }
 
 /// Set error for a specific connection (thread-safe isolation)
+/// Applies `update` to the result options of `conn_id`, a direct or a
+/// pooled connection.
+fn update_result_options(
+    state: &mut GlobalState,
+    conn_id: u32,
+    update: impl FnOnce(ResultOptions) -> Result<ResultOptions>,
+) -> Result<()> {
+    if let Some((_, pooled)) = state.pooled_connections.get_mut(&conn_id) {
+        let options = update(pooled.result_options())?;
+        pooled.set_result_options(options);
+        return Ok(());
+    }
+    let handles = state
+        .connections
+        .get(&conn_id)
+        .ok_or(OdbcError::InvalidHandle(conn_id))?
+        .get_handles();
+    let mut guard = handles
+        .lock()
+        .map_err(|_| OdbcError::InternalError("Failed to lock handles mutex".to_string()))?;
+    let options = update(guard.result_options(conn_id)?)?;
+    guard.set_result_options(conn_id, options)
+}
+
+/// Result options detected for a direct connection, falling back to the
+/// defaults when the handle registry cannot be locked.
+fn connection_result_options(handles: &SharedHandleManager, conn_id: u32) -> ResultOptions {
+    handles
+        .lock()
+        .ok()
+        .and_then(|guard| guard.result_options(conn_id).ok())
+        .unwrap_or_default()
+}
+
 fn set_connection_error(state: &mut GlobalState, conn_id: u32, error: String) {
     state.connection_errors.insert(
         conn_id,
@@ -758,6 +863,179 @@ pub extern "C" fn odbc_set_log_level(level: c_int) -> c_int {
     })
 }
 
+/// Negotiates the result protocol version (`major.minor`) for query results and
+/// streams started afterwards on `conn_id` (direct or pooled; a pooled connection
+/// keeps it until released). Connections that never call this stay on `1.0`.
+/// `1.1` / `2.1` encode float, boolean, temporal and UUID cells as fixed-width
+/// binary (see `doc/notes/TYPE_MAPPING.md`); `1.0` / `2.0` keep text cells.
+///
+/// Returns: 0 on success; -1 if the version is not supported or the connection is
+/// invalid (see `odbc_get_error`).
+#[no_mangle]
+pub extern "C" fn odbc_set_protocol_version(
+    conn_id: c_uint,
+    major: c_uint,
+    minor: c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let (Ok(major), Ok(minor)) = (u16::try_from(major), u16::try_from(minor)) else {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                format!("Unsupported protocol version {major}.{minor}"),
+            );
+            return -1;
+        };
+        let version = ProtocolVersion::new(major, minor);
+        match update_result_options(&mut state, conn_id, |options| {
+            options.with_protocol_version(version)
+        }) {
+            Ok(()) => 0,
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, e.to_string());
+                -1
+            }
+        }
+    })
+}
+
+/// Selects the output format of query results and streams started afterwards
+/// on `conn_id` (direct or pooled; a pooled connection keeps it until released):
+/// `0` = the binary protocol (default), `1` = Apache Arrow IPC stream (one schema
+/// message, then a record batch per fetched batch; needs the `arrow-ipc` feature).
+/// Calls with OUT parameters or ref cursors keep failing under Arrow IPC.
+///
+/// Returns: 0 on success; -1 if the format is unknown or not built in, or the
+/// connection is invalid (see `odbc_get_error`).
+#[no_mangle]
+pub extern "C" fn odbc_set_output_format(conn_id: c_uint, format: c_uint) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let result = OutputFormat::from_code(format)
+            .ok_or_else(|| OdbcError::ValidationError(format!("Unknown output format {format}")))
+            .and_then(|format| {
+                update_result_options(&mut state, conn_id, |options| {
+                    options.with_output_format(format)
+                })
+            });
+        match result {
+            Ok(()) => 0,
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, e.to_string());
+                -1
+            }
+        }
+    })
+}
+
+/// Bounds how far the worker of batched and async streams started afterwards
+/// on `conn_id` (direct or pooled; single- and multi-result) may run ahead of
+/// `odbc_stream_fetch`.
+/// max_batches: encoded batches held in memory (>= 1; the default is 1)
+/// max_bytes: bytes held in memory, 0 = no byte limit (a single larger batch
+/// is still accepted when nothing else is buffered)
+/// overflow: `0` = the worker waits for the consumer, `1` = it spills further
+/// batches to a temp file and keeps fetching
+///
+/// Returns: 0 on success; -1 on an invalid policy or an invalid connection
+/// (see `odbc_get_error`).
+#[no_mangle]
+pub extern "C" fn odbc_set_stream_buffer_policy(
+    conn_id: c_uint,
+    max_batches: c_uint,
+    max_bytes: u64,
+    overflow: c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let overflow = match overflow {
+            0 => Ok(StreamOverflow::Block),
+            1 => Ok(StreamOverflow::Spill),
+            other => Err(OdbcError::ValidationError(format!(
+                "Unknown stream overflow mode {other}"
+            ))),
+        };
+        let result = overflow.and_then(|overflow| {
+            let policy = StreamBufferPolicy {
+                max_batches: max_batches as usize,
+                max_bytes: (max_bytes > 0).then_some(max_bytes),
+                overflow,
+            };
+            update_result_options(&mut state, conn_id, |options| {
+                options.with_buffer_policy(policy)
+            })
+        });
+        match result {
+            Ok(()) => 0,
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, e.to_string());
+                -1
+            }
+        }
+    })
+}
+
+/// Sets how the spill files of streams and scrollable cursors started afterwards
+/// on `conn_id` (direct or pooled) are stored: result spills of buffer-mode
+/// streams, the buffers of batched / async streams and emulated scrollable cursors.
+/// compression: `0` = none, `1` = zstd, `2` = lz4
+/// encrypt: `1` = AES-256-GCM with a per-spill key kept in memory, `0` = off
+///
+/// Returns: 0 on success; -1 on an unknown option or an invalid connection
+/// (see `odbc_get_error`).
+#[no_mangle]
+pub extern "C" fn odbc_set_spill_options(
+    conn_id: c_uint,
+    compression: c_uint,
+    encrypt: c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let compression = match compression {
+            0 => Ok(CompressionType::None),
+            1 => Ok(CompressionType::Zstd),
+            2 => Ok(CompressionType::Lz4),
+            other => Err(OdbcError::ValidationError(format!(
+                "Unknown spill compression {other}"
+            ))),
+        };
+        let encrypt = match encrypt {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(OdbcError::ValidationError(format!(
+                "Unknown spill encryption flag {other}"
+            ))),
+        };
+        let result =
+            compression
+                .and_then(|c| encrypt.map(|e| (c, e)))
+                .and_then(|(compression, encrypt)| {
+                    let spill = SpillOptions::PLAIN
+                        .with_compression(compression)
+                        .with_encryption(encrypt);
+                    update_result_options(&mut state, conn_id, |options| {
+                        Ok(options.with_spill_options(spill))
+                    })
+                });
+        match result {
+            Ok(()) => 0,
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, e.to_string());
+                -1
+            }
+        }
+    })
+}
+
 /// Returns engine version as JSON for client compatibility checks.
 ///
 /// Output format: `{"api":"0.1.0","abi":"1.0.0"}` (UTF-8).
@@ -1065,6 +1343,8 @@ pub extern "C" fn odbc_disconnect(conn_id: c_uint) -> c_int {
                 state.stream_connections.remove(&stream_id);
                 state.pending_stream_chunks.remove(&stream_id);
             }
+            state.cursors.retain(|_, c| c.conn_id != conn_id);
+            state.lobs.retain(|_, l| l.conn_id != conn_id);
             state.async_requests.free_for_connection(conn_id);
             state.pending_result_buffers.retain(|key, _| match key {
                 PendingResultKey::ExecQuery {
@@ -1077,6 +1357,14 @@ pub extern "C" fn odbc_disconnect(conn_id: c_uint) -> c_int {
                     conn_id: key_conn, ..
                 } => *key_conn != conn_id,
                 PendingResultKey::Execute { stmt_id, .. } => !stmts_to_drop.contains(stmt_id),
+                PendingResultKey::BulkInsertRejects {
+                    owner_id,
+                    from_pool,
+                    ..
+                } => *from_pool || *owner_id != conn_id,
+                PendingResultKey::FileImport {
+                    conn_id: key_conn, ..
+                } => *key_conn != conn_id,
             });
             match conn.disconnect() {
                 Ok(_) => {
@@ -2862,6 +3150,7 @@ pub extern "C" fn odbc_exec_query(
                     return -1;
                 }
             };
+            let options = handles_guard.result_options(conn_id).unwrap_or_default();
             drop(handles_guard);
             drop(state);
 
@@ -2877,10 +3166,14 @@ pub extern "C" fn odbc_exec_query(
                     return -1;
                 }
             };
-            execute_query_with_cached_connection(&mut conn_guard, sql_str)
+            execute_query_with_cached_connection(&mut conn_guard, sql_str, &options)
         } else if let Some((pool_id, pooled)) = state.pooled_connections.remove(&conn_id) {
+            let options = pooled.result_options();
             drop(state);
-            let result = execute_query_with_connection(pooled.get_connection(), sql_str);
+            let result = execute_query_with_connection(
+                QueryConnection::new(pooled.get_connection(), options),
+                sql_str,
+            );
             let Some(mut state) = try_lock_global_state() else {
                 set_out_written_zero(out_written);
                 return -1;
@@ -3100,6 +3393,273 @@ pub extern "C" fn odbc_async_free(request_id: c_uint) -> c_int {
     })
 }
 
+/// Starts exporting a query result straight to a file, on a worker thread.
+/// `sql`, `path`: NUL-terminated UTF-8. `params_buffer`: same layout as
+/// `odbc_exec_query_params`, `INPUT` parameters only (NULL = none).
+/// `options_json`: NUL-terminated UTF-8 JSON, every key optional:
+/// `{ "format": "csv"|"ndjson", "delimiter": ",", "null_marker": "",
+///    "header": true, "compression": "none"|"zstd"|"lz4", "fetch_size": 1000 }`.
+/// Track it with `odbc_async_poll` / `odbc_async_cancel` / `odbc_async_free`;
+/// `odbc_async_get_result` yields the JSON report
+/// `{ rows, bytes_written, elapsed_ms }`. A cancelled or failed export removes
+/// the partial file.
+/// Returns: request_id (>0) on success, 0 on failure.
+#[no_mangle]
+pub extern "C" fn odbc_export_query_async(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    path: *const c_char,
+    options_json: *const c_char,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        start_file_export(
+            "odbc_export_query_async",
+            conn_id,
+            sql,
+            (params_buffer, params_len),
+            path,
+            options_json,
+            query_export_job,
+        )
+    })
+}
+
+/// Starts exporting a query result to a Parquet file, on a worker thread.
+/// Arguments and tracking as `odbc_export_query_async`; `options_json` keys,
+/// all optional: `{ "codec": "snappy"|"zstd"|"none", "row_group_rows": 100000,
+///    "fetch_size": 1000 }`. The JSON report is
+/// `{ rows, row_groups, bytes_written, elapsed_ms }`.
+/// Needs the `parquet` feature; without it the call fails with
+/// `UnsupportedFeature`.
+/// Returns: request_id (>0) on success, 0 on failure.
+#[no_mangle]
+pub extern "C" fn odbc_export_parquet_async(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    path: *const c_char,
+    options_json: *const c_char,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        start_file_export(
+            "odbc_export_parquet_async",
+            conn_id,
+            sql,
+            (params_buffer, params_len),
+            path,
+            options_json,
+            parquet_export_job,
+        )
+    })
+}
+
+/// Writes the result of `sql` to `path` and returns the JSON report.
+type ExportJob = Box<
+    dyn FnOnce(
+            &odbc_api::Connection<'static>,
+            &str,
+            &std::path::Path,
+            TemporalBinding,
+            &AtomicBool,
+        ) -> Result<Vec<u8>>
+        + Send,
+>;
+
+/// Shared body of the `odbc_export_*_async` functions: validates the
+/// arguments, builds the job from `options_json` and starts it.
+fn start_file_export(
+    fn_name: &str,
+    conn_id: c_uint,
+    sql: *const c_char,
+    (params_buffer, params_len): (*const u8, c_uint),
+    path: *const c_char,
+    options_json: *const c_char,
+    make_job: fn(&str, Vec<ParamValue>) -> Result<ExportJob>,
+) -> c_uint {
+    let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+        return 0;
+    };
+    let Some(mut state) = try_lock_global_state() else {
+        return 0;
+    };
+    if sql.is_null() || path.is_null() || options_json.is_null() {
+        set_error(
+            &mut state,
+            format!("{fn_name}: sql, path and options_json must be non-null"),
+        );
+        return 0;
+    }
+    // SAFETY: each pointer was checked non-null above; caller guarantees C-string contract.
+    let (sql_rs, path_rs, options_rs) = match unsafe {
+        (
+            CStr::from_ptr(sql).to_str(),
+            CStr::from_ptr(path).to_str(),
+            CStr::from_ptr(options_json).to_str(),
+        )
+    } {
+        (Ok(q), Ok(p), Ok(o)) => (q.to_string(), std::path::PathBuf::from(p), o),
+        _ => {
+            set_error(
+                &mut state,
+                format!("{fn_name}: arguments must be valid UTF-8"),
+            );
+            return 0;
+        }
+    };
+    let job = match make_job(options_rs, params) {
+        Ok(j) => j,
+        Err(e) => {
+            set_error(&mut state, format!("{fn_name}: {e}"));
+            return 0;
+        }
+    };
+
+    let handles = match state.connections.get(&conn_id) {
+        Some(c) => c.get_handles(),
+        None => {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                format!("Invalid connection ID: {}", conn_id),
+            );
+            return 0;
+        }
+    };
+
+    state
+        .async_requests
+        .start_task(conn_id, move |cancelled| {
+            let handles_guard = handles.lock().map_err(|_| {
+                OdbcError::InternalError("Failed to lock handles mutex".to_string())
+            })?;
+            let conn_arc = handles_guard.get_connection(conn_id)?;
+            let options = handles_guard.result_options(conn_id).unwrap_or_default();
+            drop(handles_guard);
+            let conn_guard = conn_arc
+                .lock()
+                .map_err(|_| OdbcError::InternalError("Failed to lock connection".to_string()))?;
+            job(
+                conn_guard.connection(),
+                &sql_rs,
+                &path_rs,
+                options.temporal,
+                cancelled,
+            )
+        })
+        .unwrap_or(0)
+}
+
+fn report_json<T: serde::Serialize>(report: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(report).map_err(|e| OdbcError::InternalError(e.to_string()))
+}
+
+fn query_export_job(options_json: &str, params: Vec<ParamValue>) -> Result<ExportJob> {
+    let exporter = QueryExporter::new(export_options_from_json(options_json)?).with_params(params);
+    Ok(Box::new(move |conn, sql, path, temporal, cancelled| {
+        let exporter = exporter.with_temporal_binding(temporal);
+        report_json(&exporter.export_to_file(conn, sql, path, Some(cancelled))?)
+    }))
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_export_job(options_json: &str, params: Vec<ParamValue>) -> Result<ExportJob> {
+    #[derive(serde::Deserialize)]
+    struct ParquetExportRequest {
+        codec: Option<String>,
+        row_group_rows: Option<usize>,
+        fetch_size: Option<usize>,
+    }
+    let request: ParquetExportRequest = serde_json::from_str(options_json)
+        .map_err(|e| OdbcError::ValidationError(format!("invalid options_json: {e}")))?;
+    let codec = match request.codec.as_deref() {
+        None | Some("snappy") => ParquetCodec::Snappy,
+        Some("zstd") => ParquetCodec::Zstd,
+        Some("none") => ParquetCodec::Uncompressed,
+        Some(other) => {
+            return Err(OdbcError::ValidationError(format!(
+                "unknown codec {other:?}"
+            )))
+        }
+    };
+    let defaults = ParquetExportOptions::default();
+    let options = ParquetExportOptions {
+        codec,
+        row_group_rows: request.row_group_rows.unwrap_or(defaults.row_group_rows),
+        fetch_size: request.fetch_size.unwrap_or(defaults.fetch_size),
+    };
+    let exporter = ParquetExporter::new(options).with_params(params);
+    Ok(Box::new(move |conn, sql, path, temporal, cancelled| {
+        let exporter = exporter.with_temporal_binding(temporal);
+        report_json(&exporter.export_to_file(conn, sql, path, Some(cancelled))?)
+    }))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_export_job(_options_json: &str, _params: Vec<ParamValue>) -> Result<ExportJob> {
+    Err(parquet_unavailable())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_unavailable() -> OdbcError {
+    OdbcError::UnsupportedFeature("Parquet support requires the `parquet` feature".to_string())
+}
+
+fn export_options_from_json(options_json: &str) -> Result<ExportOptions> {
+    #[derive(serde::Deserialize)]
+    struct ExportRequest {
+        format: Option<String>,
+        delimiter: Option<String>,
+        null_marker: Option<String>,
+        header: Option<bool>,
+        compression: Option<String>,
+        fetch_size: Option<usize>,
+    }
+    let request: ExportRequest = serde_json::from_str(options_json)
+        .map_err(|e| OdbcError::ValidationError(format!("invalid options_json: {e}")))?;
+    let mut options = match request.format.as_deref() {
+        None | Some("csv") => ExportOptions::csv(),
+        Some("ndjson") => ExportOptions::ndjson(),
+        Some(other) => {
+            return Err(OdbcError::ValidationError(format!(
+                "unknown format {other:?}"
+            )))
+        }
+    };
+    if let Some(d) = request.delimiter {
+        options.delimiter =
+            match d.as_bytes() {
+                [b] if b.is_ascii() && !matches!(b, b'"' | b'\r' | b'\n') => *b,
+                _ => return Err(OdbcError::ValidationError(
+                    "delimiter must be a single ASCII character other than a quote or line break"
+                        .to_string(),
+                )),
+            };
+    }
+    if let Some(n) = request.null_marker {
+        options.null_marker = n;
+    }
+    if let Some(h) = request.header {
+        options.header = h;
+    }
+    options.compression = match request.compression.as_deref() {
+        None | Some("none") => CompressionType::None,
+        Some("zstd") => CompressionType::Zstd,
+        Some("lz4") => CompressionType::Lz4,
+        Some(other) => {
+            return Err(OdbcError::ValidationError(format!(
+                "unknown compression {other:?}"
+            )))
+        }
+    };
+    if let Some(f) = request.fetch_size {
+        options.fetch_size = f;
+    }
+    Ok(options)
+}
+
 /// Execute parameterized query and return binary buffer
 /// conn_id: connection ID
 /// sql: null-terminated UTF-8 SQL query
@@ -3172,6 +3732,7 @@ pub extern "C" fn odbc_exec_query_params(
                 return -1;
             }
         };
+        let options = handles_guard.result_options(conn_id).unwrap_or_default();
         drop(handles_guard);
 
         let mut conn_guard = match conn_arc.lock() {
@@ -3211,11 +3772,15 @@ pub extern "C" fn odbc_exec_query_params(
         }
 
         let result = if params_buffer.is_null() || params_len == 0 {
-            execute_query_with_cached_connection(&mut conn_guard, sql_str)
+            execute_query_with_cached_connection(&mut conn_guard, sql_str, &options)
         } else {
             let params_slice =
                 unsafe { std::slice::from_raw_parts(params_buffer, params_len as usize) };
-            match execute_query_with_param_buffer(conn_guard.connection(), sql_str, params_slice) {
+            match execute_query_with_param_buffer(
+                QueryConnection::new(conn_guard.connection(), options),
+                sql_str,
+                params_slice,
+            ) {
                 Ok(d) => Ok(d),
                 Err(e) => {
                     metrics.record_error();
@@ -3350,6 +3915,7 @@ pub extern "C" fn odbc_exec_query_multi(
                     return -1;
                 }
             };
+            let options = handles_guard.result_options(conn_id).unwrap_or_default();
             drop(handles_guard);
             let conn_guard = match conn_arc.lock() {
                 Ok(g) => g,
@@ -3362,9 +3928,15 @@ pub extern "C" fn odbc_exec_query_multi(
                     return -1;
                 }
             };
-            execute_multi_result(conn_guard.connection(), sql_str)
+            execute_multi_result(
+                QueryConnection::new(conn_guard.connection(), options),
+                sql_str,
+            )
         } else if let Some((_pool_id, pooled)) = state.pooled_connections.get(&conn_id) {
-            execute_multi_result(pooled.get_connection(), sql_str)
+            execute_multi_result(
+                QueryConnection::new(pooled.get_connection(), pooled.result_options()),
+                sql_str,
+            )
         } else {
             drop(state);
             let Some(mut s) = try_lock_global_state() else {
@@ -3538,6 +4110,7 @@ pub extern "C" fn odbc_exec_query_multi_params(
                     return -1;
                 }
             };
+            let options = handles_guard.result_options(conn_id).unwrap_or_default();
             drop(handles_guard);
             let conn_guard = match conn_arc.lock() {
                 Ok(g) => g,
@@ -3550,9 +4123,17 @@ pub extern "C" fn odbc_exec_query_multi_params(
                     return -1;
                 }
             };
-            execute_multi_result_with_params(conn_guard.connection(), sql_str, &params)
+            execute_multi_result_with_params(
+                QueryConnection::new(conn_guard.connection(), options),
+                sql_str,
+                &params,
+            )
         } else if let Some((_pool_id, pooled)) = state.pooled_connections.get(&conn_id) {
-            execute_multi_result_with_params(pooled.get_connection(), sql_str, &params)
+            execute_multi_result_with_params(
+                QueryConnection::new(pooled.get_connection(), pooled.result_options()),
+                sql_str,
+                &params,
+            )
         } else {
             drop(state);
             let Some(mut s) = try_lock_global_state() else {
@@ -4382,11 +4963,39 @@ fn ptr_to_opt_str(ptr: *const c_char) -> Option<String> {
     Some(t.to_string())
 }
 
+/// Shared connection behind direct connection `conn_id`, which holds the
+/// prepared handles of its statements. `None` for pooled or unknown IDs.
+#[cfg(feature = "statement-handle-reuse")]
+fn direct_shared_connection(
+    state: &GlobalState,
+    conn_id: u32,
+) -> Option<crate::handles::SharedConnection> {
+    let conn = state.connections.get(&conn_id)?;
+    let handles = conn.get_handles();
+    let guard = handles.lock().ok()?;
+    guard.get_connection(conn_id).ok()
+}
+
+/// Releases the handle prepared for `stmt_id` on connection `conn_id`
+/// (`statement-handle-reuse`; nothing is held otherwise).
+#[cfg_attr(not(feature = "statement-handle-reuse"), allow(unused_variables))]
+fn close_prepared_handle(state: &GlobalState, conn_id: u32, stmt_id: u32) {
+    #[cfg(feature = "statement-handle-reuse")]
+    if let Some(shared) = direct_shared_connection(state, conn_id) {
+        if let Ok(mut cached) = shared.lock() {
+            cached.close_statement(stmt_id);
+        }
+    }
+}
+
 /// Prepare a statement with optional timeout.
 /// conn_id: connection ID from odbc_connect
 /// sql: null-terminated UTF-8 SQL
 /// timeout_ms: 0 = no timeout, else timeout in milliseconds
 /// Returns: statement ID (>0) on success, 0 on failure
+///
+/// With `statement-handle-reuse`, statements on direct (non-pooled)
+/// connections are prepared here and `odbc_execute` rebinds that handle.
 #[no_mangle]
 pub extern "C" fn odbc_prepare(conn_id: c_uint, sql: *const c_char, timeout_ms: c_uint) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
@@ -4436,6 +5045,19 @@ pub extern "C" fn odbc_prepare(conn_id: c_uint, sql: *const c_char, timeout_ms:
             }
             id
         };
+        #[cfg(feature = "statement-handle-reuse")]
+        if let Some(shared) = direct_shared_connection(&state, conn_id) {
+            let prepared = match shared.lock() {
+                Ok(mut cached) => cached.prepare_statement(stmt_id, stmt.sql()),
+                Err(_) => Err(OdbcError::InternalError(
+                    "Failed to lock connection".to_string(),
+                )),
+            };
+            if let Err(e) = prepared {
+                set_connection_structured_error(&mut state, conn_id, e.to_structured());
+                return 0;
+            }
+        }
         state.statements.insert(stmt_id, stmt);
         stmt_id
     })
@@ -4548,9 +5170,10 @@ pub extern "C" fn odbc_execute(
                     return -1;
                 }
             };
+            let options = handles_guard.result_options(conn_id).unwrap_or_default();
             drop(handles_guard);
 
-            let conn_guard = match conn_arc.lock() {
+            let mut conn_guard = match conn_arc.lock() {
                 Ok(g) => g,
                 Err(_) => {
                     drop(state);
@@ -4562,16 +5185,18 @@ pub extern "C" fn odbc_execute(
                 }
             };
 
-            execute_query_with_param_buffer_and_timeout(
-                conn_guard.connection(),
+            execute_prepared_with_param_buffer(
+                &mut conn_guard,
+                stmt_id,
                 &sql_str,
                 params_slice,
                 timeout_sec,
                 fetch_size_opt,
+                &options,
             )
         } else if let Some((_pool_id, pooled)) = state.pooled_connections.get(&conn_id) {
             execute_query_with_param_buffer_and_timeout(
-                pooled.get_connection(),
+                QueryConnection::new(pooled.get_connection(), pooled.result_options()),
                 &sql_str,
                 params_slice,
                 timeout_sec,
@@ -4677,7 +5302,8 @@ pub extern "C" fn odbc_close_statement(stmt_id: c_uint) -> c_int {
             return -1;
         };
 
-        if state.statements.remove(&stmt_id).is_some() {
+        if let Some(stmt) = state.statements.remove(&stmt_id) {
+            close_prepared_handle(&state, stmt.conn_id(), stmt_id);
             state.pending_result_buffers.retain(|key, _| match key {
                 PendingResultKey::Execute {
                     stmt_id: key_stmt, ..
@@ -4701,7 +5327,9 @@ pub extern "C" fn odbc_clear_all_statements() -> c_int {
             return -1;
         };
 
-        state.statements.clear();
+        for (stmt_id, stmt) in std::mem::take(&mut state.statements) {
+            close_prepared_handle(&state, stmt.conn_id(), stmt_id);
+        }
         state
             .pending_result_buffers
             .retain(|key, _| !matches!(key, PendingResultKey::Execute { .. }));
@@ -4709,6 +5337,33 @@ pub extern "C" fn odbc_clear_all_statements() -> c_int {
     })
 }
 
+/// Decodes the parameter buffer of an `odbc_stream_*_params` call: a legacy
+/// `ParamValue` list or a DRT1 list of `INPUT` parameters (streams carry no
+/// OUT footer). Sets a connection error and returns `None` when invalid.
+fn stream_params(
+    conn_id: c_uint,
+    params_buffer: *const u8,
+    params_len: c_uint,
+) -> Option<Vec<ParamValue>> {
+    if params_buffer.is_null() || params_len == 0 {
+        return Some(Vec::new());
+    }
+    let params_slice = unsafe { std::slice::from_raw_parts(params_buffer, params_len as usize) };
+    let error = match deserialize_param_buffer(params_slice) {
+        Ok(ParamList::Legacy(p)) => return Some(p),
+        Ok(ParamList::Directed(b)) => {
+            if b.iter().all(|x| x.direction == ParamDirection::Input) {
+                return Some(b.into_iter().map(|x| x.value).collect());
+            }
+            "OUTPUT/INOUT parameters are not supported on streams".to_string()
+        }
+        Err(e) => format!("Invalid params: {}", e),
+    };
+    let mut state = try_lock_global_state()?;
+    set_connection_error(&mut state, conn_id, error);
+    None
+}
+
 /// Start streaming query execution
 /// conn_id: connection ID
 /// sql: null-terminated UTF-8 SQL query
@@ -4719,8 +5374,37 @@ pub extern "C" fn odbc_stream_start(
     conn_id: c_uint,
     sql: *const c_char,
     chunk_size: c_uint,
+) -> c_uint {
+    stream_start(conn_id, sql, std::ptr::null(), 0, chunk_size)
+}
+
+/// [`odbc_stream_start`] with parameters bound to the query's `?` markers.
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_stream_start_params(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    stream_start(conn_id, sql, params_buffer, params_len, chunk_size)
+}
+
+fn stream_start(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
 ) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
         if sql.is_null() {
             return 0;
         }
@@ -4774,6 +5458,7 @@ pub extern "C" fn odbc_stream_start(
                 return 0;
             }
         };
+        let options = handles_guard.result_options(conn_id).unwrap_or_default();
         drop(handles_guard);
 
         let conn_guard = match conn_arc.lock() {
@@ -4798,7 +5483,13 @@ pub extern "C" fn odbc_stream_start(
             .and_then(|s| s.parse::<usize>().ok())
             .filter(|&t| t > 0);
 
-        let executor = StreamingExecutor::new(chunk_size);
+        let executor = StreamingExecutor::new(chunk_size)
+            .with_protocol_version(options.protocol)
+            .with_output_format(options.format)
+            .with_max_row_array_size(options.max_row_array_size)
+            .with_spill_options(options.spill)
+            .with_temporal_binding(options.temporal)
+            .with_params(params);
         let stream_state = if let Some(threshold) = spill_threshold_mb {
             executor.execute_streaming_with_spill(conn_guard.connection(), sql_str, Some(threshold))
         } else {
@@ -4867,7 +5558,45 @@ pub extern "C" fn odbc_stream_start_batched(
     fetch_size: c_uint,
     chunk_size: c_uint,
 ) -> c_uint {
-    crate::ffi_guard_id!(c_uint, {
+    stream_start_batched(conn_id, sql, std::ptr::null(), 0, fetch_size, chunk_size)
+}
+
+/// [`odbc_stream_start_batched`] with parameters bound to the query's `?` markers.
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_stream_start_batched_params(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    fetch_size: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    stream_start_batched(
+        conn_id,
+        sql,
+        params_buffer,
+        params_len,
+        fetch_size,
+        chunk_size,
+    )
+}
+
+fn stream_start_batched(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    fetch_size: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
         if sql.is_null() {
             return 0;
         }
@@ -4913,7 +5642,15 @@ pub extern "C" fn odbc_stream_start_batched(
 
         drop(state);
 
-        let executor = StreamingExecutor::new(chunk_size);
+        let options = connection_result_options(&handles, conn_id);
+        let executor = StreamingExecutor::new(chunk_size)
+            .with_protocol_version(options.protocol)
+            .with_output_format(options.format)
+            .with_buffer_policy(options.buffer)
+            .with_max_row_array_size(options.max_row_array_size)
+            .with_spill_options(options.spill)
+            .with_temporal_binding(options.temporal)
+            .with_params(params);
         match executor.start_batched_stream(handles, conn_id, sql_owned, fetch_size, chunk_size) {
             Ok(batched_state) => {
                 let Some(mut state) = try_lock_global_state() else {
@@ -4960,6 +5697,165 @@ pub extern "C" fn odbc_stream_start_batched(
     })
 }
 
+/// Start a resumable batched stream that walks the query in key order with
+/// dialect-specific keyset-pagination queries instead of one cursor.
+/// params_buffer/params_len: as in `odbc_stream_start_batched_params` (NULL = none)
+/// keys_json: NUL-terminated UTF-8 JSON array of the key columns, most
+/// significant first, e.g. `["tenant_id", "id"]`; together they must be
+/// unique and non-null, and `sql` must not have its own ORDER BY
+/// resume_token: token from `odbc_stream_get_continuation_token`, or NULL to
+/// start at the first row; valid on any connection for the same sql and keys
+/// page_rows: rows per page query (0 = 10000)
+/// fetch_size: rows per batch (0 = DEFAULT_FETCH_SIZE)
+/// chunk_size: bytes per FFI chunk (0 = DEFAULT_CHUNK_SIZE)
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_stream_start_keyset(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    keys_json: *const c_char,
+    resume_token: *const c_char,
+    page_rows: c_uint,
+    fetch_size: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
+        if sql.is_null() || keys_json.is_null() {
+            return 0;
+        }
+        // SAFETY: sql and keys_json were checked non-null above; caller
+        // guarantees the C-string contract, as for resume_token when non-null.
+        let sql_str = match unsafe { CStr::from_ptr(sql) }.to_str() {
+            Ok(s) => s,
+            Err(_) => return 0,
+        };
+        let pagination = unsafe { CStr::from_ptr(keys_json) }
+            .to_str()
+            .map_err(|e| OdbcError::ValidationError(format!("invalid keys_json: {e}")))
+            .and_then(|s| {
+                serde_json::from_str::<Vec<String>>(s)
+                    .map_err(|e| OdbcError::ValidationError(format!("invalid keys_json: {e}")))
+            })
+            .and_then(|keys| {
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                KeysetPagination::new(&keys)
+            })
+            .and_then(|pagination| {
+                let pagination = pagination.with_page_rows(page_rows);
+                if resume_token.is_null() {
+                    return Ok(pagination);
+                }
+                let token = unsafe { CStr::from_ptr(resume_token) }
+                    .to_str()
+                    .map_err(|_| {
+                        OdbcError::ValidationError("malformed continuation token".to_string())
+                    })
+                    .and_then(KeysetToken::decode)?;
+                Ok(pagination.resume_from(token))
+            });
+
+        let Some(mut state) = try_lock_global_state() else {
+            return 0;
+        };
+        let pagination = match pagination {
+            Ok(p) => p,
+            Err(e) => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("odbc_stream_start_keyset failed: {}", e),
+                );
+                return 0;
+            }
+        };
+        let handles = match state.connections.get(&conn_id) {
+            Some(c) => c.get_handles(),
+            None => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("Invalid connection ID: {}", conn_id),
+                );
+                return 0;
+            }
+        };
+        drop(state);
+
+        let fetch_size = if fetch_size > 0 {
+            fetch_size as usize
+        } else {
+            DEFAULT_FETCH_SIZE as usize
+        };
+        let chunk_size = if chunk_size > 0 {
+            chunk_size as usize
+        } else {
+            DEFAULT_CHUNK_SIZE as usize
+        };
+
+        let options = connection_result_options(&handles, conn_id);
+        let executor = StreamingExecutor::new(chunk_size)
+            .with_protocol_version(options.protocol)
+            .with_output_format(options.format)
+            .with_buffer_policy(options.buffer)
+            .with_max_row_array_size(options.max_row_array_size)
+            .with_spill_options(options.spill)
+            .with_temporal_binding(options.temporal)
+            .with_params(params);
+        let started = executor.start_keyset_stream(
+            handles,
+            conn_id,
+            sql_str.to_string(),
+            pagination,
+            fetch_size,
+            chunk_size,
+        );
+
+        let Some(mut state) = try_lock_global_state() else {
+            return 0;
+        };
+        match started {
+            Ok(batched_state) => {
+                let mut stream_id = 0u32;
+                for _ in 0..MAX_ID_ALLOC_ATTEMPTS {
+                    let candidate = state.next_stream_id;
+                    state.next_stream_id = state.next_stream_id.wrapping_add(1);
+                    if candidate != 0 && !state.streams.contains_key(&candidate) {
+                        stream_id = candidate;
+                        break;
+                    }
+                }
+                if stream_id == 0 {
+                    set_connection_error(
+                        &mut state,
+                        conn_id,
+                        "Failed to allocate stream ID".to_string(),
+                    );
+                    return 0;
+                }
+                state
+                    .streams
+                    .insert(stream_id, StreamKind::Batched(batched_state));
+                state.stream_connections.insert(stream_id, conn_id);
+                stream_id
+            }
+            Err(e) => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("odbc_stream_start_keyset failed: {}", e),
+                );
+                0
+            }
+        }
+    })
+}
+
 /// Start async batched stream execution. The query runs in a background worker and
 /// stream readiness is observable via `odbc_stream_poll_async`.
 /// Returns stream_id (>0) on success, 0 on error.
@@ -4969,8 +5865,46 @@ pub extern "C" fn odbc_stream_start_async(
     sql: *const c_char,
     fetch_size: c_uint,
     chunk_size: c_uint,
+) -> c_uint {
+    stream_start_async(conn_id, sql, std::ptr::null(), 0, fetch_size, chunk_size)
+}
+
+/// [`odbc_stream_start_async`] with parameters bound to the query's `?` markers.
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_stream_start_async_params(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    fetch_size: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    stream_start_async(
+        conn_id,
+        sql,
+        params_buffer,
+        params_len,
+        fetch_size,
+        chunk_size,
+    )
+}
+
+fn stream_start_async(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    fetch_size: c_uint,
+    chunk_size: c_uint,
 ) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
         if sql.is_null() {
             return 0;
         }
@@ -5009,7 +5943,15 @@ pub extern "C" fn odbc_stream_start_async(
 
         drop(state);
 
-        let executor = StreamingExecutor::new(chunk_size);
+        let options = connection_result_options(&handles, conn_id);
+        let executor = StreamingExecutor::new(chunk_size)
+            .with_protocol_version(options.protocol)
+            .with_output_format(options.format)
+            .with_buffer_policy(options.buffer)
+            .with_max_row_array_size(options.max_row_array_size)
+            .with_spill_options(options.spill)
+            .with_temporal_binding(options.temporal)
+            .with_params(params);
         match executor.start_async_stream(handles, conn_id, sql_owned, fetch_size, chunk_size) {
             Ok(async_state) => {
                 let Some(mut state) = try_lock_global_state() else {
@@ -5079,8 +6021,37 @@ pub extern "C" fn odbc_stream_multi_start_batched(
     conn_id: c_uint,
     sql: *const c_char,
     chunk_size: c_uint,
+) -> c_uint {
+    stream_multi_start_batched(conn_id, sql, std::ptr::null(), 0, chunk_size)
+}
+
+/// [`odbc_stream_multi_start_batched`] with parameters bound to the query's `?` markers.
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_stream_multi_start_batched_params(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    stream_multi_start_batched(conn_id, sql, params_buffer, params_len, chunk_size)
+}
+
+fn stream_multi_start_batched(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
 ) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
         if sql.is_null() {
             return 0;
         }
@@ -5116,7 +6087,9 @@ pub extern "C" fn odbc_stream_multi_start_batched(
         let sql_owned = sql_str.to_string();
         drop(state);
 
-        match crate::engine::start_multi_batched_stream(handles, conn_id, sql_owned, chunk_size) {
+        match crate::engine::start_multi_batched_stream_with_params(
+            handles, conn_id, sql_owned, params, chunk_size,
+        ) {
             Ok(batched_state) => {
                 let Some(mut state) = try_lock_global_state() else {
                     return 0;
@@ -5155,8 +6128,37 @@ pub extern "C" fn odbc_stream_multi_start_async(
     conn_id: c_uint,
     sql: *const c_char,
     chunk_size: c_uint,
+) -> c_uint {
+    stream_multi_start_async(conn_id, sql, std::ptr::null(), 0, chunk_size)
+}
+
+/// [`odbc_stream_multi_start_async`] with parameters bound to the query's `?` markers.
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// Returns: stream_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_stream_multi_start_async_params(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
+) -> c_uint {
+    stream_multi_start_async(conn_id, sql, params_buffer, params_len, chunk_size)
+}
+
+fn stream_multi_start_async(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    chunk_size: c_uint,
 ) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+            return 0;
+        };
         if sql.is_null() {
             return 0;
         }
@@ -5184,7 +6186,9 @@ pub extern "C" fn odbc_stream_multi_start_async(
         let sql_owned = sql_str.to_string();
         drop(state);
 
-        match crate::engine::start_multi_async_stream(handles, conn_id, sql_owned, chunk_size) {
+        match crate::engine::start_multi_async_stream_with_params(
+            handles, conn_id, sql_owned, params, chunk_size,
+        ) {
             Ok(async_state) => {
                 let Some(mut state) = try_lock_global_state() else {
                     return 0;
@@ -5259,74 +6263,186 @@ pub extern "C" fn odbc_stream_poll_async(stream_id: c_uint, out_status: *mut c_i
     })
 }
 
-/// Fetch next chunk from stream
-/// stream_id: stream ID from odbc_stream_start
-/// out_buf: output buffer
-/// buf_len: buffer size
-/// out_written: bytes written
-/// has_more: 1 if more data available, 0 otherwise
-/// Returns: 0 on success, non-zero on error
+/// Buffer statistics of a batched or async stream as JSON:
+/// `{ buffered_batches, buffered_bytes, high_water_batches, high_water_bytes,
+///    spilled_batches, spilled_bytes, spill_pending_bytes, producer_waits }`.
+/// Streams started with `odbc_stream_start` are fully materialised and have
+/// no worker buffer; they report an error.
+/// Returns: 0 on success, -1 on error, -2 if buffer too small.
 #[no_mangle]
-pub extern "C" fn odbc_stream_fetch(
+pub extern "C" fn odbc_stream_get_buffer_stats(
     stream_id: c_uint,
-    out_buf: *mut u8,
-    buf_len: c_uint,
+    buffer: *mut u8,
+    buffer_len: c_uint,
     out_written: *mut c_uint,
-    has_more: *mut u8,
 ) -> c_int {
     crate::ffi_guard_int!({
-        if out_buf.is_null() || out_written.is_null() || has_more.is_null() {
+        if buffer.is_null() || out_written.is_null() || buffer_len == 0 {
+            set_out_written_zero(out_written);
             return -1;
         }
-
         let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(out_written);
             return -1;
         };
-
-        let stream_conn_id = state.stream_connections.get(&stream_id).copied();
-
-        if let Some(needed_len) = state
-            .pending_stream_chunks
-            .get(&stream_id)
-            .map(|pending| pending.data.len())
-        {
-            if needed_len > buf_len as usize {
-                if let Some(conn_id) = stream_conn_id {
-                    set_connection_error(
-                        &mut state,
-                        conn_id,
-                        format!(
-                            "Buffer too small: need {} bytes, got {}",
-                            needed_len, buf_len
-                        ),
-                    );
-                } else {
-                    set_error(
-                        &mut state,
-                        format!(
-                            "Buffer too small: need {} bytes, got {}",
-                            needed_len, buf_len
-                        ),
-                    );
-                }
-                return -2;
+        let stats = match state.streams.get(&stream_id) {
+            Some(stream) => stream.buffer_stats(),
+            None => {
+                set_error(&mut state, format!("Invalid stream ID: {}", stream_id));
+                set_out_written_zero(out_written);
+                return -1;
             }
-
-            let pending = match state.pending_stream_chunks.remove(&stream_id) {
-                Some(p) => p,
-                None => {
-                    // Race: chunk vanished between length check and removal. Treat as
-                    // an internal-state error rather than panicking across the FFI.
-                    set_error(
-                        &mut state,
-                        format!("Stream {} pending chunk vanished concurrently", stream_id),
-                    );
-                    return -1;
-                }
-            };
-            // Keep has_more from the original fetch that produced this chunk.
-            // This preserves stream semantics across buffer-resize retries.
-            let has_more_value = pending.has_more;
+        };
+        let Some(stats) = stats else {
+            set_error(
+                &mut state,
+                format!("Stream {stream_id} is not a batched or async stream"),
+            );
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let json = match report_json(&stats) {
+            Ok(json) => json,
+            Err(e) => {
+                set_error(&mut state, e.to_string());
+                set_out_written_zero(out_written);
+                return -1;
+            }
+        };
+        if json.len() > buffer_len as usize {
+            set_out_written_zero(out_written);
+            return -2;
+        }
+        // SAFETY: buffer has buffer_len capacity, verified above; out_written non-null.
+        unsafe {
+            std::ptr::copy_nonoverlapping(json.as_ptr(), buffer, json.len());
+            *out_written = json.len() as c_uint;
+        }
+        0
+    })
+}
+
+/// Continuation token of a stream started with `odbc_stream_start_keyset`:
+/// the resume point after the last batch fully returned by
+/// `odbc_stream_fetch`, as NUL-free UTF-8 text to pass back as
+/// `resume_token`. Writes 0 bytes before the first batch completes.
+/// Returns: 0 on success, -1 on error (invalid or non-resumable stream),
+/// -2 if buffer too small.
+#[no_mangle]
+pub extern "C" fn odbc_stream_get_continuation_token(
+    stream_id: c_uint,
+    buffer: *mut u8,
+    buffer_len: c_uint,
+    out_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if buffer.is_null() || out_written.is_null() || buffer_len == 0 {
+            set_out_written_zero(out_written);
+            return -1;
+        }
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let token = match state.streams.get(&stream_id) {
+            Some(StreamKind::Batched(s)) if s.is_resumable() => {
+                s.continuation_token().unwrap_or_default().to_string()
+            }
+            Some(_) => {
+                set_error(
+                    &mut state,
+                    format!("Stream {stream_id} is not a keyset stream"),
+                );
+                set_out_written_zero(out_written);
+                return -1;
+            }
+            None => {
+                set_error(&mut state, format!("Invalid stream ID: {}", stream_id));
+                set_out_written_zero(out_written);
+                return -1;
+            }
+        };
+        if token.len() > buffer_len as usize {
+            set_out_written_zero(out_written);
+            return -2;
+        }
+        // SAFETY: buffer has buffer_len capacity, verified above; out_written non-null.
+        unsafe {
+            std::ptr::copy_nonoverlapping(token.as_ptr(), buffer, token.len());
+            *out_written = token.len() as c_uint;
+        }
+        0
+    })
+}
+
+/// Fetch next chunk from stream
+/// stream_id: stream ID from odbc_stream_start
+/// out_buf: output buffer
+/// buf_len: buffer size
+/// out_written: bytes written
+/// has_more: 1 if more data available, 0 otherwise
+/// Returns: 0 on success, non-zero on error
+#[no_mangle]
+pub extern "C" fn odbc_stream_fetch(
+    stream_id: c_uint,
+    out_buf: *mut u8,
+    buf_len: c_uint,
+    out_written: *mut c_uint,
+    has_more: *mut u8,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if out_buf.is_null() || out_written.is_null() || has_more.is_null() {
+            return -1;
+        }
+
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+
+        let stream_conn_id = state.stream_connections.get(&stream_id).copied();
+
+        if let Some(needed_len) = state
+            .pending_stream_chunks
+            .get(&stream_id)
+            .map(|pending| pending.data.len())
+        {
+            if needed_len > buf_len as usize {
+                if let Some(conn_id) = stream_conn_id {
+                    set_connection_error(
+                        &mut state,
+                        conn_id,
+                        format!(
+                            "Buffer too small: need {} bytes, got {}",
+                            needed_len, buf_len
+                        ),
+                    );
+                } else {
+                    set_error(
+                        &mut state,
+                        format!(
+                            "Buffer too small: need {} bytes, got {}",
+                            needed_len, buf_len
+                        ),
+                    );
+                }
+                return -2;
+            }
+
+            let pending = match state.pending_stream_chunks.remove(&stream_id) {
+                Some(p) => p,
+                None => {
+                    // Race: chunk vanished between length check and removal. Treat as
+                    // an internal-state error rather than panicking across the FFI.
+                    set_error(
+                        &mut state,
+                        format!("Stream {} pending chunk vanished concurrently", stream_id),
+                    );
+                    return -1;
+                }
+            };
+            // Keep has_more from the original fetch that produced this chunk.
+            // This preserves stream semantics across buffer-resize retries.
+            let has_more_value = pending.has_more;
             let written_len = pending.data.len();
             // SAFETY: `out_buf` was checked non-null and `buf_len` covers `written_len`
             // (verified above against `needed_len`). `out_written`/`has_more` were
@@ -5462,101 +6578,669 @@ pub extern "C" fn odbc_stream_close(stream_id: c_uint) -> c_int {
     })
 }
 
-/// Create connection pool
-/// conn_str: null-terminated UTF-8 connection string
-/// max_size: maximum pool size
-/// Returns: pool_id (>0) on success, 0 on failure
+/// Open a scrollable cursor over the result of a query
+/// conn_id: connection ID
+/// sql: null-terminated UTF-8 SQL query
+/// params_buffer: serialized ParamValue array or DRT1 list of `INPUT`
+/// parameters, same layout as `odbc_exec_query_params` (NULL = none)
+/// params_len: length of params_buffer in bytes
+/// The driver's scrollable cursor is used when available; otherwise the
+/// result is read once into a spill file and scrolled from there
+/// (see `odbc_cursor_is_emulated`).
+/// Returns: cursor_id (>0) on success, 0 on failure
 #[no_mangle]
-pub extern "C" fn odbc_pool_create(conn_str: *const c_char, max_size: c_uint) -> c_uint {
+pub extern "C" fn odbc_cursor_open(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+) -> c_uint {
     crate::ffi_guard_id!(c_uint, {
-        if conn_str.is_null() {
+        let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
             return 0;
-        }
-
-        // Safety: `conn_str` must be a valid null-terminated C string pointer
-        let c_str = unsafe { CStr::from_ptr(conn_str) };
-        let conn_str_rust = match c_str.to_str() {
-            Ok(s) => s,
-            Err(_) => return 0,
         };
-
-        pool_create_inner(conn_str_rust, max_size, crate::pool::PoolOptions::default())
-    })
-}
-
-/// Create a connection pool with explicit eviction/timeout options (NEW v3.0).
-///
-/// `conn_str`: NUL-terminated UTF-8 connection string.
-/// `max_size`: maximum number of connections.
-/// `options_json`: NUL-terminated UTF-8 JSON
-///   `{ "idle_timeout_ms"?: int, "max_lifetime_ms"?: int, "connection_timeout_ms"?: int }`.
-///   May be null/empty to use defaults.
-///
-/// Returns: pool_id (>0) on success, 0 on failure.
-#[no_mangle]
-pub extern "C" fn odbc_pool_create_with_options(
-    conn_str: *const c_char,
-    max_size: c_uint,
-    options_json: *const c_char,
-) -> c_uint {
-    crate::ffi_guard_id!(c_uint, {
-        if conn_str.is_null() {
+        if sql.is_null() {
             return 0;
         }
-        let c_str = unsafe { CStr::from_ptr(conn_str) };
-        let conn_str_rust = match c_str.to_str() {
+        // SAFETY: sql was checked non-null; caller guarantees the C-string contract.
+        let sql_str = match unsafe { CStr::from_ptr(sql) }.to_str() {
             Ok(s) => s,
             Err(_) => return 0,
         };
 
-        let opts = if options_json.is_null() {
-            crate::pool::PoolOptions::default()
-        } else {
-            let s = match unsafe { CStr::from_ptr(options_json).to_str() } {
-                Ok(s) => s,
-                Err(_) => return 0,
-            };
-            if s.trim().is_empty() {
-                crate::pool::PoolOptions::default()
-            } else {
-                #[derive(serde::Deserialize)]
-                struct OptsJson {
-                    #[serde(default)]
-                    idle_timeout_ms: Option<u64>,
-                    #[serde(default)]
-                    max_lifetime_ms: Option<u64>,
-                    #[serde(default)]
-                    connection_timeout_ms: Option<u64>,
-                }
-                let parsed: OptsJson = match serde_json::from_str(s) {
-                    Ok(p) => p,
-                    Err(_) => return 0,
-                };
-                crate::pool::PoolOptions {
-                    idle_timeout: parsed.idle_timeout_ms.map(Duration::from_millis),
-                    max_lifetime: parsed.max_lifetime_ms.map(Duration::from_millis),
-                    connection_timeout: parsed.connection_timeout_ms.map(Duration::from_millis),
-                }
+        let Some(mut state) = try_lock_global_state() else {
+            return 0;
+        };
+        let handles = match state.connections.get(&conn_id) {
+            Some(c) => c.get_handles(),
+            None => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("Invalid connection ID: {}", conn_id),
+                );
+                return 0;
             }
         };
+        drop(state);
 
-        pool_create_inner(conn_str_rust, max_size, opts)
+        let opened = handles
+            .lock()
+            .map_err(|_| OdbcError::InternalError("Failed to lock handles mutex".to_string()))
+            .and_then(|h| {
+                let conn = h.get_connection(conn_id)?;
+                Ok((conn, h.result_options(conn_id).unwrap_or_default()))
+            })
+            .and_then(|(conn, options)| ScrollableCursor::open(conn, sql_str, &params, &options));
+
+        let Some(mut state) = try_lock_global_state() else {
+            return 0;
+        };
+        let cursor = match opened {
+            Ok(c) => c,
+            Err(e) => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("odbc_cursor_open failed: {}", e),
+                );
+                return 0;
+            }
+        };
+        let mut cursor_id = 0u32;
+        for _ in 0..MAX_ID_ALLOC_ATTEMPTS {
+            let candidate = state.next_cursor_id;
+            state.next_cursor_id = state.next_cursor_id.wrapping_add(1);
+            if candidate != 0 && !state.cursors.contains_key(&candidate) {
+                cursor_id = candidate;
+                break;
+            }
+        }
+        if cursor_id == 0 {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                "Failed to allocate cursor ID".to_string(),
+            );
+            return 0;
+        }
+        state.cursors.insert(
+            cursor_id,
+            OpenCursor {
+                conn_id,
+                cursor,
+                pending_block: None,
+            },
+        );
+        cursor_id
     })
 }
 
-fn pool_create_inner(
-    conn_str: &str,
-    max_size: c_uint,
-    options: crate::pool::PoolOptions,
-) -> c_uint {
-    match ConnectionPool::new_with_options(conn_str, max_size, options) {
-        Ok(pool) => {
-            let Some(mut state) = try_lock_global_state() else {
-                return 0;
-            };
-            let pool_id = {
-                let mut id = 0u32;
-                for _ in 0..MAX_ID_ALLOC_ATTEMPTS {
+/// Fetch a block of rows from a scrollable cursor
+/// cursor_id: cursor ID from odbc_cursor_open
+/// orientation: ODBC fetch orientation: 2 = FIRST, 3 = LAST, 5 = ABSOLUTE
+/// (offset is the 1-based start row, negative counts from the end),
+/// 6 = RELATIVE (offset rows from the start of the current block)
+/// offset: row offset for ABSOLUTE / RELATIVE, ignored otherwise
+/// rows: block size (> 0)
+/// out_buf / buf_len: receives one binary result message, same layout as
+/// `odbc_exec_query`; it has no rows once the cursor moved past either end
+/// out_written: bytes written
+/// Returns: 0 on success, -1 on error, -2 if buf_len is too small; the
+/// block is then kept and returned by the next call without moving the
+/// cursor
+#[no_mangle]
+pub extern "C" fn odbc_cursor_fetch(
+    cursor_id: c_uint,
+    orientation: c_int,
+    offset: i64,
+    rows: c_uint,
+    out_buf: *mut u8,
+    buf_len: c_uint,
+    out_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if out_buf.is_null() || out_written.is_null() {
+            set_out_written_zero(out_written);
+            return -1;
+        }
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let Some(mut open) = state.cursors.remove(&cursor_id) else {
+            set_error(&mut state, format!("Invalid cursor ID: {}", cursor_id));
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let conn_id = open.conn_id;
+
+        let block = match open.pending_block.take() {
+            Some(block) => Ok(block),
+            None => {
+                drop(state);
+                let fetch = match orientation {
+                    2 => Ok(ScrollFetch::First),
+                    3 => Ok(ScrollFetch::Last),
+                    5 => Ok(ScrollFetch::Absolute(offset)),
+                    6 => Ok(ScrollFetch::Relative(offset)),
+                    other => Err(OdbcError::ValidationError(format!(
+                        "unsupported fetch orientation: {other}"
+                    ))),
+                };
+                let block = fetch.and_then(|f| open.cursor.fetch(f, rows as usize));
+                let Some(relocked) = try_lock_global_state() else {
+                    set_out_written_zero(out_written);
+                    return -1;
+                };
+                state = relocked;
+                block
+            }
+        };
+
+        let result = match block {
+            Ok(block) if block.len() > buf_len as usize => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!(
+                        "Buffer too small: need {} bytes, got {}",
+                        block.len(),
+                        buf_len
+                    ),
+                );
+                open.pending_block = Some(block);
+                set_out_written_zero(out_written);
+                -2
+            }
+            Ok(block) => {
+                // SAFETY: out_buf has buf_len capacity, verified above; out_written non-null.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(block.as_ptr(), out_buf, block.len());
+                    *out_written = block.len() as c_uint;
+                }
+                0
+            }
+            Err(e) => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("odbc_cursor_fetch failed: {}", e),
+                );
+                set_out_written_zero(out_written);
+                -1
+            }
+        };
+        // The connection may have been closed while the fetch ran unlocked.
+        if state.connections.contains_key(&conn_id) {
+            state.cursors.insert(cursor_id, open);
+        }
+        result
+    })
+}
+
+/// Report whether a cursor scrolls over a spill file rather than a driver
+/// scrollable cursor
+/// Returns: 1 if emulated, 0 if native, -1 if cursor_id is invalid
+#[no_mangle]
+pub extern "C" fn odbc_cursor_is_emulated(cursor_id: c_uint) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        match state.cursors.get(&cursor_id) {
+            Some(open) => c_int::from(open.cursor.is_emulated()),
+            None => {
+                set_error(&mut state, format!("Invalid cursor ID: {}", cursor_id));
+                -1
+            }
+        }
+    })
+}
+
+/// Close a scrollable cursor and delete its spill file, if any
+/// cursor_id: cursor ID to close
+/// Returns: 0 on success, non-zero on failure
+#[no_mangle]
+pub extern "C" fn odbc_cursor_close(cursor_id: c_uint) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        if state.cursors.remove(&cursor_id).is_some() {
+            0
+        } else {
+            set_error(&mut state, format!("Invalid cursor ID: {}", cursor_id));
+            1
+        }
+    })
+}
+
+/// Opens a LOB handle on `conn_id` with `open` and registers it.
+/// Returns the lob_id, or 0 with the connection error set.
+fn lob_open_inner(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    context: &str,
+    open: impl FnOnce(
+        crate::handles::SharedConnection,
+        &str,
+        &[ParamValue],
+        TemporalBinding,
+    ) -> Result<LobHandle>,
+) -> c_uint {
+    let Some(params) = stream_params(conn_id, params_buffer, params_len) else {
+        return 0;
+    };
+    if sql.is_null() {
+        return 0;
+    }
+    // SAFETY: sql was checked non-null; caller guarantees the C-string contract.
+    let sql_str = match unsafe { CStr::from_ptr(sql) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+
+    let Some(mut state) = try_lock_global_state() else {
+        return 0;
+    };
+    let handles = match state.connections.get(&conn_id) {
+        Some(c) => c.get_handles(),
+        None => {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                format!("Invalid connection ID: {}", conn_id),
+            );
+            return 0;
+        }
+    };
+    drop(state);
+
+    let opened = handles
+        .lock()
+        .map_err(|_| OdbcError::InternalError("Failed to lock handles mutex".to_string()))
+        .and_then(|h| {
+            let conn = h.get_connection(conn_id)?;
+            Ok((conn, h.result_options(conn_id).unwrap_or_default()))
+        })
+        .and_then(|(conn, options)| open(conn, sql_str, &params, options.temporal));
+
+    let Some(mut state) = try_lock_global_state() else {
+        return 0;
+    };
+    let handle = match opened {
+        Ok(h) => h,
+        Err(e) => {
+            set_connection_error(&mut state, conn_id, format!("{} failed: {}", context, e));
+            return 0;
+        }
+    };
+    let mut lob_id = 0u32;
+    for _ in 0..MAX_ID_ALLOC_ATTEMPTS {
+        let candidate = state.next_lob_id;
+        state.next_lob_id = state.next_lob_id.wrapping_add(1);
+        if candidate != 0 && !state.lobs.contains_key(&candidate) {
+            lob_id = candidate;
+            break;
+        }
+    }
+    if lob_id == 0 {
+        set_connection_error(&mut state, conn_id, "Failed to allocate LOB ID".to_string());
+        return 0;
+    }
+    state.lobs.insert(lob_id, OpenLob { conn_id, handle });
+    lob_id
+}
+
+/// Open one column of the first row of a query for piecewise reading
+/// conn_id: connection ID
+/// sql: null-terminated UTF-8 SQL query
+/// params_buffer / params_len: `INPUT` parameters, same layout as
+/// `odbc_cursor_open` (NULL = none)
+/// column: 1-based column to read
+/// as_text: 0 = raw bytes, 1 = UTF-8 text
+/// Returns: lob_id (>0) on success, 0 on failure (also when the query
+/// returns no rows)
+#[no_mangle]
+pub extern "C" fn odbc_lob_open_read(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    column: c_uint,
+    as_text: c_uint,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        lob_open_inner(
+            conn_id,
+            sql,
+            params_buffer,
+            params_len,
+            "odbc_lob_open_read",
+            |conn, sql, params, temporal| {
+                let column = u16::try_from(column).map_err(|_| {
+                    OdbcError::ValidationError(format!("LOB column {column} out of range"))
+                })?;
+                LobReader::open(conn, sql, params, temporal, column, lob_kind(as_text)?)
+                    .map(|r| LobHandle::Reader(Box::new(r)))
+            },
+        )
+    })
+}
+
+/// Read the next piece of a LOB opened with `odbc_lob_open_read`
+/// out_buf / buf_len: receives up to buf_len bytes of the value
+/// out_written: bytes written; 0 once the whole value has been read
+/// Returns: 0 on success, 1 if the cell is NULL, -1 on error
+#[no_mangle]
+pub extern "C" fn odbc_lob_read(
+    lob_id: c_uint,
+    out_buf: *mut u8,
+    buf_len: c_uint,
+    out_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if out_buf.is_null() || out_written.is_null() || buf_len == 0 {
+            set_out_written_zero(out_written);
+            return -1;
+        }
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let Some(mut open) = state.lobs.remove(&lob_id) else {
+            set_error(&mut state, format!("Invalid LOB ID: {}", lob_id));
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let conn_id = open.conn_id;
+        drop(state);
+
+        let piece = match &mut open.handle {
+            LobHandle::Reader(reader) => reader
+                .read(buf_len as usize)
+                .and_then(|piece| Ok((piece, reader.is_null()?))),
+            LobHandle::Writer(_) => Err(OdbcError::ValidationError(format!(
+                "LOB {lob_id} was opened for writing"
+            ))),
+        };
+
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(out_written);
+            return -1;
+        };
+        let result = match piece {
+            Ok((piece, is_null)) => {
+                // SAFETY: piece.len() <= buf_len; out_buf and out_written non-null.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(piece.as_ptr(), out_buf, piece.len());
+                    *out_written = piece.len() as c_uint;
+                }
+                c_int::from(is_null)
+            }
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, format!("odbc_lob_read failed: {}", e));
+                set_out_written_zero(out_written);
+                -1
+            }
+        };
+        // The connection may have been closed while the read ran unlocked.
+        if state.connections.contains_key(&conn_id) {
+            state.lobs.insert(lob_id, open);
+        }
+        result
+    })
+}
+
+/// Prepare a statement and stream one of its parameters with
+/// `odbc_lob_write`, for values larger than the 16 MiB limit of a bound
+/// parameter (`MAX_PARAM_VALUE_PAYLOAD_LEN`)
+/// conn_id: connection ID
+/// sql: null-terminated UTF-8 SQL statement
+/// params_buffer / params_len: values for the other `?` markers, in order
+/// (NULL = none)
+/// lob_param: 1-based position of the streamed `?` marker
+/// as_text: 0 = raw bytes, 1 = UTF-8 text
+/// total_len: byte length of a binary value if known, 0 = unknown
+/// The statement executes when the handle is closed with `odbc_lob_close`.
+/// Returns: lob_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_lob_open_write(
+    conn_id: c_uint,
+    sql: *const c_char,
+    params_buffer: *const u8,
+    params_len: c_uint,
+    lob_param: c_uint,
+    as_text: c_uint,
+    total_len: u64,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        lob_open_inner(
+            conn_id,
+            sql,
+            params_buffer,
+            params_len,
+            "odbc_lob_open_write",
+            |conn, sql, params, temporal| {
+                let lob_param = u16::try_from(lob_param).map_err(|_| {
+                    OdbcError::ValidationError(format!("LOB parameter {lob_param} out of range"))
+                })?;
+                let total_len = (total_len > 0).then_some(total_len);
+                LobWriter::open(
+                    conn,
+                    sql,
+                    params,
+                    temporal,
+                    lob_param,
+                    lob_kind(as_text)?,
+                    total_len,
+                )
+                .map(|w| LobHandle::Writer(Box::new(w)))
+            },
+        )
+    })
+}
+
+/// Send the next chunk of a LOB opened with `odbc_lob_open_write`
+/// data / len: chunk bytes; text chunks may split UTF-8 sequences
+/// Returns: 0 on success, -1 on error
+#[no_mangle]
+pub extern "C" fn odbc_lob_write(lob_id: c_uint, data: *const u8, len: c_uint) -> c_int {
+    crate::ffi_guard_int!({
+        if data.is_null() && len > 0 {
+            return -1;
+        }
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let Some(mut open) = state.lobs.remove(&lob_id) else {
+            set_error(&mut state, format!("Invalid LOB ID: {}", lob_id));
+            return -1;
+        };
+        let conn_id = open.conn_id;
+        drop(state);
+
+        let chunk: &[u8] = if len == 0 {
+            &[]
+        } else {
+            // SAFETY: data non-null with len bytes per the caller contract.
+            unsafe { std::slice::from_raw_parts(data, len as usize) }
+        };
+        let written = match &mut open.handle {
+            LobHandle::Writer(writer) => writer.write(chunk),
+            LobHandle::Reader(_) => Err(OdbcError::ValidationError(format!(
+                "LOB {lob_id} was opened for reading"
+            ))),
+        };
+
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let result = match written {
+            Ok(()) => 0,
+            Err(e) => {
+                set_connection_error(&mut state, conn_id, format!("odbc_lob_write failed: {}", e));
+                -1
+            }
+        };
+        if state.connections.contains_key(&conn_id) {
+            state.lobs.insert(lob_id, open);
+        }
+        result
+    })
+}
+
+/// Close a LOB handle. For an upload this ends the value and executes the
+/// statement; the handle is released even if that fails.
+/// out_rows: affected row count of an upload (-1 if unknown, 0 for reads);
+/// may be NULL
+/// Returns: 0 on success, -1 on error
+#[no_mangle]
+pub extern "C" fn odbc_lob_close(lob_id: c_uint, out_rows: *mut i64) -> c_int {
+    crate::ffi_guard_int!({
+        let Some(mut state) = try_lock_global_state() else {
+            return -1;
+        };
+        let Some(open) = state.lobs.remove(&lob_id) else {
+            set_error(&mut state, format!("Invalid LOB ID: {}", lob_id));
+            return -1;
+        };
+        drop(state);
+
+        let finished = match open.handle {
+            LobHandle::Writer(mut writer) => writer.finish(),
+            LobHandle::Reader(_) => Ok(0),
+        };
+        match finished {
+            Ok(rows) => {
+                if !out_rows.is_null() {
+                    // SAFETY: out_rows checked non-null.
+                    unsafe { *out_rows = rows };
+                }
+                0
+            }
+            Err(e) => {
+                if let Some(mut state) = try_lock_global_state() {
+                    set_connection_error(
+                        &mut state,
+                        open.conn_id,
+                        format!("odbc_lob_close failed: {}", e),
+                    );
+                }
+                -1
+            }
+        }
+    })
+}
+
+fn lob_kind(as_text: c_uint) -> Result<LobKind> {
+    match as_text {
+        0 => Ok(LobKind::Binary),
+        1 => Ok(LobKind::Text),
+        other => Err(OdbcError::ValidationError(format!(
+            "as_text must be 0 or 1, got {other}"
+        ))),
+    }
+}
+
+/// Create connection pool
+/// conn_str: null-terminated UTF-8 connection string
+/// max_size: maximum pool size
+/// Returns: pool_id (>0) on success, 0 on failure
+#[no_mangle]
+pub extern "C" fn odbc_pool_create(conn_str: *const c_char, max_size: c_uint) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        if conn_str.is_null() {
+            return 0;
+        }
+
+        // Safety: `conn_str` must be a valid null-terminated C string pointer
+        let c_str = unsafe { CStr::from_ptr(conn_str) };
+        let conn_str_rust = match c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => return 0,
+        };
+
+        pool_create_inner(conn_str_rust, max_size, crate::pool::PoolOptions::default())
+    })
+}
+
+/// Create a connection pool with explicit eviction/timeout options (NEW v3.0).
+///
+/// `conn_str`: NUL-terminated UTF-8 connection string.
+/// `max_size`: maximum number of connections.
+/// `options_json`: NUL-terminated UTF-8 JSON
+///   `{ "idle_timeout_ms"?: int, "max_lifetime_ms"?: int, "connection_timeout_ms"?: int }`.
+///   May be null/empty to use defaults.
+///
+/// Returns: pool_id (>0) on success, 0 on failure.
+#[no_mangle]
+pub extern "C" fn odbc_pool_create_with_options(
+    conn_str: *const c_char,
+    max_size: c_uint,
+    options_json: *const c_char,
+) -> c_uint {
+    crate::ffi_guard_id!(c_uint, {
+        if conn_str.is_null() {
+            return 0;
+        }
+        let c_str = unsafe { CStr::from_ptr(conn_str) };
+        let conn_str_rust = match c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => return 0,
+        };
+
+        let opts = if options_json.is_null() {
+            crate::pool::PoolOptions::default()
+        } else {
+            let s = match unsafe { CStr::from_ptr(options_json).to_str() } {
+                Ok(s) => s,
+                Err(_) => return 0,
+            };
+            if s.trim().is_empty() {
+                crate::pool::PoolOptions::default()
+            } else {
+                #[derive(serde::Deserialize)]
+                struct OptsJson {
+                    #[serde(default)]
+                    idle_timeout_ms: Option<u64>,
+                    #[serde(default)]
+                    max_lifetime_ms: Option<u64>,
+                    #[serde(default)]
+                    connection_timeout_ms: Option<u64>,
+                }
+                let parsed: OptsJson = match serde_json::from_str(s) {
+                    Ok(p) => p,
+                    Err(_) => return 0,
+                };
+                crate::pool::PoolOptions {
+                    idle_timeout: parsed.idle_timeout_ms.map(Duration::from_millis),
+                    max_lifetime: parsed.max_lifetime_ms.map(Duration::from_millis),
+                    connection_timeout: parsed.connection_timeout_ms.map(Duration::from_millis),
+                }
+            }
+        };
+
+        pool_create_inner(conn_str_rust, max_size, opts)
+    })
+}
+
+fn pool_create_inner(
+    conn_str: &str,
+    max_size: c_uint,
+    options: crate::pool::PoolOptions,
+) -> c_uint {
+    match ConnectionPool::new_with_options(conn_str, max_size, options) {
+        Ok(pool) => {
+            let Some(mut state) = try_lock_global_state() else {
+                return 0;
+            };
+            let pool_id = {
+                let mut id = 0u32;
+                for _ in 0..MAX_ID_ALLOC_ATTEMPTS {
                     let candidate = state.next_pool_id;
                     state.next_pool_id = state.next_pool_id.wrapping_add(1);
                     if candidate != 0 && !state.pools.contains_key(&candidate) {
@@ -6031,6 +7715,208 @@ pub extern "C" fn odbc_bulk_insert_array(
     })
 }
 
+/// Array-bound `UPDATE table SET ... WHERE key = ?`, one parameter set per row.
+/// data_buffer: bulk insert binary payload; columns named in key_columns_json
+/// form the WHERE clause, the others are assigned.
+/// key_columns_json: NUL-terminated UTF-8 JSON array of key column names.
+/// rows_affected: output, total rows updated.
+/// batch_counts: optional output (may be null), rows affected by each array
+/// execution (-1 when the driver reported none); at most batch_counts_len
+/// entries are written and batch_counts_written receives how many.
+/// Returns: 0 on success, -1 on error.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_bulk_update_array(
+    conn_id: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    key_columns_json: *const c_char,
+    rows_affected: *mut c_uint,
+    batch_counts: *mut i64,
+    batch_counts_len: c_uint,
+    batch_counts_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        bulk_keyed_dml(
+            "odbc_bulk_update_array",
+            ArrayBinding::bulk_update_by_key,
+            conn_id,
+            data_buffer,
+            buffer_len,
+            key_columns_json,
+            rows_affected,
+            batch_counts,
+            batch_counts_len,
+            batch_counts_written,
+        )
+    })
+}
+
+/// Array-bound `DELETE FROM table WHERE key = ?`, one parameter set per row.
+/// Payload columns not named in key_columns_json are ignored; the other
+/// arguments and return codes are those of `odbc_bulk_update_array`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_bulk_delete_array(
+    conn_id: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    key_columns_json: *const c_char,
+    rows_affected: *mut c_uint,
+    batch_counts: *mut i64,
+    batch_counts_len: c_uint,
+    batch_counts_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        bulk_keyed_dml(
+            "odbc_bulk_delete_array",
+            ArrayBinding::bulk_delete_by_key,
+            conn_id,
+            data_buffer,
+            buffer_len,
+            key_columns_json,
+            rows_affected,
+            batch_counts,
+            batch_counts_len,
+            batch_counts_written,
+        )
+    })
+}
+
+type KeyedDmlFn = fn(
+    &ArrayBinding,
+    &odbc_api::Connection<'static>,
+    &BulkInsertPayload,
+    &[&str],
+) -> Result<BulkDmlOutcome>;
+
+#[allow(clippy::too_many_arguments)]
+fn bulk_keyed_dml(
+    fn_name: &str,
+    op: KeyedDmlFn,
+    conn_id: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    key_columns_json: *const c_char,
+    rows_affected: *mut c_uint,
+    batch_counts: *mut i64,
+    batch_counts_len: c_uint,
+    batch_counts_written: *mut c_uint,
+) -> c_int {
+    let Some(mut state) = try_lock_global_state() else {
+        return -1;
+    };
+    if data_buffer.is_null()
+        || buffer_len == 0
+        || key_columns_json.is_null()
+        || rows_affected.is_null()
+        || (!batch_counts.is_null() && batch_counts_written.is_null())
+    {
+        set_error(
+            &mut state,
+            format!(
+                "{fn_name}: data_buffer, key_columns_json and rows_affected must be non-null, \
+                 buffer_len > 0, batch_counts_written required with batch_counts"
+            ),
+        );
+        return -1;
+    }
+
+    // SAFETY: key_columns_json was checked non-null above; caller guarantees C-string contract.
+    let keys: Vec<String> = match unsafe { CStr::from_ptr(key_columns_json).to_str() }
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(s).map_err(|e| e.to_string()))
+    {
+        Ok(k) => k,
+        Err(e) => {
+            set_error(
+                &mut state,
+                format!("{fn_name}: invalid key_columns_json: {e}"),
+            );
+            return -1;
+        }
+    };
+    let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+    let slice = unsafe { std::slice::from_raw_parts(data_buffer, buffer_len as usize) };
+    let payload = match parse_bulk_insert_payload(slice) {
+        Ok(p) => p,
+        Err(e) => {
+            set_error(&mut state, e.to_string());
+            return -1;
+        }
+    };
+
+    let conn = match state.connections.get(&conn_id) {
+        Some(c) => c,
+        None => {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                format!("Invalid connection ID: {}", conn_id),
+            );
+            return -1;
+        }
+    };
+
+    let handles = conn.get_handles();
+    let Ok(handles_guard) = handles.lock() else {
+        set_error(&mut state, "Failed to lock handles mutex".to_string());
+        return -1;
+    };
+    let conn_arc = match handles_guard.get_connection(conn_id) {
+        Ok(c) => c,
+        Err(e) => {
+            set_error(&mut state, format!("Failed to get connection: {}", e));
+            return -1;
+        }
+    };
+    drop(handles_guard);
+
+    let conn_guard = match conn_arc.lock() {
+        Ok(g) => g,
+        Err(_) => {
+            set_error(&mut state, "Failed to lock connection".to_string());
+            return -1;
+        }
+    };
+
+    let result = op(
+        &ArrayBinding::default(),
+        conn_guard.connection(),
+        &payload,
+        &keys,
+    );
+    drop(conn_guard);
+
+    match result {
+        Ok(outcome) => {
+            unsafe {
+                *rows_affected = outcome.rows_affected as c_uint;
+            }
+            if !batch_counts.is_null() {
+                let n = outcome
+                    .batch_rows_affected
+                    .len()
+                    .min(batch_counts_len as usize);
+                // SAFETY: batch_counts has batch_counts_len slots; n never exceeds it.
+                let out = unsafe { std::slice::from_raw_parts_mut(batch_counts, n) };
+                for (slot, count) in out.iter_mut().zip(&outcome.batch_rows_affected) {
+                    *slot = count.map_or(-1, |c| c as i64);
+                }
+                unsafe {
+                    *batch_counts_written = n as c_uint;
+                }
+            }
+            0
+        }
+        Err(e) => {
+            set_connection_structured_error(&mut state, conn_id, e.to_structured());
+            -1
+        }
+    }
+}
+
 /// Bulk insert using BulkCopyExecutor when sqlserver-bcp is enabled, else ArrayBinding.
 /// conn_str: when Some, enables native BCP attempt for SQL Server (requires pre-connect SQL_COPT_SS_BCP).
 fn bulk_insert_payload(
@@ -6062,16 +7948,6 @@ fn row_chunk_ranges(row_count: usize, parallelism: usize) -> Vec<(usize, usize)>
         .collect()
 }
 
-fn slice_null_bitmap(bitmap: &[u8], start: usize, len: usize) -> Vec<u8> {
-    let mut out = vec![0u8; len.div_ceil(8)];
-    for i in 0..len {
-        if is_null(bitmap, start + i) {
-            out[i / 8] |= 1u8 << (i % 8);
-        }
-    }
-    out
-}
-
 fn slice_payload_rows(
     payload: &BulkInsertPayload,
     start: usize,
@@ -6083,70 +7959,7 @@ fn slice_payload_rows(
             "Invalid bulk insert chunk range".to_string(),
         ));
     }
-    let chunk_rows = end - start;
-
-    let mut chunk_data = Vec::with_capacity(payload.column_data.len());
-    for col in &payload.column_data {
-        let sliced = match col {
-            BulkColumnData::I32 {
-                values,
-                null_bitmap,
-            } => BulkColumnData::I32 {
-                values: values[start..end].to_vec(),
-                null_bitmap: null_bitmap
-                    .as_ref()
-                    .map(|bm| slice_null_bitmap(bm, start, chunk_rows)),
-            },
-            BulkColumnData::I64 {
-                values,
-                null_bitmap,
-            } => BulkColumnData::I64 {
-                values: values[start..end].to_vec(),
-                null_bitmap: null_bitmap
-                    .as_ref()
-                    .map(|bm| slice_null_bitmap(bm, start, chunk_rows)),
-            },
-            BulkColumnData::Text {
-                rows,
-                max_len,
-                null_bitmap,
-            } => BulkColumnData::Text {
-                rows: rows[start..end].to_vec(),
-                max_len: *max_len,
-                null_bitmap: null_bitmap
-                    .as_ref()
-                    .map(|bm| slice_null_bitmap(bm, start, chunk_rows)),
-            },
-            BulkColumnData::Binary {
-                rows,
-                max_len,
-                null_bitmap,
-            } => BulkColumnData::Binary {
-                rows: rows[start..end].to_vec(),
-                max_len: *max_len,
-                null_bitmap: null_bitmap
-                    .as_ref()
-                    .map(|bm| slice_null_bitmap(bm, start, chunk_rows)),
-            },
-            BulkColumnData::Timestamp {
-                values,
-                null_bitmap,
-            } => BulkColumnData::Timestamp {
-                values: values[start..end].to_vec(),
-                null_bitmap: null_bitmap
-                    .as_ref()
-                    .map(|bm| slice_null_bitmap(bm, start, chunk_rows)),
-            },
-        };
-        chunk_data.push(sliced);
-    }
-
-    Ok(BulkInsertPayload {
-        table: payload.table.clone(),
-        columns: payload.columns.clone(),
-        row_count: chunk_rows as u32,
-        column_data: chunk_data,
-    })
+    Ok(payload.slice_rows(start..end))
 }
 
 fn bulk_insert_parallel_with_pool(
@@ -6198,82 +8011,890 @@ fn bulk_insert_parallel_with_pool(
 
 /// Parallel bulk insert using pool.
 #[no_mangle]
-pub extern "C" fn odbc_bulk_insert_parallel(
-    pool_id: c_uint,
-    _table: *const c_char,
-    _columns: *const *const c_char,
-    _column_count: c_uint,
+pub extern "C" fn odbc_bulk_insert_parallel(
+    pool_id: c_uint,
+    _table: *const c_char,
+    _columns: *const *const c_char,
+    _column_count: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    parallelism: c_uint,
+    rows_inserted: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if data_buffer.is_null() || rows_inserted.is_null() || buffer_len == 0 {
+            let Some(mut state) = try_lock_global_state() else {
+                return -1;
+            };
+            set_error(
+            &mut state,
+            "odbc_bulk_insert_parallel: data_buffer and rows_inserted must be non-null, buffer_len > 0"
+                .to_string(),
+        );
+            return -1;
+        }
+
+        if parallelism == 0 {
+            let Some(mut state) = try_lock_global_state() else {
+                return -1;
+            };
+            set_error(
+                &mut state,
+                "odbc_bulk_insert_parallel: parallelism must be >= 1".to_string(),
+            );
+            return -1;
+        }
+
+        let slice = unsafe { std::slice::from_raw_parts(data_buffer, buffer_len as usize) };
+        let payload = match parse_bulk_insert_payload(slice) {
+            Ok(p) => p,
+            Err(e) => {
+                let Some(mut state) = try_lock_global_state() else {
+                    return -1;
+                };
+                set_error(&mut state, e.to_string());
+                return -1;
+            }
+        };
+
+        let pool = {
+            let Some(mut state) = try_lock_global_state() else {
+                return -1;
+            };
+            match state.pools.get(&pool_id) {
+                Some(p) => Arc::clone(p),
+                None => {
+                    set_error(&mut state, format!("Invalid pool ID: {}", pool_id));
+                    return -1;
+                }
+            }
+        };
+
+        match bulk_insert_parallel_with_pool(pool.as_ref(), &payload, parallelism as usize) {
+            Ok(total) => {
+                unsafe {
+                    *rows_inserted = total as c_uint;
+                }
+                0
+            }
+            Err(e) => {
+                let Some(mut state) = try_lock_global_state() else {
+                    return -1;
+                };
+                set_structured_error(&mut state, e.to_structured());
+                -1
+            }
+        }
+    })
+}
+/// Writes the outcome of a continue-on-error bulk insert. When the rejected
+/// rows do not fit, the result is stashed so that a retry with a larger buffer
+/// gets it back instead of inserting the payload a second time.
+#[allow(clippy::too_many_arguments)]
+fn write_bulk_rejects_result(
+    state: &mut GlobalState,
+    key: PendingResultKey,
+    inserted: u32,
+    encoded: Vec<u8>,
+    rows_inserted: *mut c_uint,
+    rejected_buffer: *mut u8,
+    rejected_buffer_len: c_uint,
+    rejected_written: *mut c_uint,
+) -> c_int {
+    unsafe {
+        *rows_inserted = inserted;
+    }
+    if encoded.len() > rejected_buffer_len as usize {
+        set_error(
+            state,
+            format!(
+                "Buffer too small: need {} bytes, got {}",
+                encoded.len(),
+                rejected_buffer_len
+            ),
+        );
+        let mut stashed = Vec::with_capacity(4 + encoded.len());
+        stashed.extend_from_slice(&inserted.to_le_bytes());
+        stashed.extend_from_slice(&encoded);
+        stash_pending_result(state, key, stashed);
+        set_out_written_zero(rejected_written);
+        return -2;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(encoded.as_ptr(), rejected_buffer, encoded.len());
+        *rejected_written = encoded.len() as c_uint;
+    }
+    0
+}
+
+fn try_write_pending_bulk_rejects(
+    state: &mut GlobalState,
+    key: &PendingResultKey,
+    rows_inserted: *mut c_uint,
+    rejected_buffer: *mut u8,
+    rejected_buffer_len: c_uint,
+    rejected_written: *mut c_uint,
+) -> Option<c_int> {
+    let entry = state.pending_result_buffers.remove(key)?;
+    if entry.created_at.elapsed() > PENDING_RESULT_TTL || entry.data.len() < 4 {
+        set_error(
+            state,
+            "Pending bulk insert result expired; rows were already inserted".to_string(),
+        );
+        set_out_written_zero(rejected_written);
+        return Some(-1);
+    }
+    let (count, encoded) = entry.data.split_at(4);
+    let inserted = u32::from_le_bytes([count[0], count[1], count[2], count[3]]);
+    Some(write_bulk_rejects_result(
+        state,
+        key.clone(),
+        inserted,
+        encoded.to_vec(),
+        rows_inserted,
+        rejected_buffer,
+        rejected_buffer_len,
+        rejected_written,
+    ))
+}
+
+fn bulk_rejects_args_valid(
+    fn_name: &str,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    rows_inserted: *mut c_uint,
+    rejected_buffer: *mut u8,
+    rejected_written: *mut c_uint,
+) -> bool {
+    if !data_buffer.is_null()
+        && buffer_len > 0
+        && !rows_inserted.is_null()
+        && !rejected_buffer.is_null()
+        && !rejected_written.is_null()
+    {
+        return true;
+    }
+    if let Some(mut state) = try_lock_global_state() {
+        set_error(
+            &mut state,
+            format!(
+                "{fn_name}: data_buffer, rows_inserted, rejected_buffer and rejected_written \
+                 must be non-null, buffer_len > 0"
+            ),
+        );
+    }
+    set_out_written_zero(rejected_written);
+    false
+}
+
+/// Bulk insert using array binding that keeps going when rows fail.
+/// data_buffer: bulk insert binary payload, as for `odbc_bulk_insert_array`.
+/// rows_inserted: output, number of rows inserted.
+/// rejected_buffer: output, rejected rows (index, SQLSTATE, native code,
+/// message) followed by a bulk insert payload holding exactly those rows.
+/// Returns: 0 on success, -1 on error, -2 if rejected_buffer is too small
+/// (rows_inserted is still set; call again with the same payload and a larger
+/// buffer to fetch the rejected rows without inserting again).
+#[no_mangle]
+pub extern "C" fn odbc_bulk_insert_array_with_rejects(
+    conn_id: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    rows_inserted: *mut c_uint,
+    rejected_buffer: *mut u8,
+    rejected_buffer_len: c_uint,
+    rejected_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if !bulk_rejects_args_valid(
+            "odbc_bulk_insert_array_with_rejects",
+            data_buffer,
+            buffer_len,
+            rows_inserted,
+            rejected_buffer,
+            rejected_written,
+        ) {
+            return -1;
+        }
+
+        let slice = unsafe { std::slice::from_raw_parts(data_buffer, buffer_len as usize) };
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(rejected_written);
+            return -1;
+        };
+        let pending_key = PendingResultKey::BulkInsertRejects {
+            owner_id: conn_id,
+            from_pool: false,
+            payload_hash: hash_bytes(slice),
+        };
+        if let Some(code) = try_write_pending_bulk_rejects(
+            &mut state,
+            &pending_key,
+            rows_inserted,
+            rejected_buffer,
+            rejected_buffer_len,
+            rejected_written,
+        ) {
+            return code;
+        }
+
+        let payload = match parse_bulk_insert_payload(slice) {
+            Ok(p) => p,
+            Err(e) => {
+                set_error(&mut state, e.to_string());
+                set_out_written_zero(rejected_written);
+                return -1;
+            }
+        };
+
+        let conn = match state.connections.get(&conn_id) {
+            Some(c) => c,
+            None => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("Invalid connection ID: {}", conn_id),
+                );
+                set_out_written_zero(rejected_written);
+                return -1;
+            }
+        };
+
+        let handles = conn.get_handles();
+        let Ok(handles_guard) = handles.lock() else {
+            set_error(&mut state, "Failed to lock handles mutex".to_string());
+            set_out_written_zero(rejected_written);
+            return -1;
+        };
+        let conn_arc = match handles_guard.get_connection(conn_id) {
+            Ok(c) => c,
+            Err(e) => {
+                set_error(&mut state, format!("Failed to get connection: {}", e));
+                set_out_written_zero(rejected_written);
+                return -1;
+            }
+        };
+        drop(handles_guard);
+
+        let conn_guard = match conn_arc.lock() {
+            Ok(g) => g,
+            Err(_) => {
+                set_error(&mut state, "Failed to lock connection".to_string());
+                set_out_written_zero(rejected_written);
+                return -1;
+            }
+        };
+
+        let result = ArrayBinding::default()
+            .bulk_insert_generic_with_rejects(conn_guard.connection(), &payload)
+            .and_then(|outcome| Ok((outcome.inserted, outcome.encode_rejected(&payload)?)));
+        drop(conn_guard);
+
+        match result {
+            Ok((inserted, encoded)) => write_bulk_rejects_result(
+                &mut state,
+                pending_key,
+                inserted as u32,
+                encoded,
+                rows_inserted,
+                rejected_buffer,
+                rejected_buffer_len,
+                rejected_written,
+            ),
+            Err(e) => {
+                set_connection_structured_error(&mut state, conn_id, e.to_structured());
+                set_out_written_zero(rejected_written);
+                -1
+            }
+        }
+    })
+}
+
+/// Parallel variant of `odbc_bulk_insert_array_with_rejects` using a pool.
+/// Rejected row indexes refer to the whole payload. Return codes are the same.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_bulk_insert_parallel_with_rejects(
+    pool_id: c_uint,
+    data_buffer: *const u8,
+    buffer_len: c_uint,
+    parallelism: c_uint,
+    rows_inserted: *mut c_uint,
+    rejected_buffer: *mut u8,
+    rejected_buffer_len: c_uint,
+    rejected_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        if !bulk_rejects_args_valid(
+            "odbc_bulk_insert_parallel_with_rejects",
+            data_buffer,
+            buffer_len,
+            rows_inserted,
+            rejected_buffer,
+            rejected_written,
+        ) {
+            return -1;
+        }
+
+        if parallelism == 0 {
+            let Some(mut state) = try_lock_global_state() else {
+                return -1;
+            };
+            set_error(
+                &mut state,
+                "odbc_bulk_insert_parallel_with_rejects: parallelism must be >= 1".to_string(),
+            );
+            set_out_written_zero(rejected_written);
+            return -1;
+        }
+
+        let slice = unsafe { std::slice::from_raw_parts(data_buffer, buffer_len as usize) };
+        let pending_key = PendingResultKey::BulkInsertRejects {
+            owner_id: pool_id,
+            from_pool: true,
+            payload_hash: hash_bytes(slice),
+        };
+        let pool = {
+            let Some(mut state) = try_lock_global_state() else {
+                set_out_written_zero(rejected_written);
+                return -1;
+            };
+            if let Some(code) = try_write_pending_bulk_rejects(
+                &mut state,
+                &pending_key,
+                rows_inserted,
+                rejected_buffer,
+                rejected_buffer_len,
+                rejected_written,
+            ) {
+                return code;
+            }
+            match state.pools.get(&pool_id) {
+                Some(p) => Arc::clone(p),
+                None => {
+                    set_error(&mut state, format!("Invalid pool ID: {}", pool_id));
+                    set_out_written_zero(rejected_written);
+                    return -1;
+                }
+            }
+        };
+
+        let result = parse_bulk_insert_payload(slice).and_then(|payload| {
+            let outcome = ParallelBulkInsert::new(pool, parallelism as usize)
+                .insert_payload_with_rejects(&payload)?;
+            Ok((outcome.inserted, outcome.encode_rejected(&payload)?))
+        });
+
+        let Some(mut state) = try_lock_global_state() else {
+            set_out_written_zero(rejected_written);
+            return -1;
+        };
+        match result {
+            Ok((inserted, encoded)) => write_bulk_rejects_result(
+                &mut state,
+                pending_key,
+                inserted as u32,
+                encoded,
+                rows_inserted,
+                rejected_buffer,
+                rejected_buffer_len,
+                rejected_written,
+            ),
+            Err(e) => {
+                set_structured_error(&mut state, e.to_structured());
+                set_out_written_zero(rejected_written);
+                -1
+            }
+        }
+    })
+}
+
+/// Bulk upsert: stages the payload in a session temp table and merges it into
+/// the payload's table with one set-based statement.
+/// data_buffer: bulk insert binary payload, as for `odbc_bulk_insert_array`.
+/// keys_json: NUL-terminated UTF-8 JSON `{ "conflict": [...], "update": [...]? }`;
+/// `update` defaults to every non-conflict column, `[]` means insert only.
+/// rows_inserted/rows_updated/rows_affected: outputs, -1 when the engine does
+/// not report that count.
+/// Returns: 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn odbc_bulk_upsert(
+    conn_id: c_uint,
     data_buffer: *const u8,
     buffer_len: c_uint,
-    parallelism: c_uint,
-    rows_inserted: *mut c_uint,
+    keys_json: *const c_char,
+    rows_inserted: *mut i64,
+    rows_updated: *mut i64,
+    rows_affected: *mut i64,
 ) -> c_int {
     crate::ffi_guard_int!({
-        if data_buffer.is_null() || rows_inserted.is_null() || buffer_len == 0 {
-            let Some(mut state) = try_lock_global_state() else {
-                return -1;
-            };
-            set_error(
-            &mut state,
-            "odbc_bulk_insert_parallel: data_buffer and rows_inserted must be non-null, buffer_len > 0"
-                .to_string(),
-        );
+        let Some(mut state) = try_lock_global_state() else {
             return -1;
-        }
-
-        if parallelism == 0 {
-            let Some(mut state) = try_lock_global_state() else {
-                return -1;
-            };
+        };
+        if data_buffer.is_null()
+            || buffer_len == 0
+            || keys_json.is_null()
+            || rows_inserted.is_null()
+            || rows_updated.is_null()
+            || rows_affected.is_null()
+        {
             set_error(
                 &mut state,
-                "odbc_bulk_insert_parallel: parallelism must be >= 1".to_string(),
+                "odbc_bulk_upsert: data_buffer, keys_json and the row count outputs must be \
+                 non-null, buffer_len > 0"
+                    .to_string(),
             );
             return -1;
         }
 
+        #[derive(serde::Deserialize)]
+        struct UpsertKeys {
+            conflict: Vec<String>,
+            #[serde(default)]
+            update: Option<Vec<String>>,
+        }
+        // SAFETY: keys_json was checked non-null above; caller guarantees C-string contract.
+        let keys: UpsertKeys = match unsafe { CStr::from_ptr(keys_json).to_str() }
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str(s).map_err(|e| e.to_string()))
+        {
+            Ok(k) => k,
+            Err(e) => {
+                set_error(
+                    &mut state,
+                    format!("odbc_bulk_upsert: invalid keys_json: {e}"),
+                );
+                return -1;
+            }
+        };
+
         let slice = unsafe { std::slice::from_raw_parts(data_buffer, buffer_len as usize) };
         let payload = match parse_bulk_insert_payload(slice) {
             Ok(p) => p,
             Err(e) => {
-                let Some(mut state) = try_lock_global_state() else {
-                    return -1;
-                };
                 set_error(&mut state, e.to_string());
                 return -1;
             }
         };
 
-        let pool = {
-            let Some(mut state) = try_lock_global_state() else {
+        let conn = match state.connections.get(&conn_id) {
+            Some(c) => c,
+            None => {
+                set_connection_error(
+                    &mut state,
+                    conn_id,
+                    format!("Invalid connection ID: {}", conn_id),
+                );
                 return -1;
-            };
-            match state.pools.get(&pool_id) {
-                Some(p) => Arc::clone(p),
-                None => {
-                    set_error(&mut state, format!("Invalid pool ID: {}", pool_id));
-                    return -1;
-                }
             }
         };
 
-        match bulk_insert_parallel_with_pool(pool.as_ref(), &payload, parallelism as usize) {
-            Ok(total) => {
+        let handles = conn.get_handles();
+        let Ok(handles_guard) = handles.lock() else {
+            set_error(&mut state, "Failed to lock handles mutex".to_string());
+            return -1;
+        };
+        let conn_arc = match handles_guard.get_connection(conn_id) {
+            Ok(c) => c,
+            Err(e) => {
+                set_error(&mut state, format!("Failed to get connection: {}", e));
+                return -1;
+            }
+        };
+        drop(handles_guard);
+
+        let conn_guard = match conn_arc.lock() {
+            Ok(g) => g,
+            Err(_) => {
+                set_error(&mut state, "Failed to lock connection".to_string());
+                return -1;
+            }
+        };
+
+        let conflict: Vec<&str> = keys.conflict.iter().map(String::as_str).collect();
+        let mut upsert = BulkUpsert::new(&conflict);
+        if let Some(update) = &keys.update {
+            let update: Vec<&str> = update.iter().map(String::as_str).collect();
+            upsert = upsert.with_update_columns(&update);
+        }
+        let result = upsert.execute(conn_guard.connection(), payload);
+        drop(conn_guard);
+
+        match result {
+            Ok(report) => {
+                let count = |n: Option<u64>| n.map_or(-1, |n| n as i64);
                 unsafe {
-                    *rows_inserted = total as c_uint;
+                    *rows_inserted = count(report.inserted);
+                    *rows_updated = count(report.updated);
+                    *rows_affected = count(report.rows_affected);
                 }
                 0
             }
             Err(e) => {
-                let Some(mut state) = try_lock_global_state() else {
-                    return -1;
-                };
-                set_structured_error(&mut state, e.to_structured());
+                set_connection_structured_error(&mut state, conn_id, e.to_structured());
                 -1
             }
         }
     })
 }
+
+/// Progress callback for `odbc_import_csv`: lines read, bytes read, rows
+/// loaded and lines rejected so far, plus the caller's `user_data`.
+pub type CsvImportProgressCallback =
+    Option<extern "C" fn(u64, u64, u64, u64, *mut std::ffi::c_void)>;
+
+/// Import a CSV/TSV file from disk into `table`, in bounded-memory chunks fed
+/// to the engine's bulk loader (or array binding).
+/// `path`, `table`: NUL-terminated UTF-8.
+/// `options_json`: NUL-terminated UTF-8 JSON, every key optional:
+/// `{ "format": "csv"|"tsv", "delimiter": ",", "quote": "\"" ("" = none),
+///    "header": true, "null_marker": "", "batch_size": 10000,
+///    "max_errors": null, "max_record_len": 1048576,
+///    "columns": [...], "types": { "col": "i32"|"i64"|"f64"|"date"|... } }`.
+/// `on_progress`: optional, called after each loaded chunk on the calling
+/// thread; it must not call back into this library.
+/// `out_buf`/`buf_len`/`out_written`: JSON report
+/// `{ lines_read, rows_loaded, batches, malformed_count, malformed: [{ line, message }] }`.
+/// Returns: 0 on success, -1 on error, -2 if out_buf is too small (the file was
+/// imported; call again with the same arguments and a larger buffer to fetch
+/// the report without importing twice).
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_import_csv(
+    conn_id: c_uint,
+    path: *const c_char,
+    table: *const c_char,
+    options_json: *const c_char,
+    on_progress: CsvImportProgressCallback,
+    user_data: *mut std::ffi::c_void,
+    out_buf: *mut u8,
+    buf_len: c_uint,
+    out_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        run_file_import(
+            "odbc_import_csv",
+            conn_id,
+            (path, table, options_json),
+            (out_buf, buf_len, out_written),
+            |table, options_json| {
+                let importer = csv_importer_from_json(table, options_json)?;
+                Ok(Box::new(move |conn, path| {
+                    report_json(&importer.import_file(conn, path, &mut |p| {
+                        if let Some(cb) = on_progress {
+                            cb(
+                                p.lines_read,
+                                p.bytes_read,
+                                p.rows_loaded,
+                                p.rows_rejected,
+                                user_data,
+                            );
+                        }
+                    })?)
+                }))
+            },
+        )
+    })
+}
+
+/// Progress callback for `odbc_import_parquet`: rows loaded so far and rows
+/// in the file, plus the caller's `user_data`.
+pub type ParquetImportProgressCallback = Option<extern "C" fn(u64, u64, *mut std::ffi::c_void)>;
+
+/// Import a Parquet file from disk into `table`, one bulk load per batch of
+/// `batch_rows` rows. `path`, `table`: NUL-terminated UTF-8.
+/// `options_json`: NUL-terminated UTF-8 JSON, `{ "batch_rows": 10000 }`
+/// (optional). `on_progress`: optional, called after each loaded batch on the
+/// calling thread; it must not call back into this library.
+/// `out_buf`/`buf_len`/`out_written`: JSON report
+/// `{ rows_read, rows_loaded, batches }`.
+/// Needs the `parquet` feature; without it the call fails with
+/// `UnsupportedFeature`.
+/// Returns: 0 on success, -1 on error, -2 if out_buf is too small (same retry
+/// contract as `odbc_import_csv`).
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn odbc_import_parquet(
+    conn_id: c_uint,
+    path: *const c_char,
+    table: *const c_char,
+    options_json: *const c_char,
+    on_progress: ParquetImportProgressCallback,
+    user_data: *mut std::ffi::c_void,
+    out_buf: *mut u8,
+    buf_len: c_uint,
+    out_written: *mut c_uint,
+) -> c_int {
+    crate::ffi_guard_int!({
+        run_file_import(
+            "odbc_import_parquet",
+            conn_id,
+            (path, table, options_json),
+            (out_buf, buf_len, out_written),
+            |table, options_json| parquet_import_job(table, options_json, on_progress, user_data),
+        )
+    })
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_import_job<'a>(
+    table: &str,
+    options_json: &str,
+    on_progress: ParquetImportProgressCallback,
+    user_data: *mut std::ffi::c_void,
+) -> Result<ImportJob<'a>> {
+    #[derive(serde::Deserialize)]
+    struct ParquetImportRequest {
+        batch_rows: Option<usize>,
+    }
+    let request: ParquetImportRequest = serde_json::from_str(options_json)
+        .map_err(|e| OdbcError::ValidationError(format!("invalid options_json: {e}")))?;
+    let options = ParquetImportOptions {
+        batch_rows: request
+            .batch_rows
+            .unwrap_or(ParquetImportOptions::default().batch_rows),
+    };
+    let importer = ParquetImporter::new(table, options);
+    Ok(Box::new(move |conn, path| {
+        report_json(&importer.import_file(conn, path, &mut |p| {
+            if let Some(cb) = on_progress {
+                cb(p.rows_loaded, p.total_rows, user_data);
+            }
+        })?)
+    }))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_import_job<'a>(
+    _table: &str,
+    _options_json: &str,
+    _on_progress: ParquetImportProgressCallback,
+    _user_data: *mut std::ffi::c_void,
+) -> Result<ImportJob<'a>> {
+    Err(parquet_unavailable())
+}
+
+/// Loads the file at the given path and returns the JSON report.
+type ImportJob<'a> =
+    Box<dyn FnOnce(&odbc_api::Connection<'static>, &std::path::Path) -> Result<Vec<u8>> + 'a>;
+
+/// Shared body of the `odbc_import_*` functions: validates the arguments,
+/// builds the job from table and `options_json`, runs it without holding the
+/// global state and writes (or stashes) the report.
+fn run_file_import<'a>(
+    fn_name: &str,
+    conn_id: c_uint,
+    (path, table, options_json): (*const c_char, *const c_char, *const c_char),
+    (out_buf, buf_len, out_written): (*mut u8, c_uint, *mut c_uint),
+    make_job: impl FnOnce(&str, &str) -> Result<ImportJob<'a>>,
+) -> c_int {
+    let Some(mut state) = try_lock_global_state() else {
+        set_out_written_zero(out_written);
+        return -1;
+    };
+    if path.is_null()
+        || table.is_null()
+        || options_json.is_null()
+        || out_buf.is_null()
+        || out_written.is_null()
+        || buf_len == 0
+    {
+        set_error(
+            &mut state,
+            format!(
+                "{fn_name}: path, table, options_json, out_buf and out_written must be \
+                 non-null, buf_len > 0"
+            ),
+        );
+        set_out_written_zero(out_written);
+        return -1;
+    }
+    // SAFETY: each pointer was checked non-null above; caller guarantees C-string contract.
+    let (path_rs, table_rs, options_rs) = match unsafe {
+        (
+            CStr::from_ptr(path).to_str(),
+            CStr::from_ptr(table).to_str(),
+            CStr::from_ptr(options_json).to_str(),
+        )
+    } {
+        (Ok(p), Ok(t), Ok(o)) => (p, t, o),
+        _ => {
+            set_error(
+                &mut state,
+                format!("{fn_name}: arguments must be valid UTF-8"),
+            );
+            set_out_written_zero(out_written);
+            return -1;
+        }
+    };
+
+    let pending_key = PendingResultKey::FileImport {
+        conn_id,
+        request_hash: hash_bytes(
+            format!("{fn_name}\0{path_rs}\0{table_rs}\0{options_rs}").as_bytes(),
+        ),
+    };
+    if let Some(code) = try_write_pending_result(
+        &mut state,
+        &pending_key,
+        out_buf,
+        buf_len,
+        out_written,
+        Some(conn_id),
+    ) {
+        return code;
+    }
+
+    let job = match make_job(table_rs, options_rs) {
+        Ok(j) => j,
+        Err(e) => {
+            set_error(&mut state, format!("{fn_name}: {e}"));
+            set_out_written_zero(out_written);
+            return -1;
+        }
+    };
+    let conn = match state.connections.get(&conn_id) {
+        Some(c) => c,
+        None => {
+            set_connection_error(
+                &mut state,
+                conn_id,
+                format!("Invalid connection ID: {}", conn_id),
+            );
+            set_out_written_zero(out_written);
+            return -1;
+        }
+    };
+    let handles = conn.get_handles();
+    let Ok(handles_guard) = handles.lock() else {
+        set_error(&mut state, "Failed to lock handles mutex".to_string());
+        set_out_written_zero(out_written);
+        return -1;
+    };
+    let conn_arc = match handles_guard.get_connection(conn_id) {
+        Ok(c) => c,
+        Err(e) => {
+            set_error(&mut state, format!("Failed to get connection: {}", e));
+            set_out_written_zero(out_written);
+            return -1;
+        }
+    };
+    drop(handles_guard);
+    // Imports run for minutes and call back into the host, so the global
+    // state is not held meanwhile.
+    drop(state);
+
+    let result = match conn_arc.lock() {
+        Ok(conn_guard) => job(conn_guard.connection(), std::path::Path::new(path_rs)),
+        Err(_) => Err(OdbcError::InternalError(
+            "Failed to lock connection".to_string(),
+        )),
+    };
+
+    let Some(mut state) = try_lock_global_state() else {
+        set_out_written_zero(out_written);
+        return -1;
+    };
+    let json = match result {
+        Ok(json) => json,
+        Err(e) => {
+            set_connection_structured_error(&mut state, conn_id, e.to_structured());
+            set_out_written_zero(out_written);
+            return -1;
+        }
+    };
+    if json.len() > buf_len as usize {
+        set_connection_error(
+            &mut state,
+            conn_id,
+            format!(
+                "Buffer too small: need {} bytes, got {}",
+                json.len(),
+                buf_len
+            ),
+        );
+        stash_pending_result(&mut state, pending_key, json);
+        set_out_written_zero(out_written);
+        return -2;
+    }
+    // SAFETY: out_buf has buf_len capacity, verified above; out_written non-null.
+    unsafe {
+        std::ptr::copy_nonoverlapping(json.as_ptr(), out_buf, json.len());
+        *out_written = json.len() as c_uint;
+    }
+    0
+}
+
+fn csv_importer_from_json(table: &str, options_json: &str) -> Result<CsvImporter> {
+    #[derive(serde::Deserialize)]
+    struct CsvImportRequest {
+        format: Option<String>,
+        delimiter: Option<String>,
+        quote: Option<String>,
+        header: Option<bool>,
+        null_marker: Option<String>,
+        batch_size: Option<usize>,
+        max_errors: Option<u64>,
+        max_record_len: Option<usize>,
+        columns: Option<Vec<String>>,
+        #[serde(default)]
+        types: HashMap<String, String>,
+    }
+    let single_byte = |name: &str, value: &str| -> Result<u8> {
+        match value.as_bytes() {
+            [b] if b.is_ascii() => Ok(*b),
+            _ => Err(OdbcError::ValidationError(format!(
+                "{name} must be a single ASCII character"
+            ))),
+        }
+    };
+
+    let request: CsvImportRequest = serde_json::from_str(options_json)
+        .map_err(|e| OdbcError::ValidationError(format!("invalid options_json: {e}")))?;
+    let mut options = match request.format.as_deref() {
+        None | Some("csv") => CsvImportOptions::csv(),
+        Some("tsv") => CsvImportOptions::tsv(),
+        Some(other) => {
+            return Err(OdbcError::ValidationError(format!(
+                "unknown format {other:?}"
+            )))
+        }
+    };
+    if let Some(d) = &request.delimiter {
+        options.delimiter = single_byte("delimiter", d)?;
+    }
+    if let Some(q) = &request.quote {
+        options.quote = if q.is_empty() {
+            None
+        } else {
+            Some(single_byte("quote", q)?)
+        };
+    }
+    if let Some(h) = request.header {
+        options.has_header = h;
+    }
+    if let Some(n) = request.null_marker {
+        options.null_marker = n;
+    }
+    if let Some(b) = request.batch_size {
+        options.batch_size = b;
+    }
+    options.max_errors = request.max_errors.or(options.max_errors);
+    if let Some(m) = request.max_record_len {
+        options.max_record_len = m;
+    }
+
+    let mut importer = CsvImporter::new(table, options);
+    if let Some(columns) = &request.columns {
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        importer = importer.with_columns(&columns);
+    }
+    for (column, type_name) in &request.types {
+        importer = importer.with_type(column, type_name.parse()?);
+    }
+    Ok(importer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6622,20 +9243,99 @@ mod tests {
             buffer.len() as c_uint,
             &mut written,
         );
-        assert_eq!(result, -1);
-        assert_eq!(written, 0);
+        assert_eq!(result, -1);
+        assert_eq!(written, 0);
+    }
+
+    #[test]
+    fn test_ffi_set_log_level() {
+        let result = odbc_set_log_level(0);
+        assert_eq!(result, 0);
+        assert_eq!(odbc_set_log_level(1), 0);
+        assert_eq!(odbc_set_log_level(2), 0);
+        assert_eq!(odbc_set_log_level(3), 0);
+        assert_eq!(odbc_set_log_level(4), 0);
+        assert_eq!(odbc_set_log_level(5), 0);
+        assert_eq!(odbc_set_log_level(99), 0);
+    }
+
+    #[test]
+    fn test_ffi_set_protocol_version_rejects_unsupported() {
+        assert_eq!(odbc_set_protocol_version(1, 3, 0), -1);
+        assert_eq!(odbc_set_protocol_version(1, 1, 2), -1);
+        assert_eq!(odbc_set_protocol_version(1, u32::MAX, 0), -1);
+        assert_eq!(odbc_set_protocol_version(0xDEAD_BEEF, 1, 1), -1);
+    }
+
+    #[test]
+    fn test_ffi_set_output_format_rejects_unknown() {
+        assert_eq!(odbc_set_output_format(1, 7), -1);
+        if !cfg!(feature = "arrow-ipc") {
+            assert_eq!(odbc_set_output_format(1, 1), -1);
+        }
+    }
+
+    #[test]
+    fn test_ffi_set_output_format_invalid_connection() {
+        assert_eq!(odbc_set_output_format(0xDEAD_BEEF, 0), -1);
+    }
+
+    #[test]
+    fn test_ffi_set_spill_options_rejects_unknown() {
+        assert_eq!(odbc_set_spill_options(1, 3, 0), -1);
+        assert_eq!(odbc_set_spill_options(1, 0, 2), -1);
+        assert_eq!(odbc_set_spill_options(0xDEAD_BEEF, 1, 1), -1);
+    }
+
+    #[test]
+    fn test_ffi_lob_invalid_ids() {
+        let mut buffer = vec![0u8; 16];
+        let mut written: c_uint = 7;
+        assert_eq!(
+            odbc_lob_read(
+                0xDEAD_BEEF,
+                buffer.as_mut_ptr(),
+                buffer.len() as c_uint,
+                &mut written
+            ),
+            -1
+        );
+        assert_eq!(written, 0);
+        assert_eq!(odbc_lob_write(0xDEAD_BEEF, b"x".as_ptr(), 1), -1);
+        assert_eq!(odbc_lob_write(0xDEAD_BEEF, std::ptr::null(), 1), -1);
+        assert_eq!(odbc_lob_close(0xDEAD_BEEF, std::ptr::null_mut()), -1);
+        let sql = CString::new("SELECT 1").unwrap();
+        assert_eq!(
+            odbc_lob_open_read(0xDEAD_BEEF, sql.as_ptr(), std::ptr::null(), 0, 1, 0),
+            0
+        );
+        assert_eq!(
+            odbc_lob_open_write(0xDEAD_BEEF, sql.as_ptr(), std::ptr::null(), 0, 1, 0, 0),
+            0
+        );
     }
 
     #[test]
-    fn test_ffi_set_log_level() {
-        let result = odbc_set_log_level(0);
-        assert_eq!(result, 0);
-        assert_eq!(odbc_set_log_level(1), 0);
-        assert_eq!(odbc_set_log_level(2), 0);
-        assert_eq!(odbc_set_log_level(3), 0);
-        assert_eq!(odbc_set_log_level(4), 0);
-        assert_eq!(odbc_set_log_level(5), 0);
-        assert_eq!(odbc_set_log_level(99), 0);
+    fn test_ffi_set_stream_buffer_policy_rejects_invalid() {
+        assert_eq!(odbc_set_stream_buffer_policy(1, 0, 0, 0), -1);
+        assert_eq!(odbc_set_stream_buffer_policy(1, 4, 0, 2), -1);
+        assert_eq!(odbc_set_stream_buffer_policy(0xDEAD_BEEF, 4, 0, 0), -1);
+    }
+
+    #[test]
+    fn test_ffi_stream_get_buffer_stats_rejects_bad_args() {
+        let mut buf = vec![0u8; 256];
+        let mut written: c_uint = 7;
+        let r = odbc_stream_get_buffer_stats(1, std::ptr::null_mut(), 256, &mut written);
+        assert_eq!(r, -1);
+        assert_eq!(written, 0);
+        let r = odbc_stream_get_buffer_stats(
+            TEST_INVALID_ID,
+            buf.as_mut_ptr(),
+            buf.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid stream_id should return -1");
     }
 
     #[test]
@@ -7024,6 +9724,43 @@ mod tests {
         assert!(manager.requests.contains_key(&2));
     }
 
+    #[test]
+    fn test_async_task_sees_cancel_flag() {
+        let mut manager = AsyncRequestManager::new();
+        let id = manager
+            .start_task(7, |cancelled| {
+                while !cancelled.load(Ordering::SeqCst) {
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+                Err(OdbcError::Cancelled)
+            })
+            .expect("task should start");
+        assert_eq!(manager.poll(id), Some(ASYNC_STATUS_PENDING));
+
+        assert!(manager.cancel(id));
+        assert_eq!(manager.poll(id), Some(ASYNC_STATUS_CANCELLED));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while manager.requests[&id]
+            .join_handle
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|h| !h.is_finished())
+        {
+            assert!(std::time::Instant::now() < deadline, "task ignored cancel");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(manager.poll(id), Some(ASYNC_STATUS_CANCELLED));
+
+        let done = manager
+            .start_task(7, |_| Ok(b"{}".to_vec()))
+            .expect("task should start");
+        while manager.poll(done) == Some(ASYNC_STATUS_PENDING) {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(matches!(manager.take_result(done), Some((7, Ok(r))) if r == b"{}"));
+    }
+
     #[test]
     fn test_ffi_stream_start_null_sql() {
         odbc_init();
@@ -7124,6 +9861,74 @@ mod tests {
         assert_eq!(stream_id, 0, "Invalid connection should return 0");
     }
 
+    fn stream_connection_error(conn_id: u32) -> String {
+        let state = try_lock_global_state().expect("global state");
+        get_connection_error(&state, Some(conn_id))
+    }
+
+    #[test]
+    fn test_ffi_stream_start_params_rejects_output_params() {
+        odbc_init();
+
+        let mut drt1 = b"DRT1".to_vec();
+        drt1.extend_from_slice(&1u32.to_le_bytes());
+        drt1.push(ParamDirection::Output as u8);
+        drt1.extend_from_slice(&ParamValue::Integer(0).serialize());
+        let sql = CString::new("SELECT ?").unwrap();
+
+        let conn_id = next_test_invalid_id();
+        let stream_id = odbc_stream_start_params(
+            conn_id,
+            sql.as_ptr(),
+            drt1.as_ptr(),
+            drt1.len() as c_uint,
+            0,
+        );
+        assert_eq!(stream_id, 0);
+        assert!(stream_connection_error(conn_id).contains("OUTPUT/INOUT"));
+
+        let conn_id = next_test_invalid_id();
+        let stream_id = odbc_stream_multi_start_async_params(
+            conn_id,
+            sql.as_ptr(),
+            drt1.as_ptr(),
+            drt1.len() as c_uint,
+            0,
+        );
+        assert_eq!(stream_id, 0);
+        assert!(stream_connection_error(conn_id).contains("OUTPUT/INOUT"));
+    }
+
+    #[test]
+    fn test_ffi_stream_start_batched_params_invalid_buffer() {
+        odbc_init();
+
+        let garbage = [0xFFu8, 0x01, 0x02];
+        let sql = CString::new("SELECT ?").unwrap();
+        let conn_id = next_test_invalid_id();
+        let stream_id = odbc_stream_start_batched_params(
+            conn_id,
+            sql.as_ptr(),
+            garbage.as_ptr(),
+            garbage.len() as c_uint,
+            100,
+            1024,
+        );
+        assert_eq!(stream_id, 0);
+        assert!(stream_connection_error(conn_id).contains("Invalid params"));
+    }
+
+    #[test]
+    fn test_ffi_stream_start_params_null_buffer_binds_nothing() {
+        odbc_init();
+
+        let sql = CString::new("SELECT 1").unwrap();
+        let conn_id = next_test_invalid_id();
+        let stream_id = odbc_stream_start_params(conn_id, sql.as_ptr(), std::ptr::null(), 0, 0);
+        assert_eq!(stream_id, 0);
+        assert!(stream_connection_error(conn_id).contains("Invalid connection ID"));
+    }
+
     #[test]
     fn test_ffi_stream_poll_async_null_out_status() {
         let result = odbc_stream_poll_async(1, std::ptr::null_mut());
@@ -8872,6 +11677,352 @@ mod tests {
         assert_eq!(r, -1, "parallelism=0 should return -1");
     }
 
+    fn one_row_i32_payload() -> Vec<u8> {
+        serialize_bulk_insert_payload(&BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![BulkColumnSpec {
+                name: "a".to_string(),
+                col_type: BulkColumnType::I32,
+                nullable: false,
+                max_len: 0,
+            }],
+            row_count: 1,
+            column_data: vec![BulkColumnData::I32 {
+                values: vec![1],
+                null_bitmap: None,
+            }],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_bulk_insert_with_rejects_null_rejected_buffer() {
+        odbc_init();
+        let enc = one_row_i32_payload();
+        let mut rows: c_uint = 0;
+        let mut written: c_uint = 7;
+        let r = odbc_bulk_insert_array_with_rejects(
+            1,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            &mut rows,
+            std::ptr::null_mut(),
+            64,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Null rejected_buffer should return -1");
+        assert_eq!(written, 0);
+        assert!(get_last_error().contains("rejected_buffer"));
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_bulk_insert_with_rejects_invalid_conn() {
+        odbc_init();
+        let enc = one_row_i32_payload();
+        let mut rows: c_uint = 0;
+        let mut out = vec![0u8; 256];
+        let mut written: c_uint = 0;
+        let r = odbc_bulk_insert_array_with_rejects(
+            TEST_INVALID_ID,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            &mut rows,
+            out.as_mut_ptr(),
+            out.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid conn_id should return -1");
+
+        let r = odbc_bulk_insert_parallel_with_rejects(
+            TEST_INVALID_ID,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            2,
+            &mut rows,
+            out.as_mut_ptr(),
+            out.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid pool_id should return -1");
+        assert!(get_last_error().contains("Invalid pool ID"));
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_bulk_insert_with_rejects_replays_pending_result() {
+        odbc_init();
+        let enc = one_row_i32_payload();
+        let conn_id = next_test_invalid_id();
+        let key = PendingResultKey::BulkInsertRejects {
+            owner_id: conn_id,
+            from_pool: false,
+            payload_hash: hash_bytes(&enc),
+        };
+        let rejected = vec![5u8; 40];
+        let mut rows: c_uint = 0;
+        let mut small = vec![0u8; 8];
+        let mut written: c_uint = 0;
+        {
+            let Some(mut state) = try_lock_global_state() else {
+                panic!("Failed to lock global state");
+            };
+            let r = write_bulk_rejects_result(
+                &mut state,
+                key,
+                3,
+                rejected.clone(),
+                &mut rows,
+                small.as_mut_ptr(),
+                small.len() as c_uint,
+                &mut written,
+            );
+            assert_eq!(r, -2);
+        }
+        assert_eq!(rows, 3);
+
+        // The connection does not exist, so a hit can only come from the stash.
+        rows = 0;
+        let mut out = vec![0u8; 64];
+        let r = odbc_bulk_insert_array_with_rejects(
+            conn_id,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            &mut rows,
+            out.as_mut_ptr(),
+            out.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, 0);
+        assert_eq!(rows, 3);
+        assert_eq!(&out[..written as usize], &rejected[..]);
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_bulk_update_delete_array_validation() {
+        odbc_init();
+        let enc = one_row_i32_payload();
+        let mut affected: c_uint = 0;
+        let mut written: c_uint = 0;
+        let r = odbc_bulk_update_array(
+            1,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            std::ptr::null(),
+            &mut affected,
+            std::ptr::null_mut(),
+            0,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Null key_columns_json should return -1");
+        assert!(get_last_error().contains("odbc_bulk_update_array"));
+
+        let bad = CString::new("id").unwrap();
+        let r = odbc_bulk_delete_array(
+            1,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            bad.as_ptr(),
+            &mut affected,
+            std::ptr::null_mut(),
+            0,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Non-JSON key list should return -1");
+        assert!(get_last_error().contains("key_columns_json"));
+
+        let keys = CString::new("[\"id\"]").unwrap();
+        let mut counts = [0i64; 4];
+        let r = odbc_bulk_delete_array(
+            TEST_INVALID_ID,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            keys.as_ptr(),
+            &mut affected,
+            counts.as_mut_ptr(),
+            counts.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid conn_id should return -1");
+    }
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_bulk_upsert_rejects_bad_keys_and_conn() {
+        odbc_init();
+        let enc = one_row_i32_payload();
+        let (mut ins, mut upd, mut aff) = (0i64, 0i64, 0i64);
+        let bad = CString::new("{\"update\": []}").unwrap();
+        let r = odbc_bulk_upsert(
+            1,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            bad.as_ptr(),
+            &mut ins,
+            &mut upd,
+            &mut aff,
+        );
+        assert_eq!(r, -1, "keys_json without conflict should return -1");
+        assert!(get_last_error().contains("keys_json"));
+
+        let keys = CString::new("{\"conflict\": [\"id\"]}").unwrap();
+        let r = odbc_bulk_upsert(
+            TEST_INVALID_ID,
+            enc.as_ptr(),
+            enc.len() as c_uint,
+            keys.as_ptr(),
+            &mut ins,
+            &mut upd,
+            &mut aff,
+        );
+        assert_eq!(r, -1, "Invalid conn_id should return -1");
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_import_csv_rejects_bad_args() {
+        odbc_init();
+        let path = CString::new("/nonexistent/data.csv").unwrap();
+        let table = CString::new("t").unwrap();
+        let mut buf = vec![0u8; 256];
+        let mut written: c_uint = 0;
+        let call = |path: *const c_char,
+                    options: &CString,
+                    conn_id: c_uint,
+                    buf: &mut [u8],
+                    written: &mut c_uint| {
+            odbc_import_csv(
+                conn_id,
+                path,
+                table.as_ptr(),
+                options.as_ptr(),
+                None,
+                std::ptr::null_mut(),
+                buf.as_mut_ptr(),
+                buf.len() as c_uint,
+                written,
+            )
+        };
+
+        let defaults = CString::new("{}").unwrap();
+        let r = call(std::ptr::null(), &defaults, 1, &mut buf, &mut written);
+        assert_eq!(r, -1, "null path should return -1");
+        assert_eq!(written, 0);
+
+        let bad = CString::new("{\"delimiter\": \";;\"}").unwrap();
+        let r = call(path.as_ptr(), &bad, 1, &mut buf, &mut written);
+        assert_eq!(r, -1, "multi-byte delimiter should return -1");
+        assert!(get_last_error().contains("delimiter"));
+
+        let bad = CString::new("{\"types\": {\"id\": \"int\"}}").unwrap();
+        let r = call(path.as_ptr(), &bad, 1, &mut buf, &mut written);
+        assert_eq!(r, -1, "unknown type hint should return -1");
+
+        let r = call(
+            path.as_ptr(),
+            &defaults,
+            TEST_INVALID_ID,
+            &mut buf,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid conn_id should return -1");
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_export_query_async_rejects_bad_args() {
+        odbc_init();
+        let sql = CString::new("SELECT 1").unwrap();
+        let path = CString::new("/tmp/odbc_export_test.csv").unwrap();
+        let defaults = CString::new("{}").unwrap();
+        let id = odbc_export_query_async(
+            1,
+            sql.as_ptr(),
+            std::ptr::null(),
+            0,
+            std::ptr::null(),
+            defaults.as_ptr(),
+        );
+        assert_eq!(id, 0, "null path should return 0");
+
+        let bad = CString::new("{\"compression\": \"gzip\"}").unwrap();
+        let id = odbc_export_query_async(
+            1,
+            sql.as_ptr(),
+            std::ptr::null(),
+            0,
+            path.as_ptr(),
+            bad.as_ptr(),
+        );
+        assert_eq!(id, 0, "unknown compression should return 0");
+        assert!(get_last_error().contains("compression"));
+
+        let id = odbc_export_query_async(
+            TEST_INVALID_ID,
+            sql.as_ptr(),
+            std::ptr::null(),
+            0,
+            path.as_ptr(),
+            defaults.as_ptr(),
+        );
+        assert_eq!(id, 0, "Invalid conn_id should return 0");
+    }
+
+    #[test]
+    #[serial(ffi_last_error)]
+    fn test_ffi_parquet_rejects_bad_args() {
+        odbc_init();
+        let sql = CString::new("SELECT 1").unwrap();
+        let path = CString::new("/tmp/odbc_export_test.parquet").unwrap();
+        let bad = CString::new("{\"codec\": \"gzip\"}").unwrap();
+        let id = odbc_export_parquet_async(
+            1,
+            sql.as_ptr(),
+            std::ptr::null(),
+            0,
+            path.as_ptr(),
+            bad.as_ptr(),
+        );
+        assert_eq!(id, 0, "unknown codec should return 0");
+        if cfg!(feature = "parquet") {
+            assert!(get_last_error().contains("codec"));
+        } else {
+            assert!(get_last_error().contains("`parquet` feature"));
+        }
+
+        let table = CString::new("t").unwrap();
+        let defaults = CString::new("{}").unwrap();
+        let mut buf = vec![0u8; 256];
+        let mut written: c_uint = 0;
+        let r = odbc_import_parquet(
+            1,
+            std::ptr::null(),
+            table.as_ptr(),
+            defaults.as_ptr(),
+            None,
+            std::ptr::null_mut(),
+            buf.as_mut_ptr(),
+            buf.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "null path should return -1");
+        assert_eq!(written, 0);
+
+        let r = odbc_import_parquet(
+            TEST_INVALID_ID,
+            path.as_ptr(),
+            table.as_ptr(),
+            defaults.as_ptr(),
+            None,
+            std::ptr::null_mut(),
+            buf.as_mut_ptr(),
+            buf.len() as c_uint,
+            &mut written,
+        );
+        assert_eq!(r, -1, "Invalid conn_id should return -1");
+    }
+
     #[test]
     fn test_ffi_prepare_execute_close() {
         let Some(dsn) = ffi_test_dsn() else {
@@ -2,7 +2,8 @@
 //!
 //! When `statement-handle-reuse` feature is enabled, maintains an LRU cache
 //! of prepared statements per connection to avoid repeated prepare calls
-//! for the same SQL.
+//! for the same SQL, plus the handles behind `odbc_prepare` statement IDs,
+//! which are prepared once and re-executed with fresh parameters.
 //!
 //! **Safety note**: Uses type erasure with Box to store prepared statements.
 //! The prepared statement borrows from the connection, so we must ensure:
@@ -16,28 +17,38 @@
 use crate::error::{OdbcError, Result};
 #[cfg(feature = "statement-handle-reuse")]
 use lru::LruCache;
-use odbc_api::{Connection, Cursor, Prepared, ResultSetMetadata};
+use odbc_api::handles::StatementRef;
+#[cfg(feature = "statement-handle-reuse")]
+use odbc_api::Prepared;
+use odbc_api::{Connection, CursorImpl};
+#[cfg(feature = "statement-handle-reuse")]
+use std::collections::HashMap;
 #[cfg(feature = "statement-handle-reuse")]
 use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use std::ops::Deref;
 
-use crate::engine::cell_reader::CellReader;
-use crate::protocol::{OdbcType, RowBuffer, RowBufferEncoder};
-
 /// Default cache size when statement-handle-reuse is enabled.
 #[cfg(feature = "statement-handle-reuse")]
 const DEFAULT_STMT_CACHE_SIZE: usize = 32;
 
 #[cfg(feature = "statement-handle-reuse")]
-type StaticPrepared = Prepared<odbc_api::handles::StatementImpl<'static>>;
+pub(crate) type StaticPrepared = Prepared<odbc_api::handles::StatementImpl<'static>>;
 
 #[cfg(feature = "statement-handle-reuse")]
 struct CachedPrepared {
     stmt: StaticPrepared,
 }
 
+/// Handle prepared for an `odbc_prepare` statement ID, with the SQL it was
+/// prepared from so a reused ID is never executed against other text.
+#[cfg(feature = "statement-handle-reuse")]
+struct StatementEntry {
+    sql: String,
+    stmt: StaticPrepared,
+}
+
 /// Wrapper around Connection that optionally caches prepared statements.
 ///
 /// When `statement-handle-reuse` is disabled (default), always prepares fresh.
@@ -50,6 +61,8 @@ pub struct CachedConnection {
     cache_evictions: AtomicU64,
     #[cfg(feature = "statement-handle-reuse")]
     stmt_cache: LruCache<String, CachedPrepared>,
+    #[cfg(feature = "statement-handle-reuse")]
+    statements: HashMap<u32, StatementEntry>,
 }
 
 impl CachedConnection {
@@ -73,6 +86,7 @@ impl CachedConnection {
             cache_misses: AtomicU64::new(0),
             cache_evictions: AtomicU64::new(0),
             stmt_cache: LruCache::new(cap),
+            statements: HashMap::new(),
         }
     }
 
@@ -87,58 +101,136 @@ impl CachedConnection {
     /// no borrowed statements remain alive while connection is mutated.
     pub fn connection_mut(&mut self) -> &mut Connection<'static> {
         #[cfg(feature = "statement-handle-reuse")]
-        self.invalidate_cache();
+        self.invalidate_statements();
         &mut self.conn
     }
 
-    /// Execute a no-param query, using cached prepared statement when available.
-    pub fn execute_query_no_params(&mut self, sql: &str) -> Result<Vec<u8>> {
+    /// Executes a no-param query, using the cached prepared statement when
+    /// available, and hands its cursor (if any) to `on_result`.
+    pub fn execute_query_no_params<T>(
+        &mut self,
+        sql: &str,
+        on_result: impl FnOnce(Option<CursorImpl<StatementRef<'_>>>) -> Result<T>,
+    ) -> Result<T> {
         #[cfg(feature = "statement-handle-reuse")]
         {
-            self.execute_query_with_reuse(sql)
+            self.execute_query_with_reuse(sql, on_result)
         }
 
         #[cfg(not(feature = "statement-handle-reuse"))]
         {
             let mut stmt = self.conn.prepare(sql).map_err(OdbcError::from)?;
-            execute_stmt_to_buffer(&mut stmt)
+            let cursor = stmt.execute(()).map_err(OdbcError::from)?;
+            on_result(cursor)
         }
     }
 
     #[cfg(feature = "statement-handle-reuse")]
-    fn execute_query_with_reuse(&mut self, sql: &str) -> Result<Vec<u8>> {
-        let sql_key = sql.to_string();
-
-        if let Some(cached) = self.stmt_cache.get_mut(&sql_key) {
+    fn execute_query_with_reuse<T>(
+        &mut self,
+        sql: &str,
+        on_result: impl FnOnce(Option<CursorImpl<StatementRef<'_>>>) -> Result<T>,
+    ) -> Result<T> {
+        let cached = self.stmt_cache.contains(sql);
+        if cached {
             self.cache_hits.fetch_add(1, Ordering::Relaxed);
-            return execute_stmt_to_buffer(&mut cached.stmt);
+        } else {
+            self.cache_misses.fetch_add(1, Ordering::Relaxed);
+            let stmt = self.prepare_static(sql)?;
+            if self.stmt_cache.len() >= self.stmt_cache.cap().get() {
+                self.cache_evictions.fetch_add(1, Ordering::Relaxed);
+            }
+            self.stmt_cache
+                .put(sql.to_string(), CachedPrepared { stmt });
         }
 
-        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+        let entry = self
+            .stmt_cache
+            .get_mut(sql)
+            .ok_or_else(|| OdbcError::InternalError("Cached statement missing".to_string()))?;
+        let result = entry
+            .stmt
+            .execute(())
+            .map_err(OdbcError::from)
+            .and_then(on_result);
+        // Only statements that ran once stay cached.
+        if result.is_err() && !cached {
+            self.stmt_cache.pop(sql);
+        }
+        result
+    }
 
+    #[cfg(feature = "statement-handle-reuse")]
+    fn prepare_static(&self, sql: &str) -> Result<StaticPrepared> {
         let prepared = self.conn.prepare(sql).map_err(OdbcError::from)?;
-
-        let capacity = self.stmt_cache.cap().get();
-        let should_count_eviction = self.stmt_cache.len() >= capacity;
-
-        // SAFETY: `CachedConnection` owns the `Connection<'static>` and the
-        // cache is cleared before `connection_mut` exposes mutable access or
+        // SAFETY: `CachedConnection` owns the `Connection<'static>` and both
+        // caches are cleared before `connection_mut` exposes mutable access or
         // before the connection is dropped. Cached statements therefore never
         // outlive or alias a mutated connection handle while this feature is
         // enabled. Keep this feature experimental until `odbc-api` exposes a
         // cache-friendly prepared statement lifetime.
-        let static_stmt: StaticPrepared = unsafe { std::mem::transmute(prepared) };
-
-        let mut cached = CachedPrepared { stmt: static_stmt };
-        let result = execute_stmt_to_buffer(&mut cached.stmt)?;
+        Ok(unsafe { std::mem::transmute::<Prepared<_>, StaticPrepared>(prepared) })
+    }
 
-        self.stmt_cache.put(sql_key, cached);
+    /// Prepares `sql` for statement `stmt_id` (`SQLPrepare`), replacing any
+    /// handle previously held under that ID.
+    #[cfg(feature = "statement-handle-reuse")]
+    pub fn prepare_statement(&mut self, stmt_id: u32, sql: &str) -> Result<()> {
+        let stmt = self.prepare_static(sql)?;
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+        self.statements.insert(
+            stmt_id,
+            StatementEntry {
+                sql: sql.to_string(),
+                stmt,
+            },
+        );
+        Ok(())
+    }
 
-        if should_count_eviction {
-            self.cache_evictions.fetch_add(1, Ordering::Relaxed);
+    /// Handle prepared for `stmt_id`. Prepares `sql` again when the handle
+    /// was invalidated (or prepared from different text) since
+    /// [`Self::prepare_statement`].
+    #[cfg(feature = "statement-handle-reuse")]
+    pub(crate) fn prepared_statement(
+        &mut self,
+        stmt_id: u32,
+        sql: &str,
+    ) -> Result<&mut StaticPrepared> {
+        let reusable = self
+            .statements
+            .get(&stmt_id)
+            .is_some_and(|entry| entry.sql == sql);
+        if reusable {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.prepare_statement(stmt_id, sql)?;
         }
+        self.statements
+            .get_mut(&stmt_id)
+            .map(|entry| &mut entry.stmt)
+            .ok_or_else(|| OdbcError::InternalError("Prepared statement missing".to_string()))
+    }
+
+    /// Releases the handle of `stmt_id`. Returns whether one was held.
+    #[cfg(feature = "statement-handle-reuse")]
+    pub fn close_statement(&mut self, stmt_id: u32) -> bool {
+        self.statements.remove(&stmt_id).is_some()
+    }
 
-        Ok(result)
+    /// Drops every cached and per-ID prepared handle, e.g. after the
+    /// connection was reported lost. Statement IDs stay valid: their next
+    /// execute prepares again.
+    #[cfg(feature = "statement-handle-reuse")]
+    pub fn invalidate_statements(&mut self) {
+        self.invalidate_cache();
+        self.statements.clear();
+    }
+
+    /// Number of statement IDs currently holding a prepared handle.
+    #[cfg(feature = "statement-handle-reuse")]
+    pub fn prepared_statement_count(&self) -> usize {
+        self.statements.len()
     }
 
     /// Cache hits (when feature enabled).
@@ -183,53 +275,7 @@ impl Deref for CachedConnection {
 #[cfg(feature = "statement-handle-reuse")]
 impl Drop for CachedConnection {
     fn drop(&mut self) {
+        self.statements.clear();
         self.stmt_cache.clear();
     }
 }
-
-fn execute_stmt_to_buffer<S>(stmt: &mut Prepared<S>) -> Result<Vec<u8>>
-where
-    S: odbc_api::handles::AsStatementRef,
-{
-    let cursor = stmt.execute(()).map_err(OdbcError::from)?;
-
-    let mut row_buffer = RowBuffer::new();
-
-    if let Some(mut cursor) = cursor {
-        let cols_i16 = cursor.num_result_cols().map_err(OdbcError::from)?;
-        let cols_u16: u16 = cols_i16
-            .try_into()
-            .map_err(|_| OdbcError::InternalError("Invalid column count".to_string()))?;
-        let cols_usize: usize = cols_u16.into();
-
-        let mut column_types: Vec<OdbcType> = Vec::with_capacity(cols_usize);
-
-        for col_idx in 1..=cols_u16 {
-            let col_name = cursor.col_name(col_idx).map_err(OdbcError::from)?;
-            let col_type = cursor.col_data_type(col_idx).map_err(OdbcError::from)?;
-            let sql_type_code = OdbcType::sql_type_code_from_data_type(&col_type);
-            let odbc_type = OdbcType::from_odbc_sql_type(sql_type_code);
-            row_buffer.add_column(col_name.to_string(), odbc_type);
-            column_types.push(odbc_type);
-        }
-
-        let mut cell_reader = CellReader::new();
-        while let Some(mut row) = cursor.next_row().map_err(OdbcError::from)? {
-            let mut row_data = Vec::with_capacity(column_types.len());
-
-            for (col_idx, &odbc_type) in column_types.iter().enumerate() {
-                let col_number: u16 = (col_idx + 1)
-                    .try_into()
-                    .map_err(|_| OdbcError::InternalError("Invalid column number".to_string()))?;
-
-                let cell_data = cell_reader.read_cell_bytes(&mut row, col_number, odbc_type)?;
-
-                row_data.push(cell_data);
-            }
-
-            row_buffer.add_row(row_data);
-        }
-    }
-
-    Ok(RowBufferEncoder::encode(&row_buffer))
-}
@@ -2,6 +2,7 @@ mod cached_connection;
 
 pub use cached_connection::CachedConnection;
 
+use crate::engine::core::{DriverCapabilities, ResultOptions};
 use crate::error::{OdbcError, Result};
 use odbc_api::{Connection, ConnectionOptions, Environment};
 use std::collections::HashMap;
@@ -17,6 +18,9 @@ const MAX_CONN_ID_ALLOC_ATTEMPTS: u32 = 1000;
 pub struct HandleManager {
     env: Option<&'static Environment>,
     connections: HashMap<u32, SharedConnection>,
+    /// Options of each connection, kept outside its lock so they can be read
+    /// while a stream holds the connection.
+    result_options: HashMap<u32, ResultOptions>,
     next_conn_id: u32,
 }
 
@@ -25,6 +29,7 @@ impl HandleManager {
         Self {
             env: None,
             connections: HashMap::new(),
+            result_options: HashMap::new(),
             next_conn_id: 1,
         }
     }
@@ -71,6 +76,8 @@ impl HandleManager {
         let env = self.env.ok_or(OdbcError::EnvironmentNotInitialized)?;
 
         let connection = env.connect_with_connection_string(conn_str, opts)?;
+        let capabilities = DriverCapabilities::detect(&connection)
+            .unwrap_or_else(|_| DriverCapabilities::detect_from_connection_string(conn_str));
 
         let mut conn_id = 0u32;
         for _ in 0..MAX_CONN_ID_ALLOC_ATTEMPTS {
@@ -92,6 +99,8 @@ impl HandleManager {
             conn_id,
             Arc::new(Mutex::new(CachedConnection::new(connection))),
         );
+        self.result_options
+            .insert(conn_id, ResultOptions::for_driver(&capabilities));
         Ok(conn_id)
     }
 
@@ -117,10 +126,30 @@ impl HandleManager {
         f(guard.connection())
     }
 
+    /// Result options of `conn_id`, derived from the driver detected when it
+    /// was opened.
+    pub fn result_options(&self, conn_id: u32) -> Result<ResultOptions> {
+        self.result_options
+            .get(&conn_id)
+            .copied()
+            .ok_or(OdbcError::InvalidHandle(conn_id))
+    }
+
+    /// Replaces the result options of `conn_id`.
+    pub fn set_result_options(&mut self, conn_id: u32, options: ResultOptions) -> Result<()> {
+        let slot = self
+            .result_options
+            .get_mut(&conn_id)
+            .ok_or(OdbcError::InvalidHandle(conn_id))?;
+        *slot = options;
+        Ok(())
+    }
+
     pub fn remove_connection(&mut self, conn_id: u32) -> Result<()> {
         self.connections
             .remove(&conn_id)
             .ok_or(OdbcError::InvalidHandle(conn_id))?;
+        self.result_options.remove(&conn_id);
         Ok(())
     }
 
@@ -187,6 +216,7 @@ mod tests {
             &conn,
             &manager.get_connection(conn_id).unwrap()
         ));
+        assert!(manager.result_options(conn_id).is_ok());
     }
 
     #[test]
@@ -223,6 +253,24 @@ mod tests {
         assert!(!Arc::ptr_eq(&conn1, &conn2));
     }
 
+    #[test]
+    fn test_handle_manager_result_options_not_found() {
+        let manager = HandleManager::new();
+        assert!(matches!(
+            manager.result_options(42),
+            Err(OdbcError::InvalidHandle(42))
+        ));
+    }
+
+    #[test]
+    fn test_handle_manager_set_result_options_not_found() {
+        let mut manager = HandleManager::new();
+        assert!(matches!(
+            manager.set_result_options(42, ResultOptions::default()),
+            Err(OdbcError::InvalidHandle(42))
+        ));
+    }
+
     #[test]
     fn test_handle_manager_get_connection_not_found() {
         let manager = HandleManager::new();
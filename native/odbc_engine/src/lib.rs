@@ -8,7 +8,7 @@ pub mod plugins;
 pub mod pool;
 pub mod protocol;
 pub mod security;
-mod versioning;
+pub mod versioning;
 
 pub use engine::{
     execute_multi_result, execute_query_with_connection, execute_query_with_params, OdbcConnection,
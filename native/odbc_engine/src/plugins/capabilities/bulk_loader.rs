@@ -5,10 +5,14 @@
 //!
 //! - **SQL Server**: `bcp_initW` + `bcp_sendrow` (already implemented in
 //!   `engine::core::sqlserver_bcp`).
-//! - **PostgreSQL**: `COPY ... FROM STDIN BINARY` via `SQLPutData` chunks.
-//! - **MySQL/MariaDB**: `LOAD DATA LOCAL INFILE` against a temp CSV.
+//! - **PostgreSQL**: array-binding INSERT in `batch_size`-row batches. ODBC
+//!   drivers (psqlODBC included) cannot feed a parameter into
+//!   `COPY ... FROM STDIN`, so there is no COPY path.
+//! - **MySQL/MariaDB**: `LOAD DATA LOCAL INFILE` against a temp CSV
+//!   (`engine::core::mysql_load_data`).
 //! - **Oracle**: `INSERT /*+ APPEND */ ...` direct-path (via array binding).
-//! - **Snowflake**: `PUT file://... ; COPY INTO ...` (file-staged).
+//! - **Snowflake**: `PUT file://... ; COPY INTO ...` (file-staged,
+//!   `engine::core::snowflake_stage`).
 
 use crate::error::Result;
 use crate::protocol::BulkInsertPayload;
@@ -20,7 +24,7 @@ pub struct BulkLoadOptions {
     /// Maximum rows committed per native call (when applicable).
     pub batch_size: usize,
     /// Marker written for NULL values when the path uses a text format
-    /// (CSV-style). Ignored by binary paths (BCP, PG COPY BINARY).
+    /// (CSV-style). Ignored by binary paths (BCP).
     pub null_marker: String,
     /// Field delimiter for text formats. Default: `\t` (tab).
     pub delimiter: char,
@@ -49,12 +53,25 @@ impl Default for BulkLoadOptions {
     }
 }
 
+/// Reported after each batch a native loader commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkLoadProgress {
+    /// Zero-based index of the batch that just finished.
+    pub batch_index: usize,
+    /// Rows in that batch.
+    pub batch_rows: usize,
+    /// Rows loaded so far, including this batch.
+    pub rows_loaded: usize,
+    /// Rows in the whole payload.
+    pub total_rows: usize,
+}
+
 /// Capability trait for engines with a native bulk-load fast path.
 ///
 /// Plugins that do not have one simply do not implement this trait.
 pub trait BulkLoader: Send + Sync {
     /// Stable identifier for the underlying technique
-    /// (`"bcp"`, `"copy_binary"`, `"load_data"`, `"direct_path"`, `"put_copy"`).
+    /// (`"bcp"`, `"copy"`, `"load_data"`, `"direct_path"`, `"put_copy"`).
     fn technique(&self) -> &'static str;
 
     /// Quick check (no I/O) — when `false`, the runtime must use the fallback
@@ -74,6 +91,27 @@ pub trait BulkLoader: Send + Sync {
         payload: &BulkInsertPayload,
         options: &BulkLoadOptions,
     ) -> Result<usize>;
+
+    /// Same as [`Self::execute_bulk_native`], reporting progress per batch.
+    ///
+    /// The default runs the whole load and reports it as a single batch;
+    /// loaders that commit in batches override this.
+    fn execute_bulk_native_with_progress(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        let loaded = self.execute_bulk_native(conn, payload, options)?;
+        on_batch(&BulkLoadProgress {
+            batch_index: 0,
+            batch_rows: loaded,
+            rows_loaded: loaded,
+            total_rows: payload.row_count as usize,
+        });
+        Ok(loaded)
+    }
 }
 
 #[cfg(test)]
@@ -2,9 +2,10 @@
 //!
 //! `DriverPlugin` defines the *contract every plugin must satisfy* (name, type
 //! mapping, query optimisation). Anything beyond that — native bulk loaders,
-//! UPSERT, RETURNING, dialect-aware quoting, schema introspection, session
-//! initialization — is grouped here as **separate traits** that a plugin may
-//! implement when (and only when) it makes sense for that engine.
+//! UPSERT, RETURNING, dialect-aware quoting, keyset pagination, schema
+//! introspection, session initialization — is grouped here as **separate
+//! traits** that a plugin may implement when (and only when) it makes sense
+//! for that engine.
 //!
 //! ## Discovery
 //!
@@ -24,21 +25,23 @@
 
 pub mod bulk_loader;
 pub mod catalog_provider;
+pub mod paginator;
 pub mod quoter;
 pub mod returning;
 pub mod session_init;
 pub mod type_catalog;
 pub mod upsert;
 
-pub use bulk_loader::{BulkLoadOptions, BulkLoader};
+pub use bulk_loader::{BulkLoadOptions, BulkLoadProgress, BulkLoader};
 pub use catalog_provider::{CatalogProvider, CatalogQuery};
+pub use paginator::{KeysetPaginator, RowLimitSyntax};
 pub use quoter::IdentifierQuoter;
 pub use returning::Returnable;
 pub use session_init::{SessionInitializer, SessionOptions};
 pub use type_catalog::TypeCatalog;
 pub use upsert::Upsertable;
 
-/// Convenience enum naming the seven capabilities exposed in v3.0.0.
+/// Convenience enum naming the plugin capabilities.
 /// Used by introspection endpoints (`odbc_get_plugin_capabilities`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -50,6 +53,7 @@ pub enum CapabilityKind {
     IdentifierQuoter,
     CatalogProvider,
     SessionInitializer,
+    KeysetPaginator,
 }
 
 impl CapabilityKind {
@@ -62,6 +66,7 @@ impl CapabilityKind {
             CapabilityKind::IdentifierQuoter => "identifier_quoter",
             CapabilityKind::CatalogProvider => "catalog_provider",
             CapabilityKind::SessionInitializer => "session_initializer",
+            CapabilityKind::KeysetPaginator => "keyset_paginator",
         }
     }
 }
@@ -85,6 +90,7 @@ mod tests {
             CapabilityKind::SessionInitializer.as_str(),
             "session_initializer"
         );
+        assert_eq!(CapabilityKind::KeysetPaginator.as_str(), "keyset_paginator");
     }
 
     #[test]
@@ -0,0 +1,150 @@
+//! Keyset (seek) pagination in the engine's dialect.
+
+use super::quoter::IdentifierQuoter;
+use crate::error::{OdbcError, Result};
+
+/// How an engine caps the rows a `SELECT` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowLimitSyntax {
+    /// `SELECT TOP n ...` (SQL Server, Sybase).
+    Top,
+    /// `... LIMIT n` (PostgreSQL, MySQL/MariaDB, SQLite, Snowflake).
+    Limit,
+    /// `... FETCH FIRST n ROWS ONLY` (Oracle 12c+, Db2).
+    FetchFirst,
+}
+
+/// Capability trait for walking a query in key order, one bounded page at
+/// a time, without `OFFSET`.
+///
+/// The page query wraps the caller's `SELECT` as a derived table, so that
+/// query must not carry its own `ORDER BY`, and the key columns must be
+/// non-null and unique together.
+pub trait KeysetPaginator: IdentifierQuoter {
+    fn row_limit_syntax(&self) -> RowLimitSyntax {
+        RowLimitSyntax::Limit
+    }
+
+    /// `SELECT * FROM (<base_sql>) odbc_keyset ... ORDER BY <keys>` capped
+    /// at `page_rows` rows.
+    ///
+    /// With `after_last_key` the page starts strictly after a key tuple,
+    /// expanded as `k1 > ? OR (k1 = ? AND k2 > ?) OR ...` so it needs no
+    /// row-value comparison; the markers take the tuple's prefixes in order
+    /// (see `engine::keyset::predicate_params`) after the base query's own.
+    fn build_keyset_page_sql(
+        &self,
+        base_sql: &str,
+        keys: &[&str],
+        after_last_key: bool,
+        page_rows: u32,
+    ) -> Result<String> {
+        if keys.is_empty() {
+            return Err(OdbcError::ValidationError(
+                "keyset pagination needs at least one key column".to_string(),
+            ));
+        }
+        if page_rows == 0 {
+            return Err(OdbcError::ValidationError(
+                "keyset page size must be positive".to_string(),
+            ));
+        }
+        let base_sql = base_sql.trim().trim_end_matches(';').trim_end();
+        if base_sql.is_empty() {
+            return Err(OdbcError::ValidationError(
+                "keyset pagination needs a base query".to_string(),
+            ));
+        }
+        let quoted = keys
+            .iter()
+            .map(|k| self.quote(k))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut sql = String::from("SELECT ");
+        if self.row_limit_syntax() == RowLimitSyntax::Top {
+            sql.push_str(&format!("TOP {page_rows} "));
+        }
+        sql.push_str(&format!("* FROM ({base_sql}) odbc_keyset"));
+        if after_last_key {
+            let disjuncts: Vec<String> = (0..quoted.len())
+                .map(|i| {
+                    let mut terms: Vec<String> =
+                        quoted[..i].iter().map(|k| format!("{k} = ?")).collect();
+                    terms.push(format!("{} > ?", quoted[i]));
+                    format!("({})", terms.join(" AND "))
+                })
+                .collect();
+            sql.push_str(&format!(" WHERE {}", disjuncts.join(" OR ")));
+        }
+        sql.push_str(&format!(" ORDER BY {}", quoted.join(", ")));
+        match self.row_limit_syntax() {
+            RowLimitSyntax::Top => {}
+            RowLimitSyntax::Limit => sql.push_str(&format!(" LIMIT {page_rows}")),
+            RowLimitSyntax::FetchFirst => {
+                sql.push_str(&format!(" FETCH FIRST {page_rows} ROWS ONLY"))
+            }
+        }
+        Ok(sql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::identifier::IdentifierQuoting;
+
+    struct Ansi;
+    impl IdentifierQuoter for Ansi {}
+    impl KeysetPaginator for Ansi {}
+
+    struct Tsql;
+    impl IdentifierQuoter for Tsql {
+        fn quoting_style(&self) -> IdentifierQuoting {
+            IdentifierQuoting::Brackets
+        }
+    }
+    impl KeysetPaginator for Tsql {
+        fn row_limit_syntax(&self) -> RowLimitSyntax {
+            RowLimitSyntax::Top
+        }
+    }
+
+    #[test]
+    fn first_page_has_no_predicate() {
+        let sql = Ansi
+            .build_keyset_page_sql("SELECT * FROM t;", &["id"], false, 50)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT * FROM t) odbc_keyset ORDER BY \"id\" LIMIT 50"
+        );
+    }
+
+    #[test]
+    fn composite_key_expands_to_disjunction() {
+        let sql = Tsql
+            .build_keyset_page_sql("SELECT a, b, c FROM t", &["a", "b"], true, 10)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT TOP 10 * FROM (SELECT a, b, c FROM t) odbc_keyset \
+             WHERE ([a] > ?) OR ([a] = ? AND [b] > ?) ORDER BY [a], [b]"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(Ansi
+            .build_keyset_page_sql("SELECT 1", &[], false, 1)
+            .is_err());
+        assert!(Ansi
+            .build_keyset_page_sql("SELECT 1", &["id"], false, 0)
+            .is_err());
+        assert!(Ansi
+            .build_keyset_page_sql(" ; ", &["id"], false, 1)
+            .is_err());
+        assert!(Ansi
+            .build_keyset_page_sql("SELECT 1", &["id; DROP"], false, 1)
+            .is_err());
+    }
+}
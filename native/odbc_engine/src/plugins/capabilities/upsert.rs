@@ -4,7 +4,9 @@
 //! standard prepared-statement execution. Identifiers are validated and
 //! quoted via [`crate::engine::identifier`] to avoid injection.
 
-use crate::engine::identifier::{quote_identifier_default, validate_identifier};
+use crate::engine::identifier::{
+    quote_identifier, quote_identifier_default, validate_identifier, IdentifierQuoting,
+};
 use crate::error::{OdbcError, Result};
 
 /// Capability trait for engines that expose a single-statement UPSERT.
@@ -27,6 +29,26 @@ pub trait Upsertable: Send + Sync {
         conflict_columns: &[&str],
         update_columns: Option<&[&str]>,
     ) -> Result<String>;
+
+    /// Build one set-based UPSERT that reads every row of `source` (an
+    /// already-quoted table holding `columns`) into `table`, with the same
+    /// conflict and update rules as [`Self::build_upsert_sql`].
+    ///
+    /// When the statement produces a result set, its first row carries the
+    /// inserted and updated row counts, in that order.
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        let _ = (table, source, columns, conflict_columns, update_columns);
+        Err(OdbcError::UnsupportedFeature(
+            "Bulk UPSERT is not implemented for this engine".to_string(),
+        ))
+    }
 }
 
 /// Validate and quote a list of identifiers. Returns the comma-joined,
@@ -109,6 +131,48 @@ pub fn placeholder_list(n: usize) -> String {
     std::iter::repeat_n("?", n).collect::<Vec<_>>().join(", ")
 }
 
+/// Quote each column with `style` and join `template(quoted)` with `sep`.
+pub fn join_columns(
+    columns: &[&str],
+    style: IdentifierQuoting,
+    sep: &str,
+    template: impl Fn(&str) -> String,
+) -> Result<String> {
+    let mut out = Vec::with_capacity(columns.len());
+    for c in columns {
+        out.push(template(&quote_identifier(c, style)?));
+    }
+    Ok(out.join(sep))
+}
+
+/// `MERGE` from a staged `source` table, shared by the MERGE dialects.
+/// `qtable` and `source` must already be quoted; no trailing semicolon.
+pub fn merge_from_source_sql(
+    qtable: &str,
+    source: &str,
+    columns: &[&str],
+    conflict_columns: &[&str],
+    update_columns: Option<&[&str]>,
+    style: IdentifierQuoting,
+) -> Result<String> {
+    let on_clause = join_columns(conflict_columns, style, " AND ", |q| {
+        format!("t.{q} = s.{q}")
+    })?;
+    let updates = effective_update_columns(columns, conflict_columns, update_columns);
+    let when_matched = if updates.is_empty() {
+        String::new()
+    } else {
+        let set = join_columns(&updates, style, ", ", |q| format!("{q} = s.{q}"))?;
+        format!(" WHEN MATCHED THEN UPDATE SET {set}")
+    };
+    let insert_cols = join_columns(columns, style, ", ", str::to_string)?;
+    let insert_vals = join_columns(columns, style, ", ", |q| format!("s.{q}"))?;
+    Ok(format!(
+        "MERGE INTO {qtable} t USING {source} s ON ({on_clause}){when_matched} \
+         WHEN NOT MATCHED THEN INSERT ({insert_cols}) VALUES ({insert_vals})"
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +238,36 @@ mod tests {
         assert!(r.is_ok());
     }
 
+    #[test]
+    fn merge_from_source_sql_uses_conflict_and_update_rules() {
+        let sql = merge_from_source_sql(
+            "\"t\"",
+            "\"stage\"",
+            &["id", "a", "b"],
+            &["id"],
+            Some(&["a"]),
+            IdentifierQuoting::DoubleQuote,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "MERGE INTO \"t\" t USING \"stage\" s ON (t.\"id\" = s.\"id\") \
+             WHEN MATCHED THEN UPDATE SET \"a\" = s.\"a\" \
+             WHEN NOT MATCHED THEN INSERT (\"id\", \"a\", \"b\") \
+             VALUES (s.\"id\", s.\"a\", s.\"b\")"
+        );
+        let insert_only = merge_from_source_sql(
+            "[t]",
+            "[#s]",
+            &["id"],
+            &["id"],
+            None,
+            IdentifierQuoting::Brackets,
+        )
+        .unwrap();
+        assert!(!insert_only.contains("WHEN MATCHED"));
+    }
+
     #[test]
     fn placeholder_list_emits_correct_count() {
         assert_eq!(placeholder_list(0), "");
@@ -6,12 +6,17 @@
 
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::DmlVerb;
-use super::capabilities::upsert::{effective_update_columns, validate_upsert_inputs, Upsertable};
+use super::capabilities::upsert::{
+    effective_update_columns, merge_from_source_sql, validate_upsert_inputs, Upsertable,
+};
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, RowLimitSyntax, SessionInitializer,
+    SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
-use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
+use crate::engine::identifier::{
+    quote_identifier_default, quote_qualified_default, IdentifierQuoting,
+};
 use crate::error::{OdbcError, Result};
 use crate::protocol::types::OdbcType;
 use crate::protocol::ParamValue;
@@ -137,6 +142,25 @@ impl Upsertable for Db2Plugin {
              WHEN NOT MATCHED THEN INSERT ({insert_cols}) VALUES ({insert_vals})"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        merge_from_source_sql(
+            &quote_qualified_default(table)?,
+            source,
+            columns,
+            conflict_columns,
+            update_columns,
+            IdentifierQuoting::DoubleQuote,
+        )
+    }
 }
 
 impl Returnable for Db2Plugin {
@@ -168,6 +192,12 @@ impl Returnable for Db2Plugin {
 
 impl IdentifierQuoter for Db2Plugin {}
 
+impl KeysetPaginator for Db2Plugin {
+    fn row_limit_syntax(&self) -> RowLimitSyntax {
+        RowLimitSyntax::FetchFirst
+    }
+}
+
 impl TypeCatalog for Db2Plugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
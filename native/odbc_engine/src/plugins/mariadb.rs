@@ -4,21 +4,28 @@
 //! - `RETURNING` clause (MariaDB 10.5+)
 //! - `INSERT ... ON DUPLICATE KEY UPDATE` (same as MySQL)
 //!
+//! Bulk loads share MySQL's `LOAD DATA LOCAL INFILE` path.
+//!
 //! Until v3.0 the registry mapped MariaDB to the MySQL plugin; this dedicated
 //! plugin enables RETURNING and provides MariaDB-specific type mapping.
 
+use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress, BulkLoader};
 use super::capabilities::catalog_provider::CatalogProvider;
 use super::capabilities::returning::{quote_returning_columns, DmlVerb};
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, quote_columns, validate_upsert_inputs, Upsertable,
+    effective_update_columns, join_columns, placeholder_list, quote_columns,
+    validate_upsert_inputs, Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
+use crate::engine::core::MySqlLoadDataLoader;
 use crate::engine::identifier::{quote_identifier, quote_qualified_default, IdentifierQuoting};
 use crate::error::Result;
 use crate::protocol::types::OdbcType;
+use crate::protocol::BulkInsertPayload;
+use odbc_api::Connection;
 
 pub struct MariaDbPlugin;
 
@@ -79,6 +86,35 @@ impl DriverPlugin for MariaDbPlugin {
     }
 }
 
+impl BulkLoader for MariaDbPlugin {
+    fn technique(&self) -> &'static str {
+        "load_data"
+    }
+
+    fn supports_native_bulk(&self) -> bool {
+        true
+    }
+
+    fn execute_bulk_native(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+    ) -> Result<usize> {
+        self.execute_bulk_native_with_progress(conn, payload, options, &mut |_| {})
+    }
+
+    fn execute_bulk_native_with_progress(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        MySqlLoadDataLoader::new().load_or_insert_values(conn, payload, options, on_batch)
+    }
+}
+
 impl Upsertable for MariaDbPlugin {
     fn build_upsert_sql(
         &self,
@@ -110,6 +146,38 @@ impl Upsertable for MariaDbPlugin {
              ON DUPLICATE KEY UPDATE {set_clause}"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        let qtable = table
+            .split('.')
+            .map(|part| quote_identifier(part, IdentifierQuoting::Backtick))
+            .collect::<Result<Vec<_>>>()?
+            .join(".");
+        let qcols = join_columns(columns, IdentifierQuoting::Backtick, ", ", str::to_string)?;
+        let updates = effective_update_columns(columns, conflict_columns, update_columns);
+        // With nothing to update, a self-assignment of the first conflict
+        // column turns duplicates into no-ops.
+        let set_columns = if updates.is_empty() {
+            vec![conflict_columns[0]]
+        } else {
+            updates
+        };
+        let set = join_columns(&set_columns, IdentifierQuoting::Backtick, ", ", |q| {
+            format!("{q} = VALUES({q})")
+        })?;
+        Ok(format!(
+            "INSERT INTO {qtable} ({qcols}) SELECT {qcols} FROM {source} \
+             ON DUPLICATE KEY UPDATE {set}"
+        ))
+    }
 }
 
 impl Returnable for MariaDbPlugin {
@@ -134,6 +202,8 @@ impl IdentifierQuoter for MariaDbPlugin {
     }
 }
 
+impl KeysetPaginator for MariaDbPlugin {}
+
 impl TypeCatalog for MariaDbPlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
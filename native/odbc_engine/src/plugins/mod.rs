@@ -12,8 +12,8 @@ pub mod sqlserver;
 pub mod sybase;
 
 pub use capabilities::{
-    BulkLoadOptions, BulkLoader, CapabilityKind, CatalogProvider, CatalogQuery, IdentifierQuoter,
-    Returnable, SessionInitializer, SessionOptions, TypeCatalog, Upsertable,
+    BulkLoadOptions, BulkLoadProgress, BulkLoader, CapabilityKind, CatalogProvider, CatalogQuery,
+    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog, Upsertable,
 };
 pub use driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
 pub use registry::PluginRegistry;
@@ -1,14 +1,15 @@
-use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoader};
+use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress, BulkLoader};
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::DmlVerb;
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, quote_columns, validate_upsert_inputs, Upsertable,
+    effective_update_columns, join_columns, placeholder_list, quote_columns,
+    validate_upsert_inputs, Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
-use crate::engine::core::ArrayBinding;
+use crate::engine::core::MySqlLoadDataLoader;
 use crate::engine::identifier::{quote_identifier, quote_qualified_default, IdentifierQuoting};
 use crate::error::{OdbcError, Result};
 use crate::protocol::types::OdbcType;
@@ -88,10 +89,7 @@ impl DriverPlugin for MySqlPlugin {
 
 impl BulkLoader for MySqlPlugin {
     fn technique(&self) -> &'static str {
-        // LOAD DATA LOCAL INFILE streaming is tracked for v3.1 (requires
-        // server flag `local_infile=1` plus client-side temp file management).
-        // v3.0 uses optimised array-binding multi-row INSERT.
-        "array_binding_optimised"
+        "load_data"
     }
 
     fn supports_native_bulk(&self) -> bool {
@@ -104,9 +102,17 @@ impl BulkLoader for MySqlPlugin {
         payload: &BulkInsertPayload,
         options: &BulkLoadOptions,
     ) -> Result<usize> {
-        let batch = options.batch_size.clamp(1, 2_000);
-        let ab = ArrayBinding::new(batch);
-        ab.bulk_insert_generic(conn, payload)
+        self.execute_bulk_native_with_progress(conn, payload, options, &mut |_| {})
+    }
+
+    fn execute_bulk_native_with_progress(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        MySqlLoadDataLoader::new().load_or_insert_values(conn, payload, options, on_batch)
     }
 }
 
@@ -140,6 +146,43 @@ impl Upsertable for MySqlPlugin {
              ON DUPLICATE KEY UPDATE {set_clause}"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        let qtable = table
+            .split('.')
+            .map(|part| quote_identifier(part, IdentifierQuoting::Backtick))
+            .collect::<Result<Vec<_>>>()?
+            .join(".");
+        let qcols = join_columns(columns, IdentifierQuoting::Backtick, ", ", str::to_string)?;
+        let updates = effective_update_columns(columns, conflict_columns, update_columns);
+        // With nothing to update, a self-assignment of the first conflict
+        // column turns duplicates into no-ops (unlike INSERT IGNORE, which
+        // would also swallow unrelated errors such as truncation).
+        let set = if updates.is_empty() {
+            join_columns(
+                &conflict_columns[..1],
+                IdentifierQuoting::Backtick,
+                ", ",
+                |q| format!("{q} = {q}"),
+            )?
+        } else {
+            join_columns(&updates, IdentifierQuoting::Backtick, ", ", |q| {
+                format!("{q} = VALUES({q})")
+            })?
+        };
+        Ok(format!(
+            "INSERT INTO {qtable} ({qcols}) SELECT {qcols} FROM {source} \
+             ON DUPLICATE KEY UPDATE {set}"
+        ))
+    }
 }
 
 impl Returnable for MySqlPlugin {
@@ -167,6 +210,8 @@ impl IdentifierQuoter for MySqlPlugin {
     }
 }
 
+impl KeysetPaginator for MySqlPlugin {}
+
 impl TypeCatalog for MySqlPlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
@@ -383,4 +428,30 @@ mod tests {
         let optimized = plugin.optimize_query(sql);
         assert_eq!(optimized, "DELETE FROM users WHERE id = 1");
     }
+
+    #[test]
+    fn bulk_upsert_updates_non_key_columns() {
+        let p = MySqlPlugin::new();
+        let sql = p
+            .build_bulk_upsert_sql("t", "`stage`", &["id", "name"], &["id"], None)
+            .unwrap();
+        assert!(sql.contains("SELECT `id`, `name` FROM `stage`"));
+        assert!(sql.ends_with("ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"));
+    }
+
+    #[test]
+    fn bulk_upsert_without_update_columns_skips_duplicates() {
+        let p = MySqlPlugin::new();
+        let expected = "INSERT INTO `t` (`id`) SELECT `id` FROM `stage` \
+                        ON DUPLICATE KEY UPDATE `id` = `id`";
+        assert_eq!(
+            p.build_bulk_upsert_sql("t", "`stage`", &["id"], &["id"], None)
+                .unwrap(),
+            expected
+        );
+        let sql = p
+            .build_bulk_upsert_sql("t", "`stage`", &["id", "name"], &["id"], Some(&[]))
+            .unwrap();
+        assert!(sql.ends_with("ON DUPLICATE KEY UPDATE `id` = `id`"));
+    }
 }
@@ -2,14 +2,18 @@ use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoader};
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::{quote_returning_columns, DmlVerb};
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, validate_upsert_inputs, Upsertable,
+    effective_update_columns, merge_from_source_sql, placeholder_list, validate_upsert_inputs,
+    Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, RowLimitSyntax, SessionInitializer,
+    SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
 use crate::engine::core::ArrayBinding;
-use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
+use crate::engine::identifier::{
+    quote_identifier_default, quote_qualified_default, IdentifierQuoting,
+};
 use crate::error::Result;
 use crate::protocol::types::OdbcType;
 use crate::protocol::{BulkInsertPayload, ParamValue};
@@ -186,6 +190,25 @@ impl Upsertable for OraclePlugin {
              WHEN NOT MATCHED THEN INSERT ({insert_cols}) VALUES ({insert_vals})"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        merge_from_source_sql(
+            &quote_qualified_default(table)?,
+            source,
+            columns,
+            conflict_columns,
+            update_columns,
+            IdentifierQuoting::DoubleQuote,
+        )
+    }
 }
 
 impl Returnable for OraclePlugin {
@@ -223,6 +246,13 @@ impl IdentifierQuoter for OraclePlugin {
     // Note: Oracle FOLDS UNQUOTED IDENTIFIERS TO UPPERCASE; quoted are case-sensitive.
 }
 
+impl KeysetPaginator for OraclePlugin {
+    // `FETCH FIRST` needs Oracle 12c or later.
+    fn row_limit_syntax(&self) -> RowLimitSyntax {
+        RowLimitSyntax::FetchFirst
+    }
+}
+
 impl TypeCatalog for OraclePlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
@@ -1,11 +1,12 @@
-use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoader};
+use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress, BulkLoader};
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::{quote_returning_columns, DmlVerb};
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, quote_columns, validate_upsert_inputs, Upsertable,
+    effective_update_columns, join_columns, placeholder_list, quote_columns,
+    validate_upsert_inputs, Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
 use crate::engine::core::ArrayBinding;
@@ -89,10 +90,10 @@ impl DriverPlugin for PostgresPlugin {
 // --- v3.0 capabilities -------------------------------------------------------
 
 impl BulkLoader for PostgresPlugin {
+    /// ODBC offers no way to stream `COPY ... FROM STDIN` (psqlODBC does not
+    /// feed a data-at-execution parameter into it), so bulk loads use
+    /// array-binding INSERT.
     fn technique(&self) -> &'static str {
-        // The native COPY-binary streaming path is tracked for v3.1 (requires
-        // raw odbc_sys SQLPutData chunking + binary header authoring).
-        // v3.0 falls back to optimised array-binding INSERT.
         "array_binding_optimised"
     }
 
@@ -106,12 +107,34 @@ impl BulkLoader for PostgresPlugin {
         payload: &BulkInsertPayload,
         options: &BulkLoadOptions,
     ) -> Result<usize> {
-        // PostgreSQL benefits from large array-binding batches; default to
-        // 5_000 rows per network round-trip when the caller passes the
-        // standard 10k.
+        self.execute_bulk_native_with_progress(conn, payload, options, &mut |_| {})
+    }
+
+    fn execute_bulk_native_with_progress(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        // PostgreSQL benefits from large array-binding batches; cap at
+        // 5_000 rows per network round-trip.
         let batch = options.batch_size.clamp(1, 5_000);
-        let ab = ArrayBinding::new(batch);
-        ab.bulk_insert_generic(conn, payload)
+        let total_rows = payload.row_count as usize;
+        let binding = ArrayBinding::new(batch);
+        let mut loaded = 0usize;
+        for (batch_index, start) in (0..total_rows).step_by(batch).enumerate() {
+            let chunk = payload.slice_rows(start..(start + batch).min(total_rows));
+            let batch_rows = binding.bulk_insert_generic(conn, &chunk)?;
+            loaded += batch_rows;
+            on_batch(&BulkLoadProgress {
+                batch_index,
+                batch_rows,
+                rows_loaded: loaded,
+                total_rows,
+            });
+        }
+        Ok(loaded)
     }
 }
 
@@ -149,6 +172,36 @@ impl Upsertable for PostgresPlugin {
              ON CONFLICT ({qconflict}) DO UPDATE SET {set_clause}"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        let qtable = quote_qualified_default(table)?;
+        let qcols = quote_columns(columns)?;
+        let qconflict = quote_columns(conflict_columns)?;
+        let updates = effective_update_columns(columns, conflict_columns, update_columns);
+        let action = if updates.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            let set = join_columns(&updates, IdentifierQuoting::DoubleQuote, ", ", |q| {
+                format!("{q} = EXCLUDED.{q}")
+            })?;
+            format!("DO UPDATE SET {set}")
+        };
+        // `xmax = 0` only holds for freshly inserted tuples.
+        Ok(format!(
+            "WITH upserted AS (INSERT INTO {qtable} ({qcols}) SELECT {qcols} FROM {source} \
+             ON CONFLICT ({qconflict}) {action} RETURNING (xmax = 0) AS inserted) \
+             SELECT COUNT(*) FILTER (WHERE inserted), COUNT(*) FILTER (WHERE NOT inserted) \
+             FROM upserted"
+        ))
+    }
 }
 
 impl Returnable for PostgresPlugin {
@@ -173,6 +226,8 @@ impl IdentifierQuoter for PostgresPlugin {
     }
 }
 
+impl KeysetPaginator for PostgresPlugin {}
+
 impl TypeCatalog for PostgresPlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
@@ -280,6 +335,13 @@ mod tests {
         assert_eq!(plugin.name(), "postgres");
     }
 
+    #[test]
+    fn test_postgres_bulk_loader_uses_array_binding() {
+        let plugin = PostgresPlugin::new();
+        assert_eq!(plugin.technique(), "array_binding_optimised");
+        assert!(plugin.supports_native_bulk());
+    }
+
     #[test]
     fn test_postgres_plugin_name() {
         let plugin = PostgresPlugin::new();
@@ -373,4 +435,17 @@ mod tests {
         ));
         assert!(matches!(rules[3], OptimizationRule::EnableStreaming));
     }
+
+    #[test]
+    fn bulk_upsert_counts_inserts_via_xmax() {
+        let p = PostgresPlugin::new();
+        let sql = p
+            .build_bulk_upsert_sql("t", "\"stage\"", &["id", "name"], &["id"], None)
+            .unwrap();
+        assert!(sql.starts_with(
+            "WITH upserted AS (INSERT INTO \"t\" (\"id\", \"name\") SELECT \"id\", \"name\" FROM \"stage\""
+        ));
+        assert!(sql.contains("ON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\""));
+        assert!(sql.contains("RETURNING (xmax = 0) AS inserted"));
+    }
 }
@@ -192,6 +192,72 @@ impl PluginRegistry {
         }
     }
 
+    /// Set-based UPSERT reading every row of the already-quoted `source`
+    /// table (see [`Upsertable::build_bulk_upsert_sql`]) for a plugin id.
+    ///
+    /// [`Upsertable::build_bulk_upsert_sql`]: super::capabilities::Upsertable::build_bulk_upsert_sql
+    pub fn build_bulk_upsert_sql(
+        &self,
+        plugin_id: &str,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        use super::capabilities::Upsertable;
+        let plugin: Box<dyn Upsertable> = match plugin_id {
+            "sqlserver" => Box::new(super::sqlserver::SqlServerPlugin::new()),
+            "postgres" => Box::new(super::postgres::PostgresPlugin::new()),
+            "mysql" => Box::new(super::mysql::MySqlPlugin::new()),
+            "mariadb" => Box::new(super::mariadb::MariaDbPlugin::new()),
+            "oracle" => Box::new(super::oracle::OraclePlugin::new()),
+            "sybase" => Box::new(super::sybase::SybasePlugin::new()),
+            "sqlite" => Box::new(super::sqlite::SqlitePlugin::new()),
+            "db2" => Box::new(super::db2::Db2Plugin::new()),
+            "snowflake" => Box::new(super::snowflake::SnowflakePlugin::new()),
+            _ => {
+                return Err(OdbcError::UnsupportedFeature(format!(
+                    "No bulk UPSERT support for plugin {plugin_id:?}"
+                )))
+            }
+        };
+        plugin.build_bulk_upsert_sql(table, source, columns, conflict_columns, update_columns)
+    }
+
+    /// One page of a keyset walk over `base_sql` (see
+    /// [`KeysetPaginator::build_keyset_page_sql`]) for a plugin or engine id;
+    /// both Sybase engine ids resolve to the Sybase plugin.
+    ///
+    /// [`KeysetPaginator::build_keyset_page_sql`]: super::capabilities::KeysetPaginator::build_keyset_page_sql
+    pub fn build_keyset_page_sql(
+        &self,
+        plugin_id: &str,
+        base_sql: &str,
+        keys: &[&str],
+        after_last_key: bool,
+        page_rows: u32,
+    ) -> Result<String> {
+        use super::capabilities::KeysetPaginator;
+        let plugin: Box<dyn KeysetPaginator> = match plugin_id {
+            "sqlserver" => Box::new(super::sqlserver::SqlServerPlugin::new()),
+            "postgres" => Box::new(super::postgres::PostgresPlugin::new()),
+            "mysql" => Box::new(super::mysql::MySqlPlugin::new()),
+            "mariadb" => Box::new(super::mariadb::MariaDbPlugin::new()),
+            "oracle" => Box::new(super::oracle::OraclePlugin::new()),
+            "sybase" | "sybase_ase" | "sybase_asa" => Box::new(super::sybase::SybasePlugin::new()),
+            "sqlite" => Box::new(super::sqlite::SqlitePlugin::new()),
+            "db2" => Box::new(super::db2::Db2Plugin::new()),
+            "snowflake" => Box::new(super::snowflake::SnowflakePlugin::new()),
+            _ => {
+                return Err(OdbcError::UnsupportedFeature(format!(
+                    "No keyset pagination support for plugin {plugin_id:?}"
+                )))
+            }
+        };
+        plugin.build_keyset_page_sql(base_sql, keys, after_last_key, page_rows)
+    }
+
     /// Build a RETURNING/OUTPUT clause appended to `sql` for the connection's plugin.
     pub fn append_returning_sql(
         &self,
@@ -505,4 +571,44 @@ mod tests {
         assert_eq!(plugin.map_type(4), OdbcType::Integer);
         assert_eq!(plugin.map_type(-5), OdbcType::BigInt);
     }
+
+    #[test]
+    fn test_build_bulk_upsert_sql_dispatch() {
+        let registry = PluginRegistry::new();
+        let sql = registry
+            .build_bulk_upsert_sql("oracle", "t", "\"s\"", &["id", "v"], &["id"], None)
+            .unwrap();
+        assert!(sql.starts_with("MERGE INTO \"t\" t USING \"s\" s"));
+        assert!(matches!(
+            registry.build_bulk_upsert_sql("sybase", "t", "s", &["id"], &["id"], None),
+            Err(OdbcError::UnsupportedFeature(_))
+        ));
+        assert!(matches!(
+            registry.build_bulk_upsert_sql("mongodb", "t", "s", &["id"], &["id"], None),
+            Err(OdbcError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_keyset_page_sql_uses_dialect_row_limit() {
+        let registry = PluginRegistry::new();
+        let page = |id: &str| {
+            registry
+                .build_keyset_page_sql(id, "SELECT * FROM t", &["id"], true, 100)
+                .unwrap()
+        };
+        assert_eq!(
+            page("sqlserver"),
+            "SELECT TOP 100 * FROM (SELECT * FROM t) odbc_keyset WHERE ([id] > ?) ORDER BY [id]"
+        );
+        assert!(page("sybase_ase").starts_with("SELECT TOP 100 * FROM"));
+        assert!(page("mysql").ends_with("WHERE (`id` > ?) ORDER BY `id` LIMIT 100"));
+        assert!(page("postgres").ends_with("ORDER BY \"id\" LIMIT 100"));
+        assert!(page("oracle").ends_with("ORDER BY \"id\" FETCH FIRST 100 ROWS ONLY"));
+        assert!(page("db2").ends_with("FETCH FIRST 100 ROWS ONLY"));
+        assert!(matches!(
+            registry.build_keyset_page_sql("mongodb", "SELECT 1", &["id"], false, 1),
+            Err(OdbcError::UnsupportedFeature(_))
+        ));
+    }
 }
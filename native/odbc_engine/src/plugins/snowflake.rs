@@ -2,18 +2,27 @@
 //!
 //! Snowflake supports `LIMIT`, `MERGE`, `RETURNING` (added 2024) and exposes
 //! semi-structured types (`VARIANT`, `OBJECT`, `ARRAY`).
+//!
+//! Bulk loads go through a temporary internal stage (`PUT` + `COPY INTO`).
 
+use super::capabilities::bulk_loader::{BulkLoadOptions, BulkLoadProgress, BulkLoader};
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::{quote_returning_columns, DmlVerb};
-use super::capabilities::upsert::{effective_update_columns, validate_upsert_inputs, Upsertable};
+use super::capabilities::upsert::{
+    effective_update_columns, merge_from_source_sql, validate_upsert_inputs, Upsertable,
+};
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
-use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
+use crate::engine::core::SnowflakeStageLoader;
+use crate::engine::identifier::{
+    quote_identifier_default, quote_qualified_default, IdentifierQuoting,
+};
 use crate::error::Result;
 use crate::protocol::types::OdbcType;
-use crate::protocol::ParamValue;
+use crate::protocol::{BulkInsertPayload, ParamValue};
+use odbc_api::Connection;
 
 pub struct SnowflakePlugin;
 
@@ -74,6 +83,32 @@ impl DriverPlugin for SnowflakePlugin {
     }
 }
 
+impl BulkLoader for SnowflakePlugin {
+    fn technique(&self) -> &'static str {
+        "put_copy"
+    }
+
+    fn execute_bulk_native(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+    ) -> Result<usize> {
+        self.execute_bulk_native_with_progress(conn, payload, options, &mut |_| {})
+    }
+
+    fn execute_bulk_native_with_progress(
+        &self,
+        conn: &Connection<'static>,
+        payload: &BulkInsertPayload,
+        options: &BulkLoadOptions,
+        on_batch: &mut dyn FnMut(&BulkLoadProgress),
+    ) -> Result<usize> {
+        let report = SnowflakeStageLoader::new().load(conn, payload, options, on_batch)?;
+        Ok(report.rows_loaded() as usize)
+    }
+}
+
 impl Upsertable for SnowflakePlugin {
     fn build_upsert_sql(
         &self,
@@ -141,6 +176,26 @@ impl Upsertable for SnowflakePlugin {
              WHEN NOT MATCHED THEN INSERT ({insert_cols}) VALUES ({insert_vals})"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        // Snowflake answers MERGE with a row of inserted/updated counts.
+        merge_from_source_sql(
+            &quote_qualified_default(table)?,
+            source,
+            columns,
+            conflict_columns,
+            update_columns,
+            IdentifierQuoting::DoubleQuote,
+        )
+    }
 }
 
 impl Returnable for SnowflakePlugin {
@@ -163,6 +218,8 @@ impl Returnable for SnowflakePlugin {
 
 impl IdentifierQuoter for SnowflakePlugin {}
 
+impl KeysetPaginator for SnowflakePlugin {}
+
 impl TypeCatalog for SnowflakePlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
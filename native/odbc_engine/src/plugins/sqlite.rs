@@ -9,13 +9,16 @@
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::{quote_returning_columns, DmlVerb};
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, quote_columns, validate_upsert_inputs, Upsertable,
+    effective_update_columns, join_columns, placeholder_list, quote_columns,
+    validate_upsert_inputs, Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
-use crate::engine::identifier::{quote_identifier_default, quote_qualified_default};
+use crate::engine::identifier::{
+    quote_identifier_default, quote_qualified_default, IdentifierQuoting,
+};
 use crate::error::Result;
 use crate::protocol::types::OdbcType;
 use crate::protocol::ParamValue;
@@ -105,6 +108,35 @@ impl Upsertable for SqlitePlugin {
              ON CONFLICT ({qconflict}) DO UPDATE SET {set_clause}"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        let qtable = quote_qualified_default(table)?;
+        let qcols = quote_columns(columns)?;
+        let qconflict = quote_columns(conflict_columns)?;
+        let updates = effective_update_columns(columns, conflict_columns, update_columns);
+        let action = if updates.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            let set = join_columns(&updates, IdentifierQuoting::DoubleQuote, ", ", |q| {
+                format!("{q} = excluded.{q}")
+            })?;
+            format!("DO UPDATE SET {set}")
+        };
+        // The WHERE clause keeps SQLite from parsing ON CONFLICT as a join
+        // constraint of the SELECT.
+        Ok(format!(
+            "INSERT INTO {qtable} ({qcols}) SELECT {qcols} FROM {source} WHERE 1 = 1 \
+             ON CONFLICT ({qconflict}) {action}"
+        ))
+    }
 }
 
 impl Returnable for SqlitePlugin {
@@ -128,6 +160,8 @@ impl IdentifierQuoter for SqlitePlugin {
     // brackets (T-SQL-compat). Default to double-quotes.
 }
 
+impl KeysetPaginator for SqlitePlugin {}
+
 impl TypeCatalog for SqlitePlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
@@ -1,10 +1,12 @@
 use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::DmlVerb;
 use super::capabilities::upsert::{
-    effective_update_columns, placeholder_list, validate_upsert_inputs, Upsertable,
+    effective_update_columns, merge_from_source_sql, placeholder_list, validate_upsert_inputs,
+    Upsertable,
 };
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, RowLimitSyntax, SessionInitializer,
+    SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
 use crate::engine::identifier::{quote_identifier, validate_identifier, IdentifierQuoting};
@@ -172,6 +174,35 @@ impl Upsertable for SqlServerPlugin {
              WHEN NOT MATCHED THEN INSERT ({insert_cols}) VALUES ({insert_vals});"
         ))
     }
+
+    fn build_bulk_upsert_sql(
+        &self,
+        table: &str,
+        source: &str,
+        columns: &[&str],
+        conflict_columns: &[&str],
+        update_columns: Option<&[&str]>,
+    ) -> Result<String> {
+        validate_upsert_inputs(table, columns, conflict_columns, update_columns)?;
+        let merge = merge_from_source_sql(
+            &quote_table_brackets(table)?,
+            source,
+            columns,
+            conflict_columns,
+            update_columns,
+            IdentifierQuoting::Brackets,
+        )?;
+        // MERGE reports a single row count; OUTPUT $action splits it into
+        // inserts and updates. NOCOUNT keeps the count SELECT the first result.
+        Ok(format!(
+            "SET NOCOUNT ON; \
+             DECLARE @odbc_upsert_actions TABLE (action NVARCHAR(10)); \
+             {merge} OUTPUT $action INTO @odbc_upsert_actions; \
+             SELECT COUNT(CASE WHEN action = 'INSERT' THEN 1 END), \
+             COUNT(CASE WHEN action = 'UPDATE' THEN 1 END) FROM @odbc_upsert_actions; \
+             SET NOCOUNT OFF;"
+        ))
+    }
 }
 
 impl Returnable for SqlServerPlugin {
@@ -241,6 +272,12 @@ impl IdentifierQuoter for SqlServerPlugin {
     }
 }
 
+impl KeysetPaginator for SqlServerPlugin {
+    fn row_limit_syntax(&self) -> RowLimitSyntax {
+        RowLimitSyntax::Top
+    }
+}
+
 impl TypeCatalog for SqlServerPlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
@@ -449,4 +486,16 @@ mod tests {
         ));
         assert!(matches!(rules[3], OptimizationRule::EnableStreaming));
     }
+
+    #[test]
+    fn bulk_upsert_merges_from_source_and_splits_actions() {
+        let p = SqlServerPlugin::new();
+        let sql = p
+            .build_bulk_upsert_sql("dbo.t", "[#stage]", &["id", "name"], &["id"], None)
+            .unwrap();
+        assert!(sql.contains("MERGE INTO [dbo].[t] t USING [#stage] s ON (t.[id] = s.[id])"));
+        assert!(sql.contains("WHEN MATCHED THEN UPDATE SET [name] = s.[name]"));
+        assert!(sql.contains("OUTPUT $action INTO @odbc_upsert_actions;"));
+        assert!(sql.starts_with("SET NOCOUNT ON;"));
+    }
 }
@@ -2,7 +2,8 @@ use super::capabilities::catalog_provider::{CatalogProvider, CatalogQuery};
 use super::capabilities::returning::DmlVerb;
 use super::capabilities::upsert::Upsertable;
 use super::capabilities::{
-    IdentifierQuoter, Returnable, SessionInitializer, SessionOptions, TypeCatalog,
+    IdentifierQuoter, KeysetPaginator, Returnable, RowLimitSyntax, SessionInitializer,
+    SessionOptions, TypeCatalog,
 };
 use super::driver_plugin::{DriverCapabilities, DriverPlugin, OptimizationRule};
 use crate::engine::identifier::IdentifierQuoting;
@@ -115,6 +116,12 @@ impl IdentifierQuoter for SybasePlugin {
     }
 }
 
+impl KeysetPaginator for SybasePlugin {
+    fn row_limit_syntax(&self) -> RowLimitSyntax {
+        RowLimitSyntax::Top
+    }
+}
+
 impl TypeCatalog for SybasePlugin {
     fn map_type_extended(&self, sql_type: i16, type_name: Option<&str>) -> OdbcType {
         if let Some(name) = type_name {
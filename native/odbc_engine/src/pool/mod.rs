@@ -1,3 +1,4 @@
+use crate::engine::core::{DriverCapabilities, ResultOptions};
 use crate::error::{OdbcError, Result};
 use odbc_api::{Connection, ConnectionOptions, Environment};
 use r2d2::{Pool, PooledConnection};
@@ -216,6 +217,7 @@ pub struct ConnectionPool {
     connection_string: String,
     max_size: u32,
     test_on_check_out: bool,
+    result_options: ResultOptions,
 }
 
 /// Options for pool creation (eviction, timeouts).
@@ -277,12 +279,21 @@ impl ConnectionPool {
         let pool = builder
             .build(manager)
             .map_err(|e| OdbcError::PoolError(format!("Pool creation failed: {}", e)))?;
+        // Every connection of the pool talks to the same driver: detect it once.
+        let capabilities = pool
+            .get()
+            .ok()
+            .and_then(|conn| DriverCapabilities::detect(&conn).ok())
+            .unwrap_or_else(|| {
+                DriverCapabilities::detect_from_connection_string(connection_string)
+            });
 
         Ok(Self {
             pool,
             connection_string: config.sanitized_connection_string,
             max_size,
             test_on_check_out: config.test_on_check_out,
+            result_options: ResultOptions::for_driver(&capabilities),
         })
     }
 
@@ -290,7 +301,10 @@ impl ConnectionPool {
         let pooled = self.pool.get().map_err(|e| {
             OdbcError::PoolError(format!("Failed to get connection from pool: {}", e))
         })?;
-        Ok(PooledConnectionWrapper { pooled })
+        Ok(PooledConnectionWrapper {
+            pooled,
+            result_options: self.result_options,
+        })
     }
 
     pub fn health_check(&self) -> bool {
@@ -309,6 +323,12 @@ impl ConnectionPool {
         self.test_on_check_out
     }
 
+    /// Result options of the pooled connections, from the driver detected
+    /// when the pool was created.
+    pub fn result_options(&self) -> ResultOptions {
+        self.result_options
+    }
+
     pub fn state(&self) -> PoolState {
         PoolState {
             size: self.pool.state().connections,
@@ -360,9 +380,19 @@ impl ConnectionPool {
 
 pub struct PooledConnectionWrapper {
     pooled: PooledConnection<OdbcConnectionManager>,
+    /// Starts as the pool's options; changes last until the checkout ends.
+    result_options: ResultOptions,
 }
 
 impl PooledConnectionWrapper {
+    pub fn result_options(&self) -> ResultOptions {
+        self.result_options
+    }
+
+    pub fn set_result_options(&mut self, options: ResultOptions) {
+        self.result_options = options;
+    }
+
     pub fn get_connection(&self) -> &Connection<'static> {
         &self.pooled
     }
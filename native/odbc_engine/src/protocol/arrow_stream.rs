@@ -0,0 +1,656 @@
+//! Apache Arrow IPC stream output (feature `arrow-ipc`).
+//!
+//! [`ArrowStreamEncoder`] turns [`RowBuffer`]s into an Arrow IPC *stream*: one schema message
+//! built from the first buffer's columns, one record batch per buffer, then the end-of-stream
+//! marker. Concatenating every byte it returns yields a stream any Arrow reader accepts, so the
+//! FFI can hand it out in arbitrary chunk sizes.
+//!
+//! Column types follow the driver-reported SQL type when the buffer carries one (see
+//! [`ColumnMetadata::sql_type`]), so a `REAL` column becomes `Float32` even when the row
+//! buffer itself holds text cells:
+//!
+//! | `OdbcType`                          | Arrow type                                        |
+//! | ----------------------------------- | ------------------------------------------------- |
+//! | `SmallInt` / `Integer` / `BigInt`   | `Int16` / `Int32` / `Int64`                       |
+//! | `Boolean`                           | `Boolean`                                         |
+//! | `Float` / `Double`                  | `Float32` / `Float64`                             |
+//! | `Decimal`                           | `Decimal128(p, s)` from the SQL type (`p` ≤ 38)   |
+//! | `Money`                             | `Decimal128(19, 4)`                               |
+//! | `Date`                              | `Date32`                                          |
+//! | `Time`                              | `Time64(µs)`                                      |
+//! | `Timestamp`                         | `Timestamp(µs)`                                   |
+//! | `DatetimeOffset`, `TimestampWithTz` | `Timestamp(µs, "UTC")` (the offset is normalised) |
+//! | `Uuid`                              | `FixedSizeBinary(16)`, extension `arrow.uuid`     |
+//! | `Binary`                            | `Binary`                                          |
+//! | `Json`                              | `Utf8`, extension `arrow.json`                    |
+//! | everything else                     | `Utf8`                                            |
+//!
+//! Decimals without a known precision (or wider than 38 digits) stay `Utf8`. A non-null cell
+//! that cannot be converted to its column type fails the encode with a `ValidationError`
+//! rather than being silently nulled.
+
+use crate::error::{OdbcError, Result};
+use crate::protocol::native_cell::{
+    decode, encode_text, native_column_type, native_width, parse_time_micros, CellEncoding,
+    NativeValue,
+};
+use crate::protocol::row_buffer::{ColumnMetadata, RowBuffer};
+use crate::protocol::types::OdbcType;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryArray,
+    Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, RecordBatch, StringArray,
+    Time64MicrosecondArray, TimestampMicrosecondArray,
+};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType as ArrowType, Field, Schema, SchemaRef, TimeUnit};
+use odbc_api::DataType;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Field metadata key of Arrow canonical extension types.
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+const MAX_DECIMAL128_PRECISION: usize = 38;
+const UTC: &str = "UTC";
+
+fn arrow_error(e: ArrowError) -> OdbcError {
+    OdbcError::InternalError(format!("Arrow IPC encoding failed: {e}"))
+}
+
+/// Type the column's values are converted to: the native-cell refinement of the SQL type
+/// when known, else the advertised `odbc_type`.
+fn logical_type(column: &ColumnMetadata) -> OdbcType {
+    column
+        .sql_type
+        .as_ref()
+        .and_then(native_column_type)
+        .unwrap_or(column.odbc_type)
+}
+
+fn decimal_type(sql_type: Option<&DataType>) -> Option<ArrowType> {
+    let (precision, scale) = match sql_type? {
+        DataType::Decimal { precision, scale } | DataType::Numeric { precision, scale } => {
+            (*precision, *scale)
+        }
+        _ => return None,
+    };
+    if precision == 0 || precision > MAX_DECIMAL128_PRECISION {
+        return None;
+    }
+    Some(ArrowType::Decimal128(
+        u8::try_from(precision).ok()?,
+        i8::try_from(scale).ok()?,
+    ))
+}
+
+/// Arrow field for one result column.
+pub fn arrow_field(column: &ColumnMetadata) -> Field {
+    let logical = logical_type(column);
+    let data_type = match logical {
+        OdbcType::SmallInt => ArrowType::Int16,
+        OdbcType::Integer => ArrowType::Int32,
+        OdbcType::BigInt => ArrowType::Int64,
+        OdbcType::Boolean => ArrowType::Boolean,
+        OdbcType::Float => ArrowType::Float32,
+        OdbcType::Double => ArrowType::Float64,
+        OdbcType::Decimal => decimal_type(column.sql_type.as_ref()).unwrap_or(ArrowType::Utf8),
+        OdbcType::Money => ArrowType::Decimal128(19, 4),
+        OdbcType::Date => ArrowType::Date32,
+        OdbcType::Time => ArrowType::Time64(TimeUnit::Microsecond),
+        OdbcType::Timestamp => ArrowType::Timestamp(TimeUnit::Microsecond, None),
+        OdbcType::DatetimeOffset | OdbcType::TimestampWithTz => {
+            ArrowType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+        }
+        OdbcType::Uuid => ArrowType::FixedSizeBinary(16),
+        OdbcType::Binary => ArrowType::Binary,
+        _ => ArrowType::Utf8,
+    };
+    let field = Field::new(column.name.clone(), data_type, true);
+    let extension = match logical {
+        OdbcType::Uuid => "arrow.uuid",
+        OdbcType::Json => "arrow.json",
+        _ => return field,
+    };
+    field.with_metadata(HashMap::from([(
+        EXTENSION_NAME_KEY.to_string(),
+        extension.to_string(),
+    )]))
+}
+
+/// Arrow schema for the columns of `row_buffer`.
+pub fn arrow_schema(row_buffer: &RowBuffer) -> Schema {
+    Schema::new(
+        row_buffer
+            .columns
+            .iter()
+            .map(arrow_field)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Reads the cells of one column as whatever the row buffer stored them as.
+struct CellSource<'a> {
+    rows: &'a [Vec<Option<Vec<u8>>>],
+    index: usize,
+    column: &'a ColumnMetadata,
+    native: bool,
+}
+
+impl<'a> CellSource<'a> {
+    fn cell(&self, row: usize) -> Option<&'a [u8]> {
+        self.rows[row].get(self.index)?.as_deref()
+    }
+
+    /// Binary `Integer` / `BigInt` cell.
+    fn integer(&self, bytes: &[u8]) -> Option<i64> {
+        match (self.column.odbc_type, bytes.len()) {
+            (OdbcType::Integer, 4) => Some(i32::from_le_bytes(bytes.try_into().ok()?).into()),
+            (OdbcType::BigInt, 8) => Some(i64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Native cell of the stored type, or the text cell parsed as `logical`.
+    fn native(&self, bytes: &[u8], logical: OdbcType) -> Option<NativeValue> {
+        if self.native && native_width(self.column.odbc_type) == Some(bytes.len()) {
+            return decode(bytes, self.column.odbc_type);
+        }
+        let text = std::str::from_utf8(bytes).ok()?;
+        decode(&encode_text(text, logical)?, logical)
+    }
+
+    fn text(&self, bytes: &[u8]) -> Option<String> {
+        if let Some(v) = self.integer(bytes) {
+            return Some(v.to_string());
+        }
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn whole_number(&self, bytes: &[u8]) -> Option<i64> {
+        if let Some(v) = self.integer(bytes) {
+            return Some(v);
+        }
+        if let Some(NativeValue::SmallInt(v)) = self.native(bytes, OdbcType::SmallInt) {
+            return Some(v.into());
+        }
+        std::str::from_utf8(bytes).ok()?.trim().parse().ok()
+    }
+
+    /// Converts every non-null cell with `convert`; a `None` from it is an error.
+    fn collect<T>(&self, mut convert: impl FnMut(&[u8]) -> Option<T>) -> Result<Vec<Option<T>>> {
+        (0..self.rows.len())
+            .map(|row| match self.cell(row) {
+                None => Ok(None),
+                Some(bytes) => convert(bytes).map(Some).ok_or_else(|| {
+                    OdbcError::ValidationError(format!(
+                        "Arrow IPC: cannot convert row {row} of column '{}' to {:?}",
+                        self.column.name,
+                        logical_type(self.column)
+                    ))
+                }),
+            })
+            .collect()
+    }
+}
+
+/// `[-]digits[.digits]` scaled by `10^scale`; extra fraction digits must be zeros.
+fn parse_decimal(text: &str, scale: i8) -> Option<i128> {
+    let scale = u32::try_from(scale).ok()?;
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let mut value: i128 = 0;
+    for b in whole.bytes() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add(i128::from(b - b'0'))?;
+    }
+    for (i, b) in fraction.bytes().enumerate() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        if i as u32 >= scale {
+            if b != b'0' {
+                return None;
+            }
+            continue;
+        }
+        value = value.checked_mul(10)?.checked_add(i128::from(b - b'0'))?;
+    }
+    let padding = scale.saturating_sub(fraction.len() as u32);
+    value = value.checked_mul(10i128.checked_pow(padding)?)?;
+    Some(if negative { -value } else { value })
+}
+
+fn timestamp_micros(value: NativeValue) -> Option<i64> {
+    match value {
+        NativeValue::Timestamp(micros) => Some(micros),
+        NativeValue::TimestampOffset { utc_micros, .. } => Some(utc_micros),
+        NativeValue::Date(days) => i64::from(days).checked_mul(86_400_000_000),
+        _ => None,
+    }
+}
+
+fn column_array(source: &CellSource<'_>, data_type: &ArrowType) -> Result<ArrayRef> {
+    let array: ArrayRef = match data_type {
+        ArrowType::Int16 => Arc::new(Int16Array::from(
+            source.collect(|b| i16::try_from(source.whole_number(b)?).ok())?,
+        )),
+        ArrowType::Int32 => Arc::new(Int32Array::from(
+            source.collect(|b| i32::try_from(source.whole_number(b)?).ok())?,
+        )),
+        ArrowType::Int64 => Arc::new(Int64Array::from(
+            source.collect(|b| source.whole_number(b))?,
+        )),
+        ArrowType::Boolean => Arc::new(BooleanArray::from(source.collect(|b| {
+            if let Some(v) = source.integer(b) {
+                return Some(v != 0);
+            }
+            match source.native(b, OdbcType::Boolean)? {
+                NativeValue::Boolean(v) => Some(v),
+                _ => None,
+            }
+        })?)),
+        ArrowType::Float32 => Arc::new(Float32Array::from(source.collect(|b| {
+            match source.native(b, OdbcType::Float)? {
+                NativeValue::Float(v) => Some(v),
+                _ => None,
+            }
+        })?)),
+        ArrowType::Float64 => Arc::new(Float64Array::from(source.collect(|b| {
+            if let Some(v) = source.integer(b) {
+                return Some(v as f64);
+            }
+            match source.native(b, OdbcType::Double)? {
+                NativeValue::Double(v) => Some(v),
+                NativeValue::Float(v) => Some(v.into()),
+                _ => None,
+            }
+        })?)),
+        ArrowType::Decimal128(precision, scale) => {
+            let values = source.collect(|b| {
+                if let Some(v) = source.integer(b) {
+                    return i128::from(v)
+                        .checked_mul(10i128.checked_pow(u32::try_from(*scale).ok()?)?);
+                }
+                parse_decimal(std::str::from_utf8(b).ok()?, *scale)
+            })?;
+            Arc::new(
+                Decimal128Array::from(values)
+                    .with_precision_and_scale(*precision, *scale)
+                    .map_err(arrow_error)?,
+            )
+        }
+        ArrowType::Date32 => {
+            Arc::new(Date32Array::from(source.collect(
+                |b| match source.native(b, OdbcType::Date)? {
+                    NativeValue::Date(days) => Some(days),
+                    _ => None,
+                },
+            )?))
+        }
+        ArrowType::Time64(_) => {
+            Arc::new(Time64MicrosecondArray::from(source.collect(|b| {
+                parse_time_micros(std::str::from_utf8(b).ok()?.trim())
+            })?))
+        }
+        ArrowType::Timestamp(_, tz) => {
+            let target = if tz.is_some() {
+                OdbcType::DatetimeOffset
+            } else {
+                OdbcType::Timestamp
+            };
+            let values = source.collect(|b| {
+                source
+                    .native(b, target)
+                    .or_else(|| source.native(b, OdbcType::Timestamp))
+                    .and_then(timestamp_micros)
+            })?;
+            let array = TimestampMicrosecondArray::from(values);
+            Arc::new(match tz {
+                Some(tz) => array.with_timezone(tz.clone()),
+                None => array,
+            })
+        }
+        ArrowType::FixedSizeBinary(width) => {
+            let values = source.collect(|b| match source.native(b, OdbcType::Uuid)? {
+                NativeValue::Uuid(bytes) => Some(bytes),
+                _ => None,
+            })?;
+            Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(values.into_iter(), *width)
+                    .map_err(arrow_error)?,
+            )
+        }
+        ArrowType::Binary => Arc::new(BinaryArray::from(
+            source
+                .collect(|b| Some(b.to_vec()))?
+                .iter()
+                .map(|v| v.as_deref())
+                .collect::<Vec<_>>(),
+        )),
+        _ => Arc::new(StringArray::from(source.collect(|b| source.text(b))?)),
+    };
+    Ok(array)
+}
+
+/// One record batch holding every row of `row_buffer`.
+pub fn record_batch(schema: &SchemaRef, row_buffer: &RowBuffer) -> Result<RecordBatch> {
+    if schema.fields().len() != row_buffer.column_count() {
+        return Err(OdbcError::InternalError(format!(
+            "Arrow IPC: row buffer has {} columns, schema has {}",
+            row_buffer.column_count(),
+            schema.fields().len()
+        )));
+    }
+    let native = row_buffer.cell_encoding == CellEncoding::Native;
+    let columns = row_buffer
+        .columns
+        .iter()
+        .zip(schema.fields())
+        .enumerate()
+        .map(|(index, (column, field))| {
+            let source = CellSource {
+                rows: &row_buffer.rows,
+                index,
+                column,
+                native,
+            };
+            column_array(&source, field.data_type())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(Arc::clone(schema), columns).map_err(arrow_error)
+}
+
+/// Incremental Arrow IPC stream writer over row buffers sharing one column layout.
+pub struct ArrowStreamEncoder {
+    schema: SchemaRef,
+    writer: StreamWriter<Vec<u8>>,
+}
+
+impl ArrowStreamEncoder {
+    /// Starts a stream for the columns of `row_buffer` (its rows are not written); the schema
+    /// message is pending in [`Self::take_bytes`].
+    pub fn new(row_buffer: &RowBuffer) -> Result<Self> {
+        let schema = Arc::new(arrow_schema(row_buffer));
+        let writer = StreamWriter::try_new(Vec::new(), &schema).map_err(arrow_error)?;
+        Ok(Self { schema, writer })
+    }
+
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Appends the rows of `row_buffer` as one record batch. Empty buffers write nothing.
+    pub fn write_rows(&mut self, row_buffer: &RowBuffer) -> Result<()> {
+        if row_buffer.row_count() == 0 {
+            return Ok(());
+        }
+        let batch = record_batch(&self.schema, row_buffer)?;
+        self.writer.write(&batch).map_err(arrow_error)
+    }
+
+    /// Drains the bytes written so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.get_mut())
+    }
+
+    /// Writes the end-of-stream marker and returns the remaining bytes.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        self.writer.finish().map_err(arrow_error)?;
+        self.writer.into_inner().map_err(arrow_error)
+    }
+
+    /// Complete stream (schema, one batch, end-of-stream) for `row_buffer`.
+    pub fn encode(row_buffer: &RowBuffer) -> Result<Vec<u8>> {
+        let mut encoder = Self::new(row_buffer)?;
+        encoder.write_rows(row_buffer)?;
+        let mut out = encoder.take_bytes();
+        out.extend(encoder.finish()?);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use arrow_ipc::reader::StreamReader;
+
+    fn read_stream(bytes: &[u8]) -> (SchemaRef, Vec<RecordBatch>) {
+        let reader = StreamReader::try_new(bytes, None).unwrap();
+        let schema = reader.schema();
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>().unwrap();
+        (schema, batches)
+    }
+
+    fn text(s: &str) -> Option<Vec<u8>> {
+        Some(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_types_follow_sql_type() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("id".to_string(), OdbcType::Integer);
+        buffer.add_described_column(
+            "amount".to_string(),
+            OdbcType::Decimal,
+            DataType::Decimal {
+                precision: 12,
+                scale: 2,
+            },
+        );
+        buffer.add_described_column("ratio".to_string(), OdbcType::Varchar, DataType::Real);
+        buffer.add_described_column(
+            "at".to_string(),
+            OdbcType::Varchar,
+            DataType::Timestamp { precision: 3 },
+        );
+        buffer.add_column("doc".to_string(), OdbcType::Json);
+        let schema = arrow_schema(&buffer);
+        let types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ArrowType::Int32,
+                ArrowType::Decimal128(12, 2),
+                ArrowType::Float32,
+                ArrowType::Timestamp(TimeUnit::Microsecond, None),
+                ArrowType::Utf8,
+            ]
+        );
+        assert_eq!(
+            schema
+                .field(4)
+                .metadata()
+                .get(EXTENSION_NAME_KEY)
+                .map(String::as_str),
+            Some("arrow.json")
+        );
+    }
+
+    #[test]
+    fn test_wide_or_unknown_decimal_stays_text() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("a".to_string(), OdbcType::Decimal);
+        buffer.add_described_column(
+            "b".to_string(),
+            OdbcType::Decimal,
+            DataType::Numeric {
+                precision: 50,
+                scale: 0,
+            },
+        );
+        let schema = arrow_schema(&buffer);
+        assert_eq!(schema.field(0).data_type(), &ArrowType::Utf8);
+        assert_eq!(schema.field(1).data_type(), &ArrowType::Utf8);
+    }
+
+    #[test]
+    fn test_text_cells_round_trip_through_stream() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("id".to_string(), OdbcType::Integer);
+        buffer.add_described_column(
+            "amount".to_string(),
+            OdbcType::Decimal,
+            DataType::Decimal {
+                precision: 10,
+                scale: 2,
+            },
+        );
+        buffer.add_column("name".to_string(), OdbcType::Varchar);
+        buffer.add_column("at".to_string(), OdbcType::DatetimeOffset);
+        buffer.add_column("t".to_string(), OdbcType::Time);
+        buffer.add_row(vec![
+            Some(7i32.to_le_bytes().to_vec()),
+            text("-12.5"),
+            text("x"),
+            text("1970-01-01 02:00:00 +02:00"),
+            text("00:00:01.5"),
+        ]);
+        buffer.add_row(vec![None, None, None, None, None]);
+
+        let bytes = ArrowStreamEncoder::encode(&buffer).unwrap();
+        let (schema, batches) = read_stream(&bytes);
+        assert_eq!(
+            schema.field(3).data_type(),
+            &ArrowType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+        );
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(ids.value(0), 7);
+        assert!(ids.is_null(1));
+        let amounts = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(amounts.value(0), -1250);
+        let names = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "x");
+        let at = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(at.value(0), 0);
+        let t = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<Time64MicrosecondArray>()
+            .unwrap();
+        assert_eq!(t.value(0), 1_500_000);
+    }
+
+    #[test]
+    fn test_native_cells_are_decoded() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("ok".to_string(), OdbcType::Boolean);
+        buffer.add_column("d".to_string(), OdbcType::Date);
+        buffer.add_column("id".to_string(), OdbcType::Uuid);
+        let uuid = [7u8; 16];
+        buffer.add_row(vec![
+            Some(vec![1]),
+            Some(19_000i32.to_le_bytes().to_vec()),
+            Some(uuid.to_vec()),
+        ]);
+        let bytes = ArrowStreamEncoder::encode(&buffer).unwrap();
+        let (schema, batches) = read_stream(&bytes);
+        assert_eq!(
+            schema
+                .field(2)
+                .metadata()
+                .get(EXTENSION_NAME_KEY)
+                .map(String::as_str),
+            Some("arrow.uuid")
+        );
+        let batch = &batches[0];
+        let ok = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(ok.value(0));
+        let d = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(d.value(0), 19_000);
+        let id = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(id.value(0), uuid);
+    }
+
+    #[test]
+    fn test_incremental_stream_has_one_schema_and_batch_per_chunk() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("n".to_string(), OdbcType::BigInt);
+        let mut encoder = ArrowStreamEncoder::new(&buffer).unwrap();
+        let mut bytes = encoder.take_bytes();
+        assert!(!bytes.is_empty(), "schema message is written up front");
+        for chunk in 0..3i64 {
+            buffer.rows.clear();
+            buffer.add_row(vec![Some(chunk.to_le_bytes().to_vec())]);
+            buffer.add_row(vec![Some((chunk + 10).to_le_bytes().to_vec())]);
+            encoder.write_rows(&buffer).unwrap();
+            bytes.extend(encoder.take_bytes());
+        }
+        buffer.rows.clear();
+        encoder.write_rows(&buffer).unwrap();
+        bytes.extend(encoder.finish().unwrap());
+
+        let (_, batches) = read_stream(&bytes);
+        assert_eq!(batches.len(), 3);
+        let last = batches[2]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(last.values().to_vec(), vec![2, 12]);
+    }
+
+    #[test]
+    fn test_unparseable_cell_is_rejected() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("d".to_string(), OdbcType::Date);
+        buffer.add_row(vec![text("not a date")]);
+        let err = ArrowStreamEncoder::encode(&buffer).unwrap_err();
+        assert!(matches!(err, OdbcError::ValidationError(_)), "{err}");
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("12.3", 2), Some(1230));
+        assert_eq!(parse_decimal("-0.05", 2), Some(-5));
+        assert_eq!(parse_decimal("7", 0), Some(7));
+        assert_eq!(parse_decimal("1.500", 1), Some(15));
+        assert_eq!(parse_decimal("1.55", 1), None);
+        assert_eq!(parse_decimal("abc", 2), None);
+        assert_eq!(parse_decimal(".", 2), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::{OdbcError, Result};
+use std::ops::Range;
 use std::str;
 
 /// Hard cap on column count to bound memory in `parse_bulk_insert_payload`.
@@ -19,7 +20,16 @@ const TAG_TEXT: u8 = 2;
 const TAG_DECIMAL: u8 = 3;
 const TAG_BINARY: u8 = 4;
 const TAG_TIMESTAMP: u8 = 5;
+const TAG_BOOL: u8 = 6;
+const TAG_F64: u8 = 7;
+const TAG_F32: u8 = 8;
+const TAG_DATE: u8 = 9;
+const TAG_TIME: u8 = 10;
+const TAG_UUID: u8 = 11;
+const TAG_NTEXT: u8 = 12;
 
+/// Column type tag. Tags 0-5 are the original wire format; 6-12 were added
+/// later, so payloads produced by older clients still parse unchanged.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BulkColumnType {
     I32,
@@ -28,6 +38,18 @@ pub enum BulkColumnType {
     Decimal,
     Binary,
     Timestamp,
+    /// One byte per row (0 = false).
+    Bool,
+    F64,
+    F32,
+    /// `i16` year, `u16` month, `u16` day.
+    Date,
+    /// `u16` hour, minute, second.
+    Time,
+    /// 16 bytes in RFC 4122 (big-endian) order.
+    Uuid,
+    /// UTF-16LE; `max_len` counts code units, not bytes.
+    NText,
 }
 
 impl BulkColumnType {
@@ -39,6 +61,13 @@ impl BulkColumnType {
             TAG_DECIMAL => Ok(BulkColumnType::Decimal),
             TAG_BINARY => Ok(BulkColumnType::Binary),
             TAG_TIMESTAMP => Ok(BulkColumnType::Timestamp),
+            TAG_BOOL => Ok(BulkColumnType::Bool),
+            TAG_F64 => Ok(BulkColumnType::F64),
+            TAG_F32 => Ok(BulkColumnType::F32),
+            TAG_DATE => Ok(BulkColumnType::Date),
+            TAG_TIME => Ok(BulkColumnType::Time),
+            TAG_UUID => Ok(BulkColumnType::Uuid),
+            TAG_NTEXT => Ok(BulkColumnType::NText),
             _ => Err(OdbcError::ValidationError(format!(
                 "Unknown bulk column type tag: {}",
                 tag
@@ -54,10 +83,46 @@ impl BulkColumnType {
             BulkColumnType::Decimal => TAG_DECIMAL,
             BulkColumnType::Binary => TAG_BINARY,
             BulkColumnType::Timestamp => TAG_TIMESTAMP,
+            BulkColumnType::Bool => TAG_BOOL,
+            BulkColumnType::F64 => TAG_F64,
+            BulkColumnType::F32 => TAG_F32,
+            BulkColumnType::Date => TAG_DATE,
+            BulkColumnType::Time => TAG_TIME,
+            BulkColumnType::Uuid => TAG_UUID,
+            BulkColumnType::NText => TAG_NTEXT,
         }
     }
 }
 
+/// Lower-case type names (`"i32"`, `"text"`, `"timestamp"`, ...), as used by
+/// JSON-configured callers such as the CSV importer.
+impl str::FromStr for BulkColumnType {
+    type Err = OdbcError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "i32" => BulkColumnType::I32,
+            "i64" => BulkColumnType::I64,
+            "text" => BulkColumnType::Text,
+            "decimal" => BulkColumnType::Decimal,
+            "binary" => BulkColumnType::Binary,
+            "timestamp" => BulkColumnType::Timestamp,
+            "bool" => BulkColumnType::Bool,
+            "f64" => BulkColumnType::F64,
+            "f32" => BulkColumnType::F32,
+            "date" => BulkColumnType::Date,
+            "time" => BulkColumnType::Time,
+            "uuid" => BulkColumnType::Uuid,
+            "ntext" => BulkColumnType::NText,
+            _ => {
+                return Err(OdbcError::ValidationError(format!(
+                    "Unknown bulk column type name: {name:?}"
+                )))
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BulkColumnSpec {
     pub name: String,
@@ -98,6 +163,35 @@ pub enum BulkColumnData {
         values: Vec<BulkTimestamp>,
         null_bitmap: Option<Vec<u8>>,
     },
+    Bool {
+        values: Vec<bool>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    F64 {
+        values: Vec<f64>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    F32 {
+        values: Vec<f32>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    Date {
+        values: Vec<BulkDate>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    Time {
+        values: Vec<BulkTime>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    Uuid {
+        values: Vec<[u8; 16]>,
+        null_bitmap: Option<Vec<u8>>,
+    },
+    NText {
+        rows: Vec<Vec<u16>>,
+        max_len: usize,
+        null_bitmap: Option<Vec<u8>>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,6 +205,229 @@ pub struct BulkTimestamp {
     pub fraction: u32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkDate {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkTime {
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+}
+
+/// A row a continue-on-error bulk insert could not insert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkRejectedRow {
+    /// Index of the row in the payload that was submitted.
+    pub row_index: u32,
+    /// Five-character SQLSTATE; empty when the driver gave none for the row.
+    pub sqlstate: String,
+    pub native_error: i32,
+    pub message: String,
+}
+
+impl BulkInsertPayload {
+    /// Copy of the rows at `indices`, in that order.
+    pub fn select_rows(&self, indices: &[usize]) -> BulkInsertPayload {
+        BulkInsertPayload {
+            table: self.table.clone(),
+            columns: self.columns.clone(),
+            row_count: indices.len() as u32,
+            column_data: self
+                .column_data
+                .iter()
+                .map(|c| c.select_rows(indices))
+                .collect(),
+        }
+    }
+
+    /// Copy of the contiguous rows in `range`.
+    pub fn slice_rows(&self, range: Range<usize>) -> BulkInsertPayload {
+        self.select_rows(&range.collect::<Vec<_>>())
+    }
+
+    /// Checks that `columns`, `column_data` and `row_count` agree. Payloads from
+    /// [`parse_bulk_insert_payload`] always do; hand-built ones may not.
+    pub fn validate_shape(&self) -> Result<()> {
+        if self.column_data.len() != self.columns.len() {
+            return Err(OdbcError::ValidationError(
+                "column_data length must match columns length".to_string(),
+            ));
+        }
+        let rows = self.row_count as usize;
+        for (spec, data) in self.columns.iter().zip(&self.column_data) {
+            if data.len() != rows {
+                return Err(OdbcError::ValidationError(format!(
+                    "column {} has {} values, expected {rows}",
+                    spec.name,
+                    data.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BulkColumnData {
+    /// Number of values (rows) held.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::I32 { values, .. } => values.len(),
+            Self::I64 { values, .. } => values.len(),
+            Self::Text { rows, .. } | Self::Binary { rows, .. } => rows.len(),
+            Self::Timestamp { values, .. } => values.len(),
+            Self::Bool { values, .. } => values.len(),
+            Self::F64 { values, .. } => values.len(),
+            Self::F32 { values, .. } => values.len(),
+            Self::Date { values, .. } => values.len(),
+            Self::Time { values, .. } => values.len(),
+            Self::Uuid { values, .. } => values.len(),
+            Self::NText { rows, .. } => rows.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn null_bitmap(&self) -> Option<&[u8]> {
+        match self {
+            Self::I32 { null_bitmap, .. }
+            | Self::I64 { null_bitmap, .. }
+            | Self::Text { null_bitmap, .. }
+            | Self::Binary { null_bitmap, .. }
+            | Self::Timestamp { null_bitmap, .. }
+            | Self::Bool { null_bitmap, .. }
+            | Self::F64 { null_bitmap, .. }
+            | Self::F32 { null_bitmap, .. }
+            | Self::Date { null_bitmap, .. }
+            | Self::Time { null_bitmap, .. }
+            | Self::Uuid { null_bitmap, .. }
+            | Self::NText { null_bitmap, .. } => null_bitmap.as_deref(),
+        }
+    }
+
+    /// Copy of the values at `indices`, with the null bitmap rebuilt to match.
+    pub fn select_rows(&self, indices: &[usize]) -> BulkColumnData {
+        fn pick<T: Clone>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|&i| values[i].clone()).collect()
+        }
+        let null_bitmap = self.null_bitmap().map(|_| {
+            let mut out = vec![0u8; null_bitmap_size(indices.len())];
+            for (o, &i) in indices.iter().enumerate() {
+                if self.is_null_at(i) {
+                    out[o / 8] |= 1u8 << (o % 8);
+                }
+            }
+            out
+        });
+        match self {
+            Self::I32 { values, .. } => Self::I32 {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::I64 { values, .. } => Self::I64 {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::Text { rows, max_len, .. } => Self::Text {
+                rows: pick(rows, indices),
+                max_len: *max_len,
+                null_bitmap,
+            },
+            Self::Binary { rows, max_len, .. } => Self::Binary {
+                rows: pick(rows, indices),
+                max_len: *max_len,
+                null_bitmap,
+            },
+            Self::Timestamp { values, .. } => Self::Timestamp {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::Bool { values, .. } => Self::Bool {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::F64 { values, .. } => Self::F64 {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::F32 { values, .. } => Self::F32 {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::Date { values, .. } => Self::Date {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::Time { values, .. } => Self::Time {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::Uuid { values, .. } => Self::Uuid {
+                values: pick(values, indices),
+                null_bitmap,
+            },
+            Self::NText { rows, max_len, .. } => Self::NText {
+                rows: pick(rows, indices),
+                max_len: *max_len,
+                null_bitmap,
+            },
+        }
+    }
+
+    /// True when `row` is flagged NULL (same lenient rules as [`is_null`]).
+    pub fn is_null_at(&self, row: usize) -> bool {
+        self.null_bitmap().is_some_and(|b| is_null(b, row))
+    }
+}
+
+impl BulkTimestamp {
+    /// `YYYY-MM-DD HH:MM:SS.ffffff` (`fraction` is nanoseconds; sub-µs is truncated).
+    pub fn to_iso_micros(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.fraction / 1_000
+        )
+    }
+}
+
+impl BulkDate {
+    /// `YYYY-MM-DD`.
+    pub fn to_iso(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl BulkTime {
+    /// `HH:MM:SS`.
+    pub fn to_iso(&self) -> String {
+        format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+/// Canonical lowercase `8-4-4-4-12` form of an RFC 4122 UUID.
+pub fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(36);
+    for (i, b) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push_str(&format!("{b:02x}"));
+    }
+    out
+}
+
 fn read_u32_le(data: &[u8], offset: &mut usize) -> Result<u32> {
     if data.len().saturating_sub(*offset) < 4 {
         return Err(OdbcError::ValidationError(
@@ -277,6 +594,29 @@ fn read_null_bitmap(
     Ok(Some(bytes))
 }
 
+/// Read `row_count` fixed-width cells of `N` bytes each.
+fn read_fixed<const N: usize, T>(
+    data: &[u8],
+    o: &mut usize,
+    row_count: usize,
+    what: &str,
+    decode: impl Fn([u8; N]) -> T,
+) -> Result<Vec<T>> {
+    let mut values = Vec::with_capacity(row_count);
+    for _ in 0..row_count {
+        if data.len().saturating_sub(*o) < N {
+            return Err(OdbcError::ValidationError(format!(
+                "Bulk insert payload truncated ({what})"
+            )));
+        }
+        let mut cell = [0u8; N];
+        cell.copy_from_slice(&data[*o..*o + N]);
+        *o += N;
+        values.push(decode(cell));
+    }
+    Ok(values)
+}
+
 fn parse_column_data(
     data: &[u8],
     start: usize,
@@ -410,6 +750,104 @@ fn parse_column_data(
                 o - start,
             ))
         }
+        BulkColumnType::Bool => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "bool", |[b]: [u8; 1]| b != 0)?;
+            Ok((
+                BulkColumnData::Bool {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::F64 => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "f64", f64::from_le_bytes)?;
+            Ok((
+                BulkColumnData::F64 {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::F32 => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "f32", f32::from_le_bytes)?;
+            Ok((
+                BulkColumnData::F32 {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::Date => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "date", |b: [u8; 6]| BulkDate {
+                year: i16::from_le_bytes([b[0], b[1]]),
+                month: u16::from_le_bytes([b[2], b[3]]),
+                day: u16::from_le_bytes([b[4], b[5]]),
+            })?;
+            Ok((
+                BulkColumnData::Date {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::Time => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "time", |b: [u8; 6]| BulkTime {
+                hour: u16::from_le_bytes([b[0], b[1]]),
+                minute: u16::from_le_bytes([b[2], b[3]]),
+                second: u16::from_le_bytes([b[4], b[5]]),
+            })?;
+            Ok((
+                BulkColumnData::Time {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::Uuid => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let values = read_fixed(data, &mut o, row_count, "uuid", |b: [u8; 16]| b)?;
+            Ok((
+                BulkColumnData::Uuid {
+                    values,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
+        BulkColumnType::NText => {
+            let null_bitmap = read_null_bitmap(data, &mut o, spec.nullable, row_count)?;
+            let max_len = spec.max_len.max(1);
+            let mut rows = Vec::with_capacity(row_count);
+            for _ in 0..row_count {
+                let raw = read_bytes(data, &mut o, max_len * 2)?;
+                let mut v: Vec<u16> = raw
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                if let Some(trimmed) = v.iter().position(|&u| u == 0) {
+                    v.truncate(trimmed);
+                }
+                rows.push(v);
+            }
+            Ok((
+                BulkColumnData::NText {
+                    rows,
+                    max_len,
+                    null_bitmap,
+                },
+                o - start,
+            ))
+        }
     }
 }
 
@@ -568,6 +1006,113 @@ fn serialize_column_data(
                 out.extend_from_slice(&t.fraction.to_le_bytes());
             }
         }
+        (
+            BulkColumnData::Bool {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Bool,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            out.extend(values.iter().map(|&v| u8::from(v)));
+        }
+        (
+            BulkColumnData::F64 {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::F64,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for &v in values {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        (
+            BulkColumnData::F32 {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::F32,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for &v in values {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        (
+            BulkColumnData::Date {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Date,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for d in values {
+                out.extend_from_slice(&d.year.to_le_bytes());
+                out.extend_from_slice(&d.month.to_le_bytes());
+                out.extend_from_slice(&d.day.to_le_bytes());
+            }
+        }
+        (
+            BulkColumnData::Time {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Time,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for t in values {
+                out.extend_from_slice(&t.hour.to_le_bytes());
+                out.extend_from_slice(&t.minute.to_le_bytes());
+                out.extend_from_slice(&t.second.to_le_bytes());
+            }
+        }
+        (
+            BulkColumnData::Uuid {
+                values,
+                null_bitmap,
+            },
+            BulkColumnType::Uuid,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for u in values {
+                out.extend_from_slice(u);
+            }
+        }
+        (
+            BulkColumnData::NText {
+                rows,
+                max_len,
+                null_bitmap,
+            },
+            BulkColumnType::NText,
+        ) => {
+            if let Some(bm) = null_bitmap {
+                out.extend_from_slice(bm);
+            }
+            for row in rows {
+                let len = row.len().min(*max_len);
+                for &unit in &row[..len] {
+                    out.extend_from_slice(&unit.to_le_bytes());
+                }
+                for _ in len..*max_len {
+                    out.extend_from_slice(&[0, 0]);
+                }
+            }
+        }
         _ => {
             return Err(OdbcError::ValidationError(
                 "Bulk column data does not match spec".to_string(),
@@ -577,6 +1122,77 @@ fn serialize_column_data(
     Ok(())
 }
 
+/// Encodes rejected rows followed by a payload holding exactly those rows.
+///
+/// Layout: `u32` count, then per row `u32` row_index, 5-byte SQLSTATE
+/// (zero-filled when absent), `i32` native error, `u32` message length and
+/// UTF-8 message; the remainder is a regular bulk insert payload whose
+/// row `n` is rejected row `n`.
+pub fn serialize_bulk_rejected(
+    rows: &[BulkRejectedRow],
+    payload: &BulkInsertPayload,
+) -> Result<Vec<u8>> {
+    if rows.len() != payload.row_count as usize {
+        return Err(OdbcError::ValidationError(format!(
+            "rejected rows ({}) must match rejected payload rows ({})",
+            rows.len(),
+            payload.row_count
+        )));
+    }
+    let mut out = Vec::new();
+    out.extend_from_slice(&len_to_u32(rows.len(), "rejected row count")?.to_le_bytes());
+    for row in rows {
+        out.extend_from_slice(&row.row_index.to_le_bytes());
+        let mut state = [0u8; 5];
+        let bytes = row.sqlstate.as_bytes();
+        let n = bytes.len().min(5);
+        state[..n].copy_from_slice(&bytes[..n]);
+        out.extend_from_slice(&state);
+        out.extend_from_slice(&row.native_error.to_le_bytes());
+        let msg = row.message.as_bytes();
+        out.extend_from_slice(&len_to_u32(msg.len(), "rejected row message")?.to_le_bytes());
+        out.extend_from_slice(msg);
+    }
+    out.extend_from_slice(&serialize_bulk_insert_payload(payload)?);
+    Ok(out)
+}
+
+/// Inverse of [`serialize_bulk_rejected`].
+pub fn parse_bulk_rejected(data: &[u8]) -> Result<(Vec<BulkRejectedRow>, BulkInsertPayload)> {
+    let mut o = 0usize;
+    let count = read_u32_le(data, &mut o)? as usize;
+    if count > MAX_BULK_ROWS {
+        return Err(OdbcError::ResourceLimitReached(format!(
+            "rejected row count {count} exceeds MAX_BULK_ROWS={MAX_BULK_ROWS}"
+        )));
+    }
+    let mut rows = Vec::with_capacity(count);
+    for _ in 0..count {
+        let row_index = read_u32_le(data, &mut o)?;
+        let state = read_bytes(data, &mut o, 5)?;
+        let end = state.iter().position(|&b| b == 0).unwrap_or(5);
+        let sqlstate = String::from_utf8_lossy(&state[..end]).into_owned();
+        let native_error = read_u32_le(data, &mut o)? as i32;
+        let msg_len = read_u32_le(data, &mut o)? as usize;
+        let message = String::from_utf8_lossy(read_bytes(data, &mut o, msg_len)?).into_owned();
+        rows.push(BulkRejectedRow {
+            row_index,
+            sqlstate,
+            native_error,
+            message,
+        });
+    }
+    let payload = parse_bulk_insert_payload(&data[o..])?;
+    if payload.row_count as usize != rows.len() {
+        return Err(OdbcError::MalformedPayload(format!(
+            "rejected rows ({}) do not match rejected payload rows ({})",
+            rows.len(),
+            payload.row_count
+        )));
+    }
+    Ok((rows, payload))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -677,6 +1293,289 @@ mod tests {
         }
     }
 
+    fn spec(
+        name: &str,
+        col_type: BulkColumnType,
+        nullable: bool,
+        max_len: usize,
+    ) -> BulkColumnSpec {
+        BulkColumnSpec {
+            name: name.to_string(),
+            col_type,
+            nullable,
+            max_len,
+        }
+    }
+
+    #[test]
+    fn test_bulk_insert_parse_roundtrip_extended_types() {
+        let uuid = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+            0xcd, 0xef,
+        ];
+        let payload = BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![
+                spec("flag", BulkColumnType::Bool, true, 0),
+                spec("ratio", BulkColumnType::F64, false, 0),
+                spec("score", BulkColumnType::F32, false, 0),
+                spec("day", BulkColumnType::Date, false, 0),
+                spec("at", BulkColumnType::Time, true, 0),
+                spec("id", BulkColumnType::Uuid, false, 0),
+                spec("label", BulkColumnType::NText, false, 4),
+            ],
+            row_count: 2,
+            column_data: vec![
+                BulkColumnData::Bool {
+                    values: vec![true, false],
+                    null_bitmap: Some(vec![0b10]),
+                },
+                BulkColumnData::F64 {
+                    values: vec![0.1, -2.5e300],
+                    null_bitmap: None,
+                },
+                BulkColumnData::F32 {
+                    values: vec![1.5, f32::MIN_POSITIVE],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Date {
+                    values: vec![
+                        BulkDate {
+                            year: 2024,
+                            month: 2,
+                            day: 29,
+                        },
+                        BulkDate {
+                            year: -1,
+                            month: 12,
+                            day: 31,
+                        },
+                    ],
+                    null_bitmap: None,
+                },
+                BulkColumnData::Time {
+                    values: vec![
+                        BulkTime {
+                            hour: 23,
+                            minute: 59,
+                            second: 58,
+                        },
+                        BulkTime {
+                            hour: 0,
+                            minute: 0,
+                            second: 0,
+                        },
+                    ],
+                    null_bitmap: Some(vec![0b01]),
+                },
+                BulkColumnData::Uuid {
+                    values: vec![uuid, [0; 16]],
+                    null_bitmap: None,
+                },
+                BulkColumnData::NText {
+                    rows: vec![
+                        "ação".encode_utf16().collect(),
+                        "😀".encode_utf16().collect(),
+                    ],
+                    max_len: 4,
+                    null_bitmap: None,
+                },
+            ],
+        };
+        let enc = serialize_bulk_insert_payload(&payload).unwrap();
+        let dec = parse_bulk_insert_payload(&enc).unwrap();
+        assert!(dec.validate_shape().is_ok());
+        assert_eq!(
+            dec.columns
+                .iter()
+                .map(|c| c.col_type.clone())
+                .collect::<Vec<_>>(),
+            payload
+                .columns
+                .iter()
+                .map(|c| c.col_type.clone())
+                .collect::<Vec<_>>()
+        );
+        match &dec.column_data[0] {
+            BulkColumnData::Bool { values, .. } => assert_eq!(values, &[true, false]),
+            _ => panic!("expected Bool"),
+        }
+        assert!(dec.column_data[0].is_null_at(1));
+        match &dec.column_data[1] {
+            BulkColumnData::F64 { values, .. } => assert_eq!(values, &[0.1, -2.5e300]),
+            _ => panic!("expected F64"),
+        }
+        match &dec.column_data[2] {
+            BulkColumnData::F32 { values, .. } => assert_eq!(values, &[1.5, f32::MIN_POSITIVE]),
+            _ => panic!("expected F32"),
+        }
+        match &dec.column_data[3] {
+            BulkColumnData::Date { values, .. } => {
+                assert_eq!(values[0].to_iso(), "2024-02-29");
+                assert_eq!(values[1].year, -1);
+            }
+            _ => panic!("expected Date"),
+        }
+        match &dec.column_data[4] {
+            BulkColumnData::Time { values, .. } => assert_eq!(values[0].to_iso(), "23:59:58"),
+            _ => panic!("expected Time"),
+        }
+        assert!(dec.column_data[4].is_null_at(0));
+        match &dec.column_data[5] {
+            BulkColumnData::Uuid { values, .. } => {
+                assert_eq!(
+                    format_uuid(&values[0]),
+                    "12345678-9abc-def0-0123-456789abcdef"
+                );
+            }
+            _ => panic!("expected Uuid"),
+        }
+        match &dec.column_data[6] {
+            BulkColumnData::NText { rows, max_len, .. } => {
+                assert_eq!(*max_len, 4);
+                assert_eq!(String::from_utf16(&rows[0]).unwrap(), "ação");
+                assert_eq!(String::from_utf16(&rows[1]).unwrap(), "😀");
+            }
+            _ => panic!("expected NText"),
+        }
+    }
+
+    #[test]
+    fn parse_accepts_payload_without_extended_tags() {
+        // Hand-encoded v1 payload: one non-nullable I32 column, two rows.
+        let mut v = Vec::new();
+        v.extend_from_slice(&1u32.to_le_bytes());
+        v.extend_from_slice(b"t");
+        v.extend_from_slice(&1u32.to_le_bytes());
+        v.extend_from_slice(&1u32.to_le_bytes());
+        v.extend_from_slice(b"a");
+        v.push(TAG_I32);
+        v.push(0);
+        v.extend_from_slice(&0u32.to_le_bytes());
+        v.extend_from_slice(&2u32.to_le_bytes());
+        v.extend_from_slice(&7i32.to_le_bytes());
+        v.extend_from_slice(&(-7i32).to_le_bytes());
+        let dec = parse_bulk_insert_payload(&v).expect("v1 payload");
+        match &dec.column_data[0] {
+            BulkColumnData::I32 { values, .. } => assert_eq!(values, &[7, -7]),
+            _ => panic!("expected I32"),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_truncated_f64_column() {
+        let mut enc = serialize_bulk_insert_payload(&BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![spec("x", BulkColumnType::F64, false, 0)],
+            row_count: 1,
+            column_data: vec![BulkColumnData::F64 {
+                values: vec![1.0],
+                null_bitmap: None,
+            }],
+        })
+        .unwrap();
+        enc.pop();
+        let e = parse_bulk_insert_payload(&enc).expect_err("truncated");
+        assert!(e.to_string().contains("truncated (f64)"));
+    }
+
+    #[test]
+    fn select_rows_reorders_values_and_null_bitmap() {
+        let payload = BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![spec("v", BulkColumnType::Text, true, 4)],
+            row_count: 4,
+            column_data: vec![BulkColumnData::Text {
+                rows: vec![b"a".to_vec(), Vec::new(), b"c".to_vec(), b"d".to_vec()],
+                max_len: 4,
+                null_bitmap: Some(vec![0b0010]),
+            }],
+        };
+        let picked = payload.select_rows(&[3, 1]);
+        assert_eq!(picked.row_count, 2);
+        assert!(picked.validate_shape().is_ok());
+        assert!(!picked.column_data[0].is_null_at(0));
+        assert!(picked.column_data[0].is_null_at(1));
+        match &picked.column_data[0] {
+            BulkColumnData::Text { rows, .. } => assert_eq!(rows[0], b"d"),
+            _ => panic!("expected Text"),
+        }
+        assert_eq!(payload.slice_rows(1..3).row_count, 2);
+    }
+
+    #[test]
+    fn bulk_rejected_roundtrip() {
+        let payload = BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![spec("id", BulkColumnType::I32, false, 0)],
+            row_count: 3,
+            column_data: vec![BulkColumnData::I32 {
+                values: vec![1, 2, 3],
+                null_bitmap: None,
+            }],
+        };
+        let rejected = vec![
+            BulkRejectedRow {
+                row_index: 2,
+                sqlstate: "23000".to_string(),
+                native_error: 2627,
+                message: "duplicate key".to_string(),
+            },
+            BulkRejectedRow {
+                row_index: 0,
+                sqlstate: String::new(),
+                native_error: 0,
+                message: "not processed".to_string(),
+            },
+        ];
+        let enc = serialize_bulk_rejected(&rejected, &payload.select_rows(&[2, 0])).unwrap();
+        let (rows, dec) = parse_bulk_rejected(&enc).unwrap();
+        assert_eq!(rows, rejected);
+        match &dec.column_data[0] {
+            BulkColumnData::I32 { values, .. } => assert_eq!(values, &[3, 1]),
+            _ => panic!("expected I32"),
+        }
+        assert!(serialize_bulk_rejected(&rejected, &payload).is_err());
+    }
+
+    #[test]
+    fn column_type_from_name() {
+        assert_eq!(
+            "Timestamp".parse::<BulkColumnType>().unwrap(),
+            BulkColumnType::Timestamp
+        );
+        assert_eq!(
+            "ntext".parse::<BulkColumnType>().unwrap(),
+            BulkColumnType::NText
+        );
+        assert!("varchar".parse::<BulkColumnType>().is_err());
+    }
+
+    #[test]
+    fn validate_shape_checks_column_lengths() {
+        let mut payload = BulkInsertPayload {
+            table: "t".to_string(),
+            columns: vec![BulkColumnSpec {
+                name: "id".to_string(),
+                col_type: BulkColumnType::I32,
+                nullable: true,
+                max_len: 0,
+            }],
+            row_count: 2,
+            column_data: vec![BulkColumnData::I32 {
+                values: vec![1, 2],
+                null_bitmap: Some(vec![0b10]),
+            }],
+        };
+        assert!(payload.validate_shape().is_ok());
+        assert!(!payload.column_data[0].is_null_at(0));
+        assert!(payload.column_data[0].is_null_at(1));
+        payload.row_count = 3;
+        assert!(payload.validate_shape().is_err());
+        payload.column_data.clear();
+        assert!(payload.validate_shape().is_err());
+    }
+
     #[test]
     fn null_bitmap_size_table() {
         assert_eq!(null_bitmap_size(0), 0);
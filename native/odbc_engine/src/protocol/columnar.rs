@@ -1,3 +1,4 @@
+use crate::protocol::native_cell::CellEncoding;
 use crate::protocol::types::OdbcType;
 
 pub struct ColumnBlock {
@@ -17,6 +18,12 @@ pub enum ColumnData {
     Integer(Vec<Option<i32>>),
     BigInt(Vec<Option<i64>>),
     Binary(Vec<Option<Vec<u8>>>),
+    /// Native fixed-width cells (see [`crate::protocol::native_cell`]); each
+    /// non-null cell is exactly `width` bytes and carries no length prefix.
+    Fixed {
+        width: usize,
+        data: Vec<Option<Vec<u8>>>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -40,6 +47,8 @@ pub struct RowBufferV2 {
     pub columns: Vec<ColumnBlock>,
     pub row_count: usize,
     pub flags: u16,
+    /// Selects the header version written by the columnar encoder.
+    pub cell_encoding: CellEncoding,
 }
 
 impl RowBufferV2 {
@@ -48,6 +57,7 @@ impl RowBufferV2 {
             columns: Vec::new(),
             row_count: 0,
             flags: 0,
+            cell_encoding: CellEncoding::Text,
         }
     }
 
@@ -2,7 +2,9 @@ use crate::error::{OdbcError, Result};
 use crate::protocol::columnar::{ColumnBlock, ColumnData, CompressionType, RowBufferV2};
 use crate::protocol::compression;
 use crate::protocol::converter::row_buffer_to_columnar;
+use crate::protocol::native_cell::CellEncoding;
 use crate::protocol::row_buffer::RowBuffer;
+use crate::versioning::protocol_version::ProtocolVersion;
 
 const MAGIC: u32 = 0x4F444243;
 const VERSION_V2: u16 = 2;
@@ -14,7 +16,11 @@ impl ColumnarEncoder {
         let mut output = Vec::with_capacity(Self::estimate_encoded_size(buffer)?);
 
         output.extend_from_slice(&MAGIC.to_le_bytes());
-        output.extend_from_slice(&VERSION_V2.to_le_bytes());
+        let version = match buffer.cell_encoding {
+            CellEncoding::Text => VERSION_V2,
+            CellEncoding::Native => ProtocolVersion::v2_native().wire_version(),
+        };
+        output.extend_from_slice(&version.to_le_bytes());
         output.extend_from_slice(&buffer.flags.to_le_bytes());
         output
             .extend_from_slice(&checked_u16(buffer.column_count(), "column count")?.to_le_bytes());
@@ -100,6 +106,23 @@ impl ColumnarEncoder {
                     }
                 }
             }
+            ColumnData::Fixed { width, data } => {
+                for cell in data {
+                    if let Some(bytes) = cell {
+                        if bytes.len() != *width {
+                            return Err(OdbcError::InternalError(format!(
+                                "Fixed-width cell has {} bytes, expected {}",
+                                bytes.len(),
+                                width
+                            )));
+                        }
+                        raw_data.push(0);
+                        raw_data.extend_from_slice(bytes);
+                    } else {
+                        raw_data.push(1);
+                    }
+                }
+            }
         }
 
         let (compressed_data, compression_type) = if use_compression && raw_data.len() > 100 {
@@ -179,6 +202,14 @@ impl ColumnarEncoder {
                     }
                 }
             }
+            ColumnData::Fixed { width, data } => {
+                for cell in data {
+                    size = checked_add(size, 1, "cell null flag")?;
+                    if cell.is_some() {
+                        size = checked_add(size, *width, "fixed-width cell")?;
+                    }
+                }
+            }
         }
         Ok(size)
     }
@@ -581,4 +612,55 @@ mod tests {
 
         assert_eq!(encoded, expected);
     }
+
+    #[test]
+    fn test_encode_native_fixed_width_column() {
+        let mut buffer = RowBufferV2::new();
+        buffer.cell_encoding = CellEncoding::Native;
+        buffer.set_row_count(2);
+        buffer.add_column(
+            ColumnMetadata {
+                name: "d".to_string(),
+                odbc_type: OdbcType::Date,
+            },
+            ColumnData::Fixed {
+                width: 4,
+                data: vec![Some(19_723i32.to_le_bytes().to_vec()), None],
+            },
+        );
+
+        let encoded = ColumnarEncoder::encode(&buffer, false).expect("Should encode");
+        let version = u16::from_le_bytes([encoded[4], encoded[5]]);
+        assert_eq!(version, ProtocolVersion::v2_native().wire_version());
+
+        // type(2) + name len(2) + "d" + compressed flag(1) + payload len(4)
+        let payload_len_pos = 19 + 2 + 2 + 1 + 1;
+        let payload_len = u32::from_le_bytes(
+            encoded[payload_len_pos..payload_len_pos + 4]
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(payload_len, 1 + 4 + 1);
+        let payload = &encoded[payload_len_pos + 4..];
+        assert_eq!(payload[0], 0);
+        assert_eq!(&payload[1..5], &19_723i32.to_le_bytes());
+        assert_eq!(payload[5], 1);
+    }
+
+    #[test]
+    fn test_encode_fixed_width_rejects_wrong_cell_size() {
+        let mut buffer = RowBufferV2::new();
+        buffer.set_row_count(1);
+        buffer.add_column(
+            ColumnMetadata {
+                name: "b".to_string(),
+                odbc_type: OdbcType::Boolean,
+            },
+            ColumnData::Fixed {
+                width: 1,
+                data: vec![Some(vec![1, 0])],
+            },
+        );
+        assert!(ColumnarEncoder::encode(&buffer, false).is_err());
+    }
 }
@@ -1,23 +1,697 @@
-//! Columnar wire format v2 — header constants and helpers.
+//! Columnar wire format v2 — array-layout encoder and a decoder for every columnar revision.
 //!
-//! Full emitter/parser lives in follow-up work; see
-//! `doc/notes/columnar_protocol_sketch.md`. Gated by Cargo feature
-//! `columnar-v2` so default builds stay unchanged.
+//! Layout reference: `doc/notes/columnar_protocol_sketch.md`. Gated by Cargo feature
+//! `columnar-v2`; [`crate::protocol::ColumnarEncoder`] keeps emitting the `2.0` / `2.1`
+//! per-cell layout, which [`ColumnarV2Decoder`] also reads.
+//!
+//! Every revision starts with the same 19-byte header:
+//!
+//! ```text
+//! magic u32 | version u16 | flags u16 | col_count u16 | row_count u32 | compression u8 | payload u32
+//! ```
+//!
+//! `2.0` / `2.1` (per-cell) column block:
+//!
+//! ```text
+//! type u16 | name_len u16 | name | compressed u8 | [algo u8] | data_len u32 | data
+//! data: per row a null flag u8, then the value when not null — i32 / i64 for
+//!       Integer / BigInt, the native width for native-cell types in 2.1,
+//!       otherwise len u32 + bytes
+//! ```
+//!
+//! `2.2` (array) column block:
+//!
+//! ```text
+//! type u16 | name_len u16 | name | width u8 | compressed u8 | [algo u8] | data_len u32
+//!   | pad_len u8 | pad_len zero bytes | data
+//! data: validity bitmap, then values
+//! ```
+//!
+//! - The validity bitmap holds one bit per row, least significant bit first, `1` = not null,
+//!   zero-padded to a multiple of 8 bytes.
+//! - `width > 0`: `row_count * width` little-endian values; null slots are zero-filled.
+//! - `width == 0`: `row_count + 1` `u32` offsets, then the concatenated value bytes.
+//! - The padding places `data` on an 8-byte boundary of the message, so an uncompressed value
+//!   array can be viewed as `Int32List` / `Float64List` / ... without copying.
+//!
+//! A column uses the fixed layout only when every non-null cell has its type's width; text
+//! fallbacks (see [`crate::protocol::native_cell`]) keep the column variable-width with its
+//! original type.
+
+use crate::error::{OdbcError, Result};
+use crate::protocol::columnar::CompressionType;
+use crate::protocol::compression;
+use crate::protocol::decoder::{validate_shape, ColumnInfo, DecodedResult};
+use crate::protocol::native_cell::{native_width, CellEncoding};
+use crate::protocol::row_buffer::RowBuffer;
+use crate::protocol::types::OdbcType;
+use crate::versioning::protocol_version::{ProtocolVersion, COLUMNAR_ARRAYS_MINOR};
 
 /// Little-endian `b"ODBC"` — first four bytes of the v2 header in the
-/// design sketch.
+/// design sketch. The encoders actually write [`WIRE_MAGIC`].
 pub const COLUMNAR_V2_MAGIC: u32 = u32::from_le_bytes(*b"ODBC");
 
+/// Magic written by every encoder (row-major, [`crate::protocol::ColumnarEncoder`] and
+/// [`ColumnarV2Encoder`]): `0x4F444243` as a little-endian `u32`.
+pub const WIRE_MAGIC: u32 = 0x4F444243;
+
 /// Protocol version field value from the sketch (`ver` = `u16` after magic).
 pub const COLUMNAR_V2_VERSION: u16 = 2;
 
+const HEADER_SIZE: usize = 19;
+const ALIGNMENT: usize = 8;
+const COMPRESSION_THRESHOLD: usize = 100;
+
+/// Wire width of a fixed-width column of `odbc_type`, if it has one.
+pub fn fixed_width(odbc_type: OdbcType) -> Option<usize> {
+    match odbc_type {
+        OdbcType::Integer => Some(4),
+        OdbcType::BigInt => Some(8),
+        other => native_width(other),
+    }
+}
+
+/// Encodes a [`RowBuffer`] in the `2.2` array layout.
+pub struct ColumnarV2Encoder;
+
+impl ColumnarV2Encoder {
+    pub fn encode(buffer: &RowBuffer, use_compression: bool) -> Result<Vec<u8>> {
+        let mut output = Vec::with_capacity(HEADER_SIZE);
+        output.extend_from_slice(&WIRE_MAGIC.to_le_bytes());
+        output.extend_from_slice(&ProtocolVersion::v2_arrays().wire_version().to_le_bytes());
+        output.extend_from_slice(&0u16.to_le_bytes());
+        output
+            .extend_from_slice(&checked_u16(buffer.column_count(), "column count")?.to_le_bytes());
+        output.extend_from_slice(&checked_u32(buffer.row_count(), "row count")?.to_le_bytes());
+        output.push(u8::from(use_compression));
+        let payload_size_pos = output.len();
+        output.extend_from_slice(&0u32.to_le_bytes());
+
+        for (col_idx, column) in buffer.columns.iter().enumerate() {
+            let cells: Vec<Option<&[u8]>> = buffer
+                .rows
+                .iter()
+                .map(|row| row.get(col_idx).and_then(|cell| cell.as_deref()))
+                .collect();
+            Self::encode_column(
+                &mut output,
+                &column.name,
+                column.odbc_type,
+                &cells,
+                use_compression,
+            )?;
+        }
+
+        let payload_size = checked_u32(output.len() - HEADER_SIZE, "payload size")?;
+        output[payload_size_pos..payload_size_pos + 4].copy_from_slice(&payload_size.to_le_bytes());
+        Ok(output)
+    }
+
+    fn encode_column(
+        output: &mut Vec<u8>,
+        name: &str,
+        odbc_type: OdbcType,
+        cells: &[Option<&[u8]>],
+        use_compression: bool,
+    ) -> Result<()> {
+        let width =
+            fixed_width(odbc_type).filter(|&w| cells.iter().flatten().all(|cell| cell.len() == w));
+
+        let mut raw = validity_bitmap(cells);
+        match width {
+            Some(width) => {
+                raw.reserve(cells.len().saturating_mul(width));
+                for cell in cells {
+                    match cell {
+                        Some(bytes) => raw.extend_from_slice(bytes),
+                        None => raw.resize(raw.len() + width, 0),
+                    }
+                }
+            }
+            None => {
+                let mut end = 0usize;
+                raw.extend_from_slice(&0u32.to_le_bytes());
+                for cell in cells {
+                    end += cell.map_or(0, <[u8]>::len);
+                    raw.extend_from_slice(&checked_u32(end, "column value bytes")?.to_le_bytes());
+                }
+                for bytes in cells.iter().flatten() {
+                    raw.extend_from_slice(bytes);
+                }
+            }
+        }
+
+        let (data, compression_type) = if use_compression && raw.len() > COMPRESSION_THRESHOLD {
+            match compression::compress(&raw, CompressionType::Zstd) {
+                Ok(compressed) if compressed.len() < raw.len() => {
+                    (compressed, CompressionType::Zstd)
+                }
+                _ => (raw, CompressionType::None),
+            }
+        } else {
+            (raw, CompressionType::None)
+        };
+
+        let name_bytes = name.as_bytes();
+        output.extend_from_slice(&(odbc_type as u16).to_le_bytes());
+        output
+            .extend_from_slice(&checked_u16(name_bytes.len(), "column name length")?.to_le_bytes());
+        output.extend_from_slice(name_bytes);
+        output.push(width.map_or(0, |w| w as u8));
+        if compression_type == CompressionType::None {
+            output.push(0);
+        } else {
+            output.push(1);
+            output.push(compression_type as u8);
+        }
+        output.extend_from_slice(&checked_u32(data.len(), "column payload length")?.to_le_bytes());
+        let pad = padding_after(output.len() + 1);
+        output.push(pad as u8);
+        output.resize(output.len() + pad, 0);
+        output.extend_from_slice(&data);
+        Ok(())
+    }
+}
+
+/// One decoded column; `cells` hold the same bytes a row-major message carries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedColumn {
+    pub name: String,
+    pub odbc_type: OdbcType,
+    /// Value width when the column was sent as a fixed-width array (or fixed-width cells).
+    pub width: Option<usize>,
+    pub cells: Vec<Option<Vec<u8>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedColumnar {
+    pub version: ProtocolVersion,
+    pub flags: u16,
+    pub row_count: usize,
+    pub columns: Vec<DecodedColumn>,
+}
+
+impl DecodedColumnar {
+    pub fn cell_encoding(&self) -> CellEncoding {
+        CellEncoding::for_version(&self.version)
+    }
+
+    /// Transposes into the shape [`crate::protocol::BinaryProtocolDecoder`] returns.
+    pub fn into_row_major(self) -> DecodedResult {
+        let cell_encoding = self.cell_encoding();
+        let row_count = self.row_count;
+        let mut rows: Vec<Vec<Option<Vec<u8>>>> = (0..row_count)
+            .map(|_| Vec::with_capacity(self.columns.len()))
+            .collect();
+        let mut columns = Vec::with_capacity(self.columns.len());
+        for column in self.columns {
+            for (row, cell) in rows.iter_mut().zip(column.cells) {
+                row.push(cell);
+            }
+            columns.push(ColumnInfo {
+                name: column.name,
+                odbc_type: column.odbc_type,
+            });
+        }
+        DecodedResult {
+            column_count: columns.len(),
+            columns,
+            rows,
+            row_count,
+            cell_encoding,
+        }
+    }
+}
+
+/// Decodes columnar messages (`2.0`, `2.1` and `2.2`).
+pub struct ColumnarV2Decoder;
+
+impl ColumnarV2Decoder {
+    pub fn parse(buffer: &[u8]) -> Result<DecodedColumnar> {
+        if buffer.len() < HEADER_SIZE {
+            return Err(OdbcError::ValidationError(format!(
+                "Buffer too small: need at least {} bytes, got {}",
+                HEADER_SIZE,
+                buffer.len()
+            )));
+        }
+        let mut reader = Reader::new(buffer);
+        let magic = reader.u32("magic")?;
+        if magic != WIRE_MAGIC {
+            return Err(OdbcError::ValidationError(format!(
+                "Invalid magic number: expected 0x{:08X}, got 0x{:08X}",
+                WIRE_MAGIC, magic
+            )));
+        }
+        let wire_version = reader.u16("version")?;
+        let version = ProtocolVersion::from_wire_version(wire_version);
+        if version.major != 2 || version.minor > COLUMNAR_ARRAYS_MINOR {
+            return Err(OdbcError::ValidationError(format!(
+                "Unsupported columnar version 0x{:04X}",
+                wire_version
+            )));
+        }
+        let flags = reader.u16("flags")?;
+        let column_count = usize::from(reader.u16("column count")?);
+        let row_count = reader.u32("row count")? as usize;
+        let _compression = reader.u8("compression flag")?;
+        let payload_size = reader.u32("payload size")? as usize;
+        validate_shape(column_count, row_count, payload_size)?;
+        if HEADER_SIZE + payload_size != buffer.len() {
+            return Err(OdbcError::ValidationError(format!(
+                "Payload size mismatch: header declares {}, buffer has {} payload bytes",
+                payload_size,
+                buffer.len() - HEADER_SIZE
+            )));
+        }
+
+        let arrays = version.has_columnar_arrays();
+        let mut columns = Vec::with_capacity(column_count);
+        for _ in 0..column_count {
+            let odbc_type = OdbcType::from_protocol_discriminant(reader.u16("column type")?);
+            let name_len = usize::from(reader.u16("column name length")?);
+            let name = String::from_utf8(reader.bytes(name_len, "column name")?.to_vec()).map_err(
+                |e| OdbcError::ValidationError(format!("Invalid UTF-8 in column name: {}", e)),
+            )?;
+            let declared_width = if arrays {
+                Some(usize::from(reader.u8("column width")?))
+            } else {
+                None
+            };
+            let compression_type = match reader.u8("compression flag")? {
+                0 => CompressionType::None,
+                _ => match reader.u8("compression algorithm")? {
+                    1 => CompressionType::Zstd,
+                    2 => CompressionType::Lz4,
+                    other => {
+                        return Err(OdbcError::ValidationError(format!(
+                            "Unknown compression algorithm {}",
+                            other
+                        )))
+                    }
+                },
+            };
+            let data_len = reader.u32("column payload length")? as usize;
+            if arrays {
+                let pad = usize::from(reader.u8("column padding length")?);
+                reader.bytes(pad, "column padding")?;
+            }
+            let data = compression::decompress_cow(
+                reader.bytes(data_len, "column payload")?,
+                compression_type,
+            )?;
+
+            let (width, cells) = match declared_width {
+                Some(width) => decode_array_column(&data, row_count, width)?,
+                None => decode_cell_column(&data, row_count, odbc_type, &version)?,
+            };
+            columns.push(DecodedColumn {
+                name,
+                odbc_type,
+                width,
+                cells,
+            });
+        }
+
+        if reader.pos != buffer.len() {
+            return Err(OdbcError::ValidationError(
+                "Buffer has trailing bytes".to_string(),
+            ));
+        }
+
+        Ok(DecodedColumnar {
+            version,
+            flags,
+            row_count,
+            columns,
+        })
+    }
+
+    /// [`Self::parse`] followed by [`DecodedColumnar::into_row_major`].
+    pub fn parse_rows(buffer: &[u8]) -> Result<DecodedResult> {
+        Self::parse(buffer).map(DecodedColumnar::into_row_major)
+    }
+}
+
+type DecodedCells = (Option<usize>, Vec<Option<Vec<u8>>>);
+
+fn decode_array_column(data: &[u8], row_count: usize, width: usize) -> Result<DecodedCells> {
+    let mut reader = Reader::new(data);
+    let bitmap = reader.bytes(padded_bitmap_len(row_count), "validity bitmap")?;
+    let is_valid = |row: usize| bitmap[row / 8] & (1 << (row % 8)) != 0;
+    let cells = if width > 0 {
+        let values_len = row_count
+            .checked_mul(width)
+            .ok_or_else(|| OdbcError::ValidationError("Column size overflow".to_string()))?;
+        let values = reader.bytes(values_len, "fixed-width values")?;
+        values
+            .chunks_exact(width)
+            .enumerate()
+            .map(|(row, value)| is_valid(row).then(|| value.to_vec()))
+            .collect()
+    } else {
+        let offsets_len = (row_count + 1) * 4;
+        let offsets: Vec<usize> = reader
+            .bytes(offsets_len, "value offsets")?
+            .chunks_exact(4)
+            .map(|o| u32::from_le_bytes([o[0], o[1], o[2], o[3]]) as usize)
+            .collect();
+        let values = reader.bytes(offsets[row_count], "variable-width values")?;
+        let mut cells = Vec::with_capacity(row_count);
+        for row in 0..row_count {
+            let (start, end) = (offsets[row], offsets[row + 1]);
+            if start > end || end > values.len() {
+                return Err(OdbcError::ValidationError(format!(
+                    "Invalid value offsets {}..{} for row {}",
+                    start, end, row
+                )));
+            }
+            cells.push(is_valid(row).then(|| values[start..end].to_vec()));
+        }
+        cells
+    };
+    if reader.pos != data.len() {
+        return Err(OdbcError::ValidationError(
+            "Column payload has trailing bytes".to_string(),
+        ));
+    }
+    Ok(((width > 0).then_some(width), cells))
+}
+
+fn decode_cell_column(
+    data: &[u8],
+    row_count: usize,
+    odbc_type: OdbcType,
+    version: &ProtocolVersion,
+) -> Result<DecodedCells> {
+    let width = match odbc_type {
+        OdbcType::Integer | OdbcType::BigInt => fixed_width(odbc_type),
+        other if version.has_native_cells() => native_width(other),
+        _ => None,
+    };
+    let mut reader = Reader::new(data);
+    let mut cells = Vec::with_capacity(row_count);
+    for _ in 0..row_count {
+        let cell = match reader.u8("null flag")? {
+            1 => None,
+            0 => {
+                let len = match width {
+                    Some(width) => width,
+                    None => reader.u32("cell length")? as usize,
+                };
+                Some(reader.bytes(len, "cell data")?.to_vec())
+            }
+            other => {
+                return Err(OdbcError::ValidationError(format!(
+                    "Invalid null flag {}",
+                    other
+                )))
+            }
+        };
+        cells.push(cell);
+    }
+    if reader.pos != data.len() {
+        return Err(OdbcError::ValidationError(
+            "Column payload has trailing bytes".to_string(),
+        ));
+    }
+    Ok((width, cells))
+}
+
+fn padded_bitmap_len(row_count: usize) -> usize {
+    row_count.div_ceil(8).next_multiple_of(ALIGNMENT)
+}
+
+fn validity_bitmap(cells: &[Option<&[u8]>]) -> Vec<u8> {
+    let mut bitmap = vec![0u8; padded_bitmap_len(cells.len())];
+    for (row, cell) in cells.iter().enumerate() {
+        if cell.is_some() {
+            bitmap[row / 8] |= 1 << (row % 8);
+        }
+    }
+    bitmap
+}
+
+/// Zero bytes needed after `pos` to reach the next [`ALIGNMENT`] boundary.
+fn padding_after(pos: usize) -> usize {
+    pos.next_multiple_of(ALIGNMENT) - pos
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize, what: &str) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or_else(|| OdbcError::ValidationError(format!("Buffer too small for {}", what)))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self, what: &str) -> Result<u8> {
+        Ok(self.bytes(1, what)?[0])
+    }
+
+    fn u16(&mut self, what: &str) -> Result<u16> {
+        let b = self.bytes(2, what)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self, what: &str) -> Result<u32> {
+        let b = self.bytes(4, what)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+fn checked_u16(value: usize, field: &'static str) -> Result<u16> {
+    value.try_into().map_err(|_| {
+        OdbcError::ResourceLimitReached(format!("{} {} exceeds u16 wire limit", field, value))
+    })
+}
+
+fn checked_u32(value: usize, field: &'static str) -> Result<u32> {
+    value.try_into().map_err(|_| {
+        OdbcError::ResourceLimitReached(format!("{} {} exceeds u32 wire limit", field, value))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::RowBufferEncoder;
+    use crate::protocol::{row_buffer_to_columnar, BinaryProtocolDecoder, ColumnarEncoder};
+    use proptest::prelude::*;
 
     #[test]
     fn magic_matches_sketch_label() {
         let bytes = COLUMNAR_V2_MAGIC.to_le_bytes();
         assert_eq!(&bytes, b"ODBC");
     }
+
+    fn sample_buffer() -> RowBuffer {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("id".to_string(), OdbcType::Integer);
+        buffer.add_column("name".to_string(), OdbcType::Varchar);
+        buffer.add_column("ratio".to_string(), OdbcType::Double);
+        buffer.add_row(vec![
+            Some(7i32.to_le_bytes().to_vec()),
+            Some(b"seven".to_vec()),
+            Some(0.5f64.to_le_bytes().to_vec()),
+        ]);
+        buffer.add_row(vec![None, Some(Vec::new()), None]);
+        buffer
+    }
+
+    #[test]
+    fn test_array_layout_header_and_alignment() {
+        let encoded = ColumnarV2Encoder::encode(&sample_buffer(), false).unwrap();
+        assert_eq!(&encoded[0..4], &WIRE_MAGIC.to_le_bytes());
+        assert_eq!(
+            u16::from_le_bytes([encoded[4], encoded[5]]),
+            ProtocolVersion::v2_arrays().wire_version()
+        );
+
+        // First column: type(2) name_len(2) "id" width(1) compressed(1) len(4) pad_len(1).
+        let width_pos = HEADER_SIZE + 2 + 2 + 2;
+        assert_eq!(encoded[width_pos], 4);
+        let pad_pos = width_pos + 1 + 1 + 4;
+        let data_start = pad_pos + 1 + usize::from(encoded[pad_pos]);
+        assert_eq!(data_start % ALIGNMENT, 0);
+        // Bitmap: row 0 valid, row 1 null; then the i32 array with a zeroed null slot.
+        assert_eq!(encoded[data_start], 0b01);
+        let values = &encoded[data_start + ALIGNMENT..data_start + ALIGNMENT + 8];
+        assert_eq!(&values[..4], &7i32.to_le_bytes());
+        assert_eq!(&values[4..], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_array_layout_roundtrip() {
+        let buffer = sample_buffer();
+        for compress in [false, true] {
+            let decoded =
+                ColumnarV2Decoder::parse(&ColumnarV2Encoder::encode(&buffer, compress).unwrap())
+                    .unwrap();
+            assert_eq!(decoded.cell_encoding(), CellEncoding::Native);
+            assert_eq!(decoded.columns[0].width, Some(4));
+            assert_eq!(decoded.columns[1].width, None);
+            assert_eq!(decoded.columns[2].width, Some(8));
+            assert_eq!(
+                decoded.columns[1].cells,
+                vec![Some(b"seven".to_vec()), Some(vec![])]
+            );
+            assert_eq!(decoded.columns[2].cells[1], None);
+        }
+    }
+
+    #[test]
+    fn test_text_fallback_keeps_type_and_goes_variable_width() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("flag".to_string(), OdbcType::Boolean);
+        buffer.add_row(vec![Some(vec![1])]);
+        buffer.add_row(vec![Some(b"maybe".to_vec())]);
+
+        let decoded =
+            ColumnarV2Decoder::parse(&ColumnarV2Encoder::encode(&buffer, false).unwrap()).unwrap();
+        assert_eq!(decoded.columns[0].odbc_type, OdbcType::Boolean);
+        assert_eq!(decoded.columns[0].width, None);
+        assert_eq!(decoded.columns[0].cells[1], Some(b"maybe".to_vec()));
+    }
+
+    #[test]
+    fn test_decodes_legacy_columnar_encoder_output() {
+        let buffer = sample_buffer();
+        let legacy = ColumnarEncoder::encode(&row_buffer_to_columnar(&buffer), false).unwrap();
+        let decoded = ColumnarV2Decoder::parse(&legacy).unwrap();
+        assert_eq!(decoded.version, ProtocolVersion::v2_native());
+        assert_eq!(decoded.columns[2].width, Some(8));
+        assert_eq!(
+            decoded.into_row_major().rows,
+            BinaryProtocolDecoder::parse(&RowBufferEncoder::encode(&buffer))
+                .unwrap()
+                .rows
+        );
+    }
+
+    #[test]
+    fn test_rejects_row_major_and_unknown_versions() {
+        let row_major = RowBufferEncoder::encode(&sample_buffer());
+        assert!(ColumnarV2Decoder::parse(&row_major).is_err());
+
+        let mut encoded = ColumnarV2Encoder::encode(&sample_buffer(), false).unwrap();
+        encoded[4..6].copy_from_slice(&0x0302u16.to_le_bytes());
+        assert!(ColumnarV2Decoder::parse(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_rejects_truncated_payloads_and_bad_offsets() {
+        let encoded = ColumnarV2Encoder::encode(&sample_buffer(), false).unwrap();
+        assert!(ColumnarV2Decoder::parse(&encoded[..encoded.len() - 1]).is_err());
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert!(ColumnarV2Decoder::parse(&trailing).is_err());
+
+        // Offsets that run backwards are rejected instead of slicing out of order.
+        let mut values = vec![0u8; padded_bitmap_len(2)];
+        values[0] = 0b11;
+        for offset in [0u32, 3, 1] {
+            values.extend_from_slice(&offset.to_le_bytes());
+        }
+        values.push(b'x');
+        assert!(decode_array_column(&values, 2, 0).is_err());
+    }
+
+    const TYPES: [OdbcType; 10] = [
+        OdbcType::Integer,
+        OdbcType::BigInt,
+        OdbcType::Varchar,
+        OdbcType::Binary,
+        OdbcType::Double,
+        OdbcType::SmallInt,
+        OdbcType::Boolean,
+        OdbcType::Date,
+        OdbcType::Uuid,
+        OdbcType::DatetimeOffset,
+    ];
+
+    /// Cells for `ty`: well-formed fixed-width values, or (when `allow_text`) any bytes as a
+    /// text fallback.
+    fn cell_strategy(ty: OdbcType, allow_text: bool) -> BoxedStrategy<Option<Vec<u8>>> {
+        let bytes = match fixed_width(ty) {
+            Some(width) if allow_text => prop_oneof![
+                4 => prop::collection::vec(any::<u8>(), width),
+                1 => prop::collection::vec(any::<u8>(), 0..24),
+            ]
+            .boxed(),
+            Some(width) => prop::collection::vec(any::<u8>(), width).boxed(),
+            None => prop::collection::vec(any::<u8>(), 0..48).boxed(),
+        };
+        prop::option::weighted(0.8, bytes).boxed()
+    }
+
+    type Table = (Vec<OdbcType>, Vec<Vec<Option<Vec<u8>>>>);
+
+    fn table_strategy(allow_text: bool) -> impl Strategy<Value = Table> {
+        (
+            prop::collection::vec(prop::sample::select(TYPES.to_vec()), 0..6),
+            0usize..40,
+        )
+            .prop_flat_map(move |(types, rows)| {
+                let row = types
+                    .iter()
+                    .map(|&ty| cell_strategy(ty, allow_text))
+                    .collect::<Vec<_>>();
+                (Just(types), prop::collection::vec(row, rows))
+            })
+    }
+
+    fn to_buffer((types, rows): Table) -> RowBuffer {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        for (i, ty) in types.into_iter().enumerate() {
+            buffer.add_column(format!("c{i}"), ty);
+        }
+        for row in rows {
+            buffer.add_row(row);
+        }
+        buffer
+    }
+
+    proptest! {
+        #[test]
+        fn prop_array_layout_matches_row_major(
+            table in table_strategy(true),
+            compress in any::<bool>(),
+        ) {
+            let buffer = to_buffer(table);
+            let row_major = BinaryProtocolDecoder::parse(&RowBufferEncoder::encode(&buffer)).unwrap();
+            let columnar = ColumnarV2Decoder::parse_rows(
+                &ColumnarV2Encoder::encode(&buffer, compress).unwrap(),
+            )
+            .unwrap();
+            prop_assert_eq!(columnar, row_major);
+        }
+
+        #[test]
+        fn prop_per_cell_layout_matches_row_major(
+            table in table_strategy(false),
+            compress in any::<bool>(),
+        ) {
+            let buffer = to_buffer(table);
+            let row_major = BinaryProtocolDecoder::parse(&RowBufferEncoder::encode(&buffer)).unwrap();
+            let columnar = ColumnarV2Decoder::parse_rows(
+                &ColumnarEncoder::encode(&row_buffer_to_columnar(&buffer), compress).unwrap(),
+            )
+            .unwrap();
+            prop_assert_eq!(columnar, row_major);
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::error::{OdbcError, Result};
 use crate::protocol::columnar::CompressionType;
 use std::borrow::Cow;
-use std::io::Read;
+use std::io::{Read, Write};
 
 const COMPRESSION_THRESHOLD_BYTES: usize = 1_000_000;
 pub const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 256 * 1024 * 1024;
@@ -79,6 +79,69 @@ pub fn compress_cow(data: &[u8], compression_type: CompressionType) -> Result<Co
     }
 }
 
+/// Streaming counterpart of [`compress`]: compresses everything written to
+/// it into `inner`, a frame at a time, so the input is never held whole.
+/// [`CompressedWriter::finish`] must be called to write the trailing frame.
+pub enum CompressedWriter<W: Write> {
+    None(W),
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    Lz4(lz4::Encoder<W>),
+}
+
+impl<W: Write> CompressedWriter<W> {
+    pub fn new(inner: W, compression_type: CompressionType) -> Result<Self> {
+        match compression_type {
+            CompressionType::None => Ok(Self::None(inner)),
+            CompressionType::Zstd => zstd::stream::write::Encoder::new(inner, 3)
+                .map(Self::Zstd)
+                .map_err(|e| {
+                    OdbcError::InternalError(format!("Zstd encoder creation failed: {}", e))
+                }),
+            CompressionType::Lz4 => lz4::EncoderBuilder::new()
+                .level(4)
+                .build(inner)
+                .map(Self::Lz4)
+                .map_err(|e| {
+                    OdbcError::InternalError(format!("Lz4 encoder creation failed: {}", e))
+                }),
+        }
+    }
+
+    /// Flushes the compressor's trailer and returns the inner writer.
+    pub fn finish(self) -> Result<W> {
+        match self {
+            Self::None(inner) => Ok(inner),
+            Self::Zstd(encoder) => encoder
+                .finish()
+                .map_err(|e| OdbcError::InternalError(format!("Zstd finish failed: {}", e))),
+            Self::Lz4(encoder) => {
+                let (inner, result) = encoder.finish();
+                result
+                    .map_err(|e| OdbcError::InternalError(format!("Lz4 finish failed: {}", e)))?;
+                Ok(inner)
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::None(inner) => inner.write(buf),
+            Self::Zstd(encoder) => encoder.write(buf),
+            Self::Lz4(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::None(inner) => inner.flush(),
+            Self::Zstd(encoder) => encoder.flush(),
+            Self::Lz4(encoder) => encoder.flush(),
+        }
+    }
+}
+
 pub fn decompress(data: &[u8], compression_type: CompressionType) -> Result<Vec<u8>> {
     decompress_with_limit(data, compression_type, DEFAULT_MAX_DECOMPRESSED_LEN)
 }
@@ -152,6 +215,23 @@ fn decompression_limit_error(actual: usize, limit: usize) -> OdbcError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compressed_writer_round_trips() {
+        let data = b"streamed rows, streamed rows, streamed rows".repeat(100);
+        for ct in [
+            CompressionType::None,
+            CompressionType::Zstd,
+            CompressionType::Lz4,
+        ] {
+            let mut writer = CompressedWriter::new(Vec::new(), ct).unwrap();
+            for piece in data.chunks(97) {
+                writer.write_all(piece).unwrap();
+            }
+            let out = writer.finish().unwrap();
+            assert_eq!(decompress(&out, ct).unwrap(), data, "{ct:?}");
+        }
+    }
+
     #[test]
     fn test_compress_none() {
         let data = b"test data";
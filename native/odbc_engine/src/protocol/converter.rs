@@ -1,10 +1,12 @@
 use crate::protocol::columnar::{ColumnData, ColumnMetadata, RowBufferV2};
+use crate::protocol::native_cell::native_width;
 use crate::protocol::row_buffer::RowBuffer;
 use crate::protocol::types::OdbcType;
 
 pub fn row_buffer_to_columnar(buffer: &RowBuffer) -> RowBufferV2 {
     let mut v2 = RowBufferV2::new();
     v2.set_row_count(buffer.row_count());
+    v2.cell_encoding = buffer.cell_encoding;
 
     let col_count = buffer.column_count();
     if col_count == 0 {
@@ -12,11 +14,25 @@ pub fn row_buffer_to_columnar(buffer: &RowBuffer) -> RowBufferV2 {
     }
 
     for (col_idx, col_meta) in buffer.columns.iter().enumerate() {
-        let metadata = ColumnMetadata {
+        let mut metadata = ColumnMetadata {
             name: col_meta.name.clone(),
             odbc_type: col_meta.odbc_type,
         };
 
+        let native = native_width(col_meta.odbc_type).filter(|_| buffer.cell_encoding.is_native());
+        if let Some(width) = native {
+            let cells = column_cells(buffer, col_idx);
+            let data = if cells.iter().flatten().all(|c| c.len() == width) {
+                ColumnData::Fixed { width, data: cells }
+            } else {
+                // At least one cell kept its text form: ship the column as text.
+                metadata.odbc_type = OdbcType::Varchar;
+                ColumnData::Varchar(cells)
+            };
+            v2.add_column(metadata, data);
+            continue;
+        }
+
         let data = match col_meta.odbc_type {
             OdbcType::Integer => {
                 let mut int_data = Vec::with_capacity(buffer.row_count());
@@ -50,17 +66,7 @@ pub fn row_buffer_to_columnar(buffer: &RowBuffer) -> RowBufferV2 {
                 }
                 ColumnData::BigInt(bigint_data)
             }
-            _ => {
-                let mut varchar_data = Vec::with_capacity(buffer.row_count());
-                for row in &buffer.rows {
-                    if let Some(cell) = row.get(col_idx) {
-                        varchar_data.push(cell.clone());
-                    } else {
-                        varchar_data.push(None);
-                    }
-                }
-                ColumnData::Varchar(varchar_data)
-            }
+            _ => ColumnData::Varchar(column_cells(buffer, col_idx)),
         };
 
         v2.add_column(metadata, data);
@@ -69,9 +75,18 @@ pub fn row_buffer_to_columnar(buffer: &RowBuffer) -> RowBufferV2 {
     v2
 }
 
+fn column_cells(buffer: &RowBuffer, col_idx: usize) -> Vec<Option<Vec<u8>>> {
+    buffer
+        .rows
+        .iter()
+        .map(|row| row.get(col_idx).cloned().flatten())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::native_cell::CellEncoding;
     use crate::protocol::row_buffer::RowBuffer;
 
     #[test]
@@ -203,4 +218,39 @@ mod tests {
             _ => panic!("Expected BigInt column data"),
         }
     }
+
+    #[test]
+    fn test_row_buffer_to_columnar_native_fixed_width() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("ratio".to_string(), OdbcType::Double);
+        buffer.add_column("flag".to_string(), OdbcType::Boolean);
+        buffer.add_row(vec![Some(1.5f64.to_le_bytes().to_vec()), Some(vec![1])]);
+        buffer.add_row(vec![None, Some(b"maybe".to_vec())]);
+
+        let v2 = row_buffer_to_columnar(&buffer);
+        assert_eq!(v2.cell_encoding, CellEncoding::Native);
+        assert_eq!(v2.columns[0].metadata.odbc_type, OdbcType::Double);
+        match &v2.columns[0].data {
+            ColumnData::Fixed { width, data } => {
+                assert_eq!(*width, 8);
+                assert_eq!(data[0], Some(1.5f64.to_le_bytes().to_vec()));
+                assert_eq!(data[1], None);
+            }
+            _ => panic!("Expected Fixed column data"),
+        }
+        // The unparseable boolean kept its text, so the column degrades to text.
+        assert_eq!(v2.columns[1].metadata.odbc_type, OdbcType::Varchar);
+        assert!(matches!(v2.columns[1].data, ColumnData::Varchar(_)));
+    }
+
+    #[test]
+    fn test_row_buffer_to_columnar_text_encoding_ignores_native_widths() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("ratio".to_string(), OdbcType::Double);
+        buffer.add_row(vec![Some(b"1.5".to_vec())]);
+
+        let v2 = row_buffer_to_columnar(&buffer);
+        assert!(matches!(v2.columns[0].data, ColumnData::Varchar(_)));
+        assert_eq!(v2.columns[0].metadata.odbc_type, OdbcType::Double);
+    }
 }
@@ -1,5 +1,7 @@
 use crate::error::{OdbcError, Result};
+use crate::protocol::native_cell::CellEncoding;
 use crate::protocol::types::OdbcType;
+use crate::versioning::protocol_version::ProtocolVersion;
 
 const MAGIC: u32 = 0x4F444243;
 const VERSION: u16 = 1;
@@ -22,6 +24,8 @@ pub struct DecodedResult {
     pub rows: Vec<Vec<Option<Vec<u8>>>>,
     pub row_count: usize,
     pub column_count: usize,
+    /// [`CellEncoding::Native`] when the header carries the v1.1 version.
+    pub cell_encoding: CellEncoding,
 }
 
 pub struct BinaryProtocolDecoder;
@@ -55,12 +59,17 @@ impl BinaryProtocolDecoder {
 
         // Read version
         let version = u16::from_le_bytes([buffer[offset], buffer[offset + 1]]);
-        if version != VERSION {
+        let native_version = ProtocolVersion::v1_native().wire_version();
+        let cell_encoding = if version == VERSION {
+            CellEncoding::Text
+        } else if version == native_version {
+            CellEncoding::Native
+        } else {
             return Err(OdbcError::ValidationError(format!(
-                "Invalid version: expected {}, got {}",
-                VERSION, version
+                "Invalid version: expected {} or {}, got {}",
+                VERSION, native_version, version
             )));
-        }
+        };
         offset += 2;
 
         // Read column count
@@ -197,11 +206,16 @@ impl BinaryProtocolDecoder {
             rows,
             row_count,
             column_count,
+            cell_encoding,
         })
     }
 }
 
-fn validate_shape(column_count: usize, row_count: usize, payload_size: usize) -> Result<()> {
+pub(crate) fn validate_shape(
+    column_count: usize,
+    row_count: usize,
+    payload_size: usize,
+) -> Result<()> {
     if column_count > MAX_DECODED_COLUMNS {
         return Err(OdbcError::ValidationError(format!(
             "Column count {} exceeds limit {}",
@@ -360,6 +374,22 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Invalid version"));
     }
 
+    #[test]
+    fn test_decode_native_cells_roundtrip() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("flag".to_string(), OdbcType::Boolean);
+        buffer.add_row(vec![Some(vec![1])]);
+        let decoded = BinaryProtocolDecoder::parse(&RowBufferEncoder::encode(&buffer))
+            .expect("Should decode");
+        assert_eq!(decoded.cell_encoding, CellEncoding::Native);
+        assert_eq!(decoded.columns[0].odbc_type, OdbcType::Boolean);
+        assert_eq!(decoded.rows[0][0], Some(vec![1]));
+
+        let legacy = BinaryProtocolDecoder::parse(&RowBufferEncoder::encode(&RowBuffer::new()))
+            .expect("Should decode");
+        assert_eq!(legacy.cell_encoding, CellEncoding::Text);
+    }
+
     #[test]
     fn test_decode_rejects_payload_size_mismatch() {
         let mut buffer = RowBufferEncoder::encode(&RowBuffer::new());
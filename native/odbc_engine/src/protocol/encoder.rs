@@ -1,11 +1,16 @@
 use crate::protocol::compression::CompressionStrategy;
+use crate::protocol::native_cell::CellEncoding;
 use crate::protocol::param_value::ParamValue;
 use crate::protocol::row_buffer::RowBuffer;
 use std::io::Write;
 use thiserror::Error;
 
+use crate::versioning::protocol_version::ProtocolVersion;
+
 const MAGIC: u32 = 0x4F444243;
 const VERSION: u16 = 1;
+/// Offset of `row_count` (followed by `payload_size`) in the v1 header.
+const ROW_COUNT_OFFSET: u64 = 8;
 
 /// Appended after the v1 row-major message when a query used `OUT` / `INOUT` parameters.
 pub const OUTPUT_FOOTER_MAGIC: [u8; 4] = *b"OUT1";
@@ -29,6 +34,9 @@ pub enum EncodeError {
     #[error("payload size overflow while adding {context}")]
     PayloadSizeOverflow { context: &'static str },
 
+    #[error("row has {actual} cells, expected {expected}")]
+    RowWidthMismatch { expected: usize, actual: usize },
+
     #[error("writer error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -69,30 +77,9 @@ impl RowBufferEncoder {
         }
         let payload_size = checked_u32_len(payload_size, "payload size")?;
 
-        w.write_all(&MAGIC.to_le_bytes())?;
-        w.write_all(&VERSION.to_le_bytes())?;
-        w.write_all(&column_count.to_le_bytes())?;
-        w.write_all(&row_count.to_le_bytes())?;
-        w.write_all(&payload_size.to_le_bytes())?;
-
-        for col in &buffer.columns {
-            w.write_all(&(col.odbc_type as u16).to_le_bytes())?;
-            let name_len = checked_u16_len(col.name.len(), "column name length")?;
-            w.write_all(&name_len.to_le_bytes())?;
-            w.write_all(col.name.as_bytes())?;
-        }
-
+        write_header(w, buffer, column_count, row_count, payload_size)?;
         for row in &buffer.rows {
-            for cell in row {
-                if let Some(data) = cell {
-                    w.write_all(&[0])?;
-                    let data_len = checked_u32_len(data.len(), "cell data length")?;
-                    w.write_all(&data_len.to_le_bytes())?;
-                    w.write_all(data)?;
-                } else {
-                    w.write_all(&[1])?;
-                }
-            }
+            write_row(w, row)?;
         }
 
         Ok(())
@@ -160,6 +147,155 @@ impl RowBufferEncoder {
     }
 }
 
+/// Sink for [`RowStreamEncoder`]: appends bytes and can rewrite bytes it already holds.
+pub trait PatchWrite: Write {
+    /// Bytes written so far.
+    fn written_len(&self) -> u64;
+
+    /// Overwrites `bytes` at absolute `offset` (< [`Self::written_len`]).
+    fn patch(&mut self, offset: u64, bytes: &[u8]) -> std::io::Result<()>;
+}
+
+impl PatchWrite for Vec<u8> {
+    fn written_len(&self) -> u64 {
+        self.len() as u64
+    }
+
+    fn patch(&mut self, offset: u64, bytes: &[u8]) -> std::io::Result<()> {
+        let start = usize::try_from(offset).map_err(std::io::Error::other)?;
+        let end = start
+            .checked_add(bytes.len())
+            .filter(|&end| end <= self.len())
+            .ok_or_else(|| std::io::Error::other("patch past end of buffer"))?;
+        self.get_mut(start..end)
+            .ok_or_else(|| std::io::Error::other("patch past end of buffer"))?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Single-pass v1 row-major encoder.
+///
+/// [`RowBufferEncoder::encode_to_writer`] needs every row up front because the header
+/// carries `row_count` and `payload_size`. This encoder writes the header with both set to
+/// zero, appends rows as they are fetched, and back-patches the two fields in
+/// [`Self::finish`]. Callers only hold one fetch batch plus the encoded output; the bytes
+/// are identical to the one-shot encoder's.
+pub struct RowStreamEncoder<W: PatchWrite> {
+    writer: W,
+    header_offset: u64,
+    column_count: usize,
+    row_count: u32,
+    payload_size: u32,
+}
+
+impl<W: PatchWrite> RowStreamEncoder<W> {
+    /// Writes the header and column metadata of `columns` (its rows are ignored).
+    pub fn new(mut writer: W, columns: &RowBuffer) -> Result<Self, EncodeError> {
+        let column_count = checked_u16_len(columns.column_count(), "column count")?;
+        let mut payload_size = 0usize;
+        for col in &columns.columns {
+            checked_u16_len(col.name.len(), "column name length")?;
+            payload_size = checked_payload_add(payload_size, 4, "column metadata")?;
+            payload_size = checked_payload_add(payload_size, col.name.len(), "column name")?;
+        }
+        let payload_size = checked_u32_len(payload_size, "payload size")?;
+        let header_offset = writer.written_len();
+        write_header(&mut writer, columns, column_count, 0, payload_size)?;
+        Ok(Self {
+            writer,
+            header_offset,
+            column_count: columns.column_count(),
+            row_count: 0,
+            payload_size,
+        })
+    }
+
+    /// Appends one row; it must have one cell per column.
+    pub fn write_row(&mut self, row: &[Option<Vec<u8>>]) -> Result<(), EncodeError> {
+        if row.len() != self.column_count {
+            return Err(EncodeError::RowWidthMismatch {
+                expected: self.column_count,
+                actual: row.len(),
+            });
+        }
+        let mut row_size = 0usize;
+        for data in row.iter().flatten() {
+            checked_u32_len(data.len(), "cell data length")?;
+            row_size = checked_payload_add(row_size, 4, "cell data length")?;
+            row_size = checked_payload_add(row_size, data.len(), "cell data")?;
+        }
+        row_size = checked_payload_add(row_size, row.len(), "cell null flag")?;
+        let payload_size = checked_payload_add(self.payload_size as usize, row_size, "row")?;
+        self.payload_size = checked_u32_len(payload_size, "payload size")?;
+        self.row_count = checked_u32_len(self.row_count as usize + 1, "row count")?;
+        write_row(&mut self.writer, row)?;
+        Ok(())
+    }
+
+    /// Appends every row of `buffer`.
+    pub fn write_rows(&mut self, buffer: &RowBuffer) -> Result<(), EncodeError> {
+        for row in &buffer.rows {
+            self.write_row(row)?;
+        }
+        Ok(())
+    }
+
+    pub fn row_count(&self) -> u32 {
+        self.row_count
+    }
+
+    /// Back-patches `row_count` / `payload_size` and returns the writer.
+    pub fn finish(mut self) -> Result<W, EncodeError> {
+        let mut counts = [0u8; 8];
+        counts[..4].copy_from_slice(&self.row_count.to_le_bytes());
+        counts[4..].copy_from_slice(&self.payload_size.to_le_bytes());
+        self.writer
+            .patch(self.header_offset + ROW_COUNT_OFFSET, &counts)?;
+        Ok(self.writer)
+    }
+}
+
+fn write_header<W: Write>(
+    w: &mut W,
+    buffer: &RowBuffer,
+    column_count: u16,
+    row_count: u32,
+    payload_size: u32,
+) -> Result<(), EncodeError> {
+    let version = match buffer.cell_encoding {
+        CellEncoding::Text => VERSION,
+        CellEncoding::Native => ProtocolVersion::v1_native().wire_version(),
+    };
+    w.write_all(&MAGIC.to_le_bytes())?;
+    w.write_all(&version.to_le_bytes())?;
+    w.write_all(&column_count.to_le_bytes())?;
+    w.write_all(&row_count.to_le_bytes())?;
+    w.write_all(&payload_size.to_le_bytes())?;
+
+    for col in &buffer.columns {
+        w.write_all(&(col.odbc_type as u16).to_le_bytes())?;
+        let name_len = checked_u16_len(col.name.len(), "column name length")?;
+        w.write_all(&name_len.to_le_bytes())?;
+        w.write_all(col.name.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_row<W: Write>(w: &mut W, row: &[Option<Vec<u8>>]) -> Result<(), EncodeError> {
+    for cell in row {
+        if let Some(data) = cell {
+            w.write_all(&[0])?;
+            let data_len = checked_u32_len(data.len(), "cell data length")?;
+            w.write_all(&data_len.to_le_bytes())?;
+            w.write_all(data)?;
+        } else {
+            w.write_all(&[1])?;
+        }
+    }
+    Ok(())
+}
+
 fn checked_u16_len(value: usize, field: &'static str) -> Result<u16, EncodeError> {
     value.try_into().map_err(|_| EncodeError::LengthTooLarge {
         field,
@@ -269,6 +405,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_row_stream_encoder_matches_one_shot_encoder() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("id".to_string(), OdbcType::Integer);
+        buffer.add_column("name".to_string(), OdbcType::Varchar);
+        buffer.add_row(vec![Some(1i32.to_le_bytes().to_vec()), Some(b"a".to_vec())]);
+        buffer.add_row(vec![Some(2i32.to_le_bytes().to_vec()), None]);
+
+        let mut encoder = RowStreamEncoder::new(Vec::new(), &buffer).unwrap();
+        for row in &buffer.rows {
+            encoder.write_row(row).unwrap();
+        }
+        assert_eq!(encoder.row_count(), 2);
+        assert_eq!(encoder.finish().unwrap(), RowBufferEncoder::encode(&buffer));
+
+        let empty = RowStreamEncoder::new(Vec::new(), &buffer).unwrap();
+        buffer.rows.clear();
+        assert_eq!(empty.finish().unwrap(), RowBufferEncoder::encode(&buffer));
+    }
+
+    #[test]
+    fn test_row_stream_encoder_patches_after_existing_bytes() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("x".to_string(), OdbcType::Double);
+        buffer.add_row(vec![Some(1.5f64.to_le_bytes().to_vec())]);
+        let mut encoder = RowStreamEncoder::new(b"prefix".to_vec(), &buffer).unwrap();
+        encoder.write_rows(&buffer).unwrap();
+        let out = encoder.finish().unwrap();
+        assert_eq!(&out[..6], b"prefix");
+        assert_eq!(&out[6..], RowBufferEncoder::encode(&buffer).as_slice());
+    }
+
+    #[test]
+    fn test_row_stream_encoder_rejects_ragged_row() {
+        let mut buffer = RowBuffer::new();
+        buffer.add_column("a".to_string(), OdbcType::Varchar);
+        let mut encoder = RowStreamEncoder::new(Vec::new(), &buffer).unwrap();
+        let err = encoder.write_row(&[None, None]).unwrap_err();
+        assert!(matches!(
+            err,
+            EncodeError::RowWidthMismatch {
+                expected: 1,
+                actual: 2
+            }
+        ));
+    }
+
     #[test]
     fn test_encode_empty_buffer() {
         let buffer = RowBuffer::new();
@@ -466,4 +649,14 @@ mod tests {
     fn test_version_constant() {
         assert_eq!(VERSION, 1);
     }
+
+    #[test]
+    fn test_native_cells_header_version() {
+        let mut buffer = RowBuffer::with_cell_encoding(CellEncoding::Native);
+        buffer.add_column("d".to_string(), OdbcType::Double);
+        buffer.add_row(vec![Some(1.5f64.to_le_bytes().to_vec())]);
+        let encoded = RowBufferEncoder::encode(&buffer);
+        let version = u16::from_le_bytes([encoded[4], encoded[5]]);
+        assert_eq!(version, ProtocolVersion::v1_native().wire_version());
+    }
 }
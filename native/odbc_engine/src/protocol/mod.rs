@@ -8,10 +8,14 @@ pub mod converter;
 pub mod decoder;
 pub mod encoder;
 pub mod multi_result;
+pub mod native_cell;
+pub mod output_format;
 pub mod param_value;
 pub mod row_buffer;
 pub mod types;
 
+#[cfg(feature = "arrow-ipc")]
+pub mod arrow_stream;
 #[cfg(feature = "columnar-v2")]
 pub mod columnar_v2;
 
@@ -20,21 +24,25 @@ pub use bound_param::{
     deserialize_param_buffer, is_directed_param_buffer, BoundParam, ParamDirection, ParamList,
 };
 pub use bulk_insert::{
-    parse_bulk_insert_payload, serialize_bulk_insert_payload, BulkColumnData, BulkColumnSpec,
-    BulkColumnType, BulkInsertPayload, BulkTimestamp,
+    format_uuid, parse_bulk_insert_payload, parse_bulk_rejected, serialize_bulk_insert_payload,
+    serialize_bulk_rejected, BulkColumnData, BulkColumnSpec, BulkColumnType, BulkDate,
+    BulkInsertPayload, BulkRejectedRow, BulkTime, BulkTimestamp,
 };
 pub use columnar::{ColumnBlock, ColumnData, ColumnMetadata, CompressionType, RowBufferV2};
 pub use columnar_encoder::ColumnarEncoder;
 pub use compression::{compress, decompress};
 pub use converter::row_buffer_to_columnar;
 pub use decoder::{BinaryProtocolDecoder, ColumnInfo, DecodedResult};
-pub use encoder::RowBufferEncoder;
+pub use encoder::{PatchWrite, RowBufferEncoder, RowStreamEncoder};
 pub use multi_result::{
     decode_multi, encode_multi, MultiResultItem, MULTI_RESULT_MAGIC, MULTI_RESULT_VERSION,
 };
+pub use native_cell::CellEncoding;
+pub use output_format::OutputFormat;
 pub use param_value::{
     deserialize_params, has_null_param, max_param_string_len, param_count_exceeds_limit,
-    param_values_to_strings, serialize_params, ParamValue,
+    param_values_to_strings, serialize_params, uuid_to_string, ParamDate, ParamTime,
+    ParamTimestamp, ParamValue,
 };
 pub use row_buffer::RowBuffer;
 pub use types::OdbcType;
@@ -0,0 +1,471 @@
+//! Fixed-width binary cell encodings (protocol v1.1 row-major / v2.1 columnar).
+//!
+//! Legacy messages carry every non-integer value as UTF-8 text, which clients must re-parse
+//! (and whose shape depends on the driver's locale). Clients that negotiate
+//! [`ProtocolVersion::v1_native`] or [`ProtocolVersion::v2_native`] instead receive:
+//!
+//! | `OdbcType`                          | Width | Encoding                                          |
+//! | ----------------------------------- | ----- | ------------------------------------------------- |
+//! | `Double`                            | 8     | IEEE 754 `f64` LE                                 |
+//! | `Float`                             | 4     | IEEE 754 `f32` LE                                 |
+//! | `SmallInt`                          | 2     | `i16` LE                                          |
+//! | `Boolean`                           | 1     | `0` / `1`                                         |
+//! | `Date`                              | 4     | `i32` LE days since 1970-01-01                    |
+//! | `Timestamp`                         | 8     | `i64` LE microseconds since 1970-01-01T00:00:00   |
+//! | `Uuid`                              | 16    | RFC 4122 byte order                               |
+//! | `DatetimeOffset`, `TimestampWithTz` | 10    | `i64` LE UTC microseconds + `i16` LE offset (min) |
+//!
+//! A value the engine cannot parse keeps its UTF-8 text, exactly like unparseable integers do
+//! in v1; readers tell the two apart by the cell length. Sub-microsecond fractions are
+//! truncated.
+
+use crate::protocol::types::OdbcType;
+use crate::versioning::protocol_version::ProtocolVersion;
+use odbc_api::sys::SqlDataType;
+use odbc_api::DataType;
+
+/// `SQL_SS_TIMESTAMPOFFSET` (SQL Server `datetimeoffset`).
+const SQL_SS_TIMESTAMPOFFSET: i16 = -155;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+/// How result cells of non-text columns are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellEncoding {
+    /// v1.0 / v2.0: only `Integer` and `BigInt` are binary; everything else is UTF-8 text.
+    #[default]
+    Text,
+    /// v1.1 / v2.1: the fixed-width encodings listed in the module docs.
+    Native,
+}
+
+impl CellEncoding {
+    pub fn for_version(version: &ProtocolVersion) -> Self {
+        if version.has_native_cells() {
+            Self::Native
+        } else {
+            Self::Text
+        }
+    }
+
+    pub fn is_native(self) -> bool {
+        self == Self::Native
+    }
+}
+
+/// Decoded native cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NativeValue {
+    Double(f64),
+    Float(f32),
+    SmallInt(i16),
+    Boolean(bool),
+    /// Days since the Unix epoch.
+    Date(i32),
+    /// Microseconds since the Unix epoch (no time zone).
+    Timestamp(i64),
+    Uuid([u8; 16]),
+    /// UTC microseconds since the Unix epoch plus the original offset in minutes.
+    TimestampOffset {
+        utc_micros: i64,
+        offset_minutes: i16,
+    },
+}
+
+/// Byte width of the native encoding of `odbc_type`, or `None` when it stays text.
+pub fn native_width(odbc_type: OdbcType) -> Option<usize> {
+    match odbc_type {
+        OdbcType::Double => Some(8),
+        OdbcType::Float => Some(4),
+        OdbcType::SmallInt => Some(2),
+        OdbcType::Boolean => Some(1),
+        OdbcType::Date => Some(4),
+        OdbcType::Timestamp => Some(8),
+        OdbcType::Uuid => Some(16),
+        OdbcType::DatetimeOffset | OdbcType::TimestampWithTz => Some(10),
+        _ => None,
+    }
+}
+
+/// Column type to advertise under [`CellEncoding::Native`] for a driver-reported `data_type`,
+/// or `None` to keep the legacy mapping (which folds these types into `Varchar`/`Integer`).
+pub fn native_column_type(data_type: &DataType) -> Option<OdbcType> {
+    match data_type {
+        DataType::Double => Some(OdbcType::Double),
+        DataType::Real => Some(OdbcType::Float),
+        DataType::Float { precision } if *precision <= 24 => Some(OdbcType::Float),
+        DataType::Float { .. } => Some(OdbcType::Double),
+        DataType::SmallInt | DataType::TinyInt => Some(OdbcType::SmallInt),
+        DataType::Bit => Some(OdbcType::Boolean),
+        DataType::Date => Some(OdbcType::Date),
+        DataType::Timestamp { .. } => Some(OdbcType::Timestamp),
+        DataType::Other { data_type, .. } if *data_type == SqlDataType::EXT_GUID => {
+            Some(OdbcType::Uuid)
+        }
+        DataType::Other { data_type, .. } if data_type.0 == SQL_SS_TIMESTAMPOFFSET => {
+            Some(OdbcType::DatetimeOffset)
+        }
+        _ => None,
+    }
+}
+
+/// Native bytes for a driver-formatted text value, or `None` when `odbc_type` has no native
+/// encoding or `text` does not parse.
+pub fn encode_text(text: &str, odbc_type: OdbcType) -> Option<Vec<u8>> {
+    let text = text.trim();
+    let bytes = match odbc_type {
+        OdbcType::Double => text.parse::<f64>().ok()?.to_le_bytes().to_vec(),
+        OdbcType::Float => text.parse::<f32>().ok()?.to_le_bytes().to_vec(),
+        OdbcType::SmallInt => text.parse::<i16>().ok()?.to_le_bytes().to_vec(),
+        OdbcType::Boolean => vec![u8::from(parse_bool(text)?)],
+        OdbcType::Date => {
+            let (y, m, d) = parse_date(text)?;
+            i32::try_from(days_from_civil(y, m, d))
+                .ok()?
+                .to_le_bytes()
+                .to_vec()
+        }
+        OdbcType::Timestamp => parse_timestamp_micros(text)?.to_le_bytes().to_vec(),
+        OdbcType::Uuid => parse_uuid(text)?.to_vec(),
+        OdbcType::DatetimeOffset | OdbcType::TimestampWithTz => {
+            let (local, offset) = split_offset(text)?;
+            let local_micros = parse_timestamp_micros(local)?;
+            let utc = local_micros.checked_sub(i64::from(offset) * 60 * MICROS_PER_SECOND)?;
+            let mut out = Vec::with_capacity(10);
+            out.extend_from_slice(&utc.to_le_bytes());
+            out.extend_from_slice(&offset.to_le_bytes());
+            out
+        }
+        _ => return None,
+    };
+    Some(bytes)
+}
+
+/// Decodes a native cell; `None` when the length does not match (text fallback) or the type
+/// has no native encoding.
+pub fn decode(bytes: &[u8], odbc_type: OdbcType) -> Option<NativeValue> {
+    if native_width(odbc_type)? != bytes.len() {
+        return None;
+    }
+    let value = match odbc_type {
+        OdbcType::Double => NativeValue::Double(f64::from_le_bytes(bytes.try_into().ok()?)),
+        OdbcType::Float => NativeValue::Float(f32::from_le_bytes(bytes.try_into().ok()?)),
+        OdbcType::SmallInt => NativeValue::SmallInt(i16::from_le_bytes(bytes.try_into().ok()?)),
+        OdbcType::Boolean => NativeValue::Boolean(bytes[0] != 0),
+        OdbcType::Date => NativeValue::Date(i32::from_le_bytes(bytes.try_into().ok()?)),
+        OdbcType::Timestamp => NativeValue::Timestamp(i64::from_le_bytes(bytes.try_into().ok()?)),
+        OdbcType::Uuid => NativeValue::Uuid(bytes.try_into().ok()?),
+        OdbcType::DatetimeOffset | OdbcType::TimestampWithTz => NativeValue::TimestampOffset {
+            utc_micros: i64::from_le_bytes(bytes[..8].try_into().ok()?),
+            offset_minutes: i16::from_le_bytes(bytes[8..].try_into().ok()?),
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn parse_bool(text: &str) -> Option<bool> {
+    match text {
+        "1" => Some(true),
+        "0" => Some(false),
+        t if t.eq_ignore_ascii_case("true") => Some(true),
+        t if t.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DD` (year may carry a leading `-`).
+fn parse_date(text: &str) -> Option<(i64, u32, u32)> {
+    let (negative, rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let mut parts = rest.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some((if negative { -year } else { year }, month, day))
+}
+
+/// `HH:MM:SS[.fffffffff]` as microseconds since midnight.
+pub(crate) fn parse_time_micros(text: &str) -> Option<i64> {
+    let (hms, fraction) = match text.split_once('.') {
+        Some((hms, fraction)) => (hms, fraction),
+        None => (text, ""),
+    };
+    let mut parts = hms.splitn(3, ':');
+    let hour: i64 = parts.next()?.parse().ok()?;
+    let minute: i64 = parts.next()?.parse().ok()?;
+    let second: i64 = parts.next().unwrap_or("0").parse().ok()?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut micros = 0i64;
+    for (i, digit) in fraction.bytes().take(6).enumerate() {
+        micros += i64::from(digit - b'0') * 10i64.pow(5 - i as u32);
+    }
+    Some(((hour * 60 + minute) * 60 + second) * MICROS_PER_SECOND + micros)
+}
+
+/// `YYYY-MM-DD[ T]HH:MM:SS[.f…]` (or a bare date) as microseconds since the epoch.
+fn parse_timestamp_micros(text: &str) -> Option<i64> {
+    let (date, time) = match text.find([' ', 'T']) {
+        Some(pos) => (&text[..pos], text[pos + 1..].trim()),
+        None => (text, ""),
+    };
+    let (y, m, d) = parse_date(date)?;
+    let time_micros = if time.is_empty() {
+        0
+    } else {
+        parse_time_micros(time)?
+    };
+    days_from_civil(y, m, d)
+        .checked_mul(MICROS_PER_DAY)?
+        .checked_add(time_micros)
+}
+
+/// Splits `… +HH:MM` / `…-HH:MM` / `…Z` into the local timestamp and the offset in minutes.
+fn split_offset(text: &str) -> Option<(&str, i16)> {
+    if let Some(local) = text.strip_suffix('Z') {
+        return Some((local.trim_end(), 0));
+    }
+    let sign_pos = text.rfind(['+', '-'])?;
+    // The date's own hyphens sit before the time part; an offset sign must follow a ':'.
+    if !text[..sign_pos].contains(':') {
+        return None;
+    }
+    let sign = if text.as_bytes()[sign_pos] == b'-' {
+        -1
+    } else {
+        1
+    };
+    let (hours, minutes) = text[sign_pos + 1..].trim().split_once(':')?;
+    let hours: i16 = hours.parse().ok()?;
+    let minutes: i16 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    Some((text[..sign_pos].trim_end(), sign * (hours * 60 + minutes)))
+}
+
+fn parse_uuid(text: &str) -> Option<[u8; 16]> {
+    let text = text
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .unwrap_or(text);
+    let hex: Vec<u8> = text.bytes().filter(|&b| b != b'-').collect();
+    if hex.len() != 32 || text.len() - hex.len() > 4 {
+        return None;
+    }
+    let mut out = [0u8; 16];
+    for (i, pair) in hex.chunks(2).enumerate() {
+        let s = std::str::from_utf8(pair).ok()?;
+        out[i] = u8::from_str_radix(s, 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(text: &str, odbc_type: OdbcType) -> NativeValue {
+        let bytes = encode_text(text, odbc_type).expect("encodes");
+        assert_eq!(Some(bytes.len()), native_width(odbc_type));
+        decode(&bytes, odbc_type).expect("decodes")
+    }
+
+    #[test]
+    fn test_encoding_follows_protocol_version() {
+        assert_eq!(
+            CellEncoding::for_version(&ProtocolVersion::v1()),
+            CellEncoding::Text
+        );
+        assert_eq!(
+            CellEncoding::for_version(&ProtocolVersion::v1_native()),
+            CellEncoding::Native
+        );
+        assert!(CellEncoding::for_version(&ProtocolVersion::v2_native()).is_native());
+    }
+
+    #[test]
+    fn test_numeric_and_boolean_cells() {
+        assert_eq!(
+            roundtrip("-1.5E+10", OdbcType::Double),
+            NativeValue::Double(-1.5e10)
+        );
+        assert_eq!(roundtrip("0.25", OdbcType::Float), NativeValue::Float(0.25));
+        assert_eq!(
+            roundtrip(" -32768 ", OdbcType::SmallInt),
+            NativeValue::SmallInt(i16::MIN)
+        );
+        assert_eq!(
+            roundtrip("1", OdbcType::Boolean),
+            NativeValue::Boolean(true)
+        );
+        assert_eq!(
+            roundtrip("FALSE", OdbcType::Boolean),
+            NativeValue::Boolean(false)
+        );
+    }
+
+    #[test]
+    fn test_date_cells_are_days_since_epoch() {
+        assert_eq!(
+            roundtrip("1970-01-01", OdbcType::Date),
+            NativeValue::Date(0)
+        );
+        assert_eq!(
+            roundtrip("2000-03-01", OdbcType::Date),
+            NativeValue::Date(11_017)
+        );
+        assert_eq!(
+            roundtrip("1969-12-31", OdbcType::Date),
+            NativeValue::Date(-1)
+        );
+        assert_eq!(
+            roundtrip("0001-01-01", OdbcType::Date),
+            NativeValue::Date(-719_162)
+        );
+    }
+
+    #[test]
+    fn test_timestamp_cells_are_micros_since_epoch() {
+        assert_eq!(
+            roundtrip("1970-01-01 00:00:01.5", OdbcType::Timestamp),
+            NativeValue::Timestamp(1_500_000)
+        );
+        // 100 ns digit is truncated.
+        assert_eq!(
+            roundtrip("2024-02-29T12:34:56.1234567", OdbcType::Timestamp),
+            NativeValue::Timestamp(1_709_210_096_123_456)
+        );
+        assert_eq!(
+            roundtrip("9999-12-31 23:59:59", OdbcType::Timestamp),
+            NativeValue::Timestamp(253_402_300_799_000_000)
+        );
+    }
+
+    #[test]
+    fn test_offset_timestamp_is_normalised_to_utc() {
+        assert_eq!(
+            roundtrip(
+                "2024-01-01 12:00:00.0000000 +02:00",
+                OdbcType::DatetimeOffset
+            ),
+            NativeValue::TimestampOffset {
+                utc_micros: 1_704_103_200_000_000,
+                offset_minutes: 120
+            }
+        );
+        assert_eq!(
+            roundtrip("2024-01-01 12:00:00-03:30", OdbcType::TimestampWithTz),
+            NativeValue::TimestampOffset {
+                utc_micros: 1_704_123_000_000_000,
+                offset_minutes: -210
+            }
+        );
+        assert!(encode_text("2024-01-01 12:00:00", OdbcType::DatetimeOffset).is_none());
+    }
+
+    #[test]
+    fn test_uuid_cells_use_rfc4122_order() {
+        let expected = [
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+            0xcd, 0xef,
+        ];
+        assert_eq!(
+            roundtrip("12345678-9ABC-DEF0-0123-456789ABCDEF", OdbcType::Uuid),
+            NativeValue::Uuid(expected)
+        );
+        assert_eq!(
+            roundtrip("{12345678-9abc-def0-0123-456789abcdef}", OdbcType::Uuid),
+            NativeValue::Uuid(expected)
+        );
+        assert!(encode_text("not-a-uuid", OdbcType::Uuid).is_none());
+    }
+
+    #[test]
+    fn test_unparseable_or_text_types_are_rejected() {
+        assert!(encode_text("abc", OdbcType::Double).is_none());
+        assert!(encode_text("2024-13-01", OdbcType::Date).is_none());
+        assert!(encode_text("x", OdbcType::Varchar).is_none());
+        assert!(decode(b"1.5", OdbcType::Double).is_none());
+        assert!(decode(&[0; 8], OdbcType::Decimal).is_none());
+    }
+
+    #[test]
+    fn test_civil_days_roundtrip() {
+        for days in [-719_162, -1, 0, 59, 11_016, 2_932_896] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_native_column_type_mapping() {
+        assert_eq!(
+            native_column_type(&DataType::Double),
+            Some(OdbcType::Double)
+        );
+        assert_eq!(
+            native_column_type(&DataType::Float { precision: 53 }),
+            Some(OdbcType::Double)
+        );
+        assert_eq!(native_column_type(&DataType::Real), Some(OdbcType::Float));
+        assert_eq!(
+            native_column_type(&DataType::TinyInt),
+            Some(OdbcType::SmallInt)
+        );
+        assert_eq!(native_column_type(&DataType::Bit), Some(OdbcType::Boolean));
+        assert_eq!(
+            native_column_type(&DataType::Other {
+                data_type: SqlDataType::EXT_GUID,
+                column_size: None,
+                decimal_digits: 0
+            }),
+            Some(OdbcType::Uuid)
+        );
+        assert_eq!(
+            native_column_type(&DataType::Other {
+                data_type: SqlDataType(SQL_SS_TIMESTAMPOFFSET),
+                column_size: None,
+                decimal_digits: 7
+            }),
+            Some(OdbcType::DatetimeOffset)
+        );
+        assert_eq!(native_column_type(&DataType::Integer), None);
+    }
+}
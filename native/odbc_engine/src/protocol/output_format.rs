@@ -0,0 +1,68 @@
+//! Result output formats selectable per engine / stream.
+
+use crate::error::{OdbcError, Result};
+
+/// Byte format of encoded query results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum OutputFormat {
+    /// The engine's own binary protocol (row-major v1 or columnar v2, per
+    /// the negotiated [`crate::versioning::protocol_version::ProtocolVersion`]).
+    #[default]
+    OdbcBinary = 0,
+    /// Apache Arrow IPC stream: one schema message, then record batches. See
+    /// `protocol::arrow_stream` (feature `arrow-ipc`).
+    ArrowIpc = 1,
+}
+
+impl OutputFormat {
+    /// Decodes the FFI code (`0` = ODBC binary, `1` = Arrow IPC).
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            0 => Some(Self::OdbcBinary),
+            1 => Some(Self::ArrowIpc),
+            _ => None,
+        }
+    }
+
+    /// True when this build can produce the format.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::OdbcBinary => true,
+            Self::ArrowIpc => cfg!(feature = "arrow-ipc"),
+        }
+    }
+
+    /// `Ok(self)` when [`Self::is_available`], else `UnsupportedFeature`.
+    pub fn ensure_available(self) -> Result<Self> {
+        if self.is_available() {
+            Ok(self)
+        } else {
+            Err(OdbcError::UnsupportedFeature(
+                "Arrow IPC output requires the `arrow-ipc` feature".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code_round_trips() {
+        for format in [OutputFormat::OdbcBinary, OutputFormat::ArrowIpc] {
+            assert_eq!(OutputFormat::from_code(format as u32), Some(format));
+        }
+        assert_eq!(OutputFormat::from_code(2), None);
+    }
+
+    #[test]
+    fn test_arrow_availability_follows_feature() {
+        assert!(OutputFormat::OdbcBinary.ensure_available().is_ok());
+        assert_eq!(
+            OutputFormat::ArrowIpc.ensure_available().is_ok(),
+            cfg!(feature = "arrow-ipc")
+        );
+    }
+}
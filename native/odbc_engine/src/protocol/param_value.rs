@@ -9,9 +9,50 @@ const TAG_BINARY: u8 = 5;
 /// Placeholder for Oracle `SYS_REFCURSOR` / similar `OUT` parameters (wire
 /// tag only; engine bind is engine-specific — see `TYPE_MAPPING` §3.1.1).
 const TAG_REF_CURSOR_OUT: u8 = 6;
+const TAG_BOOLEAN: u8 = 7;
+const TAG_DOUBLE: u8 = 8;
+const TAG_DATE: u8 = 9;
+const TAG_TIME: u8 = 10;
+const TAG_TIMESTAMP: u8 = 11;
+const TAG_UUID: u8 = 12;
+const TAG_JSON: u8 = 13;
+
+const DATE_PAYLOAD_LEN: usize = 4;
+const TIME_PAYLOAD_LEN: usize = 7;
+const TIMESTAMP_PAYLOAD_LEN: usize = DATE_PAYLOAD_LEN + TIME_PAYLOAD_LEN;
+const TIMESTAMP_TZ_PAYLOAD_LEN: usize = TIMESTAMP_PAYLOAD_LEN + 2;
+/// Widest UTC offset accepted for [`ParamTimestamp::offset_minutes`] (±14:00).
+const MAX_OFFSET_MINUTES: i16 = 14 * 60;
 pub const MAX_PARAM_COUNT: usize = 4096;
 pub const MAX_PARAM_VALUE_PAYLOAD_LEN: usize = 16 * 1024 * 1024;
 
+/// Calendar date carried by [`ParamValue::Date`] (wire: `i16` year LE, `u8` month, `u8` day).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamDate {
+    pub year: i16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Time of day carried by [`ParamValue::Time`] (wire: `u8` hour, minute, second + `u32`
+/// nanoseconds LE).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamTime {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanos: u32,
+}
+
+/// Timestamp carried by [`ParamValue::Timestamp`]: date + time (11 bytes), optionally followed
+/// by the UTC offset in minutes as `i16` LE (13 bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamTimestamp {
+    pub date: ParamDate,
+    pub time: ParamTime,
+    pub offset_minutes: Option<i16>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParamValue {
     String(String),
@@ -22,6 +63,128 @@ pub enum ParamValue {
     Null,
     /// Output-only marker: materialized rows may follow in an `RC1\0` trailer.
     RefCursorOut,
+    Boolean(bool),
+    Double(f64),
+    Date(ParamDate),
+    Time(ParamTime),
+    Timestamp(ParamTimestamp),
+    /// RFC 4122 byte order (the order of the hyphenated text form).
+    Uuid([u8; 16]),
+    /// JSON document text (UTF-8); bound as character data.
+    Json(String),
+}
+
+impl ParamDate {
+    fn validate(&self) -> Result<()> {
+        if !(1..=12).contains(&self.month) || !(1..=31).contains(&self.day) {
+            return Err(OdbcError::ValidationError(format!(
+                "ParamValue::Date out of range: {:04}-{:02}-{:02}",
+                self.year, self.month, self.day
+            )));
+        }
+        Ok(())
+    }
+
+    /// `YYYY-MM-DD`.
+    pub fn to_iso_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl ParamTime {
+    fn validate(&self) -> Result<()> {
+        if self.hour > 23 || self.minute > 59 || self.second > 59 || self.nanos >= 1_000_000_000 {
+            return Err(OdbcError::ValidationError(format!(
+                "ParamValue::Time out of range: {:02}:{:02}:{:02}.{:09}",
+                self.hour, self.minute, self.second, self.nanos
+            )));
+        }
+        Ok(())
+    }
+
+    /// `HH:MM:SS` with the fractional part appended only when non-zero (trailing zeros trimmed).
+    pub fn to_iso_string(&self) -> String {
+        let mut out = format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second);
+        if self.nanos != 0 {
+            let frac = format!("{:09}", self.nanos);
+            out.push('.');
+            out.push_str(frac.trim_end_matches('0'));
+        }
+        out
+    }
+}
+
+impl ParamTimestamp {
+    fn validate(&self) -> Result<()> {
+        self.date.validate()?;
+        self.time.validate()?;
+        if let Some(offset) = self.offset_minutes {
+            if !(-MAX_OFFSET_MINUTES..=MAX_OFFSET_MINUTES).contains(&offset) {
+                return Err(OdbcError::ValidationError(format!(
+                    "ParamValue::Timestamp offset {} minutes exceeds ±{}",
+                    offset, MAX_OFFSET_MINUTES
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// `YYYY-MM-DD HH:MM:SS[.fffffffff]` plus ` +HH:MM` when an offset is present.
+    pub fn to_iso_string(&self) -> String {
+        let mut out = format!(
+            "{} {}",
+            self.date.to_iso_string(),
+            self.time.to_iso_string()
+        );
+        if let Some(offset) = self.offset_minutes {
+            let sign = if offset < 0 { '-' } else { '+' };
+            let abs = offset.unsigned_abs();
+            out.push_str(&format!(" {}{:02}:{:02}", sign, abs / 60, abs % 60));
+        }
+        out
+    }
+}
+
+/// Lower-case hyphenated RFC 4122 text form.
+pub fn uuid_to_string(bytes: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(36);
+    for (i, b) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+fn encode_date(out: &mut Vec<u8>, d: &ParamDate) {
+    out.extend_from_slice(&d.year.to_le_bytes());
+    out.push(d.month);
+    out.push(d.day);
+}
+
+fn encode_time(out: &mut Vec<u8>, t: &ParamTime) {
+    out.push(t.hour);
+    out.push(t.minute);
+    out.push(t.second);
+    out.extend_from_slice(&t.nanos.to_le_bytes());
+}
+
+fn decode_date(p: &[u8]) -> ParamDate {
+    ParamDate {
+        year: i16::from_le_bytes([p[0], p[1]]),
+        month: p[2],
+        day: p[3],
+    }
+}
+
+fn decode_time(p: &[u8]) -> ParamTime {
+    ParamTime {
+        hour: p[0],
+        minute: p[1],
+        second: p[2],
+        nanos: u32::from_le_bytes([p[3], p[4], p[5], p[6]]),
+    }
 }
 
 impl ParamValue {
@@ -71,6 +234,55 @@ impl ParamValue {
                 out.push(TAG_REF_CURSOR_OUT);
                 out.extend_from_slice(&0u32.to_le_bytes());
             }
+            ParamValue::Boolean(b) => {
+                out.push(TAG_BOOLEAN);
+                out.extend_from_slice(&1u32.to_le_bytes());
+                out.push(u8::from(*b));
+            }
+            ParamValue::Double(v) => {
+                out.push(TAG_DOUBLE);
+                out.extend_from_slice(&8u32.to_le_bytes());
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+            ParamValue::Date(d) => {
+                d.validate()?;
+                out.push(TAG_DATE);
+                out.extend_from_slice(&(DATE_PAYLOAD_LEN as u32).to_le_bytes());
+                encode_date(&mut out, d);
+            }
+            ParamValue::Time(t) => {
+                t.validate()?;
+                out.push(TAG_TIME);
+                out.extend_from_slice(&(TIME_PAYLOAD_LEN as u32).to_le_bytes());
+                encode_time(&mut out, t);
+            }
+            ParamValue::Timestamp(ts) => {
+                ts.validate()?;
+                out.push(TAG_TIMESTAMP);
+                let len = if ts.offset_minutes.is_some() {
+                    TIMESTAMP_TZ_PAYLOAD_LEN
+                } else {
+                    TIMESTAMP_PAYLOAD_LEN
+                };
+                out.extend_from_slice(&(len as u32).to_le_bytes());
+                encode_date(&mut out, &ts.date);
+                encode_time(&mut out, &ts.time);
+                if let Some(offset) = ts.offset_minutes {
+                    out.extend_from_slice(&offset.to_le_bytes());
+                }
+            }
+            ParamValue::Uuid(u) => {
+                out.push(TAG_UUID);
+                out.extend_from_slice(&16u32.to_le_bytes());
+                out.extend_from_slice(u);
+            }
+            ParamValue::Json(s) => {
+                out.push(TAG_JSON);
+                let b = s.as_bytes();
+                let len = checked_payload_len(b.len(), "ParamValue::Json")?;
+                out.extend_from_slice(&len.to_le_bytes());
+                out.extend_from_slice(b);
+            }
         }
         Ok(out)
     }
@@ -145,6 +357,74 @@ impl ParamValue {
                 }
                 ParamValue::RefCursorOut
             }
+            TAG_BOOLEAN => match payload {
+                [0] => ParamValue::Boolean(false),
+                [1] => ParamValue::Boolean(true),
+                _ => {
+                    return Err(OdbcError::ValidationError(
+                        "ParamValue::Boolean expected a single 0/1 byte".to_string(),
+                    ))
+                }
+            },
+            TAG_DOUBLE => {
+                let bytes: [u8; 8] = payload.try_into().map_err(|_| {
+                    OdbcError::ValidationError("ParamValue::Double expected 8 bytes".to_string())
+                })?;
+                ParamValue::Double(f64::from_le_bytes(bytes))
+            }
+            TAG_DATE => {
+                if len != DATE_PAYLOAD_LEN {
+                    return Err(OdbcError::ValidationError(
+                        "ParamValue::Date expected 4 bytes".to_string(),
+                    ));
+                }
+                let d = decode_date(payload);
+                d.validate()?;
+                ParamValue::Date(d)
+            }
+            TAG_TIME => {
+                if len != TIME_PAYLOAD_LEN {
+                    return Err(OdbcError::ValidationError(
+                        "ParamValue::Time expected 7 bytes".to_string(),
+                    ));
+                }
+                let t = decode_time(payload);
+                t.validate()?;
+                ParamValue::Time(t)
+            }
+            TAG_TIMESTAMP => {
+                let offset_minutes = match len {
+                    TIMESTAMP_PAYLOAD_LEN => None,
+                    TIMESTAMP_TZ_PAYLOAD_LEN => Some(i16::from_le_bytes([
+                        payload[TIMESTAMP_PAYLOAD_LEN],
+                        payload[TIMESTAMP_PAYLOAD_LEN + 1],
+                    ])),
+                    _ => {
+                        return Err(OdbcError::ValidationError(
+                            "ParamValue::Timestamp expected 11 or 13 bytes".to_string(),
+                        ))
+                    }
+                };
+                let ts = ParamTimestamp {
+                    date: decode_date(&payload[..DATE_PAYLOAD_LEN]),
+                    time: decode_time(&payload[DATE_PAYLOAD_LEN..TIMESTAMP_PAYLOAD_LEN]),
+                    offset_minutes,
+                };
+                ts.validate()?;
+                ParamValue::Timestamp(ts)
+            }
+            TAG_UUID => {
+                let bytes: [u8; 16] = payload.try_into().map_err(|_| {
+                    OdbcError::ValidationError("ParamValue::Uuid expected 16 bytes".to_string())
+                })?;
+                ParamValue::Uuid(bytes)
+            }
+            TAG_JSON => {
+                let s = std::str::from_utf8(payload).map_err(|_| {
+                    OdbcError::ValidationError("Invalid UTF-8 in ParamValue::Json".to_string())
+                })?;
+                ParamValue::Json(s.to_string())
+            }
             _ => {
                 return Err(OdbcError::ValidationError(format!(
                     "Unknown ParamValue tag: {}",
@@ -212,6 +492,13 @@ pub fn param_values_to_strings(params: &[ParamValue]) -> Result<Vec<Option<Strin
                     "ParamValue::RefCursorOut is not convertible to string parameters".to_string(),
                 ));
             }
+            ParamValue::Boolean(b) => out.push(Some(if *b { "1" } else { "0" }.to_string())),
+            ParamValue::Double(v) => out.push(Some(v.to_string())),
+            ParamValue::Date(d) => out.push(Some(d.to_iso_string())),
+            ParamValue::Time(t) => out.push(Some(t.to_iso_string())),
+            ParamValue::Timestamp(ts) => out.push(Some(ts.to_iso_string())),
+            ParamValue::Uuid(u) => out.push(Some(uuid_to_string(u))),
+            ParamValue::Json(s) => out.push(Some(s.clone())),
         }
     }
     Ok(out)
@@ -228,6 +515,8 @@ pub fn max_param_string_len(params: &[ParamValue]) -> usize {
             ParamValue::String(s) => s.len(),
             ParamValue::Decimal(s) => s.len(),
             ParamValue::Binary(b) => b.len() * 2,
+            ParamValue::Json(s) => s.len(),
+            ParamValue::Uuid(_) => 36,
             ParamValue::RefCursorOut => 0,
             _ => 0,
         };
@@ -320,6 +609,181 @@ mod tests {
         assert_eq!(n, enc.len());
     }
 
+    fn sample_date() -> ParamDate {
+        ParamDate {
+            year: 2024,
+            month: 2,
+            day: 29,
+        }
+    }
+
+    fn sample_time(nanos: u32) -> ParamTime {
+        ParamTime {
+            hour: 23,
+            minute: 59,
+            second: 58,
+            nanos,
+        }
+    }
+
+    fn assert_roundtrip(p: ParamValue) {
+        let enc = p.serialize();
+        let (dec, n) = ParamValue::deserialize(&enc).unwrap();
+        assert_eq!(dec, p);
+        assert_eq!(n, enc.len());
+    }
+
+    #[test]
+    fn test_param_value_boolean_roundtrip() {
+        assert_eq!(
+            ParamValue::Boolean(true).serialize(),
+            vec![TAG_BOOLEAN, 1, 0, 0, 0, 1]
+        );
+        assert_roundtrip(ParamValue::Boolean(true));
+        assert_roundtrip(ParamValue::Boolean(false));
+    }
+
+    #[test]
+    fn test_param_value_double_roundtrip() {
+        assert_roundtrip(ParamValue::Double(-1234.5e-3));
+        assert_roundtrip(ParamValue::Double(f64::MAX));
+    }
+
+    #[test]
+    fn test_param_value_date_roundtrip() {
+        let enc = ParamValue::Date(sample_date()).serialize();
+        assert_eq!(enc, vec![TAG_DATE, 4, 0, 0, 0, 0xe8, 0x07, 2, 29]);
+        assert_roundtrip(ParamValue::Date(sample_date()));
+    }
+
+    #[test]
+    fn test_param_value_time_roundtrip() {
+        assert_roundtrip(ParamValue::Time(sample_time(0)));
+        assert_roundtrip(ParamValue::Time(sample_time(999_999_999)));
+    }
+
+    #[test]
+    fn test_param_value_timestamp_roundtrip() {
+        let naive = ParamTimestamp {
+            date: sample_date(),
+            time: sample_time(123_456_789),
+            offset_minutes: None,
+        };
+        let enc = ParamValue::Timestamp(naive).serialize();
+        assert_eq!(enc.len(), 5 + TIMESTAMP_PAYLOAD_LEN);
+        assert_roundtrip(ParamValue::Timestamp(naive));
+
+        let zoned = ParamTimestamp {
+            offset_minutes: Some(-330),
+            ..naive
+        };
+        let enc = ParamValue::Timestamp(zoned).serialize();
+        assert_eq!(enc.len(), 5 + TIMESTAMP_TZ_PAYLOAD_LEN);
+        assert_roundtrip(ParamValue::Timestamp(zoned));
+    }
+
+    #[test]
+    fn test_param_value_uuid_and_json_roundtrip() {
+        assert_roundtrip(ParamValue::Uuid([0xab; 16]));
+        assert_roundtrip(ParamValue::Json(r#"{"a":[1,2]}"#.to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_rejects_out_of_range_date() {
+        let data = vec![TAG_DATE, 4, 0, 0, 0, 0xe8, 0x07, 13, 1];
+        let msg = ParamValue::deserialize(&data).unwrap_err().to_string();
+        assert!(msg.contains("ParamValue::Date out of range"), "{msg}");
+    }
+
+    #[test]
+    fn test_deserialize_rejects_bad_time_and_offset() {
+        let mut time = vec![TAG_TIME, 7, 0, 0, 0, 24, 0, 0];
+        time.extend_from_slice(&0u32.to_le_bytes());
+        assert!(ParamValue::deserialize(&time).is_err());
+
+        let mut ts = ParamValue::Timestamp(ParamTimestamp {
+            date: sample_date(),
+            time: sample_time(0),
+            offset_minutes: Some(0),
+        })
+        .serialize();
+        let n = ts.len();
+        ts[n - 2..].copy_from_slice(&900i16.to_le_bytes());
+        let msg = ParamValue::deserialize(&ts).unwrap_err().to_string();
+        assert!(msg.contains("offset"), "{msg}");
+    }
+
+    #[test]
+    fn test_deserialize_rejects_typed_wrong_length() {
+        assert!(ParamValue::deserialize(&[TAG_BOOLEAN, 1, 0, 0, 0, 2]).is_err());
+        assert!(ParamValue::deserialize(&[TAG_DOUBLE, 4, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        assert!(ParamValue::deserialize(&[TAG_UUID, 1, 0, 0, 0, 0]).is_err());
+        let mut ts = vec![TAG_TIMESTAMP, 12, 0, 0, 0];
+        ts.extend_from_slice(&[0u8; 12]);
+        assert!(ParamValue::deserialize(&ts).is_err());
+    }
+
+    #[test]
+    fn test_try_serialize_rejects_invalid_time() {
+        let r = ParamValue::Time(ParamTime {
+            hour: 1,
+            minute: 60,
+            second: 0,
+            nanos: 0,
+        })
+        .try_serialize();
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_legacy_tags_decode_unchanged() {
+        let mut data = vec![TAG_INTEGER, 4, 0, 0, 0];
+        data.extend_from_slice(&7i32.to_le_bytes());
+        data.extend_from_slice(&[TAG_STRING, 2, 0, 0, 0, b'h', b'i']);
+        data.extend_from_slice(&[TAG_NULL, 0, 0, 0, 0]);
+        data.extend_from_slice(&[TAG_BINARY, 1, 0, 0, 0, 0xfe]);
+        assert_eq!(
+            deserialize_params(&data).unwrap(),
+            vec![
+                ParamValue::Integer(7),
+                ParamValue::String("hi".to_string()),
+                ParamValue::Null,
+                ParamValue::Binary(vec![0xfe]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_param_values_to_strings_typed() {
+        let params = vec![
+            ParamValue::Boolean(true),
+            ParamValue::Date(sample_date()),
+            ParamValue::Time(sample_time(500_000_000)),
+            ParamValue::Timestamp(ParamTimestamp {
+                date: sample_date(),
+                time: sample_time(0),
+                offset_minutes: Some(-90),
+            }),
+            ParamValue::Uuid([
+                0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+                0xcd, 0xef,
+            ]),
+            ParamValue::Json("{}".to_string()),
+        ];
+        let out = param_values_to_strings(&params).unwrap();
+        assert_eq!(
+            out,
+            vec![
+                Some("1".to_string()),
+                Some("2024-02-29".to_string()),
+                Some("23:59:58.5".to_string()),
+                Some("2024-02-29 23:59:58 -01:30".to_string()),
+                Some("12345678-9abc-def0-0123-456789abcdef".to_string()),
+                Some("{}".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_deserialize_params_empty() {
         let out = deserialize_params(&[]).unwrap();
@@ -1,13 +1,20 @@
+use crate::protocol::native_cell::CellEncoding;
 use crate::protocol::types::OdbcType;
+use odbc_api::DataType;
 
 pub struct ColumnMetadata {
     pub name: String,
     pub odbc_type: OdbcType,
+    /// Driver-reported SQL type, when the column came from a cursor. Carries
+    /// the precision/scale that `odbc_type` alone loses.
+    pub sql_type: Option<DataType>,
 }
 
 pub struct RowBuffer {
     pub columns: Vec<ColumnMetadata>,
     pub rows: Vec<Vec<Option<Vec<u8>>>>,
+    /// Encoding of the cells in `rows`; selects the header version written by the encoders.
+    pub cell_encoding: CellEncoding,
 }
 
 impl RowBuffer {
@@ -15,11 +22,32 @@ impl RowBuffer {
         Self {
             columns: Vec::new(),
             rows: Vec::new(),
+            cell_encoding: CellEncoding::Text,
+        }
+    }
+
+    pub fn with_cell_encoding(cell_encoding: CellEncoding) -> Self {
+        Self {
+            cell_encoding,
+            ..Self::new()
         }
     }
 
     pub fn add_column(&mut self, name: String, odbc_type: OdbcType) {
-        self.columns.push(ColumnMetadata { name, odbc_type });
+        self.columns.push(ColumnMetadata {
+            name,
+            odbc_type,
+            sql_type: None,
+        });
+    }
+
+    /// Like [`Self::add_column`], keeping the cursor's SQL type alongside.
+    pub fn add_described_column(&mut self, name: String, odbc_type: OdbcType, sql_type: DataType) {
+        self.columns.push(ColumnMetadata {
+            name,
+            odbc_type,
+            sql_type: Some(sql_type),
+        });
     }
 
     pub fn add_row(&mut self, row: Vec<Option<Vec<u8>>>) {
@@ -2,6 +2,13 @@
 
 use std::fmt;
 
+/// Minor revision that introduced native binary cells.
+pub const NATIVE_CELLS_MINOR: u16 = 1;
+
+/// Columnar minor revision with validity bitmaps and fixed-width value arrays (includes
+/// native cells).
+pub const COLUMNAR_ARRAYS_MINOR: u16 = 2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion {
     pub major: u16,
@@ -21,10 +28,64 @@ impl ProtocolVersion {
         Self::new(2, 0)
     }
 
+    /// Row-major v1 with fixed-width binary cells (see [`crate::protocol::native_cell`]).
+    pub fn v1_native() -> Self {
+        Self::new(1, NATIVE_CELLS_MINOR)
+    }
+
+    /// Columnar v2 with fixed-width binary cells (see [`crate::protocol::native_cell`]).
+    pub fn v2_native() -> Self {
+        Self::new(2, NATIVE_CELLS_MINOR)
+    }
+
+    /// Columnar v2 with validity bitmaps and fixed-width arrays (see
+    /// [`crate::protocol::columnar_v2`], feature `columnar-v2`).
+    pub fn v2_arrays() -> Self {
+        Self::new(2, COLUMNAR_ARRAYS_MINOR)
+    }
+
     pub fn current() -> Self {
         Self::v2()
     }
 
+    /// True from the first minor revision that encodes float, boolean, temporal and UUID
+    /// cells natively. Clients opt in by negotiating such a version; `x.0` stays text.
+    pub fn has_native_cells(&self) -> bool {
+        self.minor >= NATIVE_CELLS_MINOR
+    }
+
+    /// Value of the `u16` version field in message headers. `x.0` keeps the historical bare
+    /// major (`1`, `2`) so existing decoders are unaffected; later minors go in the high byte,
+    /// which those decoders reject instead of misreading the cells.
+    pub fn wire_version(&self) -> u16 {
+        if self.minor == 0 {
+            self.major
+        } else {
+            (self.minor << 8) | (self.major & 0xff)
+        }
+    }
+
+    /// True for the columnar layout with validity bitmaps (`2.2`).
+    pub fn has_columnar_arrays(&self) -> bool {
+        self.major == 2 && self.minor >= COLUMNAR_ARRAYS_MINOR
+    }
+
+    /// Inverse of [`Self::wire_version`].
+    pub fn from_wire_version(wire: u16) -> Self {
+        Self::new(wire & 0xff, wire >> 8)
+    }
+
+    /// Versions this engine can emit: `1.0`, `1.1`, `2.0`, `2.1`, plus `2.2` when built with
+    /// the `columnar-v2` feature.
+    pub fn is_supported(&self) -> bool {
+        match self.major {
+            1 => self.minor <= NATIVE_CELLS_MINOR,
+            2 if cfg!(feature = "columnar-v2") => self.minor <= COLUMNAR_ARRAYS_MINOR,
+            2 => self.minor <= NATIVE_CELLS_MINOR,
+            _ => false,
+        }
+    }
+
     pub fn is_compatible_with(&self, other: &ProtocolVersion) -> bool {
         self.major == other.major && self.minor >= other.minor
     }
@@ -71,6 +132,52 @@ mod tests {
         assert_eq!(v2.minor, 0);
     }
 
+    #[test]
+    fn test_native_versions() {
+        assert!(!ProtocolVersion::v1().has_native_cells());
+        assert!(!ProtocolVersion::v2().has_native_cells());
+        assert!(ProtocolVersion::v1_native().has_native_cells());
+        assert!(ProtocolVersion::v2_native().has_native_cells());
+        assert!(ProtocolVersion::v2_native().is_compatible_with(&ProtocolVersion::v2()));
+    }
+
+    #[test]
+    fn test_wire_version_keeps_legacy_values() {
+        assert_eq!(ProtocolVersion::v1().wire_version(), 1);
+        assert_eq!(ProtocolVersion::v2().wire_version(), 2);
+        assert_eq!(ProtocolVersion::v1_native().wire_version(), 0x0101);
+        assert_eq!(ProtocolVersion::v2_native().wire_version(), 0x0102);
+        for v in [
+            ProtocolVersion::v1(),
+            ProtocolVersion::v2(),
+            ProtocolVersion::v1_native(),
+            ProtocolVersion::v2_native(),
+        ] {
+            assert_eq!(ProtocolVersion::from_wire_version(v.wire_version()), v);
+        }
+    }
+
+    #[test]
+    fn test_is_supported() {
+        assert!(ProtocolVersion::v1_native().is_supported());
+        assert!(!ProtocolVersion::new(1, 2).is_supported());
+        assert!(!ProtocolVersion::new(3, 0).is_supported());
+        assert_eq!(
+            ProtocolVersion::v2_arrays().is_supported(),
+            cfg!(feature = "columnar-v2")
+        );
+    }
+
+    #[test]
+    fn test_columnar_arrays_version() {
+        let v = ProtocolVersion::v2_arrays();
+        assert!(v.has_columnar_arrays());
+        assert!(v.has_native_cells());
+        assert!(!ProtocolVersion::v2_native().has_columnar_arrays());
+        assert!(!ProtocolVersion::new(1, 2).has_columnar_arrays());
+        assert_eq!(v.wire_version(), 0x0202);
+    }
+
     #[test]
     fn test_current_is_v2() {
         assert_eq!(ProtocolVersion::current(), ProtocolVersion::v2());
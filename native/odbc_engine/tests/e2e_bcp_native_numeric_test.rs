@@ -2,26 +2,26 @@
 ///
 /// Requires:
 /// - `sqlserver-bcp` feature
-/// - Windows
+/// - Windows, or Linux with msodbcsql17/18 installed
 /// - ENABLE_E2E_TESTS=1
 /// - SQL Server DSN in env
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 use odbc_api::Connection;
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 use odbc_engine::{
     engine::core::sqlserver_bcp,
     execute_query_with_connection,
     protocol::{BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload},
     BinaryProtocolDecoder, OdbcConnection, OdbcEnvironment,
 };
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 use serial_test::serial;
 
 mod helpers;
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 use helpers::e2e::{get_connection_and_db_type, should_run_e2e_tests, DatabaseType};
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 fn decode_integer(data: &[u8]) -> i64 {
     if data.len() >= 8 {
         i64::from_le_bytes([
@@ -37,21 +37,21 @@ fn decode_integer(data: &[u8]) -> i64 {
     }
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 fn execute_command(conn: &Connection<'static>, sql: &str) -> Result<(), odbc_engine::OdbcError> {
     let mut stmt = conn.prepare(sql).map_err(odbc_engine::OdbcError::from)?;
     stmt.execute(()).map_err(odbc_engine::OdbcError::from)?;
     Ok(())
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 fn query_single_i64(conn: &Connection<'static>, sql: &str) -> i64 {
     let buf = execute_query_with_connection(conn, sql).expect("query failed");
     let decoded = BinaryProtocolDecoder::parse(&buf).expect("decode failed");
     decode_integer(decoded.rows[0][0].as_ref().expect("null scalar result"))
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 fn test_e2e_native_bcp_numeric_nullable() {
@@ -157,7 +157,7 @@ fn test_e2e_native_bcp_numeric_nullable() {
     conn.disconnect().expect("disconnect");
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 fn test_e2e_native_bcp_i32_only_non_null() {
@@ -227,7 +227,7 @@ fn test_e2e_native_bcp_i32_only_non_null() {
     conn.disconnect().expect("disconnect");
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 fn test_e2e_native_bcp_i32_zero_rows() {
@@ -292,7 +292,7 @@ fn test_e2e_native_bcp_i32_zero_rows() {
     conn.disconnect().expect("disconnect");
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 #[ignore = "Isolation test: connect-only path to isolate crash stage"]
@@ -394,7 +394,7 @@ fn test_e2e_native_bcp_connect_only() {
     eprintln!("[OK] Cleanup completed without crash");
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 #[ignore = "Isolation test: connect + bcp_init (no bind) to isolate crash stage"]
@@ -617,7 +617,7 @@ fn test_e2e_native_bcp_init_only() {
     conn.disconnect().expect("disconnect");
 }
 
-#[cfg(all(feature = "sqlserver-bcp", windows))]
+#[cfg(all(feature = "sqlserver-bcp", any(windows, target_os = "linux")))]
 #[test]
 #[serial]
 #[ignore = "Benchmark: compare native BCP vs ArrayBinding; run with --ignored"]
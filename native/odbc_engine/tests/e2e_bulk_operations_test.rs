@@ -3,10 +3,16 @@ use odbc_api::Connection;
 /// Tests CREATE TABLE, INSERT (50k rows), SELECT, UPDATE, DELETE, and DROP operations
 /// with performance metrics collection
 use odbc_engine::{
-    engine::core::{ArrayBinding, ParallelBulkInsert},
+    engine::core::{
+        parallel_insert::ParallelMode, ArrayBinding, BulkUpsert, CsvImportOptions, CsvImporter,
+        ParallelBulkInsert,
+    },
     execute_query_with_connection,
+    plugins::{
+        mysql::MySqlPlugin, postgres::PostgresPlugin, BulkLoadOptions, BulkLoadProgress, BulkLoader,
+    },
     pool::ConnectionPool,
-    protocol::{BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload},
+    protocol::{BulkColumnData, BulkColumnSpec, BulkColumnType, BulkInsertPayload, BulkTimestamp},
     BinaryProtocolDecoder, OdbcConnection, OdbcEnvironment,
 };
 use std::sync::Arc;
@@ -811,6 +817,101 @@ fn test_e2e_bulk_parallel_insert() {
     execute_command(w3.get_connection_mut(), "DROP TABLE odbc_pi_test").expect("Drop table");
 }
 
+#[test]
+fn test_e2e_bulk_parallel_insert_all_or_nothing() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let pool = Arc::new(ConnectionPool::new(&conn_str, 4).expect("Create pool"));
+    let mut wrapper = pool.get().expect("Get connection");
+    let conn = wrapper.get_connection_mut();
+
+    let _ = execute_command(conn, "DROP TABLE IF EXISTS odbc_pi_aon_test");
+    let _ = execute_command(conn, "DROP TABLE odbc_pi_aon_test");
+    std::thread::sleep(Duration::from_millis(100));
+    execute_command(
+        conn,
+        "CREATE TABLE odbc_pi_aon_test (id INT NOT NULL PRIMARY KEY, name VARCHAR(20))",
+    )
+    .expect("Create table");
+    execute_command(
+        conn,
+        "INSERT INTO odbc_pi_aon_test (id, name) VALUES (1500, 'seed')",
+    )
+    .expect("Seed row");
+    drop(wrapper);
+
+    let payload = |ids: Vec<i32>| {
+        let names: Vec<Vec<u8>> = ids.iter().map(|i| format!("n{i}").into_bytes()).collect();
+        BulkInsertPayload {
+            table: "odbc_pi_aon_test".to_string(),
+            columns: vec![
+                BulkColumnSpec {
+                    name: "id".to_string(),
+                    col_type: BulkColumnType::I32,
+                    nullable: false,
+                    max_len: 0,
+                },
+                BulkColumnSpec {
+                    name: "name".to_string(),
+                    col_type: BulkColumnType::Text,
+                    nullable: false,
+                    max_len: 20,
+                },
+            ],
+            row_count: ids.len() as u32,
+            column_data: vec![
+                BulkColumnData::I32 {
+                    values: ids,
+                    null_bitmap: None,
+                },
+                BulkColumnData::Text {
+                    rows: names,
+                    max_len: 20,
+                    null_bitmap: None,
+                },
+            ],
+        }
+    };
+
+    let pbi = ParallelBulkInsert::new(Arc::clone(&pool), 2)
+        .with_batch_size(250)
+        .with_mode(ParallelMode::AllOrNothing);
+
+    // 1500 collides with the seed row when the staged rows are copied over,
+    // so none of the 2000 rows may land.
+    let err = pbi
+        .insert_payload_parallel(&payload((1..=2_000).collect()))
+        .expect_err("duplicate key must abort the whole load");
+    eprintln!("all-or-nothing failure: {err}");
+
+    let count_rows = || {
+        let w = pool.get().expect("Get connection");
+        let buf = execute_query_with_connection(
+            w.get_connection(),
+            "SELECT COUNT(*) AS c FROM odbc_pi_aon_test",
+        )
+        .expect("SELECT COUNT");
+        let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+        decode_integer(dec.rows[0][0].as_ref().unwrap()) as usize
+    };
+    assert_eq!(count_rows(), 1);
+
+    let inserted = pbi
+        .insert_payload_parallel(&payload((2_001..=3_000).collect()))
+        .expect("insert_payload_parallel");
+    assert_eq!(inserted, 1_000);
+    assert_eq!(count_rows(), 1_001);
+
+    let mut w3 = pool.get().expect("Get connection");
+    execute_command(w3.get_connection_mut(), "DROP TABLE odbc_pi_aon_test").expect("Drop table");
+}
+
 #[test]
 fn test_e2e_bulk_insert_generic() {
     if !should_run_e2e_tests() {
@@ -905,3 +1006,700 @@ fn test_e2e_bulk_insert_generic() {
     drop(handles_guard);
     conn.disconnect().expect("Disconnect");
 }
+
+#[test]
+fn test_e2e_bulk_insert_generic_with_rejects() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_bi_rej_test");
+    let _ = execute_command(&odbc_conn, "DROP TABLE odbc_bi_rej_test");
+    std::thread::sleep(Duration::from_millis(100));
+
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_bi_rej_test (id INT NOT NULL PRIMARY KEY)",
+    )
+    .expect("Create table");
+    execute_command(&odbc_conn, "INSERT INTO odbc_bi_rej_test (id) VALUES (3)")
+        .expect("Seed duplicate key");
+
+    let payload = BulkInsertPayload {
+        table: "odbc_bi_rej_test".to_string(),
+        columns: vec![BulkColumnSpec {
+            name: "id".to_string(),
+            col_type: BulkColumnType::I32,
+            nullable: false,
+            max_len: 0,
+        }],
+        row_count: 5,
+        column_data: vec![BulkColumnData::I32 {
+            values: vec![1, 2, 3, 4, 5],
+            null_bitmap: None,
+        }],
+    };
+
+    let outcome = ArrayBinding::new(5)
+        .bulk_insert_generic_with_rejects(&odbc_conn, &payload)
+        .expect("bulk_insert_generic_with_rejects");
+
+    // Drivers that execute the array as a unit may reject more than the
+    // duplicate, but every row must be accounted for and row 2 must be there.
+    assert_eq!(outcome.inserted + outcome.rejected.len(), 5);
+    let dup = outcome
+        .rejected
+        .iter()
+        .find(|r| r.row_index == 2)
+        .expect("duplicate key row should be rejected");
+    assert!(!dup.message.is_empty());
+    assert_eq!(
+        outcome.rejected_payload(&payload).row_count as usize,
+        outcome.rejected.len()
+    );
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_bi_rej_test").expect("Drop table");
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[test]
+fn test_e2e_bulk_update_delete_by_key() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_bulk_dml_test");
+    let _ = execute_command(&odbc_conn, "DROP TABLE odbc_bulk_dml_test");
+    std::thread::sleep(Duration::from_millis(100));
+
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_bulk_dml_test (id INT NOT NULL PRIMARY KEY, name VARCHAR(20))",
+    )
+    .expect("Create table");
+
+    const N: usize = 300;
+    let payload = |ids: Vec<i32>, name: &str| {
+        let rows = vec![name.as_bytes().to_vec(); ids.len()];
+        BulkInsertPayload {
+            table: "odbc_bulk_dml_test".to_string(),
+            columns: vec![
+                BulkColumnSpec {
+                    name: "id".to_string(),
+                    col_type: BulkColumnType::I32,
+                    nullable: false,
+                    max_len: 0,
+                },
+                BulkColumnSpec {
+                    name: "name".to_string(),
+                    col_type: BulkColumnType::Text,
+                    nullable: false,
+                    max_len: 20,
+                },
+            ],
+            row_count: ids.len() as u32,
+            column_data: vec![
+                BulkColumnData::I32 {
+                    values: ids,
+                    null_bitmap: None,
+                },
+                BulkColumnData::Text {
+                    rows,
+                    max_len: 20,
+                    null_bitmap: None,
+                },
+            ],
+        }
+    };
+    let ab = ArrayBinding::new(100);
+    ab.bulk_insert_generic(&odbc_conn, &payload((1..=N as i32).collect(), "old"))
+        .expect("Seed rows");
+
+    // Ids above N match nothing and must not count.
+    let updated = ab
+        .bulk_update_by_key(&odbc_conn, &payload((101..=350).collect(), "new"), &["id"])
+        .expect("bulk_update_by_key");
+    println!("update per batch: {:?}", updated.batch_rows_affected);
+    assert_eq!(updated.batch_rows_affected.len(), 3);
+    assert_eq!(updated.rows_affected, 200);
+
+    let deleted = ab
+        .bulk_delete_by_key(&odbc_conn, &payload((1..=50).collect(), ""), &["id"])
+        .expect("bulk_delete_by_key");
+    assert_eq!(deleted.rows_affected, 50);
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT COUNT(*), SUM(CASE WHEN name = 'new' THEN 1 ELSE 0 END) FROM odbc_bulk_dml_test",
+    )
+    .expect("SELECT COUNT");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()), 250);
+    assert_eq!(decode_integer(dec.rows[0][1].as_ref().unwrap()), 200);
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_bulk_dml_test").expect("Drop table");
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[test]
+fn test_e2e_bulk_upsert() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_bulk_upsert_test");
+    let _ = execute_command(&odbc_conn, "DROP TABLE odbc_bulk_upsert_test");
+    std::thread::sleep(Duration::from_millis(100));
+
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_bulk_upsert_test (id INT NOT NULL PRIMARY KEY, name VARCHAR(20))",
+    )
+    .expect("Create table");
+    for id in 1..=10 {
+        execute_command(
+            &odbc_conn,
+            &format!("INSERT INTO odbc_bulk_upsert_test (id, name) VALUES ({id}, 'old')"),
+        )
+        .expect("Seed row");
+    }
+
+    // Ids 6..=10 exist and get updated, 11..=20 are new.
+    const N: usize = 15;
+    let payload = BulkInsertPayload {
+        table: "odbc_bulk_upsert_test".to_string(),
+        columns: vec![
+            BulkColumnSpec {
+                name: "id".to_string(),
+                col_type: BulkColumnType::I32,
+                nullable: false,
+                max_len: 0,
+            },
+            BulkColumnSpec {
+                name: "name".to_string(),
+                col_type: BulkColumnType::Text,
+                nullable: false,
+                max_len: 20,
+            },
+        ],
+        row_count: N as u32,
+        column_data: vec![
+            BulkColumnData::I32 {
+                values: (6..=20).collect(),
+                null_bitmap: None,
+            },
+            BulkColumnData::Text {
+                rows: vec![b"new".to_vec(); N],
+                max_len: 20,
+                null_bitmap: None,
+            },
+        ],
+    };
+
+    let report = BulkUpsert::new(&["id"])
+        .execute(odbc_conn.connection(), payload)
+        .expect("bulk upsert");
+    println!("bulk upsert report: {report:?}");
+    assert_eq!(report.rows_staged, N);
+    if let (Some(inserted), Some(updated)) = (report.inserted, report.updated) {
+        assert_eq!(inserted, 10);
+        assert_eq!(updated, 5);
+    }
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT COUNT(*), SUM(CASE WHEN name = 'new' THEN 1 ELSE 0 END) \
+         FROM odbc_bulk_upsert_test",
+    )
+    .expect("SELECT COUNT");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()), 20);
+    assert_eq!(decode_integer(dec.rows[0][1].as_ref().unwrap()), 15);
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_bulk_upsert_test").expect("Drop table");
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[test]
+fn test_e2e_csv_import() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_csv_import_test");
+    let _ = execute_command(&odbc_conn, "DROP TABLE odbc_csv_import_test");
+    std::thread::sleep(Duration::from_millis(100));
+
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_csv_import_test (id INT NOT NULL, name VARCHAR(40))",
+    )
+    .expect("Create table");
+
+    // 250 good rows, one line with a bad id and one with a missing field.
+    let path = std::env::temp_dir().join(format!("odbc_csv_import_{}.csv", std::process::id()));
+    let mut csv = String::from("id,name\n");
+    for id in 1..=250 {
+        if id == 100 {
+            csv.push_str("oops,\"bad id\"\n");
+        }
+        if id == 200 {
+            csv.push_str("999\n");
+        }
+        csv.push_str(&format!("{id},\"row, {id}\"\n"));
+    }
+    std::fs::write(&path, csv).expect("Write CSV");
+
+    let options = CsvImportOptions {
+        batch_size: 64,
+        max_errors: Some(10),
+        ..CsvImportOptions::csv()
+    };
+    let mut progress_calls = 0usize;
+    let report = CsvImporter::new("odbc_csv_import_test", options)
+        .with_type("id", BulkColumnType::I32)
+        .import_file(odbc_conn.connection(), &path, &mut |_| progress_calls += 1)
+        .expect("CSV import");
+    let _ = std::fs::remove_file(&path);
+    println!("CSV import report: {report:?}");
+    assert_eq!(report.rows_loaded, 250);
+    assert_eq!(report.malformed_count, 2);
+    let lines: Vec<u64> = report.malformed.iter().map(|m| m.line).collect();
+    assert_eq!(lines, vec![101, 202]);
+    assert!(progress_calls >= 4);
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT COUNT(*) FROM odbc_csv_import_test WHERE name LIKE 'row, %'",
+    )
+    .expect("SELECT COUNT");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()), 250);
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_csv_import_test").expect("Drop table");
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_e2e_parquet_round_trip() {
+    use odbc_engine::engine::core::{
+        ParquetCodec, ParquetExportOptions, ParquetExporter, ParquetImportOptions, ParquetImporter,
+    };
+
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, _db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    for table in ["odbc_parquet_src", "odbc_parquet_dst"] {
+        let _ = execute_command(&odbc_conn, &format!("DROP TABLE IF EXISTS {table}"));
+        let _ = execute_command(&odbc_conn, &format!("DROP TABLE {table}"));
+    }
+    std::thread::sleep(Duration::from_millis(100));
+    for table in ["odbc_parquet_src", "odbc_parquet_dst"] {
+        execute_command(
+            &odbc_conn,
+            &format!(
+                "CREATE TABLE {table} (id INT NOT NULL, amount DECIMAL(10,2), \
+                 due DATE, name VARCHAR(40))"
+            ),
+        )
+        .expect("Create table");
+    }
+    for id in 1..=300 {
+        let amount = if id % 7 == 0 {
+            "NULL".to_string()
+        } else {
+            format!("{id}.25")
+        };
+        execute_command(
+            &odbc_conn,
+            &format!(
+                "INSERT INTO odbc_parquet_src VALUES ({id}, {amount}, '2024-03-{:02}', 'row {id}')",
+                id % 28 + 1
+            ),
+        )
+        .expect("Insert row");
+    }
+
+    let path =
+        std::env::temp_dir().join(format!("odbc_parquet_e2e_{}.parquet", std::process::id()));
+    let export = ParquetExporter::new(ParquetExportOptions {
+        row_group_rows: 128,
+        codec: ParquetCodec::Zstd,
+        fetch_size: 50,
+    })
+    .export_to_file(
+        odbc_conn.connection(),
+        "SELECT id, amount, due, name FROM odbc_parquet_src",
+        &path,
+        None,
+    )
+    .expect("Parquet export");
+    println!("Parquet export report: {export:?}");
+    assert_eq!(export.rows, 300);
+    assert_eq!(export.row_groups, 3);
+
+    let import = ParquetImporter::new("odbc_parquet_dst", ParquetImportOptions { batch_rows: 100 })
+        .import_file(odbc_conn.connection(), &path, &mut |_| {})
+        .expect("Parquet import");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(import.rows_loaded, 300);
+    assert_eq!(import.batches, 3);
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT COUNT(*) FROM odbc_parquet_src s JOIN odbc_parquet_dst d ON s.id = d.id \
+         WHERE s.due = d.due AND s.name = d.name \
+         AND (s.amount = d.amount OR (s.amount IS NULL AND d.amount IS NULL))",
+    )
+    .expect("SELECT COUNT");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()), 300);
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_parquet_src").expect("Drop table");
+    execute_command(&odbc_conn, "DROP TABLE odbc_parquet_dst").expect("Drop table");
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[test]
+fn test_e2e_postgres_bulk_load() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+    if db_type != DatabaseType::PostgreSQL {
+        eprintln!("⚠️  Skipping: PostgreSQL bulk loader test requires PostgreSQL");
+        return;
+    }
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_pg_bulk_test");
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_pg_bulk_test (id INT, name TEXT, payload BYTEA, created_at TIMESTAMP)",
+    )
+    .expect("Create table");
+
+    const N: usize = 1_000;
+    let at = BulkTimestamp {
+        year: 2024,
+        month: 2,
+        day: 29,
+        hour: 12,
+        minute: 30,
+        second: 0,
+        fraction: 0,
+    };
+    let mut name_nulls = vec![0u8; N.div_ceil(8)];
+    name_nulls[0] = 0b1;
+    let spec = |name: &str, col_type| BulkColumnSpec {
+        name: name.to_string(),
+        col_type,
+        nullable: true,
+        max_len: 64,
+    };
+    let payload = BulkInsertPayload {
+        table: "odbc_pg_bulk_test".to_string(),
+        columns: vec![
+            spec("id", BulkColumnType::I32),
+            spec("name", BulkColumnType::Text),
+            spec("payload", BulkColumnType::Binary),
+            spec("created_at", BulkColumnType::Timestamp),
+        ],
+        row_count: N as u32,
+        column_data: vec![
+            BulkColumnData::I32 {
+                values: (1..=N as i32).collect(),
+                null_bitmap: None,
+            },
+            BulkColumnData::Text {
+                rows: (1..=N)
+                    .map(|i| format!("row\t{i}\\x").into_bytes())
+                    .collect(),
+                max_len: 64,
+                null_bitmap: Some(name_nulls),
+            },
+            BulkColumnData::Binary {
+                rows: (0..N).map(|i| vec![(i % 256) as u8, 0xff]).collect(),
+                max_len: 2,
+                null_bitmap: None,
+            },
+            BulkColumnData::Timestamp {
+                values: vec![at; N],
+                null_bitmap: None,
+            },
+        ],
+    };
+
+    let options = BulkLoadOptions::new(300);
+    let check_table = |conn: &Connection<'static>| {
+        let buf = execute_query_with_connection(
+            conn,
+            "SELECT COUNT(*), COUNT(name) FROM odbc_pg_bulk_test",
+        )
+        .expect("SELECT COUNT");
+        let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+        assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()) as usize, N);
+        assert_eq!(
+            decode_integer(dec.rows[0][1].as_ref().unwrap()) as usize,
+            N - 1
+        );
+
+        let buf =
+            execute_query_with_connection(conn, "SELECT name FROM odbc_pg_bulk_test WHERE id = 2")
+                .expect("SELECT name");
+        let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+        assert_eq!(decode_string(dec.rows[0][0].as_ref().unwrap()), "row\t2\\x");
+    };
+
+    // Array binding, one progress report per 300-row batch.
+    let plugin = PostgresPlugin::new();
+    assert_eq!(plugin.technique(), "array_binding_optimised");
+    let mut progress: Vec<BulkLoadProgress> = Vec::new();
+    let loaded = plugin
+        .execute_bulk_native_with_progress(&odbc_conn, &payload, &options, &mut |p| {
+            progress.push(*p)
+        })
+        .expect("PostgreSQL bulk load");
+    assert_eq!(loaded, N);
+    assert_eq!(progress.len(), N.div_ceil(300));
+    assert_eq!(progress.last().map(|p| p.rows_loaded), Some(N));
+    check_table(odbc_conn.connection());
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_pg_bulk_test").expect("Drop table");
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
+
+#[test]
+fn test_e2e_mysql_load_data_bulk_load() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: database not available");
+        return;
+    }
+
+    let (conn_str, db_type) =
+        get_connection_and_db_type().expect("Failed to get connection string and database type");
+    if db_type != DatabaseType::MySQL {
+        eprintln!("⚠️  Skipping: LOAD DATA loader test requires MySQL/MariaDB");
+        return;
+    }
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize environment");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Failed to connect");
+    let conn_id = conn.get_connection_id();
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn_id)
+        .expect("Failed to get ODBC connection");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let _ = execute_command(&odbc_conn, "DROP TABLE IF EXISTS odbc_load_data_test");
+    execute_command(
+        &odbc_conn,
+        "CREATE TABLE odbc_load_data_test (id BIGINT, name VARCHAR(64), \
+         payload VARBINARY(16), created_at DATETIME(6))",
+    )
+    .expect("Create table");
+
+    const N: usize = 1_000;
+    let mut name_nulls = vec![0u8; N.div_ceil(8)];
+    name_nulls[0] = 0b1;
+    let spec = |name: &str, col_type| BulkColumnSpec {
+        name: name.to_string(),
+        col_type,
+        nullable: true,
+        max_len: 64,
+    };
+    let payload = BulkInsertPayload {
+        table: "odbc_load_data_test".to_string(),
+        columns: vec![
+            spec("id", BulkColumnType::I64),
+            spec("name", BulkColumnType::Text),
+            spec("payload", BulkColumnType::Binary),
+            spec("created_at", BulkColumnType::Timestamp),
+        ],
+        row_count: N as u32,
+        column_data: vec![
+            BulkColumnData::I64 {
+                values: (1..=N as i64).collect(),
+                null_bitmap: None,
+            },
+            BulkColumnData::Text {
+                rows: (1..=N)
+                    .map(|i| format!("row\t{i}\\x").into_bytes())
+                    .collect(),
+                max_len: 64,
+                null_bitmap: Some(name_nulls),
+            },
+            BulkColumnData::Binary {
+                rows: (0..N).map(|i| vec![(i % 256) as u8, 0x0a]).collect(),
+                max_len: 2,
+                null_bitmap: None,
+            },
+            BulkColumnData::Timestamp {
+                values: vec![
+                    BulkTimestamp {
+                        year: 2024,
+                        month: 2,
+                        day: 29,
+                        hour: 12,
+                        minute: 30,
+                        second: 0,
+                        fraction: 250_000_000,
+                    };
+                    N
+                ],
+                null_bitmap: None,
+            },
+        ],
+    };
+
+    let options = BulkLoadOptions::new(400);
+    let mut batches = 0usize;
+    let loaded = MySqlPlugin::new()
+        .execute_bulk_native_with_progress(&odbc_conn, &payload, &options, &mut |_| batches += 1)
+        .expect("MySQL bulk load");
+    assert_eq!(loaded, N);
+    assert!(batches >= 1);
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT COUNT(*), COUNT(name) FROM odbc_load_data_test",
+    )
+    .expect("SELECT COUNT");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_integer(dec.rows[0][0].as_ref().unwrap()) as usize, N);
+    assert_eq!(
+        decode_integer(dec.rows[0][1].as_ref().unwrap()) as usize,
+        N - 1
+    );
+
+    let buf = execute_query_with_connection(
+        odbc_conn.connection(),
+        "SELECT name, HEX(payload) FROM odbc_load_data_test WHERE id = 2",
+    )
+    .expect("SELECT row");
+    let dec = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decode_string(dec.rows[0][0].as_ref().unwrap()), "row\t2\\x");
+    assert_eq!(decode_string(dec.rows[0][1].as_ref().unwrap()), "010A");
+
+    execute_command(&odbc_conn, "DROP TABLE odbc_load_data_test").expect("Drop table");
+    drop(handles_guard);
+    conn.disconnect().expect("Disconnect");
+}
@@ -0,0 +1,73 @@
+/// E2E tests for streamed LOB uploads (`LobWriter`).
+use odbc_engine::engine::{
+    execute_query_with_connection, LobKind, LobWriter, OdbcConnection, OdbcEnvironment,
+    TemporalBinding,
+};
+use odbc_engine::protocol::BinaryProtocolDecoder;
+use odbc_engine::ParamValue;
+
+mod helpers;
+use helpers::e2e::{
+    get_connection_and_db_type, should_run_e2e_tests, sql_drop_table_if_exists, DatabaseType,
+};
+
+#[test]
+fn test_lob_writer_dropped_after_utf8_error_cancels_upload() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: E2E tests disabled");
+        return;
+    }
+    let Some((conn_str, db_type)) = get_connection_and_db_type() else {
+        eprintln!("⚠️  Skipping E2E test: no DSN available");
+        return;
+    };
+    let text_type = match db_type {
+        DatabaseType::SqlServer => "NVARCHAR(MAX)",
+        DatabaseType::Oracle => "NCLOB",
+        _ => "TEXT",
+    };
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Init failed");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("Connect failed");
+    let conn_arc = handles
+        .lock()
+        .unwrap()
+        .get_connection(conn.get_connection_id())
+        .unwrap();
+    let run = |sql: &str| {
+        let c = conn_arc.lock().unwrap();
+        execute_query_with_connection(c.connection(), sql)
+    };
+
+    let _ = run(&sql_drop_table_if_exists("lob_utf8_test", db_type));
+    run(&format!(
+        "CREATE TABLE lob_utf8_test (id INT, body {text_type})"
+    ))
+    .unwrap();
+
+    let mut writer = LobWriter::open(
+        conn_arc.clone(),
+        "INSERT INTO lob_utf8_test (id, body) VALUES (?, ?)",
+        &[ParamValue::Integer(1)],
+        TemporalBinding::Standard,
+        2,
+        LobKind::Text,
+        None,
+    )
+    .expect("open LOB upload");
+    // "é" cut after its first byte.
+    writer.write(&[b'a', 0xC3]).unwrap();
+    assert!(writer.finish().is_err());
+    drop(writer);
+
+    // The dropped writer cancelled the upload: the connection takes new
+    // statements (no HY010) and nothing was inserted.
+    let buf = run("SELECT id FROM lob_utf8_test").expect("connection still usable");
+    let decoded = BinaryProtocolDecoder::parse(&buf).unwrap();
+    assert_eq!(decoded.row_count, 0);
+
+    let _ = run("DROP TABLE lob_utf8_test");
+    conn.disconnect().expect("Disconnect failed");
+}
@@ -3,12 +3,13 @@
 //! When `statement-handle-reuse` feature is enabled, verifies that:
 //! - execute_query_with_cached_connection works
 //! - cache metrics (hits, misses) are recorded
-//!
-//! Full LRU reuse is blocked by lifetime constraints (see cached_connection.rs);
-//! this test validates the infrastructure.
+//! - `odbc_prepare` statement IDs rebind parameters on one prepared handle
 
-use odbc_engine::engine::{execute_query_with_cached_connection, OdbcConnection, OdbcEnvironment};
-use odbc_engine::protocol::BinaryProtocolDecoder;
+use odbc_engine::engine::{
+    execute_prepared_with_param_buffer, execute_query_with_cached_connection, OdbcConnection,
+    OdbcEnvironment,
+};
+use odbc_engine::protocol::{serialize_params, BinaryProtocolDecoder, ParamValue};
 
 mod helpers;
 use helpers::e2e::{should_run_e2e_tests, should_run_slow_e2e_tests};
@@ -33,11 +34,19 @@ fn test_statement_reuse_infrastructure() {
         .unwrap()
         .get_connection(conn.get_connection_id())
         .expect("get connection");
+    let options = conn
+        .get_handles()
+        .lock()
+        .unwrap()
+        .result_options(conn.get_connection_id())
+        .expect("result options");
     let mut odbc_conn = conn_arc.lock().unwrap();
 
     let sql = "SELECT 1 AS value";
-    let buf1 = execute_query_with_cached_connection(&mut odbc_conn, sql).expect("first query");
-    let buf2 = execute_query_with_cached_connection(&mut odbc_conn, sql).expect("second query");
+    let buf1 =
+        execute_query_with_cached_connection(&mut odbc_conn, sql, &options).expect("first query");
+    let buf2 =
+        execute_query_with_cached_connection(&mut odbc_conn, sql, &options).expect("second query");
 
     let dec1 = BinaryProtocolDecoder::parse(&buf1).expect("decode 1");
     let dec2 = BinaryProtocolDecoder::parse(&buf2).expect("decode 2");
@@ -64,13 +73,93 @@ fn test_statement_reuse_infrastructure() {
     conn.disconnect().expect("disconnect");
 }
 
+#[test]
+fn test_prepared_statement_rebinds_params() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: SQL Server not available");
+        return;
+    }
+    let conn_str = get_sqlserver_test_dsn().expect("Failed to build connection string");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("init");
+    let handles = env.get_handles();
+    let conn = OdbcConnection::connect(handles.clone(), &conn_str).expect("connect");
+
+    let conn_arc = conn
+        .get_handles()
+        .lock()
+        .unwrap()
+        .get_connection(conn.get_connection_id())
+        .expect("get connection");
+    let options = conn
+        .get_handles()
+        .lock()
+        .unwrap()
+        .result_options(conn.get_connection_id())
+        .expect("result options");
+    let mut odbc_conn = conn_arc.lock().unwrap();
+
+    const STMT_ID: u32 = 1;
+    let sql = "SELECT CAST(? AS INT) + 1 AS value";
+    #[cfg(feature = "statement-handle-reuse")]
+    odbc_conn.prepare_statement(STMT_ID, sql).expect("prepare");
+
+    let mut run = |value: i32| {
+        let params = serialize_params(&[ParamValue::Integer(value)]);
+        let buf = execute_prepared_with_param_buffer(
+            &mut odbc_conn,
+            STMT_ID,
+            sql,
+            &params,
+            None,
+            None,
+            &options,
+        )
+        .expect("execute prepared");
+        let decoded = BinaryProtocolDecoder::parse(&buf).expect("decode");
+        assert_eq!(decoded.row_count, 1);
+        let cell = decoded.rows[0][0].as_ref().expect("value is not NULL");
+        String::from_utf8_lossy(cell).into_owned()
+    };
+    assert_eq!(run(1), "2");
+    assert_eq!(run(41), "42");
+
+    #[cfg(feature = "statement-handle-reuse")]
+    {
+        assert_eq!(odbc_conn.prepared_statement_count(), 1);
+        let hits = odbc_conn.cache_hits();
+        assert!(hits >= 2, "expected both executes to reuse the handle");
+
+        odbc_conn.invalidate_statements();
+        assert_eq!(odbc_conn.prepared_statement_count(), 0);
+        let params = serialize_params(&[ParamValue::Integer(9)]);
+        execute_prepared_with_param_buffer(
+            &mut odbc_conn,
+            STMT_ID,
+            sql,
+            &params,
+            None,
+            None,
+            &options,
+        )
+        .expect("execute after invalidation re-prepares");
+        assert_eq!(odbc_conn.prepared_statement_count(), 1);
+
+        assert!(odbc_conn.close_statement(STMT_ID));
+        assert!(!odbc_conn.close_statement(STMT_ID));
+    }
+
+    drop(odbc_conn);
+    conn.disconnect().expect("disconnect");
+}
+
 /// E2E benchmark: repetitive prepare/execute cycles.
 ///
 /// Run with: `cargo test test_statement_reuse_repetitive_benchmark -- --ignored --nocapture`
 /// Compare with feature: `cargo test test_statement_reuse_repetitive_benchmark --features statement-handle-reuse -- --ignored --nocapture`
 ///
-/// When LRU cache is implemented, feature-on build should show ~10%+ improvement.
-/// Currently both builds perform similarly (passthrough mode).
+/// The feature-on build reuses the prepared handle across iterations.
 #[test]
 #[ignore = "E2E benchmark; set ENABLE_SLOW_E2E_TESTS=1 + --ignored"]
 fn test_statement_reuse_repetitive_benchmark() {
@@ -98,6 +187,12 @@ fn test_statement_reuse_repetitive_benchmark() {
         .unwrap()
         .get_connection(conn.get_connection_id())
         .expect("get connection");
+    let options = conn
+        .get_handles()
+        .lock()
+        .unwrap()
+        .result_options(conn.get_connection_id())
+        .expect("result options");
     let mut odbc_conn = conn_arc.lock().unwrap();
 
     let sql = "SELECT 1 AS value";
@@ -107,7 +202,8 @@ fn test_statement_reuse_repetitive_benchmark() {
     for _ in 0..ROUNDS {
         let start = std::time::Instant::now();
         for _ in 0..ITERATIONS {
-            let buf = execute_query_with_cached_connection(&mut odbc_conn, sql).expect("query");
+            let buf =
+                execute_query_with_cached_connection(&mut odbc_conn, sql, &options).expect("query");
             let dec = odbc_engine::protocol::BinaryProtocolDecoder::parse(&buf).expect("decode");
             assert!(dec.row_count >= 1, "expected at least 1 row");
         }
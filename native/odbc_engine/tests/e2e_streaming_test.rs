@@ -1,6 +1,11 @@
 /// E2E tests for StreamingExecutor with real SQL Server connection
-use odbc_engine::engine::{OdbcConnection, OdbcEnvironment, StreamingExecutor};
-use odbc_engine::protocol::BinaryProtocolDecoder;
+use odbc_engine::engine::{
+    ExportOptions, OdbcConnection, OdbcEnvironment, QueryExporter, StreamingExecutor,
+};
+use odbc_engine::protocol::columnar::CompressionType;
+use odbc_engine::protocol::{decompress, BinaryProtocolDecoder, ParamValue};
+use odbc_engine::OdbcError;
+use std::sync::atomic::AtomicBool;
 
 mod helpers;
 use helpers::e2e::{should_run_e2e_tests, should_run_slow_e2e_tests};
@@ -560,6 +565,122 @@ fn test_streaming_batched_lazy() {
     println!("\n✅ execute_streaming_batched (lazy) test PASSED");
 }
 
+/// Parameters bound through `with_params` filter the streamed rows.
+#[test]
+fn test_streaming_batched_with_params() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: SQL Server not available");
+        return;
+    }
+    let conn_str = get_sqlserver_test_dsn().expect("Failed to build SQL Server connection string");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize ODBC environment");
+    let handles = env.get_handles();
+    let conn =
+        OdbcConnection::connect(handles, &conn_str).expect("Failed to connect to SQL Server");
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn.get_connection_id())
+        .expect("Failed to get ODBC connection handle");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let executor = StreamingExecutor::new(1024)
+        .with_params(vec![ParamValue::Integer(2), ParamValue::Integer(4)]);
+    let sql = "SELECT number FROM (SELECT 1 AS number UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5) AS t WHERE number BETWEEN ? AND ? ORDER BY number";
+
+    let mut total_rows = 0_usize;
+    executor
+        .execute_streaming_batched(
+            &odbc_conn,
+            sql,
+            2,
+            |encoded| {
+                let decoded = BinaryProtocolDecoder::parse(&encoded).expect("decode batch");
+                total_rows += decoded.row_count;
+                Ok(())
+            },
+            None,
+        )
+        .expect("execute_streaming_batched with params failed");
+    assert_eq!(total_rows, 3, "Expected rows 2..=4");
+
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Failed to disconnect");
+}
+
+/// Query-to-file export: CSV and zstd-compressed NDJSON, plus cancellation.
+#[test]
+fn test_export_query_to_file() {
+    if !should_run_e2e_tests() {
+        eprintln!("⚠️  Skipping E2E test: SQL Server not available");
+        return;
+    }
+    let conn_str = get_sqlserver_test_dsn().expect("Failed to build SQL Server connection string");
+
+    let env = OdbcEnvironment::new();
+    env.init().expect("Failed to initialize ODBC environment");
+    let handles = env.get_handles();
+    let conn =
+        OdbcConnection::connect(handles, &conn_str).expect("Failed to connect to SQL Server");
+
+    let conn_handles = conn.get_handles();
+    let handles_guard = conn_handles.lock().unwrap();
+    let conn_arc = handles_guard
+        .get_connection(conn.get_connection_id())
+        .expect("Failed to get ODBC connection handle");
+    let odbc_conn = conn_arc.lock().unwrap();
+
+    let sql = "SELECT number, CASE WHEN number = 2 THEN NULL ELSE 'n, ' + CAST(number AS VARCHAR(10)) END AS label FROM (SELECT 1 AS number UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5) AS t WHERE number <= ? ORDER BY number";
+    let dir = std::env::temp_dir();
+    let csv_path = dir.join(format!("odbc_export_{}.csv", std::process::id()));
+    let report = QueryExporter::new(ExportOptions {
+        fetch_size: 2,
+        ..ExportOptions::csv()
+    })
+    .with_params(vec![ParamValue::Integer(3)])
+    .export_to_file(&odbc_conn, sql, &csv_path, None)
+    .expect("CSV export");
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let _ = std::fs::remove_file(&csv_path);
+    assert_eq!(report.rows, 3);
+    assert_eq!(report.bytes_written, csv.len() as u64);
+    assert_eq!(csv, "number,label\n1,\"n, 1\"\n2,\n3,\"n, 3\"\n");
+
+    let json_path = dir.join(format!("odbc_export_{}.ndjson.zst", std::process::id()));
+    let report = QueryExporter::new(ExportOptions {
+        compression: CompressionType::Zstd,
+        ..ExportOptions::ndjson()
+    })
+    .with_params(vec![ParamValue::Integer(5)])
+    .export_to_file(&odbc_conn, sql, &json_path, None)
+    .expect("NDJSON export");
+    let compressed = std::fs::read(&json_path).unwrap();
+    let _ = std::fs::remove_file(&json_path);
+    assert_eq!(report.bytes_written, compressed.len() as u64);
+    let text = String::from_utf8(decompress(&compressed, CompressionType::Zstd).unwrap()).unwrap();
+    assert_eq!(text.lines().count(), 5);
+    assert_eq!(
+        text.lines().nth(1).unwrap(),
+        "{\"number\":2,\"label\":null}"
+    );
+
+    let cancelled_path = dir.join(format!("odbc_export_{}_cancelled.csv", std::process::id()));
+    let cancel = AtomicBool::new(true);
+    let result = QueryExporter::new(ExportOptions::csv())
+        .with_params(vec![ParamValue::Integer(5)])
+        .export_to_file(&odbc_conn, sql, &cancelled_path, Some(&cancel));
+    assert!(matches!(result, Err(OdbcError::Cancelled)));
+    assert!(!cancelled_path.exists(), "partial export must be removed");
+
+    drop(odbc_conn);
+    drop(handles_guard);
+    conn.disconnect().expect("Failed to disconnect");
+}
+
 /// Batched streaming with a larger result set (1000 rows, fetch_size=100).
 /// Exercises bounded-memory path; 50k rows would use fetch_size=1000 for stress.
 #[test]